@@ -0,0 +1,130 @@
+//! bcrypt_pbkdf implementation, following OpenBSD's `bcrypt_pbkdf.c`.
+
+use super::blowfish::{Blowfish, stream_to_word};
+use crate::hash::sha512;
+
+/// Length in bytes of one bcrypt_hash output.
+const HASH_LEN: usize = 32;
+
+/// Maximum output length: 32 interleaved blocks of `HASH_LEN` bytes.
+pub const MAX_OUTPUT_LEN: usize = HASH_LEN * HASH_LEN;
+
+/// Plaintext encrypted by bcrypt_hash.
+const MAGIC: &[u8; HASH_LEN] = b"OxychromaticBlowfishSwatDynamite";
+
+/// Errors that can occur during bcrypt_pbkdf derivation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BcryptPbkdfError {
+    /// The round count is zero.
+    InvalidRounds,
+    /// The password or the salt is empty.
+    EmptyInput,
+    /// The requested output is empty or longer than [`MAX_OUTPUT_LEN`].
+    InvalidOutputLength,
+}
+
+/// Derives `out.len()` bytes from `password` and `salt` with bcrypt_pbkdf.
+///
+/// # Parameters
+///
+/// - `password`: the password, non-empty
+/// - `salt`: the salt, non-empty
+/// - `rounds`: number of bcrypt_hash iterations per output block
+/// - `out`: output buffer, 1 to [`MAX_OUTPUT_LEN`] bytes
+///
+/// # Returns
+///
+/// - `Ok(())` if `out` was filled
+/// - `Err(BcryptPbkdfError)` if an argument is out of range
+pub fn bcrypt_pbkdf(
+    password: &[u8],
+    salt: &[u8],
+    rounds: u32,
+    out: &mut [u8],
+) -> Result<(), BcryptPbkdfError> {
+    if rounds == 0 {
+        return Err(BcryptPbkdfError::InvalidRounds);
+    }
+
+    if password.is_empty() || salt.is_empty() {
+        return Err(BcryptPbkdfError::EmptyInput);
+    }
+
+    if out.is_empty() || out.len() > MAX_OUTPUT_LEN {
+        return Err(BcryptPbkdfError::InvalidOutputLength);
+    }
+
+    let stride = out.len().div_ceil(HASH_LEN);
+    let amount = out.len().div_ceil(stride);
+
+    let mut sha_pass = sha512(password);
+    let mut count_salt = Vec::with_capacity(salt.len() + 4);
+
+    for block in 0..stride {
+        count_salt.clear();
+        count_salt.extend_from_slice(salt);
+        count_salt.extend_from_slice(&(block as u32 + 1).to_be_bytes());
+
+        let mut sha_salt = sha512(&count_salt);
+        let mut tmp = bcrypt_hash(&sha_pass, &sha_salt);
+        let mut acc = tmp;
+
+        for _ in 1..rounds {
+            sha_salt = sha512(&tmp);
+            tmp = bcrypt_hash(&sha_pass, &sha_salt);
+
+            for (a, t) in acc.iter_mut().zip(&tmp) {
+                *a ^= t;
+            }
+        }
+
+        // Output byte `i` of block `b` lands at `i * stride + b`.
+        for (i, byte) in acc.iter().take(amount).enumerate() {
+            let dest = i * stride + block;
+            if dest >= out.len() {
+                break;
+            }
+
+            out[dest] = *byte;
+        }
+
+        sha_salt.fill(0);
+        tmp.fill(0);
+        acc.fill(0);
+    }
+
+    sha_pass.fill(0);
+    count_salt.fill(0);
+
+    Ok(())
+}
+
+/// The bcrypt-style hash of a password hash and a salt hash.
+fn bcrypt_hash(sha_pass: &[u8; 64], sha_salt: &[u8; 64]) -> [u8; HASH_LEN] {
+    let mut state = Blowfish::new();
+
+    state.expand(sha_salt, sha_pass);
+    for _ in 0..64 {
+        state.expand0(sha_salt);
+        state.expand0(sha_pass);
+    }
+
+    let mut j = 0;
+    let mut data = [0u32; HASH_LEN / 4];
+    for word in data.iter_mut() {
+        *word = stream_to_word(MAGIC, &mut j);
+    }
+
+    for _ in 0..64 {
+        state.encrypt(&mut data);
+    }
+
+    let mut out = [0u8; HASH_LEN];
+    for (bytes, word) in out.chunks_exact_mut(4).zip(&data) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+
+    data.fill(0);
+
+    out
+}
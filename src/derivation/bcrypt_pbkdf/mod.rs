@@ -0,0 +1,27 @@
+//! bcrypt_pbkdf password-based key derivation function.
+//!
+//! bcrypt_pbkdf is the KDF OpenBSD introduced for `signify` and OpenSSH
+//! passphrase-protected keys. It follows the structure of PBKDF2, with
+//! HMAC replaced by a bcrypt-style hash:
+//!
+//! ```text
+//! bcrypt_hash(pass, salt) = EksBlowfish(SHA-512(pass), SHA-512(salt))
+//!                           encrypting "OxychromaticBlowfishSwatDynamite"
+//!                           64 times, output as little-endian words
+//!
+//! T(i)  = U(1) ^ U(2) ^ ... ^ U(rounds)
+//! U(1)  = bcrypt_hash(pass, salt || BE32(i))
+//! U(k)  = bcrypt_hash(pass, U(k - 1))
+//! ```
+//!
+//! Unlike PBKDF2, output bytes of the blocks `T(i)` are interleaved rather
+//! than concatenated, so every output byte depends on the full round
+//! count.
+//!
+//! ## Structure
+//!
+//! - `blowfish`: the Blowfish cipher and the EksBlowfish key schedule
+//! - `core`: the KDF itself
+
+pub(crate) mod blowfish;
+pub mod core;
@@ -0,0 +1,176 @@
+//! HMAC-SHA256 and HKDF-SHA256 implementation.
+//!
+//! All functions operate on caller-provided buffers and perform no heap
+//! allocations. The incremental `HmacSha256` state is also used inside
+//! the crate to MAC multi-part inputs (labels, prefixes and counters)
+//! without concatenating them first.
+
+use crate::hash::sha256::core::Sha256;
+
+/// SHA-256 block size in bytes.
+const BLOCK_LEN: usize = 64;
+
+/// Length of an HMAC-SHA256 output and of an HKDF pseudorandom key.
+pub const HASH_LEN: usize = 32;
+
+/// Maximum number of bytes HKDF-SHA256 can expand to (`255 * HashLen`).
+pub const MAX_OUTPUT_LEN: usize = 255 * HASH_LEN;
+
+/// Errors that can occur during HKDF expansion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HkdfError {
+    /// The requested output is longer than [`MAX_OUTPUT_LEN`].
+    OutputTooLong,
+}
+
+/// Incremental HMAC-SHA256 state.
+///
+/// The key is hashed first if it is longer than a SHA-256 block, then
+/// padded and XORed with the inner and outer pads as described in
+/// RFC 2104.
+#[derive(Clone)]
+pub(crate) struct HmacSha256 {
+    /// Hash of `(key ^ ipad) || message`.
+    inner: Sha256,
+
+    /// Hash state already absorbing `key ^ opad`.
+    outer: Sha256,
+}
+
+impl HmacSha256 {
+    /// Creates a new HMAC state keyed with `key`.
+    pub(crate) fn new(key: &[u8]) -> Self {
+        let mut block = [0u8; BLOCK_LEN];
+
+        if key.len() > BLOCK_LEN {
+            let mut h = Sha256::new();
+            h.update(key);
+            block[..HASH_LEN].copy_from_slice(&h.finalize());
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+
+        let mut pad = [0u8; BLOCK_LEN];
+
+        for (p, k) in pad.iter_mut().zip(block.iter()) {
+            *p = k ^ 0x36;
+        }
+        let mut inner = Sha256::new();
+        inner.update(&pad);
+
+        for (p, k) in pad.iter_mut().zip(block.iter()) {
+            *p = k ^ 0x5c;
+        }
+        let mut outer = Sha256::new();
+        outer.update(&pad);
+
+        block.fill(0);
+        pad.fill(0);
+
+        Self { inner, outer }
+    }
+
+    /// Absorbs additional message data.
+    pub(crate) fn update(&mut self, input: &[u8]) {
+        self.inner.update(input);
+    }
+
+    /// Finishes the computation and returns the 32-byte tag.
+    pub(crate) fn finalize(self) -> [u8; HASH_LEN] {
+        let mut inner = self.inner.finalize();
+
+        let mut outer = self.outer;
+        outer.update(&inner);
+        inner.fill(0);
+
+        outer.finalize()
+    }
+}
+
+/// Computes HMAC-SHA256 of `message` under `key` (RFC 2104).
+///
+/// # Parameters
+///
+/// - `key`: MAC key of any length; keys longer than 64 bytes are hashed
+/// - `message`: data to authenticate
+///
+/// # Returns
+///
+/// The 32-byte authentication tag.
+///
+/// # Security Notes
+///
+/// Tags must be compared in constant time by the caller.
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; HASH_LEN] {
+    let mut mac = HmacSha256::new(key);
+    mac.update(message);
+    mac.finalize()
+}
+
+/// HKDF-Extract: derives a pseudorandom key from input keying material.
+///
+/// An empty `salt` is treated as `HashLen` zero bytes, as specified by
+/// RFC 5869 (both produce the same HMAC key).
+pub fn hkdf_extract(salt: &[u8], ikm: &[u8]) -> [u8; HASH_LEN] {
+    hmac_sha256(salt, ikm)
+}
+
+/// HKDF-Expand: fills `okm` with output keying material.
+///
+/// # Parameters
+///
+/// - `prk`: pseudorandom key, typically the output of [`hkdf_extract`]
+/// - `info`: context and application specific information
+/// - `okm`: output buffer, at most [`MAX_OUTPUT_LEN`] bytes
+///
+/// # Returns
+///
+/// - `Ok(())` if `okm` was filled
+/// - `Err(OutputTooLong)` if `okm` is longer than [`MAX_OUTPUT_LEN`]
+pub fn hkdf_expand(prk: &[u8; HASH_LEN], info: &[u8], okm: &mut [u8]) -> Result<(), HkdfError> {
+    expand_parts(prk, &[info], okm)
+}
+
+/// One-shot HKDF: extract followed by expand.
+pub fn hkdf(salt: &[u8], ikm: &[u8], info: &[u8], okm: &mut [u8]) -> Result<(), HkdfError> {
+    let mut prk = hkdf_extract(salt, ikm);
+    let result = hkdf_expand(&prk, info, okm);
+    prk.fill(0);
+
+    result
+}
+
+/// HKDF-Expand with an `info` string given as several consecutive parts.
+///
+/// This is equivalent to [`hkdf_expand`] over the concatenation of `info`,
+/// and lets protocols add labels and length prefixes without allocating.
+pub(crate) fn expand_parts(
+    prk: &[u8; HASH_LEN],
+    info: &[&[u8]],
+    okm: &mut [u8],
+) -> Result<(), HkdfError> {
+    if okm.len() > MAX_OUTPUT_LEN {
+        return Err(HkdfError::OutputTooLong);
+    }
+
+    let mut t = [0u8; HASH_LEN];
+
+    for (i, chunk) in okm.chunks_mut(HASH_LEN).enumerate() {
+        let mut mac = HmacSha256::new(prk);
+
+        if i > 0 {
+            mac.update(&t);
+        }
+        for part in info {
+            mac.update(part);
+        }
+        mac.update(&[(i + 1) as u8]);
+
+        t = mac.finalize();
+        chunk.copy_from_slice(&t[..chunk.len()]);
+    }
+
+    t.fill(0);
+
+    Ok(())
+}
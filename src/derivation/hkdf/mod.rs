@@ -0,0 +1,16 @@
+//! HMAC-SHA256 and HKDF-SHA256 (RFC 2104, RFC 5869).
+//!
+//! HKDF turns input keying material that already carries enough entropy
+//! (for example a Diffie–Hellman shared secret) into one or more
+//! uniformly random keys. It proceeds in two steps:
+//!
+//! 1. **Extract**: `PRK = HMAC(salt, IKM)` concentrates the entropy of the
+//!    input into a fixed-size pseudorandom key.
+//! 2. **Expand**: `OKM = T(1) || T(2) || ...` with
+//!    `T(i) = HMAC(PRK, T(i-1) || info || i)` stretches the pseudorandom
+//!    key into as many output bytes as needed, bound to a context string.
+//!
+//! Unlike Argon2id, HKDF is fast and must **not** be used on low-entropy
+//! inputs such as passwords.
+
+pub mod core;
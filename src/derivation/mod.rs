@@ -1,4 +1,10 @@
 pub mod argon2id;
+pub mod bcrypt_pbkdf;
+pub mod hkdf;
+pub mod scrypt;
 
 pub use argon2id::core::{Argon2Error, argon2id};
 pub use argon2id::params::{Argon2ParamError, Argon2Params};
+pub use bcrypt_pbkdf::core::{BcryptPbkdfError, bcrypt_pbkdf};
+pub use hkdf::core::{HkdfError, hkdf, hkdf_expand, hkdf_extract, hmac_sha256};
+pub use scrypt::core::{ScryptError, ScryptParams, scrypt};
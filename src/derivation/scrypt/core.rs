@@ -0,0 +1,211 @@
+//! scrypt implementation.
+//!
+//! The `p` ROMix instances are computed one after the other and share a
+//! single `128 · r · N`-byte table, but the `p` blocks they mix are held
+//! at once, so memory use is `128 · r · (N + p)` bytes. Working memory is
+//! wiped before it is released.
+
+use crate::derivation::hkdf::core::{HASH_LEN, HmacSha256};
+
+/// Errors that can occur during scrypt derivation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScryptError {
+    /// `log_n` is zero, `r` or `p` is zero, `r · p ≥ 2³⁰`, or the working
+    /// memory does not fit in the address space.
+    InvalidParams,
+    /// The requested output is longer than `(2³² − 1) · 32` bytes.
+    OutputTooLong,
+}
+
+/// Cost parameters of scrypt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScryptParams {
+    /// Base-2 logarithm of the CPU/memory cost `N`.
+    pub log_n: u8,
+    /// Block size factor.
+    pub r: u32,
+    /// Parallelization factor.
+    pub p: u32,
+}
+
+impl ScryptParams {
+    /// Number of bytes of the ROMix table, `128 · r · N`.
+    fn memory(&self) -> Option<usize> {
+        1usize
+            .checked_shl(self.log_n as u32)
+            .and_then(|n| n.checked_mul(self.block_len()?))
+    }
+
+    /// Length in bytes of one mixed block, `128 · r`.
+    fn block_len(&self) -> Option<usize> {
+        (self.r as usize).checked_mul(128)
+    }
+
+    fn validate(&self) -> Result<(), ScryptError> {
+        if self.log_n == 0 || self.log_n >= 64 || self.r == 0 || self.p == 0 {
+            return Err(ScryptError::InvalidParams);
+        }
+
+        if (self.r as u64) * (self.p as u64) >= 1 << 30 || self.memory().is_none() {
+            return Err(ScryptError::InvalidParams);
+        }
+
+        Ok(())
+    }
+}
+
+/// Derives `out.len()` bytes from `password` and `salt` with scrypt.
+///
+/// # Parameters
+///
+/// - `password`: the password, of any length
+/// - `salt`: the salt, of any length
+/// - `params`: cost parameters `N = 2^log_n`, `r` and `p`
+/// - `out`: output buffer
+///
+/// # Returns
+///
+/// - `Ok(())` if `out` was filled
+/// - `Err(InvalidParams)` if the parameters are out of range
+/// - `Err(OutputTooLong)` if `out` exceeds the PBKDF2 output limit
+///
+/// # Panics
+///
+/// Panics if the `128 · r · N` bytes of working memory cannot be allocated.
+pub fn scrypt(
+    password: &[u8],
+    salt: &[u8],
+    params: &ScryptParams,
+    out: &mut [u8],
+) -> Result<(), ScryptError> {
+    params.validate()?;
+
+    if out.len() as u64 > u32::MAX as u64 * HASH_LEN as u64 {
+        return Err(ScryptError::OutputTooLong);
+    }
+
+    // Both checked by `validate`.
+    let block_len = params.block_len().unwrap();
+    let words = block_len / 4;
+    let n = 1usize << params.log_n;
+
+    let mut b = vec![0u8; block_len * params.p as usize];
+    pbkdf2(password, salt, &mut b);
+
+    let mut x = vec![0u32; words];
+    let mut v = vec![0u32; words * n];
+    let mut scratch = vec![0u32; words];
+
+    for chunk in b.chunks_exact_mut(block_len) {
+        for (word, bytes) in x.iter_mut().zip(chunk.chunks_exact(4)) {
+            *word = u32::from_le_bytes(bytes.try_into().unwrap());
+        }
+
+        romix(&mut x, &mut v, &mut scratch);
+
+        for (bytes, word) in chunk.chunks_exact_mut(4).zip(&x) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+    }
+
+    pbkdf2(password, &b, out);
+
+    b.fill(0);
+    x.fill(0);
+    v.fill(0);
+    scratch.fill(0);
+
+    Ok(())
+}
+
+/// PBKDF2-HMAC-SHA256 with a single iteration, as used by scrypt.
+fn pbkdf2(password: &[u8], salt: &[u8], out: &mut [u8]) {
+    let mut keyed = HmacSha256::new(password);
+    keyed.update(salt);
+
+    for (i, chunk) in out.chunks_mut(HASH_LEN).enumerate() {
+        let mut mac = keyed.clone();
+        mac.update(&(i as u32 + 1).to_be_bytes());
+
+        let mut block = mac.finalize();
+        chunk.copy_from_slice(&block[..chunk.len()]);
+        block.fill(0);
+    }
+}
+
+/// ROMix: fills `v` with `N` successive BlockMix outputs of `x`, then mixes
+/// `x` with `N` entries of `v` selected by `Integerify(x)`.
+fn romix(x: &mut [u32], v: &mut [u32], scratch: &mut [u32]) {
+    let words = x.len();
+    let n = v.len() / words;
+
+    for entry in v.chunks_exact_mut(words) {
+        entry.copy_from_slice(x);
+        block_mix(x, scratch);
+    }
+
+    for _ in 0..n {
+        // Integerify: first word of the last 64-byte sub-block, modulo N
+        // (a power of two).
+        let j = x[words - 16] as usize & (n - 1);
+
+        for (a, b) in x.iter_mut().zip(&v[j * words..(j + 1) * words]) {
+            *a ^= b;
+        }
+
+        block_mix(x, scratch);
+    }
+}
+
+/// BlockMix-Salsa20/8 over the `2r` sub-blocks of `b`.
+///
+/// Outputs are written with even sub-blocks first, then odd ones.
+fn block_mix(b: &mut [u32], scratch: &mut [u32]) {
+    let blocks = b.len() / 16;
+    let half = blocks / 2;
+
+    let mut x: [u32; 16] = b[b.len() - 16..].try_into().unwrap();
+
+    for i in 0..blocks {
+        for (a, c) in x.iter_mut().zip(&b[16 * i..16 * i + 16]) {
+            *a ^= c;
+        }
+
+        salsa20_8(&mut x);
+
+        let dest = if i % 2 == 0 { i / 2 } else { half + i / 2 };
+        scratch[16 * dest..16 * dest + 16].copy_from_slice(&x);
+    }
+
+    b.copy_from_slice(scratch);
+}
+
+/// The Salsa20/8 core: 4 double rounds followed by the feed-forward.
+fn salsa20_8(block: &mut [u32; 16]) {
+    let mut x = *block;
+
+    for _ in 0..4 {
+        quarter_round(&mut x, 0, 4, 8, 12);
+        quarter_round(&mut x, 5, 9, 13, 1);
+        quarter_round(&mut x, 10, 14, 2, 6);
+        quarter_round(&mut x, 15, 3, 7, 11);
+
+        quarter_round(&mut x, 0, 1, 2, 3);
+        quarter_round(&mut x, 5, 6, 7, 4);
+        quarter_round(&mut x, 10, 11, 8, 9);
+        quarter_round(&mut x, 15, 12, 13, 14);
+    }
+
+    for (b, x) in block.iter_mut().zip(&x) {
+        *b = b.wrapping_add(*x);
+    }
+}
+
+/// One Salsa20 quarter round.
+#[inline(always)]
+fn quarter_round(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    x[b] ^= x[a].wrapping_add(x[d]).rotate_left(7);
+    x[c] ^= x[b].wrapping_add(x[a]).rotate_left(9);
+    x[d] ^= x[c].wrapping_add(x[b]).rotate_left(13);
+    x[a] ^= x[d].wrapping_add(x[c]).rotate_left(18);
+}
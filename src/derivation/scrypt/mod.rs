@@ -0,0 +1,19 @@
+//! scrypt password-based key derivation function (RFC 7914).
+//!
+//! scrypt is the memory-hard KDF used by libsodium's
+//! `crypto_pwhash_scryptsalsa208sha256`, and therefore by file formats
+//! built on it such as minisign secret keys. New designs should prefer
+//! Argon2id; scrypt is provided for interoperability.
+//!
+//! # Algorithm Overview
+//!
+//! 1. **Expansion**: `B = PBKDF2-HMAC-SHA256(password, salt, 1, p · 128 · r)`
+//! 2. **Mixing**: each of the `p` blocks of `128 · r` bytes is processed by
+//!    ROMix, which fills a table of `N` successive BlockMix outputs and then
+//!    reads it back `N` times at data-dependent positions.
+//! 3. **Compression**: `DK = PBKDF2-HMAC-SHA256(password, B, 1, dkLen)`
+//!
+//! BlockMix is built on the Salsa20/8 core. Memory use is `128 · r · N`
+//! bytes.
+
+pub mod core;
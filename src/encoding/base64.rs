@@ -0,0 +1,153 @@
+//! Base64 encoding (RFC 4648).
+//!
+//! Encoding and strict decoding of the standard Base64 alphabet and of the
+//! URL and filename safe alphabet (base64url, section 5), with or without
+//! `=` padding.
+//!
+//! ## Canonical decoding
+//!
+//! A Base64 string whose last character carries unused bits can have
+//! several spellings for the same bytes. The decoder only accepts the
+//! spelling where those bits are zero, and rejects padding where it is
+//! not expected (and vice versa).
+
+/// Base64 flavour used for encoding and decoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// Standard alphabet (`+`, `/`) with `=` padding.
+    Standard,
+    /// Standard alphabet (`+`, `/`) without padding.
+    StandardNoPad,
+    /// URL-safe alphabet (`-`, `_`) with `=` padding.
+    UrlSafe,
+    /// URL-safe alphabet (`-`, `_`) without padding, as used by JOSE.
+    UrlSafeNoPad,
+}
+
+/// Errors that can occur while decoding Base64.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base64Error {
+    /// The input length is not valid for the variant.
+    InvalidLength,
+    /// The input contains a character outside the alphabet.
+    InvalidCharacter,
+    /// Padding is missing, misplaced or not allowed.
+    InvalidPadding,
+    /// The unused bits of the last character are not zero.
+    NonCanonical,
+}
+
+impl Variant {
+    fn alphabet(self) -> &'static [u8; 64] {
+        match self {
+            Variant::Standard | Variant::StandardNoPad => {
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"
+            }
+            Variant::UrlSafe | Variant::UrlSafeNoPad => {
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"
+            }
+        }
+    }
+
+    fn padded(self) -> bool {
+        matches!(self, Variant::Standard | Variant::UrlSafe)
+    }
+
+    /// Maps a character back to its 6-bit value.
+    fn value(self, c: u8) -> Option<u8> {
+        let alphabet = self.alphabet();
+
+        match c {
+            b'A'..=b'Z' => Some(c - b'A'),
+            b'a'..=b'z' => Some(c - b'a' + 26),
+            b'0'..=b'9' => Some(c - b'0' + 52),
+            _ if c == alphabet[62] => Some(62),
+            _ if c == alphabet[63] => Some(63),
+            _ => None,
+        }
+    }
+}
+
+/// Encodes `input` as Base64.
+pub fn encode(input: &[u8], variant: Variant) -> String {
+    let alphabet = variant.alphabet();
+    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+
+    for chunk in input.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+
+        let chars = chunk.len() + 1;
+        for i in 0..4 {
+            if i < chars {
+                out.push(alphabet[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else if variant.padded() {
+                out.push('=');
+            }
+        }
+    }
+
+    out
+}
+
+/// Decodes a Base64 string, rejecting non-canonical encodings.
+///
+/// # Returns
+///
+/// - `Ok(bytes)` on success
+/// - `Err(InvalidLength)` if the length cannot be produced by the encoder
+/// - `Err(InvalidCharacter)` if a character is outside the alphabet
+/// - `Err(InvalidPadding)` if padding is wrong for the variant
+/// - `Err(NonCanonical)` if the trailing unused bits are not zero
+pub fn decode(input: &str, variant: Variant) -> Result<Vec<u8>, Base64Error> {
+    let mut data = input.as_bytes();
+
+    if variant.padded() {
+        if !data.len().is_multiple_of(4) {
+            return Err(Base64Error::InvalidLength);
+        }
+
+        let pad = data
+            .iter()
+            .rev()
+            .take(2)
+            .take_while(|&&c| c == b'=')
+            .count();
+        data = &data[..data.len() - pad];
+    }
+
+    if data.len() % 4 == 1 {
+        return Err(Base64Error::InvalidLength);
+    }
+
+    let mut out = Vec::with_capacity(data.len() * 3 / 4);
+
+    for chunk in data.chunks(4) {
+        let mut n = 0u32;
+
+        for (i, &c) in chunk.iter().enumerate() {
+            let v = match variant.value(c) {
+                Some(v) => v,
+                None if c == b'=' => return Err(Base64Error::InvalidPadding),
+                None => return Err(Base64Error::InvalidCharacter),
+            };
+            n |= (v as u32) << (18 - 6 * i);
+        }
+
+        let bytes = n.to_be_bytes();
+        let len = chunk.len() - 1;
+
+        // Bits below the last full byte must be zero.
+        if len < 3 && n & (0x00ff_ffff >> (8 * len)) != 0 {
+            return Err(Base64Error::NonCanonical);
+        }
+
+        out.extend_from_slice(&bytes[1..1 + len]);
+    }
+
+    Ok(out)
+}
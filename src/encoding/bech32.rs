@@ -0,0 +1,165 @@
+//! Bech32 encoding (BIP 173).
+//!
+//! Bech32 strings consist of a human-readable part (HRP), the separator
+//! `1`, and a data part carrying 5 bits per character followed by a
+//! 6-character BCH checksum. The checksum detects any error affecting up
+//! to four characters.
+//!
+//! ## Notes
+//!
+//! - This is the original Bech32 checksum (constant `1`), not Bech32m.
+//! - No length limit is enforced, as formats such as age use Bech32 for
+//!   payloads longer than BIP 173 addresses.
+//! - Strings must be entirely lowercase or entirely uppercase.
+
+/// Data part alphabet.
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Errors that can occur while decoding Bech32.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bech32Error {
+    /// The string has no `1` separator, an empty HRP or a short data part.
+    InvalidFormat,
+    /// The string mixes uppercase and lowercase characters.
+    MixedCase,
+    /// A character is outside the allowed range or alphabet.
+    InvalidCharacter,
+    /// The checksum does not match.
+    InvalidChecksum,
+    /// The data part does not convert back to whole bytes.
+    InvalidPadding,
+}
+
+/// BCH checksum polynomial modulus.
+fn polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GEN: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+    let mut chk = 1u32;
+    for v in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ff_ffff) << 5) ^ v as u32;
+
+        for (i, g) in GEN.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+
+    chk
+}
+
+/// Expands the HRP for checksum computation.
+fn hrp_expand(hrp: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    hrp.iter()
+        .map(|c| c >> 5)
+        .chain(core::iter::once(0))
+        .chain(hrp.iter().map(|c| c & 31))
+}
+
+/// Regroups bits from `from`-bit to `to`-bit values.
+///
+/// When `pad` is false, leftover bits must be fewer than `from` and zero.
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, Bech32Error> {
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    let max = (1u32 << to) - 1;
+    let mut out = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+
+    for &v in data {
+        acc = (acc << from) | v as u32;
+        bits += from;
+
+        while bits >= to {
+            bits -= to;
+            out.push(((acc >> bits) & max) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            out.push(((acc << (to - bits)) & max) as u8);
+        }
+    } else if bits >= from || (acc << (to - bits)) & max != 0 {
+        return Err(Bech32Error::InvalidPadding);
+    }
+
+    Ok(out)
+}
+
+/// Encodes `data` with the human-readable part `hrp`.
+///
+/// The result is lowercase; callers that need the uppercase form (e.g.
+/// age identities) can uppercase it without affecting the checksum.
+///
+/// # Panics
+///
+/// Panics if `hrp` is empty or contains characters outside `!`..=`~`.
+pub fn encode(hrp: &str, data: &[u8]) -> String {
+    let hrp = hrp.to_ascii_lowercase();
+    assert!(!hrp.is_empty() && hrp.bytes().all(|c| (33..=126).contains(&c)));
+
+    let values = convert_bits(data, 8, 5, true).unwrap();
+
+    let checksum = polymod(
+        hrp_expand(hrp.as_bytes())
+            .chain(values.iter().copied())
+            .chain([0u8; 6]),
+    ) ^ 1;
+
+    let mut out = String::with_capacity(hrp.len() + 1 + values.len() + 6);
+    out.push_str(&hrp);
+    out.push('1');
+
+    for v in values {
+        out.push(CHARSET[v as usize] as char);
+    }
+    for i in 0..6 {
+        out.push(CHARSET[((checksum >> (5 * (5 - i))) & 31) as usize] as char);
+    }
+
+    out
+}
+
+/// Decodes a Bech32 string into its lowercase HRP and data bytes.
+///
+/// # Returns
+///
+/// - `Ok((hrp, data))` on success
+/// - `Err(_)` describing the first problem found
+pub fn decode(s: &str) -> Result<(String, Vec<u8>), Bech32Error> {
+    if s.bytes().any(|c| !(33..=126).contains(&c)) {
+        return Err(Bech32Error::InvalidCharacter);
+    }
+
+    let has_lower = s.bytes().any(|c| c.is_ascii_lowercase());
+    let has_upper = s.bytes().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper {
+        return Err(Bech32Error::MixedCase);
+    }
+
+    let s = s.to_ascii_lowercase();
+    let sep = s.rfind('1').ok_or(Bech32Error::InvalidFormat)?;
+    let (hrp, data) = (&s[..sep], &s.as_bytes()[sep + 1..]);
+
+    if hrp.is_empty() || data.len() < 6 {
+        return Err(Bech32Error::InvalidFormat);
+    }
+
+    let mut values = Vec::with_capacity(data.len());
+    for &c in data {
+        let v = CHARSET
+            .iter()
+            .position(|&x| x == c)
+            .ok_or(Bech32Error::InvalidCharacter)?;
+        values.push(v as u8);
+    }
+
+    if polymod(hrp_expand(hrp.as_bytes()).chain(values.iter().copied())) != 1 {
+        return Err(Bech32Error::InvalidChecksum);
+    }
+
+    let bytes = convert_bits(&values[..values.len() - 6], 5, 8, false)?;
+
+    Ok((hrp.to_string(), bytes))
+}
@@ -0,0 +1,144 @@
+//! Minimal DER encoding (ITU-T X.690).
+//!
+//! Only what key formats such as PKCS#8 and SubjectPublicKeyInfo need:
+//! single-byte tags and definite lengths. There is no schema support;
+//! callers walk the structure with a [`Reader`] and build it with
+//! [`write()`].
+//!
+//! ## Canonical decoding
+//!
+//! DER has exactly one encoding per value. The reader rejects lengths in
+//! long form when the short form fits, lengths with leading zero bytes,
+//! and indefinite lengths.
+
+/// Tag of an ASN.1 `INTEGER`.
+pub const INTEGER: u8 = 0x02;
+
+/// Tag of an ASN.1 `BIT STRING`.
+pub const BIT_STRING: u8 = 0x03;
+
+/// Tag of an ASN.1 `OCTET STRING`.
+pub const OCTET_STRING: u8 = 0x04;
+
+/// Tag of an ASN.1 `OBJECT IDENTIFIER`.
+pub const OBJECT_IDENTIFIER: u8 = 0x06;
+
+/// Tag of an ASN.1 `SEQUENCE`.
+pub const SEQUENCE: u8 = 0x30;
+
+/// Errors that can occur while decoding DER.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DerError {
+    /// The input ends in the middle of an element.
+    Truncated,
+    /// The element does not have the expected tag.
+    UnexpectedTag,
+    /// The length is indefinite, not minimally encoded or too large.
+    InvalidLength,
+    /// Bytes remain after the last expected element.
+    TrailingData,
+}
+
+/// Sequential reader over DER elements.
+pub struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Creates a reader over `data`.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Returns the tag of the next element, if any.
+    pub fn peek_tag(&self) -> Option<u8> {
+        self.data.first().copied()
+    }
+
+    /// Returns `true` once every element has been read.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Reads the next element, which must have tag `tag`, and returns its
+    /// contents.
+    pub fn read(&mut self, tag: u8) -> Result<&'a [u8], DerError> {
+        let (&found, rest) = self.data.split_first().ok_or(DerError::Truncated)?;
+        if found != tag {
+            return Err(DerError::UnexpectedTag);
+        }
+
+        let (&first, mut rest) = rest.split_first().ok_or(DerError::Truncated)?;
+
+        let len = if first < 0x80 {
+            first as usize
+        } else {
+            // 0x80 is the BER indefinite form; DER lengths here fit in 4 bytes.
+            let count = (first & 0x7f) as usize;
+            if count == 0 || count > 4 {
+                return Err(DerError::InvalidLength);
+            }
+            if rest.len() < count {
+                return Err(DerError::Truncated);
+            }
+
+            let (bytes, tail) = rest.split_at(count);
+            if bytes[0] == 0 {
+                return Err(DerError::InvalidLength);
+            }
+
+            let len = bytes.iter().fold(0usize, |acc, &b| (acc << 8) | b as usize);
+            if len < 0x80 {
+                return Err(DerError::InvalidLength);
+            }
+
+            rest = tail;
+            len
+        };
+
+        if rest.len() < len {
+            return Err(DerError::Truncated);
+        }
+
+        let (contents, tail) = rest.split_at(len);
+        self.data = tail;
+
+        Ok(contents)
+    }
+
+    /// Reads the next element if it has tag `tag`.
+    pub fn read_optional(&mut self, tag: u8) -> Result<Option<&'a [u8]>, DerError> {
+        if self.peek_tag() == Some(tag) {
+            self.read(tag).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Checks that every element has been read.
+    pub fn finish(self) -> Result<(), DerError> {
+        if self.data.is_empty() {
+            Ok(())
+        } else {
+            Err(DerError::TrailingData)
+        }
+    }
+}
+
+/// Appends the element `tag || length || contents` to `out`.
+pub fn write(tag: u8, contents: &[u8], out: &mut Vec<u8>) {
+    out.push(tag);
+
+    let len = contents.len();
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes = (len as u32).to_be_bytes();
+        let skip = bytes.iter().take_while(|&&b| b == 0).count();
+
+        out.push(0x80 | (4 - skip) as u8);
+        out.extend_from_slice(&bytes[skip..]);
+    }
+
+    out.extend_from_slice(contents);
+}
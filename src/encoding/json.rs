@@ -0,0 +1,477 @@
+//! JSON (RFC 8259) values.
+//!
+//! A small JSON model for the structures the crate exchanges with other
+//! systems, such as JOSE headers, claims and keys. Objects keep their
+//! members in order and numbers are held as `f64`, which represents every
+//! integer up to 2⁵³ exactly.
+//!
+//! ## Decoding
+//!
+//! The parser is strict: it rejects trailing data, duplicate member names
+//! (which would let two consumers disagree on a value), invalid escapes,
+//! unpaired surrogates and nesting deeper than 64 levels.
+//!
+//! ## Encoding
+//!
+//! Output is compact, without insignificant whitespace. Integral numbers
+//! are written without a fraction, and non-finite numbers as `null`.
+
+use std::collections::HashSet;
+
+/// Maximum nesting depth of arrays and objects accepted by the parser.
+const MAX_DEPTH: usize = 64;
+
+/// Largest magnitude below which integral numbers are written as integers.
+pub(crate) const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_992.0;
+
+/// Errors that can occur while parsing JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonError {
+    /// The input ends in the middle of a value.
+    UnexpectedEnd,
+    /// A character is not valid at its position.
+    UnexpectedCharacter,
+    /// A number does not follow the JSON grammar or is out of range.
+    InvalidNumber,
+    /// A string contains a control character, an invalid escape or an
+    /// unpaired surrogate.
+    InvalidString,
+    /// An object contains the same member name twice.
+    DuplicateKey,
+    /// Arrays and objects are nested too deeply.
+    TooDeep,
+    /// Non-whitespace data follows the value.
+    TrailingData,
+}
+
+/// A JSON value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// `null`.
+    Null,
+    /// `true` or `false`.
+    Bool(bool),
+    /// A number.
+    Number(f64),
+    /// A string.
+    String(String),
+    /// An array.
+    Array(Vec<Value>),
+    /// An object, with members in document order.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Parses a complete JSON document.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(value)` on success
+    /// - `Err(JsonError)` describing the first error found
+    pub fn parse(input: &str) -> Result<Self, JsonError> {
+        let mut parser = Parser {
+            input: input.as_bytes(),
+            pos: 0,
+        };
+
+        let value = parser.value(0)?;
+
+        parser.skip_whitespace();
+        if parser.pos != parser.input.len() {
+            return Err(JsonError::TrailingData);
+        }
+
+        Ok(value)
+    }
+
+    /// Encodes the value as compact JSON.
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out);
+
+        out
+    }
+
+    /// Returns the member `key` of an object.
+    ///
+    /// Returns `None` if the value is not an object or has no such member.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_object()?
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the boolean, if the value is one.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the number, if the value is one.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns the number if it is an integer that `f64` represents
+    /// exactly (magnitude at most 2⁵³).
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Number(n) if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER => Some(*n as i64),
+            _ => None,
+        }
+    }
+
+    /// Returns the string, if the value is one.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements, if the value is an array.
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the members, if the value is an object.
+    pub fn as_object(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Object(members) => Some(members),
+            _ => None,
+        }
+    }
+
+    /// Appends the compact encoding of the value to `out`.
+    fn write(&self, out: &mut String) {
+        match self {
+            Value::Null => out.push_str("null"),
+            Value::Bool(true) => out.push_str("true"),
+            Value::Bool(false) => out.push_str("false"),
+            Value::Number(n) if !n.is_finite() => out.push_str("null"),
+            Value::Number(n) if n.fract() == 0.0 && n.abs() < MAX_SAFE_INTEGER => {
+                out.push_str(&(*n as i64).to_string())
+            }
+            Value::Number(n) => out.push_str(&n.to_string()),
+            Value::String(s) => write_string(s, out),
+            Value::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    item.write(out);
+                }
+                out.push(']');
+            }
+            Value::Object(members) => {
+                out.push('{');
+                for (i, (name, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write_string(name, out);
+                    out.push(':');
+                    value.write(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(n: i64) -> Self {
+        Value::Number(n as f64)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+/// Appends `s` as a JSON string literal, escaping quotes, backslashes and
+/// control characters.
+fn write_string(s: &str, out: &mut String) {
+    out.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+}
+
+/// Recursive-descent JSON parser over the input bytes.
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Result<u8, JsonError> {
+        let c = self.peek().ok_or(JsonError::UnexpectedEnd)?;
+        self.pos += 1;
+
+        Ok(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    /// Consumes `literal`, whose first byte has already been matched.
+    fn expect(&mut self, literal: &[u8]) -> Result<(), JsonError> {
+        let end = self.pos + literal.len() - 1;
+        match self.input.get(self.pos..end) {
+            Some(rest) if rest == &literal[1..] => {
+                self.pos = end;
+                Ok(())
+            }
+            Some(_) => Err(JsonError::UnexpectedCharacter),
+            None => Err(JsonError::UnexpectedEnd),
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, JsonError> {
+        self.skip_whitespace();
+
+        match self.next()? {
+            b'n' => self.expect(b"null").map(|()| Value::Null),
+            b't' => self.expect(b"true").map(|()| Value::Bool(true)),
+            b'f' => self.expect(b"false").map(|()| Value::Bool(false)),
+            b'"' => self.string().map(Value::String),
+            b'-' | b'0'..=b'9' => {
+                self.pos -= 1;
+                self.number().map(Value::Number)
+            }
+            b'[' if depth < MAX_DEPTH => self.array(depth + 1),
+            b'{' if depth < MAX_DEPTH => self.object(depth + 1),
+            b'[' | b'{' => Err(JsonError::TooDeep),
+            _ => Err(JsonError::UnexpectedCharacter),
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Value, JsonError> {
+        let mut items = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Value::Array(items));
+        }
+
+        loop {
+            items.push(self.value(depth)?);
+
+            self.skip_whitespace();
+            match self.next()? {
+                b',' => {}
+                b']' => return Ok(Value::Array(items)),
+                _ => return Err(JsonError::UnexpectedCharacter),
+            }
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<Value, JsonError> {
+        let mut members: Vec<(String, Value)> = Vec::new();
+        let mut names = HashSet::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Value::Object(members));
+        }
+
+        loop {
+            self.skip_whitespace();
+            if self.next()? != b'"' {
+                return Err(JsonError::UnexpectedCharacter);
+            }
+
+            let name = self.string()?;
+            if !names.insert(name.clone()) {
+                return Err(JsonError::DuplicateKey);
+            }
+
+            self.skip_whitespace();
+            if self.next()? != b':' {
+                return Err(JsonError::UnexpectedCharacter);
+            }
+
+            let value = self.value(depth)?;
+            members.push((name, value));
+
+            self.skip_whitespace();
+            match self.next()? {
+                b',' => {}
+                b'}' => return Ok(Value::Object(members)),
+                _ => return Err(JsonError::UnexpectedCharacter),
+            }
+        }
+    }
+
+    /// Parses a string body, after its opening quote.
+    fn string(&mut self) -> Result<String, JsonError> {
+        let mut out = Vec::new();
+
+        loop {
+            match self.next()? {
+                b'"' => break,
+                b'\\' => {
+                    let c = match self.next()? {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{08}',
+                        b'f' => '\u{0c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.unicode_escape()?,
+                        _ => return Err(JsonError::InvalidString),
+                    };
+
+                    let mut buf = [0u8; 4];
+                    out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                }
+                c if c < 0x20 => return Err(JsonError::InvalidString),
+                c => out.push(c),
+            }
+        }
+
+        // The input is a `&str` and escapes are pushed as UTF-8, so the
+        // bytes are valid UTF-8.
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    /// Parses the `XXXX` of a `\u` escape, and the low surrogate escape
+    /// that must follow a high surrogate.
+    fn unicode_escape(&mut self) -> Result<char, JsonError> {
+        let high = self.hex4()?;
+
+        let code = match high {
+            0xd800..=0xdbff => {
+                if self.next()? != b'\\' || self.next()? != b'u' {
+                    return Err(JsonError::InvalidString);
+                }
+
+                let low = self.hex4()?;
+                if !(0xdc00..=0xdfff).contains(&low) {
+                    return Err(JsonError::InvalidString);
+                }
+
+                0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+            }
+            0xdc00..=0xdfff => return Err(JsonError::InvalidString),
+            _ => high,
+        };
+
+        char::from_u32(code).ok_or(JsonError::InvalidString)
+    }
+
+    fn hex4(&mut self) -> Result<u32, JsonError> {
+        let mut code = 0;
+
+        for _ in 0..4 {
+            let digit = (self.next()? as char)
+                .to_digit(16)
+                .ok_or(JsonError::InvalidString)?;
+            code = (code << 4) | digit;
+        }
+
+        Ok(code)
+    }
+
+    fn number(&mut self) -> Result<f64, JsonError> {
+        let start = self.pos;
+
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+
+        match self.next().map_err(|_| JsonError::InvalidNumber)? {
+            b'0' => {}
+            b'1'..=b'9' => self.digits(),
+            _ => return Err(JsonError::InvalidNumber),
+        }
+
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            self.required_digits()?;
+        }
+
+        if let Some(b'e' | b'E') = self.peek() {
+            self.pos += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.pos += 1;
+            }
+            self.required_digits()?;
+        }
+
+        // The accepted bytes are ASCII digits, signs, `.` and `e`.
+        let text = core::str::from_utf8(&self.input[start..self.pos]).unwrap();
+        let n: f64 = text.parse().map_err(|_| JsonError::InvalidNumber)?;
+
+        if n.is_finite() {
+            Ok(n)
+        } else {
+            Err(JsonError::InvalidNumber)
+        }
+    }
+
+    fn digits(&mut self) {
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn required_digits(&mut self) -> Result<(), JsonError> {
+        match self.peek() {
+            Some(b'0'..=b'9') => {
+                self.digits();
+                Ok(())
+            }
+            _ => Err(JsonError::InvalidNumber),
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! Text encodings for keys, signatures and file formats.
+//!
+//! This module provides the binary-to-text encodings required by the
+//! interoperable formats implemented in the crate:
+//!
+//! - `base64`: RFC 4648 Base64 and base64url, with and without padding
+//! - `bech32`: BIP 173 Bech32, as used by age keys
+//! - `der`: the subset of ASN.1 DER used by PKCS#8 and SPKI key formats
+//! - `json`: RFC 8259 JSON values, as used by JOSE
+//! - `pem`: RFC 7468 PEM armor around DER structures
+//!
+//! The `base64`, `bech32` and `der` decoders are strict: they reject any
+//! input that the matching encoder would not have produced (non-canonical
+//! trailing bits, stray padding, mixed case, ...). Cryptographic formats
+//! rely on this to avoid malleable encodings. The PEM armor is lenient
+//! about line endings and line lengths, but its Base64 body is decoded
+//! strictly.
+
+pub mod base64;
+pub mod bech32;
+pub mod der;
+pub mod json;
+pub mod pem;
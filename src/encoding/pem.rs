@@ -0,0 +1,109 @@
+//! PEM armor (RFC 7468).
+//!
+//! Wraps binary data (typically DER) between `-----BEGIN <label>-----` and
+//! `-----END <label>-----` lines, with the Base64 body split into lines of
+//! 64 characters.
+//!
+//! ## Decoding
+//!
+//! The decoder accepts LF and CRLF line endings, whitespace around the
+//! block and body lines of any length, as produced by common tools. The
+//! Base64 body itself is decoded strictly, and explanatory headers
+//! (RFC 1421 `Proc-Type:` lines) are rejected.
+
+use crate::encoding::base64::{self, Base64Error, Variant};
+
+/// Errors that can occur while decoding PEM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PemError {
+    /// The `-----BEGIN <label>-----` line is missing or malformed.
+    MissingHeader,
+    /// The `-----END <label>-----` line is missing or has another label.
+    MissingFooter,
+    /// The body is not valid Base64.
+    InvalidBase64(Base64Error),
+}
+
+const BEGIN: &str = "-----BEGIN ";
+const END: &str = "-----END ";
+const DASHES: &str = "-----";
+
+/// Length of the Base64 lines written by [`encode`].
+const LINE_LEN: usize = 64;
+
+/// Encodes `data` as a PEM block with the given label.
+pub fn encode(label: &str, data: &[u8]) -> String {
+    encode_with_width(label, data, LINE_LEN)
+}
+
+/// Encodes `data` as a PEM-style block with Base64 lines of `width`
+/// characters.
+///
+/// RFC 7468 mandates 64-character lines; some formats using the same armor
+/// (OpenSSH private keys and signatures) wrap at 70 characters instead.
+///
+/// # Panics
+///
+/// Panics if `width` is zero.
+pub fn encode_with_width(label: &str, data: &[u8], width: usize) -> String {
+    let body = base64::encode(data, Variant::Standard);
+
+    let mut out = String::with_capacity(body.len() + body.len() / width + 2 * label.len() + 40);
+    out.push_str(BEGIN);
+    out.push_str(label);
+    out.push_str(DASHES);
+    out.push('\n');
+
+    for line in body.as_bytes().chunks(width) {
+        // Base64 output is ASCII, so every chunk is valid UTF-8.
+        out.push_str(core::str::from_utf8(line).unwrap());
+        out.push('\n');
+    }
+
+    out.push_str(END);
+    out.push_str(label);
+    out.push_str(DASHES);
+    out.push('\n');
+
+    out
+}
+
+/// Decodes a single PEM block.
+///
+/// # Returns
+///
+/// - `Ok((label, data))` on success
+/// - `Err(PemError)` if the armor or the Base64 body is malformed
+pub fn decode(input: &str) -> Result<(String, Vec<u8>), PemError> {
+    let mut lines = input.trim().lines().map(str::trim_end);
+
+    let label = lines
+        .next()
+        .and_then(|line| line.strip_prefix(BEGIN))
+        .and_then(|line| line.strip_suffix(DASHES))
+        .ok_or(PemError::MissingHeader)?;
+
+    let mut body = String::new();
+    let mut closed = false;
+
+    for line in lines.by_ref() {
+        if let Some(rest) = line.strip_prefix(END) {
+            if rest.strip_suffix(DASHES) != Some(label) {
+                return Err(PemError::MissingFooter);
+            }
+
+            closed = true;
+            break;
+        }
+
+        body.push_str(line);
+    }
+
+    if !closed || lines.next().is_some() {
+        return Err(PemError::MissingFooter);
+    }
+
+    let data = base64::decode(&body, Variant::Standard).map_err(PemError::InvalidBase64)?;
+
+    Ok((label.to_string(), data))
+}
@@ -0,0 +1,151 @@
+//! Key-committing ChaCha20-Poly1305.
+//!
+//! This module turns ChaCha20-Poly1305 into a key-committing AEAD by
+//! prepending a 256-bit commitment to the ciphertext.
+//!
+//! ## Construction
+//!
+//! For a master key `K` and nonce `N`:
+//!
+//! ```text
+//! commitment = Blake2b-256(key = K, "Cryptal AEAD commit" || N)
+//! subkey     = Blake2b-256(key = K, "Cryptal AEAD subkey" || N)
+//! C, T       = ChaCha20-Poly1305(subkey, N, AAD, P)
+//! output     = commitment || C || T
+//! ```
+//!
+//! Decryption recomputes the commitment from the candidate key and rejects
+//! the input before any AEAD processing if it does not match. Finding two
+//! keys that open the same output would require a Blake2b collision.
+//!
+//! ## Notes
+//!
+//! - The output is 48 bytes longer than the plaintext.
+//! - Nonce uniqueness requirements are those of ChaCha20-Poly1305.
+//! - Failures are reported uniformly, whichever check rejected the input.
+
+use crate::encryption::chacha20poly1305::{decrypt_with_aad, encrypt_with_aad};
+use crate::hash::blake2b_keyed;
+
+/// Length of the key commitment prepended to the ciphertext.
+pub const COMMITMENT_LEN: usize = 32;
+
+/// Length of the Poly1305 tag appended to the ciphertext.
+pub const TAG_LEN: usize = 16;
+
+/// Total size overhead of a sealed message.
+pub const OVERHEAD: usize = COMMITMENT_LEN + TAG_LEN;
+
+/// Domain separation label for the key commitment.
+const COMMIT_LABEL: &[u8; 19] = b"Cryptal AEAD commit";
+
+/// Domain separation label for the per-nonce encryption subkey.
+const SUBKEY_LABEL: &[u8; 19] = b"Cryptal AEAD subkey";
+
+/// Errors that can occur when opening a committed ciphertext.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommittingError {
+    /// The input is too short to contain a commitment and a tag.
+    InvalidLength,
+    /// The commitment or the authentication tag did not verify.
+    AuthenticationFailed,
+}
+
+/// Encrypts and authenticates a message, committing to the key.
+///
+/// # Parameters
+///
+/// - `key`: 256-bit secret key
+/// - `nonce`: 96-bit nonce, unique per key
+/// - `aad`: Additional authenticated data (not encrypted)
+/// - `plaintext`: Input message to encrypt
+///
+/// # Returns
+///
+/// `commitment || ciphertext || tag`, i.e. `plaintext.len() + OVERHEAD`
+/// bytes.
+pub fn seal(key: &[u8; 32], nonce: &[u8; 12], aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    let mut subkey = derive(key, SUBKEY_LABEL, nonce);
+
+    let mut out = vec![0u8; plaintext.len() + OVERHEAD];
+    out[..COMMITMENT_LEN].copy_from_slice(&derive(key, COMMIT_LABEL, nonce));
+
+    let (body, tag) = out[COMMITMENT_LEN..].split_at_mut(plaintext.len());
+    let tag: &mut [u8; TAG_LEN] = tag.try_into().unwrap();
+
+    encrypt_with_aad(&subkey, nonce, aad, plaintext, body, tag);
+
+    subkey.fill(0);
+
+    out
+}
+
+/// Verifies the key commitment, then decrypts and authenticates a message.
+///
+/// # Parameters
+///
+/// - `key`: 256-bit secret key
+/// - `nonce`: 96-bit nonce used during sealing
+/// - `aad`: Additional authenticated data used during sealing
+/// - `sealed`: Output of [`seal`]
+///
+/// # Returns
+///
+/// - `Ok(plaintext)` if both the commitment and the tag verify
+/// - `Err(InvalidLength)` if `sealed` is shorter than `OVERHEAD`
+/// - `Err(AuthenticationFailed)` otherwise
+///
+/// # Security Notes
+///
+/// - A ciphertext produced under one key is rejected under any other key,
+///   even if it was crafted to carry a valid Poly1305 tag for both.
+/// - The commitment comparison is constant-time.
+pub fn open(
+    key: &[u8; 32],
+    nonce: &[u8; 12],
+    aad: &[u8],
+    sealed: &[u8],
+) -> Result<Vec<u8>, CommittingError> {
+    if sealed.len() < OVERHEAD {
+        return Err(CommittingError::InvalidLength);
+    }
+
+    let (commitment, rest) = sealed.split_at(COMMITMENT_LEN);
+    let (body, tag) = rest.split_at(rest.len() - TAG_LEN);
+    let tag: &[u8; TAG_LEN] = tag.try_into().unwrap();
+
+    let expected = derive(key, COMMIT_LABEL, nonce);
+
+    let mut diff = 0u8;
+    for i in 0..COMMITMENT_LEN {
+        diff |= expected[i] ^ commitment[i];
+    }
+
+    if diff != 0 {
+        return Err(CommittingError::AuthenticationFailed);
+    }
+
+    let mut subkey = derive(key, SUBKEY_LABEL, nonce);
+    let mut plaintext = vec![0u8; body.len()];
+
+    let res = decrypt_with_aad(&subkey, nonce, aad, body, tag, &mut plaintext);
+    subkey.fill(0);
+
+    res.map_err(|_| CommittingError::AuthenticationFailed)?;
+
+    Ok(plaintext)
+}
+
+/// Derives a 256-bit value bound to `(key, label, nonce)`.
+fn derive(key: &[u8; 32], label: &[u8; 19], nonce: &[u8; 12]) -> [u8; 32] {
+    let mut input = [0u8; 31];
+    input[..19].copy_from_slice(label);
+    input[19..].copy_from_slice(nonce);
+
+    let digest = blake2b_keyed(32, key, &input);
+
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&digest[..32]);
+
+    out
+}
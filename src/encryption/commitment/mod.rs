@@ -0,0 +1,17 @@
+//! Key-committing authenticated encryption.
+//!
+//! ChaCha20-Poly1305 is not key-committing: an adversary can craft a single
+//! ciphertext that decrypts successfully under several different keys. This
+//! enables partitioning-oracle attacks against schemes that try many keys
+//! (e.g. password-based or multi-recipient decryption).
+//!
+//! This module wraps the existing ChaCha20-Poly1305 AEAD with a collision-
+//! resistant key commitment derived from the key and nonce, following the
+//! "commit-then-encrypt" (UtC) transform.
+//!
+//! The implementation is split into:
+//!
+//! - `core`: the public `seal` / `open` API
+
+/// Committing ChaCha20-Poly1305 public API.
+pub mod core;
@@ -0,0 +1,428 @@
+//! Password-encrypted containers (Argon2id + ChaCha20-Poly1305).
+//!
+//! A container carries everything required to decrypt it except the
+//! passphrase: the Argon2id cost parameters, the salt and the nonce are
+//! stored in a fixed-size header, which is authenticated as associated
+//! data.
+//!
+//! ## Layout
+//!
+//! All integers are little-endian.
+//!
+//! ```text
+//! offset  size  field
+//!      0     4  magic "CRPW"
+//!      4     1  version (1)
+//!      5     4  Argon2id memory cost in KiB
+//!      9     4  Argon2id time cost (passes)
+//!     13     4  Argon2id parallelism (lanes)
+//!     17    16  salt
+//!     33    12  nonce
+//!     45    16  passphrase check value
+//!     61    16  header checksum: Blake2b-128(bytes 0..61)
+//!     77     -  ciphertext
+//!      -    16  Poly1305 tag
+//! ```
+//!
+//! ## Key derivation
+//!
+//! ```text
+//! okm   = Argon2id(passphrase, salt, params, 48 bytes)
+//! key   = okm[0..32]
+//! check = okm[32..48]
+//! ```
+//!
+//! ## Error semantics
+//!
+//! Opening distinguishes the three ways a container can fail:
+//!
+//! - the header checksum does not match: the container is corrupt
+//! - the check value does not match: the passphrase is wrong
+//! - the AEAD tag does not match: the ciphertext is corrupt or was
+//!   tampered with
+//!
+//! The check value does not weaken the construction: anybody holding the
+//! container can already test a candidate passphrase by attempting to
+//! decrypt it, at the same Argon2id cost.
+
+use crate::derivation::{Argon2Params, argon2id};
+use crate::encryption::chacha20poly1305::{decrypt_with_aad, encrypt_with_aad};
+use crate::hash::blake2b;
+use crate::rng::Csprng;
+
+/// Magic bytes identifying a password container.
+pub const MAGIC: [u8; 4] = *b"CRPW";
+
+/// Current container format version.
+pub const VERSION: u8 = 1;
+
+/// Length of the Argon2id salt in bytes.
+pub const SALT_LEN: usize = 16;
+
+/// Length of the ChaCha20-Poly1305 nonce in bytes.
+pub const NONCE_LEN: usize = 12;
+
+/// Length of the serialized header in bytes.
+pub const HEADER_LEN: usize = 77;
+
+/// Length of the Poly1305 authentication tag in bytes.
+pub const TAG_LEN: usize = 16;
+
+/// Total size overhead of a container.
+pub const OVERHEAD: usize = HEADER_LEN + TAG_LEN;
+
+/// Largest Argon2id memory cost accepted from a header by default (1 GiB).
+pub const MAX_MEM_KIB: u32 = 1024 * 1024;
+
+/// Largest Argon2id time cost accepted from a header by default.
+pub const MAX_TIME: u32 = 16;
+
+/// Largest Argon2id parallelism accepted from a header by default.
+pub const MAX_LANES: u32 = 16;
+
+/// Limits applied by [`open_with_password`] and [`seal_with_password`].
+pub const DEFAULT_LIMITS: PasswordParams = PasswordParams {
+    mem_kib: MAX_MEM_KIB,
+    time: MAX_TIME,
+    lanes: MAX_LANES,
+};
+
+/// Length of the passphrase check value.
+const CHECK_LEN: usize = 16;
+
+/// Length of the header checksum.
+const CHECKSUM_LEN: usize = 16;
+
+/// Errors that can occur while sealing or opening a container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordError {
+    /// The input is too short or does not start with [`MAGIC`].
+    InvalidFormat,
+    /// The container was written by an unknown format version.
+    UnsupportedVersion,
+    /// The Argon2id parameters are invalid or exceed the accepted limits.
+    InvalidParams,
+    /// The passphrase does not match the one used to seal the container.
+    WrongPassphrase,
+    /// The header or the ciphertext was modified or damaged.
+    Corrupted,
+}
+
+/// Argon2id cost parameters stored in a container header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasswordParams {
+    /// Memory cost in KiB.
+    pub mem_kib: u32,
+    /// Number of passes over memory.
+    pub time: u32,
+    /// Degree of parallelism.
+    pub lanes: u32,
+}
+
+impl PasswordParams {
+    /// Returns whether any cost of `self` is lower than in `minimum`.
+    pub fn is_weaker_than(&self, minimum: &PasswordParams) -> bool {
+        self.mem_kib < minimum.mem_kib || self.time < minimum.time || self.lanes < minimum.lanes
+    }
+
+    /// Returns the component-wise maximum of `self` and `other`.
+    fn max(&self, other: &PasswordParams) -> PasswordParams {
+        PasswordParams {
+            mem_kib: self.mem_kib.max(other.mem_kib),
+            time: self.time.max(other.time),
+            lanes: self.lanes.max(other.lanes),
+        }
+    }
+
+    /// Checks the parameters against `limits`.
+    fn check_limits(&self, limits: &PasswordParams) -> Result<(), PasswordError> {
+        if self.mem_kib > limits.mem_kib || self.time > limits.time || self.lanes > limits.lanes {
+            return Err(PasswordError::InvalidParams);
+        }
+
+        Ok(())
+    }
+}
+
+impl From<&Argon2Params> for PasswordParams {
+    /// Keeps the cost parameters; `tag_len`, `secret` and
+    /// `associated_data` are fixed by the container format.
+    fn from(params: &Argon2Params) -> Self {
+        Self {
+            mem_kib: params.mem_kib,
+            time: params.time,
+            lanes: params.lanes,
+        }
+    }
+}
+
+impl Default for PasswordParams {
+    /// Same costs as `Argon2Params::default()`.
+    fn default() -> Self {
+        Self::from(&Argon2Params::default())
+    }
+}
+
+/// Parsed container header.
+struct Header {
+    params: PasswordParams,
+    salt: [u8; SALT_LEN],
+    nonce: [u8; NONCE_LEN],
+    check: [u8; CHECK_LEN],
+}
+
+impl Header {
+    /// Serializes the header, including its checksum.
+    fn to_bytes(&self) -> [u8; HEADER_LEN] {
+        let mut out = [0u8; HEADER_LEN];
+
+        out[0..4].copy_from_slice(&MAGIC);
+        out[4] = VERSION;
+        out[5..9].copy_from_slice(&self.params.mem_kib.to_le_bytes());
+        out[9..13].copy_from_slice(&self.params.time.to_le_bytes());
+        out[13..17].copy_from_slice(&self.params.lanes.to_le_bytes());
+        out[17..33].copy_from_slice(&self.salt);
+        out[33..45].copy_from_slice(&self.nonce);
+        out[45..61].copy_from_slice(&self.check);
+
+        let checksum = blake2b(CHECKSUM_LEN, &out[..61]);
+        out[61..].copy_from_slice(&checksum[..CHECKSUM_LEN]);
+
+        out
+    }
+
+    /// Parses and checks the header at the start of `sealed`.
+    fn parse(sealed: &[u8]) -> Result<Self, PasswordError> {
+        if sealed.len() < OVERHEAD || sealed[0..4] != MAGIC {
+            return Err(PasswordError::InvalidFormat);
+        }
+
+        if sealed[4] != VERSION {
+            return Err(PasswordError::UnsupportedVersion);
+        }
+
+        let checksum = blake2b(CHECKSUM_LEN, &sealed[..61]);
+        if checksum[..CHECKSUM_LEN] != sealed[61..HEADER_LEN] {
+            return Err(PasswordError::Corrupted);
+        }
+
+        let u32_at = |i: usize| u32::from_le_bytes(sealed[i..i + 4].try_into().unwrap());
+
+        Ok(Self {
+            params: PasswordParams {
+                mem_kib: u32_at(5),
+                time: u32_at(9),
+                lanes: u32_at(13),
+            },
+            salt: sealed[17..33].try_into().unwrap(),
+            nonce: sealed[33..45].try_into().unwrap(),
+            check: sealed[45..61].try_into().unwrap(),
+        })
+    }
+}
+
+/// Runs Argon2id and splits its output into the key and the check value.
+fn derive(
+    password: &[u8],
+    salt: &[u8; SALT_LEN],
+    params: &PasswordParams,
+    limits: &PasswordParams,
+) -> Result<([u8; 32], [u8; CHECK_LEN]), PasswordError> {
+    params.check_limits(limits)?;
+
+    let argon2_params = Argon2Params {
+        mem_kib: params.mem_kib,
+        time: params.time,
+        lanes: params.lanes,
+        tag_len: 32 + CHECK_LEN,
+        secret: None,
+        associated_data: None,
+    };
+
+    let mut okm =
+        argon2id(password, salt, &argon2_params).map_err(|_| PasswordError::InvalidParams)?;
+
+    let key = okm[..32].try_into().unwrap();
+    let check = okm[32..].try_into().unwrap();
+    okm.fill(0);
+
+    Ok((key, check))
+}
+
+/// Encrypts `plaintext` under `password` into a self-describing container.
+///
+/// # Parameters
+///
+/// - `password`: passphrase, used as-is (no normalization is applied)
+/// - `plaintext`: data to protect
+/// - `params`: Argon2id costs; only `mem_kib`, `time` and `lanes` are used
+///
+/// # Returns
+///
+/// - `Ok(container)`, `plaintext.len() + OVERHEAD` bytes
+/// - `Err(InvalidParams)` if Argon2id rejects the parameters or they exceed
+///   [`DEFAULT_LIMITS`]
+///
+/// # Security Notes
+///
+/// A fresh random salt and nonce are drawn for every call, so sealing the
+/// same data twice yields unrelated containers.
+pub fn seal_with_password(
+    password: &[u8],
+    plaintext: &[u8],
+    params: &Argon2Params,
+) -> Result<Vec<u8>, PasswordError> {
+    seal(password, plaintext, &PasswordParams::from(params))
+}
+
+/// Seals with already reduced parameters.
+fn seal(
+    password: &[u8],
+    plaintext: &[u8],
+    params: &PasswordParams,
+) -> Result<Vec<u8>, PasswordError> {
+    let mut rng = Csprng::new();
+
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut salt);
+    rng.fill_bytes(&mut nonce);
+
+    let (mut key, check) = derive(password, &salt, params, &DEFAULT_LIMITS)?;
+
+    let header = Header {
+        params: *params,
+        salt,
+        nonce,
+        check,
+    }
+    .to_bytes();
+
+    let mut out = vec![0u8; plaintext.len() + OVERHEAD];
+    out[..HEADER_LEN].copy_from_slice(&header);
+
+    let (ciphertext, tag) = out[HEADER_LEN..].split_at_mut(plaintext.len());
+    encrypt_with_aad(
+        &key,
+        &nonce,
+        &header,
+        plaintext,
+        ciphertext,
+        tag.try_into().unwrap(),
+    );
+
+    key.fill(0);
+
+    Ok(out)
+}
+
+/// Decrypts a container produced by [`seal_with_password`].
+///
+/// # Returns
+///
+/// - `Ok(plaintext)` on success
+/// - `Err(InvalidFormat)` if the input is not a container
+/// - `Err(UnsupportedVersion)` if the format version is unknown
+/// - `Err(InvalidParams)` if the stored costs are invalid or too high
+/// - `Err(WrongPassphrase)` if `password` is not the sealing passphrase
+/// - `Err(Corrupted)` if the header or ciphertext was modified
+///
+/// # Security Notes
+///
+/// The stored costs are checked against [`DEFAULT_LIMITS`] before Argon2id
+/// runs, which still lets a header demand 1 GiB of memory and 16 passes.
+/// Containers from untrusted sources should be opened with
+/// [`open_with_password_limited`] and the caller's own cost policy.
+pub fn open_with_password(password: &[u8], sealed: &[u8]) -> Result<Vec<u8>, PasswordError> {
+    open_with_password_limited(password, sealed, &DEFAULT_LIMITS)
+}
+
+/// Decrypts a container, rejecting stored costs above `limits`.
+///
+/// # Returns
+///
+/// Same as [`open_with_password`]; `Err(InvalidParams)` is returned without
+/// running Argon2id if any stored cost exceeds the matching field of
+/// `limits`.
+pub fn open_with_password_limited(
+    password: &[u8],
+    sealed: &[u8],
+    limits: &PasswordParams,
+) -> Result<Vec<u8>, PasswordError> {
+    let header = Header::parse(sealed)?;
+    let (mut key, mut check) = derive(password, &header.salt, &header.params, limits)?;
+
+    let mut diff = 0u8;
+    for (a, b) in check.iter().zip(header.check.iter()) {
+        diff |= a ^ b;
+    }
+    check.fill(0);
+
+    if diff != 0 {
+        key.fill(0);
+        return Err(PasswordError::WrongPassphrase);
+    }
+
+    let (aad, body) = sealed.split_at(HEADER_LEN);
+    let (ciphertext, tag) = body.split_at(body.len() - TAG_LEN);
+
+    let mut plaintext = vec![0u8; ciphertext.len()];
+    let result = decrypt_with_aad(
+        &key,
+        &header.nonce,
+        aad,
+        ciphertext,
+        tag.try_into().unwrap(),
+        &mut plaintext,
+    );
+
+    key.fill(0);
+
+    result.map_err(|_| PasswordError::Corrupted)?;
+
+    Ok(plaintext)
+}
+
+/// Returns the Argon2id costs stored in a container header.
+///
+/// Only the header framing and checksum are verified; no passphrase is
+/// required.
+pub fn params_of(sealed: &[u8]) -> Result<PasswordParams, PasswordError> {
+    Ok(Header::parse(sealed)?.params)
+}
+
+/// Returns whether a container was sealed with costs below `minimum`.
+///
+/// Applications can call this after a successful open to decide whether
+/// to [`reseal_with_password`] with their current policy.
+pub fn needs_upgrade(sealed: &[u8], minimum: &Argon2Params) -> Result<bool, PasswordError> {
+    Ok(params_of(sealed)?.is_weaker_than(&PasswordParams::from(minimum)))
+}
+
+/// Opens a container and seals its contents again under `params`.
+///
+/// The new costs are the component-wise maximum of the stored costs and
+/// `params`, so re-sealing never weakens a container. A fresh salt and
+/// nonce are always used.
+///
+/// # Returns
+///
+/// The new container, or any error from [`open_with_password`] /
+/// [`seal_with_password`].
+pub fn reseal_with_password(
+    password: &[u8],
+    sealed: &[u8],
+    params: &Argon2Params,
+) -> Result<Vec<u8>, PasswordError> {
+    let stored = params_of(sealed)?;
+
+    let mut plaintext = open_with_password(password, sealed)?;
+    let result = seal(
+        password,
+        &plaintext,
+        &stored.max(&PasswordParams::from(params)),
+    );
+    plaintext.fill(0);
+
+    result
+}
@@ -0,0 +1,13 @@
+//! Password-encrypted container format.
+//!
+//! This module defines a self-describing, versioned format for encrypting
+//! a blob with a passphrase. It glues Argon2id and ChaCha20-Poly1305
+//! together in a single, well-defined way so that every caller produces
+//! the same layout and gets the same error semantics.
+//!
+//! The implementation is split into:
+//!
+//! - `core`: the header layout and the `seal` / `open` / `reseal` API
+
+/// Password container public API.
+pub mod core;
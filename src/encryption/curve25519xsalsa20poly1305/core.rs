@@ -0,0 +1,183 @@
+//! Curve25519-XSalsa20-Poly1305 public-key encryption (NaCl `crypto_box`).
+//!
+//! This module implements the `crypto_box` construction from NaCl, as also
+//! provided by libsodium. Two parties, each holding an X25519 keypair,
+//! derive a shared symmetric key and exchange messages protected by
+//! `secretbox`.
+//!
+//! ## Key derivation
+//!
+//! ```text
+//! s = X25519(private, peer_public)
+//! k = HSalsa20(s, 0^16)
+//! ```
+//!
+//! The resulting key `k` is identical for both directions, so it can be
+//! computed once with `precompute` and reused with the `*_precomputed`
+//! functions.
+//!
+//! ## Layout
+//!
+//! Sealed messages use the libsodium `crypto_box_easy` format:
+//!
+//! ```text
+//! tag (16 bytes) || ciphertext
+//! ```
+//!
+//! ## Notes
+//!
+//! - The recipient learns that the message was produced by the holder of
+//!   the sender's private key (or by the recipient itself).
+//! - Both parties share the same key, so `(nonce)` values must never be
+//!   reused across either direction.
+//! - Peer public keys of small order are rejected, matching libsodium.
+
+use crate::encryption::secretbox::{self, SecretboxError};
+use crate::encryption::xsalsa20poly1305::salsa20::hsalsa20;
+use crate::keys::x25519;
+
+/// Length of an X25519 public key in bytes.
+pub const PUBLIC_KEY_LEN: usize = 32;
+
+/// Length of an X25519 private key in bytes.
+pub const PRIVATE_KEY_LEN: usize = 32;
+
+/// Length of a precomputed shared key in bytes.
+pub const SHARED_KEY_LEN: usize = 32;
+
+/// Length of a `crypto_box` nonce in bytes.
+pub const NONCE_LEN: usize = 24;
+
+/// Length of the Poly1305 authentication tag in bytes.
+pub const TAG_LEN: usize = 16;
+
+/// Errors that can occur during `crypto_box` operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CryptoBoxError {
+    /// The peer public key yields an all-zero shared secret.
+    WeakPublicKey,
+    /// The input is too short to contain the expected framing.
+    InvalidLength,
+    /// The authentication tag verification failed.
+    AuthenticationFailed,
+}
+
+impl From<SecretboxError> for CryptoBoxError {
+    fn from(err: SecretboxError) -> Self {
+        match err {
+            SecretboxError::InvalidLength => CryptoBoxError::InvalidLength,
+            SecretboxError::AuthenticationFailed => CryptoBoxError::AuthenticationFailed,
+        }
+    }
+}
+
+/// Derives the symmetric key shared by `private` and `public`.
+///
+/// This is equivalent to libsodium's `crypto_box_beforenm`.
+///
+/// # Returns
+///
+/// - `Ok(key)` with the 32-byte `secretbox` key
+/// - `Err(WeakPublicKey)` if the X25519 output is all zeros, which happens
+///   for small-order peer public keys
+///
+/// # Security Notes
+///
+/// - The raw X25519 output is never returned; it is hashed with HSalsa20
+///   and wiped.
+/// - The all-zero check is performed in constant time.
+pub fn precompute(
+    public: &[u8; PUBLIC_KEY_LEN],
+    private: &[u8; PRIVATE_KEY_LEN],
+) -> Result<[u8; SHARED_KEY_LEN], CryptoBoxError> {
+    let mut shared = x25519::exchange(private, public);
+
+    let mut acc = 0u8;
+    for b in shared {
+        acc |= b;
+    }
+
+    if acc == 0 {
+        return Err(CryptoBoxError::WeakPublicKey);
+    }
+
+    let key = hsalsa20(&shared, &[0u8; 16]);
+    shared.fill(0);
+
+    Ok(key)
+}
+
+/// Encrypts a message with a key obtained from [`precompute`].
+///
+/// This is equivalent to libsodium's `crypto_box_easy_afternm` and returns
+/// `tag || ciphertext`.
+pub fn seal_precomputed(
+    key: &[u8; SHARED_KEY_LEN],
+    nonce: &[u8; NONCE_LEN],
+    plaintext: &[u8],
+) -> Vec<u8> {
+    secretbox::seal(key, nonce, plaintext)
+}
+
+/// Verifies and decrypts a message with a key obtained from [`precompute`].
+///
+/// This is equivalent to libsodium's `crypto_box_open_easy_afternm`.
+pub fn open_precomputed(
+    key: &[u8; SHARED_KEY_LEN],
+    nonce: &[u8; NONCE_LEN],
+    sealed: &[u8],
+) -> Result<Vec<u8>, CryptoBoxError> {
+    Ok(secretbox::open(key, nonce, sealed)?)
+}
+
+/// Encrypts and authenticates a message from `private` to `public`.
+///
+/// This is equivalent to libsodium's `crypto_box_easy`.
+///
+/// # Parameters
+///
+/// - `nonce`: 192-bit nonce, unique for every message between the two
+///   parties (in either direction)
+/// - `public`: recipient X25519 public key
+/// - `private`: sender X25519 private key
+/// - `plaintext`: message to encrypt
+///
+/// # Returns
+///
+/// - `Ok(tag || ciphertext)` on success
+/// - `Err(WeakPublicKey)` if `public` is a small-order point
+pub fn seal(
+    nonce: &[u8; NONCE_LEN],
+    public: &[u8; PUBLIC_KEY_LEN],
+    private: &[u8; PRIVATE_KEY_LEN],
+    plaintext: &[u8],
+) -> Result<Vec<u8>, CryptoBoxError> {
+    let mut key = precompute(public, private)?;
+    let sealed = seal_precomputed(&key, nonce, plaintext);
+    key.fill(0);
+
+    Ok(sealed)
+}
+
+/// Verifies and decrypts a message sent by `public` to `private`.
+///
+/// This is equivalent to libsodium's `crypto_box_open_easy`.
+///
+/// # Returns
+///
+/// - `Ok(plaintext)` if authentication succeeds
+/// - `Err(WeakPublicKey)` if `public` is a small-order point
+/// - `Err(InvalidLength)` if `sealed` is shorter than a tag
+/// - `Err(AuthenticationFailed)` if tag verification fails
+pub fn open(
+    nonce: &[u8; NONCE_LEN],
+    public: &[u8; PUBLIC_KEY_LEN],
+    private: &[u8; PRIVATE_KEY_LEN],
+    sealed: &[u8],
+) -> Result<Vec<u8>, CryptoBoxError> {
+    let mut key = precompute(public, private)?;
+    let opened = open_precomputed(&key, nonce, sealed);
+    key.fill(0);
+
+    opened
+}
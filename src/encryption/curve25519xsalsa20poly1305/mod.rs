@@ -0,0 +1,23 @@
+//! Public-key authenticated encryption (NaCl `crypto_box`).
+//!
+//! This module provides libsodium-compatible public-key encryption built
+//! from X25519 and the XSalsa20-Poly1305 `secretbox`.
+//!
+//! The implementation is split into two layers:
+//!
+//! - `core`: `crypto_box`, where both parties hold a long-term keypair and
+//!   the sender is authenticated to the recipient
+//! - `sealed`: anonymous sealed boxes, where the sender uses a fresh
+//!   ephemeral keypair and only needs the recipient's public key
+
+/// Public `crypto_box` API.
+///
+/// Exposes shared-key precomputation and the combined seal / open
+/// functions together with the associated error type.
+pub mod core;
+
+/// Anonymous sealed boxes.
+///
+/// Built on top of `core`, with an ephemeral sender key and a nonce
+/// derived from both public keys with Blake2b.
+pub mod sealed;
@@ -0,0 +1,89 @@
+//! Anonymous sealed boxes (libsodium `crypto_box_seal`).
+//!
+//! A sealed box lets anyone encrypt a message to a recipient knowing only
+//! the recipient's X25519 public key. No handshake and no sender keypair
+//! are required.
+//!
+//! ## Construction
+//!
+//! ```text
+//! (epk, esk) = fresh X25519 keypair
+//! nonce      = Blake2b-192(epk || pk)
+//! sealed     = epk || crypto_box(nonce, pk, esk, message)
+//! ```
+//!
+//! The ephemeral private key is wiped right after use, so even the sender
+//! cannot decrypt the message afterwards.
+//!
+//! ## Notes
+//!
+//! - The sender is anonymous: the recipient cannot tell who produced the
+//!   message, and anybody can produce a valid sealed box.
+//! - The output is 48 bytes longer than the input.
+//! - Output is interoperable with libsodium's `crypto_box_seal_open`.
+
+use super::core::{self as cryptobox, CryptoBoxError, NONCE_LEN, PRIVATE_KEY_LEN, PUBLIC_KEY_LEN};
+use crate::hash::blake2b;
+use crate::keys::x25519;
+
+/// Number of bytes added by [`seal`] (ephemeral public key and tag).
+pub const OVERHEAD: usize = PUBLIC_KEY_LEN + cryptobox::TAG_LEN;
+
+/// Derives the sealed box nonce from the ephemeral and recipient keys.
+fn nonce(epk: &[u8; PUBLIC_KEY_LEN], pk: &[u8; PUBLIC_KEY_LEN]) -> [u8; NONCE_LEN] {
+    let mut input = [0u8; 2 * PUBLIC_KEY_LEN];
+    input[..PUBLIC_KEY_LEN].copy_from_slice(epk);
+    input[PUBLIC_KEY_LEN..].copy_from_slice(pk);
+
+    let digest = blake2b(NONCE_LEN, &input);
+
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce.copy_from_slice(&digest[..NONCE_LEN]);
+
+    nonce
+}
+
+/// Encrypts a message anonymously to `public`.
+///
+/// # Returns
+///
+/// - `Ok(epk || tag || ciphertext)`, i.e. `plaintext.len() + OVERHEAD`
+///   bytes
+/// - `Err(WeakPublicKey)` if `public` is a small-order point
+pub fn seal(public: &[u8; PUBLIC_KEY_LEN], plaintext: &[u8]) -> Result<Vec<u8>, CryptoBoxError> {
+    let (epk, mut esk) = x25519::generate_keypair();
+    let nonce = nonce(&epk, public);
+
+    let boxed = cryptobox::seal(&nonce, public, &esk, plaintext);
+    esk.fill(0);
+
+    let mut out = Vec::with_capacity(plaintext.len() + OVERHEAD);
+    out.extend_from_slice(&epk);
+    out.extend_from_slice(&boxed?);
+
+    Ok(out)
+}
+
+/// Decrypts a sealed box addressed to the keypair `(public, private)`.
+///
+/// # Returns
+///
+/// - `Ok(plaintext)` if authentication succeeds
+/// - `Err(InvalidLength)` if `sealed` is shorter than [`OVERHEAD`]
+/// - `Err(WeakPublicKey)` if the embedded ephemeral key is a small-order
+///   point
+/// - `Err(AuthenticationFailed)` if tag verification fails
+pub fn open(
+    public: &[u8; PUBLIC_KEY_LEN],
+    private: &[u8; PRIVATE_KEY_LEN],
+    sealed: &[u8],
+) -> Result<Vec<u8>, CryptoBoxError> {
+    if sealed.len() < OVERHEAD {
+        return Err(CryptoBoxError::InvalidLength);
+    }
+
+    let (epk, boxed) = sealed.split_at(PUBLIC_KEY_LEN);
+    let epk: &[u8; PUBLIC_KEY_LEN] = epk.try_into().unwrap();
+
+    cryptobox::open(&nonce(epk, public), epk, private, boxed)
+}
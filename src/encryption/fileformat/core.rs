@@ -0,0 +1,393 @@
+//! age v1 encryption with X25519 recipients.
+//!
+//! An age file is encrypted under a random 128-bit *file key*. The file
+//! key is wrapped once per recipient in a header stanza, the header is
+//! authenticated with a MAC derived from the file key, and the payload is
+//! encrypted with the STREAM construction.
+//!
+//! ## X25519 recipient stanza
+//!
+//! ```text
+//! -> X25519 <base64(ephemeral share)>
+//! <base64(ChaCha20-Poly1305(wrap_key, 0^12, file_key))>
+//!
+//! shared   = X25519(ephemeral, recipient)
+//! wrap_key = HKDF-SHA256(ikm = shared, salt = share || recipient,
+//!                        info = "age-encryption.org/v1/X25519")
+//! ```
+//!
+//! ## Keys
+//!
+//! - Recipients are Bech32 strings with the HRP `age`, e.g. `age1...`.
+//! - Identities are uppercase Bech32 strings with the HRP
+//!   `AGE-SECRET-KEY-`, e.g. `AGE-SECRET-KEY-1...`.
+//!
+//! ## Notes
+//!
+//! - Stanzas of unknown types are ignored, so files addressed to other
+//!   recipient types as well can still be decrypted.
+//! - The whole file is processed in memory.
+//! - `encrypt_with_padding` pads the plaintext to hide its length.
+//!   Padded files decrypt with any age implementation, but only
+//!   `decrypt_with_padding` removes the padding.
+
+use super::header::{self, Stanza};
+use super::stream;
+use crate::derivation::hkdf::core::hkdf;
+use crate::encoding::base64::{self, Variant};
+use crate::encoding::bech32;
+use crate::encryption::chacha20poly1305::{decrypt_with_aad, encrypt_with_aad};
+use crate::encryption::padding::{Padding, padded_len};
+use crate::keys::x25519;
+use crate::rng::Csprng;
+
+/// Bech32 HRP of X25519 recipients.
+const RECIPIENT_HRP: &str = "age";
+
+/// Bech32 HRP of X25519 identities.
+const IDENTITY_HRP: &str = "age-secret-key-";
+
+/// HKDF info string for X25519 wrap keys.
+const X25519_LABEL: &[u8] = b"age-encryption.org/v1/X25519";
+
+/// Errors that can occur while handling age keys and files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgeError {
+    /// A recipient or identity string is malformed.
+    InvalidKey,
+    /// No recipients were given for encryption.
+    NoRecipients,
+    /// The header is malformed, or an X25519 stanza is invalid.
+    InvalidHeader,
+    /// The file uses an unsupported age format version.
+    UnsupportedVersion,
+    /// None of the identities can unwrap the file key.
+    NoMatchingIdentity,
+    /// The header MAC does not verify.
+    InvalidMac,
+    /// The payload is truncated, malformed or was tampered with.
+    InvalidPayload,
+    /// The padding scheme is invalid (zero bucket size).
+    InvalidPadding,
+}
+
+/// An X25519 recipient (public key).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Recipient([u8; 32]);
+
+impl Recipient {
+    /// Creates a recipient from a raw X25519 public key.
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the raw X25519 public key.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    /// Parses an `age1...` recipient string.
+    pub fn parse(s: &str) -> Result<Self, AgeError> {
+        // Recipients are always lowercase.
+        if s.bytes().any(|c| c.is_ascii_uppercase()) {
+            return Err(AgeError::InvalidKey);
+        }
+
+        Ok(Self(decode_key(s, RECIPIENT_HRP)?))
+    }
+
+    /// Encodes the recipient as an `age1...` string.
+    pub fn encode(&self) -> String {
+        bech32::encode(RECIPIENT_HRP, &self.0)
+    }
+}
+
+/// An X25519 identity (private key).
+///
+/// The key is wiped when the identity is dropped.
+pub struct Identity([u8; 32]);
+
+impl Identity {
+    /// Generates a fresh random identity.
+    pub fn generate() -> Self {
+        let (_, private) = x25519::generate_keypair();
+        Self(private)
+    }
+
+    /// Creates an identity from a raw X25519 private key.
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Parses an `AGE-SECRET-KEY-1...` identity string.
+    pub fn parse(s: &str) -> Result<Self, AgeError> {
+        // Identities are always uppercase.
+        if s.bytes().any(|c| c.is_ascii_lowercase()) {
+            return Err(AgeError::InvalidKey);
+        }
+
+        Ok(Self(decode_key(s, IDENTITY_HRP)?))
+    }
+
+    /// Encodes the identity as an `AGE-SECRET-KEY-1...` string.
+    ///
+    /// The returned string is secret key material.
+    pub fn encode(&self) -> String {
+        bech32::encode(IDENTITY_HRP, &self.0).to_ascii_uppercase()
+    }
+
+    /// Returns the recipient matching this identity.
+    pub fn to_public(&self) -> Recipient {
+        Recipient(x25519::public_key(&self.0))
+    }
+}
+
+impl Drop for Identity {
+    fn drop(&mut self) {
+        self.0.fill(0);
+    }
+}
+
+/// Decodes a 32-byte Bech32 key with the expected HRP.
+fn decode_key(s: &str, hrp: &str) -> Result<[u8; 32], AgeError> {
+    let (got, data) = bech32::decode(s).map_err(|_| AgeError::InvalidKey)?;
+
+    if got != hrp {
+        return Err(AgeError::InvalidKey);
+    }
+
+    data.try_into().map_err(|_| AgeError::InvalidKey)
+}
+
+/// Derives the key wrapping the file key for one X25519 stanza.
+fn wrap_key(shared: &[u8; 32], share: &[u8; 32], recipient: &[u8; 32]) -> [u8; 32] {
+    let mut salt = [0u8; 64];
+    salt[..32].copy_from_slice(share);
+    salt[32..].copy_from_slice(recipient);
+
+    let mut key = [0u8; 32];
+    hkdf(&salt, shared, X25519_LABEL, &mut key).unwrap();
+    key
+}
+
+/// Computes X25519, rejecting the all-zero output of low-order points.
+fn exchange(private: &[u8; 32], public: &[u8; 32]) -> Option<[u8; 32]> {
+    let shared = x25519::exchange(private, public);
+
+    let mut acc = 0u8;
+    for b in shared {
+        acc |= b;
+    }
+
+    (acc != 0).then_some(shared)
+}
+
+/// Wraps `file_key` for `recipient` into an X25519 stanza.
+fn wrap(file_key: &[u8; 16], recipient: &Recipient) -> Result<Stanza, AgeError> {
+    let (share, mut ephemeral) = x25519::generate_keypair();
+
+    let shared = exchange(&ephemeral, &recipient.0);
+    ephemeral.fill(0);
+    let mut shared = shared.ok_or(AgeError::InvalidKey)?;
+
+    let mut key = wrap_key(&shared, &share, &recipient.0);
+    shared.fill(0);
+
+    let mut body = vec![0u8; 32];
+    let (ciphertext, tag) = body.split_at_mut(16);
+    encrypt_with_aad(
+        &key,
+        &[0u8; 12],
+        &[],
+        file_key,
+        ciphertext,
+        tag.try_into().unwrap(),
+    );
+    key.fill(0);
+
+    Ok(Stanza {
+        args: vec![
+            "X25519".to_string(),
+            base64::encode(&share, Variant::StandardNoPad),
+        ],
+        body,
+    })
+}
+
+/// Tries to unwrap the file key from an X25519 stanza.
+///
+/// Returns `Ok(None)` if the stanza is well-formed but addressed to a
+/// different identity.
+fn unwrap(stanza: &Stanza, identity: &Identity) -> Result<Option<[u8; 16]>, AgeError> {
+    if stanza.args.len() != 2 || stanza.body.len() != 32 {
+        return Err(AgeError::InvalidHeader);
+    }
+
+    let share: [u8; 32] = header::decode_b64(stanza.args[1].as_bytes())?
+        .try_into()
+        .map_err(|_| AgeError::InvalidHeader)?;
+
+    let mut shared = exchange(&identity.0, &share).ok_or(AgeError::InvalidHeader)?;
+    let mut key = wrap_key(&shared, &share, &identity.to_public().0);
+    shared.fill(0);
+
+    let (ciphertext, tag) = stanza.body.split_at(16);
+    let mut file_key = [0u8; 16];
+
+    let result = decrypt_with_aad(
+        &key,
+        &[0u8; 12],
+        &[],
+        ciphertext,
+        tag.try_into().unwrap(),
+        &mut file_key,
+    );
+    key.fill(0);
+
+    Ok(result.ok().map(|_| file_key))
+}
+
+/// Encrypts `plaintext` to one or more X25519 recipients.
+///
+/// # Returns
+///
+/// - `Ok(file)` containing the binary (non-armored) age file
+/// - `Err(NoRecipients)` if `recipients` is empty
+/// - `Err(InvalidKey)` if a recipient is a low-order point
+pub fn encrypt(recipients: &[Recipient], plaintext: &[u8]) -> Result<Vec<u8>, AgeError> {
+    encrypt_with_padding(recipients, plaintext, Padding::None)
+}
+
+/// Encrypts `plaintext`, padded with `padding`, to one or more X25519
+/// recipients.
+///
+/// # Returns
+///
+/// Same as [`encrypt`], or `Err(InvalidPadding)` for a zero bucket size.
+pub fn encrypt_with_padding(
+    recipients: &[Recipient],
+    plaintext: &[u8],
+    padding: Padding,
+) -> Result<Vec<u8>, AgeError> {
+    padded_len(padding, 0).map_err(|_| AgeError::InvalidPadding)?;
+
+    if recipients.is_empty() {
+        return Err(AgeError::NoRecipients);
+    }
+
+    let mut rng = Csprng::new();
+
+    let mut file_key = [0u8; 16];
+    rng.fill_bytes(&mut file_key);
+
+    let stanzas = recipients
+        .iter()
+        .map(|r| wrap(&file_key, r))
+        .collect::<Result<Vec<_>, _>>();
+
+    let stanzas = match stanzas {
+        Ok(stanzas) => stanzas,
+        Err(err) => {
+            file_key.fill(0);
+            return Err(err);
+        }
+    };
+
+    let mut out = header::write(&stanzas);
+    let mac = header::mac(&file_key, &out);
+
+    out.push(b' ');
+    out.extend_from_slice(base64::encode(&mac, Variant::StandardNoPad).as_bytes());
+    out.push(b'\n');
+
+    let mut nonce = [0u8; stream::NONCE_LEN];
+    rng.fill_bytes(&mut nonce);
+    out.extend_from_slice(&nonce);
+
+    stream::seal(&file_key, &nonce, plaintext, padding, &mut out);
+    file_key.fill(0);
+
+    Ok(out)
+}
+
+/// Decrypts a binary age file with any of the given identities.
+///
+/// # Returns
+///
+/// - `Ok(plaintext)` on success
+/// - `Err(UnsupportedVersion)` if the file is not age v1
+/// - `Err(InvalidHeader)` if the header or an X25519 stanza is malformed
+/// - `Err(NoMatchingIdentity)` if no identity unwraps the file key
+/// - `Err(InvalidMac)` if the header was tampered with
+/// - `Err(InvalidPayload)` if the payload is truncated or was tampered with
+///
+/// # Security Notes
+///
+/// No plaintext is returned unless the header MAC and every payload chunk
+/// authenticate.
+pub fn decrypt(identities: &[Identity], file: &[u8]) -> Result<Vec<u8>, AgeError> {
+    open(identities, file, false)
+}
+
+/// Decrypts a file produced by [`encrypt_with_padding`] and removes its
+/// padding.
+///
+/// Any padding scheme can be removed, so the scheme itself need not be
+/// known.
+///
+/// # Returns
+///
+/// Same as [`decrypt`]; a payload without valid padding is reported as
+/// `Err(InvalidPayload)`.
+pub fn decrypt_with_padding(identities: &[Identity], file: &[u8]) -> Result<Vec<u8>, AgeError> {
+    open(identities, file, true)
+}
+
+/// Decrypts a file, removing the padding if `padded` is set.
+fn open(identities: &[Identity], file: &[u8], padded: bool) -> Result<Vec<u8>, AgeError> {
+    let header = header::parse(file)?;
+
+    let mut file_key = None;
+
+    'search: for identity in identities {
+        for stanza in &header.stanzas {
+            if stanza.args[0] != "X25519" {
+                continue;
+            }
+
+            if let Some(key) = unwrap(stanza, identity)? {
+                file_key = Some(key);
+                break 'search;
+            }
+        }
+    }
+
+    let mut file_key = file_key.ok_or(AgeError::NoMatchingIdentity)?;
+
+    let expected = header::mac(&file_key, &file[..header.mac_len]);
+
+    let mut diff = 0u8;
+    for (a, b) in expected.iter().zip(header.mac.iter()) {
+        diff |= a ^ b;
+    }
+
+    let result = if diff != 0 {
+        Err(AgeError::InvalidMac)
+    } else {
+        let payload = &file[header.payload_offset..];
+
+        match payload.get(..stream::NONCE_LEN) {
+            Some(nonce) => stream::open(
+                &file_key,
+                nonce.try_into().unwrap(),
+                &payload[stream::NONCE_LEN..],
+                padded,
+            ),
+            None => Err(AgeError::InvalidHeader),
+        }
+    };
+
+    file_key.fill(0);
+
+    result
+}
@@ -0,0 +1,170 @@
+//! age header parsing and serialization.
+//!
+//! ```text
+//! age-encryption.org/v1
+//! -> X25519 <base64 share>
+//! <base64 body, wrapped at 64 columns>
+//! --- <base64 HMAC-SHA256>
+//! ```
+//!
+//! Every stanza starts with `-> ` followed by one or more arguments and
+//! is followed by its body, wrapped in lines of exactly 64 Base64
+//! characters and terminated by a shorter (possibly empty) line.
+//!
+//! Parsing is strict: any deviation from the canonical encoding, including
+//! CRLF line endings, padded or non-canonical Base64 and empty arguments,
+//! is rejected.
+
+use super::core::AgeError;
+use crate::derivation::hkdf::core::{HmacSha256, hkdf};
+use crate::encoding::base64::{self, Variant};
+
+/// First line of every age v1 file.
+const VERSION_LINE: &[u8] = b"age-encryption.org/v1";
+
+/// Prefix shared by all age version lines.
+const VERSION_PREFIX: &[u8] = b"age-encryption.org/";
+
+/// Number of Base64 characters in a full body line.
+const COLUMNS: usize = 64;
+
+/// A recipient stanza: a type, its arguments and an opaque body.
+pub(crate) struct Stanza {
+    pub(crate) args: Vec<String>,
+    pub(crate) body: Vec<u8>,
+}
+
+/// A parsed header.
+pub(crate) struct Header {
+    pub(crate) stanzas: Vec<Stanza>,
+    pub(crate) mac: [u8; 32],
+    /// Number of bytes covered by the MAC (up to and including `---`).
+    pub(crate) mac_len: usize,
+    /// Offset of the payload (first byte after the MAC line).
+    pub(crate) payload_offset: usize,
+}
+
+/// Computes the header MAC over `header` for `file_key`.
+///
+/// ```text
+/// hmac_key = HKDF-SHA256(ikm = file_key, salt = "", info = "header")
+/// mac      = HMAC-SHA256(hmac_key, header up to and including "---")
+/// ```
+pub(crate) fn mac(file_key: &[u8; 16], header: &[u8]) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    hkdf(&[], file_key, b"header", &mut key).unwrap();
+
+    let mut mac = HmacSha256::new(&key);
+    mac.update(header);
+    key.fill(0);
+
+    mac.finalize()
+}
+
+/// Serializes the header up to and including `---` (the MAC input).
+pub(crate) fn write(stanzas: &[Stanza]) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(VERSION_LINE);
+    out.push(b'\n');
+
+    for stanza in stanzas {
+        out.extend_from_slice(b"->");
+        for arg in &stanza.args {
+            out.push(b' ');
+            out.extend_from_slice(arg.as_bytes());
+        }
+        out.push(b'\n');
+
+        let body = base64::encode(&stanza.body, Variant::StandardNoPad);
+
+        // A body whose encoding fills its last line still needs a final
+        // short (empty) line.
+        for line in body.as_bytes().chunks(COLUMNS) {
+            out.extend_from_slice(line);
+            out.push(b'\n');
+        }
+        if body.len().is_multiple_of(COLUMNS) {
+            out.push(b'\n');
+        }
+    }
+
+    out.extend_from_slice(b"---");
+    out
+}
+
+/// Splits the next `\n`-terminated line off `input` at `pos`.
+fn next_line<'a>(input: &'a [u8], pos: &mut usize) -> Result<&'a [u8], AgeError> {
+    let rest = &input[*pos..];
+    let end = rest
+        .iter()
+        .position(|&c| c == b'\n')
+        .ok_or(AgeError::InvalidHeader)?;
+
+    *pos += end + 1;
+    Ok(&rest[..end])
+}
+
+/// Decodes canonical, unpadded Base64 from a header line.
+pub(crate) fn decode_b64(line: &[u8]) -> Result<Vec<u8>, AgeError> {
+    let s = std::str::from_utf8(line).map_err(|_| AgeError::InvalidHeader)?;
+    base64::decode(s, Variant::StandardNoPad).map_err(|_| AgeError::InvalidHeader)
+}
+
+/// Parses the header at the start of an age file.
+pub(crate) fn parse(input: &[u8]) -> Result<Header, AgeError> {
+    let mut pos = 0;
+
+    let version = next_line(input, &mut pos)?;
+    if version != VERSION_LINE {
+        if version.starts_with(VERSION_PREFIX) {
+            return Err(AgeError::UnsupportedVersion);
+        }
+        return Err(AgeError::InvalidHeader);
+    }
+
+    let mut stanzas = Vec::new();
+
+    loop {
+        let line_start = pos;
+        let line = next_line(input, &mut pos)?;
+
+        if let Some(rest) = line.strip_prefix(b"--- ") {
+            let mac: [u8; 32] = decode_b64(rest)?
+                .try_into()
+                .map_err(|_| AgeError::InvalidHeader)?;
+
+            return Ok(Header {
+                stanzas,
+                mac,
+                mac_len: line_start + 3,
+                payload_offset: pos,
+            });
+        }
+
+        let args = line.strip_prefix(b"-> ").ok_or(AgeError::InvalidHeader)?;
+
+        let mut parsed = Vec::new();
+        for arg in args.split(|&c| c == b' ') {
+            if arg.is_empty() || arg.iter().any(|c| !(33..=126).contains(c)) {
+                return Err(AgeError::InvalidHeader);
+            }
+            parsed.push(String::from_utf8(arg.to_vec()).unwrap());
+        }
+
+        let mut body = Vec::new();
+        loop {
+            let line = next_line(input, &mut pos)?;
+            if line.len() > COLUMNS {
+                return Err(AgeError::InvalidHeader);
+            }
+
+            body.extend_from_slice(&decode_b64(line)?);
+
+            if line.len() < COLUMNS {
+                break;
+            }
+        }
+
+        stanzas.push(Stanza { args: parsed, body });
+    }
+}
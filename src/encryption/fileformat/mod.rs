@@ -0,0 +1,29 @@
+//! age v1 file encryption (<https://age-encryption.org/v1>).
+//!
+//! This module implements the age file format with native X25519
+//! recipients, interoperable with the reference `age` and `rage` tools.
+//!
+//! The implementation is split into three layers:
+//!
+//! - `header`: parsing and serialization of the textual header (version
+//!   line, recipient stanzas and header MAC)
+//! - `stream`: the STREAM construction encrypting the payload in 64 KiB
+//!   ChaCha20-Poly1305 chunks
+//! - `core`: keys, X25519 recipient stanzas and the public
+//!   `encrypt` / `decrypt` API
+
+/// Public age API.
+///
+/// Exposes X25519 identities and recipients together with the file
+/// encryption and decryption functions.
+pub mod core;
+
+/// age header format.
+///
+/// Internal parser and writer for stanzas and the header MAC.
+pub(crate) mod header;
+
+/// age payload encryption.
+///
+/// Internal STREAM implementation over ChaCha20-Poly1305.
+pub(crate) mod stream;
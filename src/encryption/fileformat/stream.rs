@@ -0,0 +1,167 @@
+//! age payload encryption (STREAM).
+//!
+//! The payload is split into 64 KiB chunks, each sealed with
+//! ChaCha20-Poly1305 under the payload key:
+//!
+//! ```text
+//! payload_key = HKDF-SHA256(ikm = file_key, salt = nonce, info = "payload")
+//! chunk_nonce = I2OSP(counter, 11) || last_flag
+//! ```
+//!
+//! The last chunk is flagged with `0x01`, so truncation at a chunk
+//! boundary is detected. Only the final chunk may be shorter than 64 KiB,
+//! and it may only be empty if the whole payload is empty.
+//!
+//! When padding is requested, the plaintext is padded as a whole before
+//! it is split into chunks, so padded files remain valid age files whose
+//! decrypted contents end with the padding.
+
+use super::core::AgeError;
+use crate::derivation::hkdf::core::hkdf;
+use crate::encryption::chacha20poly1305::{decrypt_with_aad, encrypt_with_aad};
+use crate::encryption::padding::{Padding, padded_len, unpad};
+
+/// Plaintext size of a full chunk.
+pub(crate) const CHUNK_LEN: usize = 64 * 1024;
+
+/// Length of the Poly1305 tag appended to each chunk.
+const TAG_LEN: usize = 16;
+
+/// Length of the payload nonce preceding the chunks.
+pub(crate) const NONCE_LEN: usize = 16;
+
+/// Derives the payload key from the file key and the payload nonce.
+fn payload_key(file_key: &[u8; 16], nonce: &[u8; NONCE_LEN]) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    hkdf(nonce, file_key, b"payload", &mut key).unwrap();
+    key
+}
+
+/// Builds the nonce of chunk number `counter`.
+fn chunk_nonce(counter: u64, last: bool) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[3..11].copy_from_slice(&counter.to_be_bytes());
+    nonce[11] = last as u8;
+    nonce
+}
+
+/// Encrypts `plaintext`, padded with `padding`, and appends the chunks to
+/// `out`.
+///
+/// The padding must have been validated with `padded_len`.
+pub(crate) fn seal(
+    file_key: &[u8; 16],
+    nonce: &[u8; NONCE_LEN],
+    plaintext: &[u8],
+    padding: Padding,
+    out: &mut Vec<u8>,
+) {
+    let mut key = payload_key(file_key, nonce);
+
+    let total = padded_len(padding, plaintext.len()).unwrap();
+    let chunks = total.div_ceil(CHUNK_LEN).max(1);
+
+    // Chunks reaching past the plaintext are assembled here with their
+    // padding.
+    let mut tail = Vec::new();
+
+    for counter in 0..chunks {
+        let start = counter * CHUNK_LEN;
+        let end = (start + CHUNK_LEN).min(total);
+
+        let chunk = if end <= plaintext.len() {
+            &plaintext[start..end]
+        } else {
+            tail.fill(0);
+            tail.clear();
+            tail.extend_from_slice(&plaintext[start.min(plaintext.len())..]);
+            if start <= plaintext.len() {
+                tail.push(0x80);
+            }
+            tail.resize(end - start, 0);
+            &tail[..]
+        };
+
+        let offset = out.len();
+        out.resize(offset + chunk.len() + TAG_LEN, 0);
+
+        let (ciphertext, tag) = out[offset..].split_at_mut(chunk.len());
+        encrypt_with_aad(
+            &key,
+            &chunk_nonce(counter as u64, counter + 1 == chunks),
+            &[],
+            chunk,
+            ciphertext,
+            tag.try_into().unwrap(),
+        );
+    }
+
+    tail.fill(0);
+    key.fill(0);
+}
+
+/// Decrypts and authenticates the chunks in `payload`, removing the
+/// padding if `padded` is set.
+pub(crate) fn open(
+    file_key: &[u8; 16],
+    nonce: &[u8; NONCE_LEN],
+    payload: &[u8],
+    padded: bool,
+) -> Result<Vec<u8>, AgeError> {
+    if payload.is_empty() {
+        return Err(AgeError::InvalidPayload);
+    }
+
+    let mut key = payload_key(file_key, nonce);
+    let mut out = Vec::with_capacity(payload.len());
+    let mut result = Ok(());
+
+    for (counter, chunk) in payload.chunks(CHUNK_LEN + TAG_LEN).enumerate() {
+        let start = counter * (CHUNK_LEN + TAG_LEN);
+        let last = start + chunk.len() == payload.len();
+
+        // An empty final chunk is only valid for an empty payload.
+        if chunk.len() < TAG_LEN || (last && counter > 0 && chunk.len() == TAG_LEN) {
+            result = Err(AgeError::InvalidPayload);
+            break;
+        }
+
+        let (ciphertext, tag) = chunk.split_at(chunk.len() - TAG_LEN);
+
+        let offset = out.len();
+        out.resize(offset + ciphertext.len(), 0);
+
+        if decrypt_with_aad(
+            &key,
+            &chunk_nonce(counter as u64, last),
+            &[],
+            ciphertext,
+            tag.try_into().unwrap(),
+            &mut out[offset..],
+        )
+        .is_err()
+        {
+            result = Err(AgeError::InvalidPayload);
+            break;
+        }
+    }
+
+    key.fill(0);
+
+    if padded && result.is_ok() {
+        match unpad(&out) {
+            Ok(len) => {
+                out[len..].fill(0);
+                out.truncate(len);
+            }
+            Err(_) => result = Err(AgeError::InvalidPayload),
+        }
+    }
+
+    if let Err(err) = result {
+        out.fill(0);
+        return Err(err);
+    }
+
+    Ok(out)
+}
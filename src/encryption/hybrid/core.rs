@@ -0,0 +1,515 @@
+//! HPKE with DHKEM(X25519, HKDF-SHA256), HKDF-SHA256 and ChaCha20-Poly1305.
+//!
+//! This module implements the four HPKE modes of RFC 9180:
+//!
+//! - **Base**: encryption to a public key, the sender is anonymous
+//! - **PSK**: additionally bound to a pre-shared key
+//! - **Auth**: additionally authenticated by the sender's X25519 key
+//! - **AuthPSK**: both of the above
+//!
+//! ## Usage
+//!
+//! A sender calls one of the `setup_*_sender` functions with the
+//! recipient's public key. It obtains an encapsulated key `enc` to transmit
+//! alongside the ciphertexts, and a `SenderContext`. The recipient passes
+//! `enc` to the matching `setup_*_recipient` function to obtain a
+//! `RecipientContext`.
+//!
+//! Both contexts derive the same AEAD key and base nonce. Each call to
+//! `SenderContext::seal` / `RecipientContext::open` uses the next
+//! sequence number, so messages must be opened in the order they were
+//! sealed. Both contexts can also `export` secrets bound to the session.
+//!
+//! ## Key schedule
+//!
+//! ```text
+//! key_schedule_context = mode || LabeledExtract("", "psk_id_hash", psk_id)
+//!                             || LabeledExtract("", "info_hash", info)
+//! secret          = LabeledExtract(shared_secret, "secret", psk)
+//! key             = LabeledExpand(secret, "key", key_schedule_context, 32)
+//! base_nonce      = LabeledExpand(secret, "base_nonce", key_schedule_context, 12)
+//! exporter_secret = LabeledExpand(secret, "exp", key_schedule_context, 32)
+//! ```
+//!
+//! ## Notes
+//!
+//! - `enc` is the sender's ephemeral X25519 public key (32 bytes).
+//! - Ciphertexts are `ciphertext || tag`, 16 bytes longer than the
+//!   plaintext.
+//! - Peer public keys yielding an all-zero X25519 output are rejected.
+
+use super::kdf::{labeled_expand, labeled_extract};
+use super::kem;
+use crate::derivation::hkdf::core::MAX_OUTPUT_LEN;
+use crate::encryption::chacha20poly1305::{decrypt_with_aad, encrypt_with_aad};
+use crate::keys::x25519;
+
+/// Length of the encapsulated key `enc` in bytes.
+pub const ENC_LEN: usize = 32;
+
+/// Length of the AEAD key in bytes.
+pub const KEY_LEN: usize = 32;
+
+/// Length of the AEAD nonce in bytes.
+pub const NONCE_LEN: usize = 12;
+
+/// Length of the Poly1305 authentication tag in bytes.
+pub const TAG_LEN: usize = 16;
+
+/// Maximum length of an exported secret (`255 * 32` bytes).
+pub const MAX_EXPORT_LEN: usize = MAX_OUTPUT_LEN;
+
+/// HPKE suite identifier: `"HPKE" || kem_id || kdf_id || aead_id`.
+const SUITE_ID: &[u8; 10] = b"HPKE\x00\x20\x00\x01\x00\x03";
+
+/// HPKE operating mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// `mode_base` (`0x00`).
+    Base,
+    /// `mode_psk` (`0x01`).
+    Psk,
+    /// `mode_auth` (`0x02`).
+    Auth,
+    /// `mode_auth_psk` (`0x03`).
+    AuthPsk,
+}
+
+impl Mode {
+    /// Returns the one-byte mode identifier used in the key schedule.
+    fn id(self) -> u8 {
+        match self {
+            Mode::Base => 0x00,
+            Mode::Psk => 0x01,
+            Mode::Auth => 0x02,
+            Mode::AuthPsk => 0x03,
+        }
+    }
+
+    /// Returns whether the mode uses a pre-shared key.
+    fn uses_psk(self) -> bool {
+        matches!(self, Mode::Psk | Mode::AuthPsk)
+    }
+
+    /// Returns whether the mode authenticates the sender's static key.
+    fn uses_auth(self) -> bool {
+        matches!(self, Mode::Auth | Mode::AuthPsk)
+    }
+}
+
+/// Errors that can occur during HPKE operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HpkeError {
+    /// The PSK or sender key inputs do not match the requested mode.
+    InvalidMode,
+    /// A public key yields an all-zero X25519 shared secret.
+    WeakPublicKey,
+    /// The context has used up its sequence numbers.
+    MessageLimitReached,
+    /// The input or requested output has an invalid length.
+    InvalidLength,
+    /// The authentication tag verification failed.
+    AuthenticationFailed,
+}
+
+/// Keys and state shared by the sender and recipient contexts.
+struct Context {
+    key: [u8; KEY_LEN],
+    base_nonce: [u8; NONCE_LEN],
+    exporter_secret: [u8; 32],
+    seq: u64,
+}
+
+impl Context {
+    /// Runs the key schedule for an already established shared secret.
+    fn new(mode: Mode, shared_secret: &[u8; 32], info: &[u8], psk: &[u8], psk_id: &[u8]) -> Self {
+        let mut context = [0u8; 65];
+        context[0] = mode.id();
+        context[1..33].copy_from_slice(&labeled_extract(SUITE_ID, &[], b"psk_id_hash", psk_id));
+        context[33..].copy_from_slice(&labeled_extract(SUITE_ID, &[], b"info_hash", info));
+
+        let mut secret = labeled_extract(SUITE_ID, shared_secret, b"secret", psk);
+
+        let mut ctx = Self {
+            key: [0u8; KEY_LEN],
+            base_nonce: [0u8; NONCE_LEN],
+            exporter_secret: [0u8; 32],
+            seq: 0,
+        };
+
+        labeled_expand(SUITE_ID, &secret, b"key", &context, &mut ctx.key);
+        labeled_expand(
+            SUITE_ID,
+            &secret,
+            b"base_nonce",
+            &context,
+            &mut ctx.base_nonce,
+        );
+        labeled_expand(
+            SUITE_ID,
+            &secret,
+            b"exp",
+            &context,
+            &mut ctx.exporter_secret,
+        );
+
+        secret.fill(0);
+
+        ctx
+    }
+
+    /// Computes the nonce for the current sequence number and advances it.
+    ///
+    /// The nonce is `base_nonce XOR I2OSP(seq, 12)`.
+    fn next_nonce(&mut self) -> Result<[u8; NONCE_LEN], HpkeError> {
+        if self.seq == u64::MAX {
+            return Err(HpkeError::MessageLimitReached);
+        }
+
+        let mut nonce = self.base_nonce;
+        for (n, s) in nonce[NONCE_LEN - 8..]
+            .iter_mut()
+            .zip(self.seq.to_be_bytes())
+        {
+            *n ^= s;
+        }
+
+        self.seq += 1;
+
+        Ok(nonce)
+    }
+
+    /// Computes `LabeledExpand(exporter_secret, "sec", exporter_context, L)`.
+    fn export(&self, exporter_context: &[u8], out: &mut [u8]) -> Result<(), HpkeError> {
+        if out.len() > MAX_EXPORT_LEN {
+            return Err(HpkeError::InvalidLength);
+        }
+
+        labeled_expand(
+            SUITE_ID,
+            &self.exporter_secret,
+            b"sec",
+            exporter_context,
+            out,
+        );
+
+        Ok(())
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        self.key.fill(0);
+        self.base_nonce.fill(0);
+        self.exporter_secret.fill(0);
+    }
+}
+
+/// Sender side of an HPKE session.
+///
+/// Created by the `setup_*_sender` functions. Key material is wiped when
+/// the context is dropped.
+pub struct SenderContext(Context);
+
+impl SenderContext {
+    /// Encrypts and authenticates the next message of the session.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(ciphertext || tag)`, `plaintext.len() + TAG_LEN` bytes
+    /// - `Err(MessageLimitReached)` if the sequence number is exhausted
+    pub fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        let nonce = self.0.next_nonce()?;
+
+        let mut out = vec![0u8; plaintext.len() + TAG_LEN];
+        let (ciphertext, tag) = out.split_at_mut(plaintext.len());
+
+        encrypt_with_aad(
+            &self.0.key,
+            &nonce,
+            aad,
+            plaintext,
+            ciphertext,
+            tag.try_into().unwrap(),
+        );
+
+        Ok(out)
+    }
+
+    /// Derives a secret of `out.len()` bytes bound to this session and to
+    /// `exporter_context`.
+    ///
+    /// Returns `Err(InvalidLength)` if `out` is longer than
+    /// [`MAX_EXPORT_LEN`].
+    pub fn export(&self, exporter_context: &[u8], out: &mut [u8]) -> Result<(), HpkeError> {
+        self.0.export(exporter_context, out)
+    }
+}
+
+/// Recipient side of an HPKE session.
+///
+/// Created by the `setup_*_recipient` functions. Key material is wiped
+/// when the context is dropped.
+pub struct RecipientContext(Context);
+
+impl RecipientContext {
+    /// Verifies and decrypts the next message of the session.
+    ///
+    /// The sequence number only advances when authentication succeeds, so
+    /// a forged message does not desynchronize the session.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(plaintext)` if authentication succeeds
+    /// - `Err(InvalidLength)` if `ciphertext` is shorter than a tag
+    /// - `Err(AuthenticationFailed)` if tag verification fails
+    /// - `Err(MessageLimitReached)` if the sequence number is exhausted
+    pub fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        if ciphertext.len() < TAG_LEN {
+            return Err(HpkeError::InvalidLength);
+        }
+
+        let seq = self.0.seq;
+        let nonce = self.0.next_nonce()?;
+
+        let (body, tag) = ciphertext.split_at(ciphertext.len() - TAG_LEN);
+        let mut plaintext = vec![0u8; body.len()];
+
+        if decrypt_with_aad(
+            &self.0.key,
+            &nonce,
+            aad,
+            body,
+            tag.try_into().unwrap(),
+            &mut plaintext,
+        )
+        .is_err()
+        {
+            self.0.seq = seq;
+            return Err(HpkeError::AuthenticationFailed);
+        }
+
+        Ok(plaintext)
+    }
+
+    /// Derives a secret of `out.len()` bytes bound to this session and to
+    /// `exporter_context`.
+    ///
+    /// Returns `Err(InvalidLength)` if `out` is longer than
+    /// [`MAX_EXPORT_LEN`].
+    pub fn export(&self, exporter_context: &[u8], out: &mut [u8]) -> Result<(), HpkeError> {
+        self.0.export(exporter_context, out)
+    }
+}
+
+/// Checks that the PSK inputs and sender key are consistent with `mode`.
+fn verify_mode(mode: Mode, psk: &[u8], psk_id: &[u8], has_sender: bool) -> Result<(), HpkeError> {
+    let got_psk = !psk.is_empty();
+    let got_psk_id = !psk_id.is_empty();
+
+    if got_psk != got_psk_id || got_psk != mode.uses_psk() {
+        return Err(HpkeError::InvalidMode);
+    }
+
+    if has_sender != mode.uses_auth() {
+        return Err(HpkeError::InvalidMode);
+    }
+
+    Ok(())
+}
+
+/// Deterministically derives an X25519 keypair from input keying material
+/// (RFC 9180 `DeriveKeyPair`).
+///
+/// Returns `(public, private)`. `ikm` should contain at least 32 bytes of
+/// entropy.
+pub fn derive_keypair(ikm: &[u8]) -> ([u8; 32], [u8; 32]) {
+    kem::derive_keypair(ikm)
+}
+
+/// Sets up a sender context with a caller-provided ephemeral private key.
+///
+/// This is the deterministic core of all `setup_*_sender` functions. It is
+/// exposed for protocols that manage ephemeral keys themselves and for
+/// reproducing test vectors.
+///
+/// # Parameters
+///
+/// - `mode`: the HPKE mode
+/// - `pk_r`: recipient public key
+/// - `info`: application-supplied context information
+/// - `psk`, `psk_id`: pre-shared key and its identifier, both empty unless
+///   the mode uses a PSK
+/// - `sk_s`: sender static private key, present only in the auth modes
+/// - `sk_e`: ephemeral private key, which must never be reused
+///
+/// # Returns
+///
+/// - `Ok((enc, context))` on success
+/// - `Err(InvalidMode)` if the inputs do not match `mode`
+/// - `Err(WeakPublicKey)` if `pk_r` is a small-order point
+pub fn setup_sender_with_ephemeral(
+    mode: Mode,
+    pk_r: &[u8; 32],
+    info: &[u8],
+    psk: &[u8],
+    psk_id: &[u8],
+    sk_s: Option<&[u8; 32]>,
+    sk_e: &[u8; 32],
+) -> Result<([u8; ENC_LEN], SenderContext), HpkeError> {
+    verify_mode(mode, psk, psk_id, sk_s.is_some())?;
+
+    let (mut shared, enc) = kem::encap(pk_r, sk_e, sk_s)?;
+    let ctx = Context::new(mode, &shared, info, psk, psk_id);
+    shared.fill(0);
+
+    Ok((enc, SenderContext(ctx)))
+}
+
+/// Sets up a sender context with a fresh ephemeral key.
+fn setup_sender(
+    mode: Mode,
+    pk_r: &[u8; 32],
+    info: &[u8],
+    psk: &[u8],
+    psk_id: &[u8],
+    sk_s: Option<&[u8; 32]>,
+) -> Result<([u8; ENC_LEN], SenderContext), HpkeError> {
+    let (_, mut sk_e) = x25519::generate_keypair();
+    let result = setup_sender_with_ephemeral(mode, pk_r, info, psk, psk_id, sk_s, &sk_e);
+    sk_e.fill(0);
+
+    result
+}
+
+/// Sets up a recipient context from the encapsulated key `enc`.
+fn setup_recipient(
+    mode: Mode,
+    enc: &[u8; ENC_LEN],
+    sk_r: &[u8; 32],
+    info: &[u8],
+    psk: &[u8],
+    psk_id: &[u8],
+    pk_s: Option<&[u8; 32]>,
+) -> Result<RecipientContext, HpkeError> {
+    verify_mode(mode, psk, psk_id, pk_s.is_some())?;
+
+    let mut shared = kem::decap(enc, sk_r, pk_s)?;
+    let ctx = Context::new(mode, &shared, info, psk, psk_id);
+    shared.fill(0);
+
+    Ok(RecipientContext(ctx))
+}
+
+/// Sets up a base mode sender context for the recipient `pk_r`.
+///
+/// Returns the encapsulated key to transmit and the sender context.
+pub fn setup_base_sender(
+    pk_r: &[u8; 32],
+    info: &[u8],
+) -> Result<([u8; ENC_LEN], SenderContext), HpkeError> {
+    setup_sender(Mode::Base, pk_r, info, &[], &[], None)
+}
+
+/// Sets up a base mode recipient context from the encapsulated key `enc`.
+pub fn setup_base_recipient(
+    enc: &[u8; ENC_LEN],
+    sk_r: &[u8; 32],
+    info: &[u8],
+) -> Result<RecipientContext, HpkeError> {
+    setup_recipient(Mode::Base, enc, sk_r, info, &[], &[], None)
+}
+
+/// Sets up a PSK mode sender context.
+///
+/// `psk` and `psk_id` must both be non-empty; `psk` should hold at least
+/// 32 bytes of entropy.
+pub fn setup_psk_sender(
+    pk_r: &[u8; 32],
+    info: &[u8],
+    psk: &[u8],
+    psk_id: &[u8],
+) -> Result<([u8; ENC_LEN], SenderContext), HpkeError> {
+    setup_sender(Mode::Psk, pk_r, info, psk, psk_id, None)
+}
+
+/// Sets up a PSK mode recipient context.
+pub fn setup_psk_recipient(
+    enc: &[u8; ENC_LEN],
+    sk_r: &[u8; 32],
+    info: &[u8],
+    psk: &[u8],
+    psk_id: &[u8],
+) -> Result<RecipientContext, HpkeError> {
+    setup_recipient(Mode::Psk, enc, sk_r, info, psk, psk_id, None)
+}
+
+/// Sets up an auth mode sender context, authenticated by `sk_s`.
+pub fn setup_auth_sender(
+    pk_r: &[u8; 32],
+    info: &[u8],
+    sk_s: &[u8; 32],
+) -> Result<([u8; ENC_LEN], SenderContext), HpkeError> {
+    setup_sender(Mode::Auth, pk_r, info, &[], &[], Some(sk_s))
+}
+
+/// Sets up an auth mode recipient context, expecting the sender `pk_s`.
+pub fn setup_auth_recipient(
+    enc: &[u8; ENC_LEN],
+    sk_r: &[u8; 32],
+    info: &[u8],
+    pk_s: &[u8; 32],
+) -> Result<RecipientContext, HpkeError> {
+    setup_recipient(Mode::Auth, enc, sk_r, info, &[], &[], Some(pk_s))
+}
+
+/// Sets up an auth-PSK mode sender context.
+pub fn setup_auth_psk_sender(
+    pk_r: &[u8; 32],
+    info: &[u8],
+    psk: &[u8],
+    psk_id: &[u8],
+    sk_s: &[u8; 32],
+) -> Result<([u8; ENC_LEN], SenderContext), HpkeError> {
+    setup_sender(Mode::AuthPsk, pk_r, info, psk, psk_id, Some(sk_s))
+}
+
+/// Sets up an auth-PSK mode recipient context.
+pub fn setup_auth_psk_recipient(
+    enc: &[u8; ENC_LEN],
+    sk_r: &[u8; 32],
+    info: &[u8],
+    psk: &[u8],
+    psk_id: &[u8],
+    pk_s: &[u8; 32],
+) -> Result<RecipientContext, HpkeError> {
+    setup_recipient(Mode::AuthPsk, enc, sk_r, info, psk, psk_id, Some(pk_s))
+}
+
+/// Encrypts a single message to `pk_r` in base mode.
+///
+/// # Returns
+///
+/// `Ok((enc, ciphertext || tag))` on success.
+pub fn seal(
+    pk_r: &[u8; 32],
+    info: &[u8],
+    aad: &[u8],
+    plaintext: &[u8],
+) -> Result<([u8; ENC_LEN], Vec<u8>), HpkeError> {
+    let (enc, mut ctx) = setup_base_sender(pk_r, info)?;
+    let ciphertext = ctx.seal(aad, plaintext)?;
+
+    Ok((enc, ciphertext))
+}
+
+/// Decrypts a single message produced by [`seal`].
+pub fn open(
+    enc: &[u8; ENC_LEN],
+    sk_r: &[u8; 32],
+    info: &[u8],
+    aad: &[u8],
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, HpkeError> {
+    setup_base_recipient(enc, sk_r, info)?.open(aad, ciphertext)
+}
@@ -0,0 +1,55 @@
+//! Labeled HKDF-SHA256 (RFC 9180 section 4).
+//!
+//! Every HPKE derivation is domain-separated by a version label, a suite
+//! identifier and an operation label:
+//!
+//! ```text
+//! LabeledExtract(salt, label, ikm) =
+//!     Extract(salt, "HPKE-v1" || suite_id || label || ikm)
+//!
+//! LabeledExpand(prk, label, info, L) =
+//!     Expand(prk, I2OSP(L, 2) || "HPKE-v1" || suite_id || label || info, L)
+//! ```
+//!
+//! The KEM and the key schedule use different suite identifiers, so their
+//! derivations never collide.
+
+use crate::derivation::hkdf::core::{HASH_LEN, HmacSha256, expand_parts};
+
+/// HPKE version label.
+const VERSION_LABEL: &[u8; 7] = b"HPKE-v1";
+
+/// Labeled HKDF-Extract.
+pub(crate) fn labeled_extract(
+    suite_id: &[u8],
+    salt: &[u8],
+    label: &[u8],
+    ikm: &[u8],
+) -> [u8; HASH_LEN] {
+    let mut mac = HmacSha256::new(salt);
+    mac.update(VERSION_LABEL);
+    mac.update(suite_id);
+    mac.update(label);
+    mac.update(ikm);
+    mac.finalize()
+}
+
+/// Labeled HKDF-Expand, filling the whole of `out`.
+///
+/// # Panics
+///
+/// Panics if `out` is longer than `u16::MAX` or than the HKDF limit. All
+/// callers in the crate either use fixed lengths or check the limit
+/// beforehand.
+pub(crate) fn labeled_expand(
+    suite_id: &[u8],
+    prk: &[u8; HASH_LEN],
+    label: &[u8],
+    info: &[u8],
+    out: &mut [u8],
+) {
+    let len = (out.len() as u16).to_be_bytes();
+
+    expand_parts(prk, &[&len, VERSION_LABEL, suite_id, label, info], out)
+        .expect("HPKE expansion length is checked by the caller");
+}
@@ -0,0 +1,131 @@
+//! DHKEM(X25519, HKDF-SHA256) (RFC 9180 section 4.1).
+//!
+//! The KEM turns an X25519 key agreement into a 32-byte shared secret bound
+//! to the public keys involved:
+//!
+//! ```text
+//! dh            = X25519(skE, pkR) [ || X25519(skS, pkR) ]
+//! kem_context   = enc || pkR [ || pkS ]
+//! eae_prk       = LabeledExtract("", "eae_prk", dh)
+//! shared_secret = LabeledExpand(eae_prk, "shared_secret", kem_context, 32)
+//! ```
+//!
+//! The bracketed parts only apply to the authenticated modes, where the
+//! sender's static key also contributes to the shared secret.
+
+use super::core::HpkeError;
+use super::kdf::{labeled_expand, labeled_extract};
+use crate::keys::x25519;
+
+/// KEM suite identifier: `"KEM" || I2OSP(0x0020, 2)`.
+const SUITE_ID: &[u8; 5] = b"KEM\x00\x20";
+
+/// Computes an X25519 shared secret, rejecting the all-zero output.
+fn dh(private: &[u8; 32], public: &[u8; 32]) -> Result<[u8; 32], HpkeError> {
+    let shared = x25519::exchange(private, public);
+
+    let mut acc = 0u8;
+    for b in shared {
+        acc |= b;
+    }
+
+    if acc == 0 {
+        return Err(HpkeError::WeakPublicKey);
+    }
+
+    Ok(shared)
+}
+
+/// Derives the KEM shared secret from the DH output(s) and the context.
+fn extract_and_expand(dh: &[u8], kem_context: &[u8]) -> [u8; 32] {
+    let mut prk = labeled_extract(SUITE_ID, &[], b"eae_prk", dh);
+
+    let mut shared = [0u8; 32];
+    labeled_expand(SUITE_ID, &prk, b"shared_secret", kem_context, &mut shared);
+
+    prk.fill(0);
+
+    shared
+}
+
+/// Deterministically derives a keypair from input keying material.
+///
+/// Returns `(public, private)`, matching `x25519::generate_keypair`.
+pub(crate) fn derive_keypair(ikm: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let mut prk = labeled_extract(SUITE_ID, &[], b"dkp_prk", ikm);
+
+    let mut private = [0u8; 32];
+    labeled_expand(SUITE_ID, &prk, b"sk", &[], &mut private);
+
+    prk.fill(0);
+
+    (x25519::public_key(&private), private)
+}
+
+/// Encapsulates a shared secret to `pk_r` using the ephemeral key `sk_e`.
+///
+/// If `sk_s` is provided, the sender's static key is mixed in (AuthEncap).
+///
+/// Returns `(shared_secret, enc)`.
+pub(crate) fn encap(
+    pk_r: &[u8; 32],
+    sk_e: &[u8; 32],
+    sk_s: Option<&[u8; 32]>,
+) -> Result<([u8; 32], [u8; 32]), HpkeError> {
+    let enc = x25519::public_key(sk_e);
+
+    let mut dh_out = [0u8; 64];
+    let mut context = [0u8; 96];
+
+    dh_out[..32].copy_from_slice(&dh(sk_e, pk_r)?);
+    context[..32].copy_from_slice(&enc);
+    context[32..64].copy_from_slice(pk_r);
+
+    let len = match sk_s {
+        Some(sk_s) => {
+            dh_out[32..].copy_from_slice(&dh(sk_s, pk_r)?);
+            context[64..].copy_from_slice(&x25519::public_key(sk_s));
+            2
+        }
+        None => 1,
+    };
+
+    let shared = extract_and_expand(&dh_out[..32 * len], &context[..32 + 32 * len]);
+    dh_out.fill(0);
+
+    Ok((shared, enc))
+}
+
+/// Recovers the shared secret encapsulated in `enc` for the keypair of
+/// `sk_r`.
+///
+/// If `pk_s` is provided, the sender's static public key is authenticated
+/// (AuthDecap).
+pub(crate) fn decap(
+    enc: &[u8; 32],
+    sk_r: &[u8; 32],
+    pk_s: Option<&[u8; 32]>,
+) -> Result<[u8; 32], HpkeError> {
+    let pk_r = x25519::public_key(sk_r);
+
+    let mut dh_out = [0u8; 64];
+    let mut context = [0u8; 96];
+
+    dh_out[..32].copy_from_slice(&dh(sk_r, enc)?);
+    context[..32].copy_from_slice(enc);
+    context[32..64].copy_from_slice(&pk_r);
+
+    let len = match pk_s {
+        Some(pk_s) => {
+            dh_out[32..].copy_from_slice(&dh(sk_r, pk_s)?);
+            context[64..].copy_from_slice(pk_s);
+            2
+        }
+        None => 1,
+    };
+
+    let shared = extract_and_expand(&dh_out[..32 * len], &context[..32 + 32 * len]);
+    dh_out.fill(0);
+
+    Ok(shared)
+}
@@ -0,0 +1,34 @@
+//! Hybrid Public Key Encryption (HPKE, RFC 9180).
+//!
+//! HPKE combines a key encapsulation mechanism (KEM), a key derivation
+//! function (KDF) and an AEAD into a standard way of encrypting to a
+//! recipient's public key. This module implements the single ciphersuite:
+//!
+//! - KEM: DHKEM(X25519, HKDF-SHA256) (`0x0020`)
+//! - KDF: HKDF-SHA256 (`0x0001`)
+//! - AEAD: ChaCha20-Poly1305 (`0x0003`)
+//!
+//! The implementation is split into three layers:
+//!
+//! - `kdf`: the labeled extract / expand functions shared by the KEM and
+//!   the key schedule
+//! - `kem`: DHKEM key derivation, encapsulation and decapsulation
+//! - `core`: the public API (modes, context setup, seal / open, export)
+
+/// Public HPKE API.
+///
+/// Exposes the setup functions for all four modes, the sender and
+/// recipient encryption contexts and the single-shot helpers.
+pub mod core;
+
+/// Labeled HKDF-SHA256 functions.
+///
+/// Internal helpers binding every derivation to the `HPKE-v1` version
+/// label and a suite identifier.
+pub(crate) mod kdf;
+
+/// DHKEM(X25519, HKDF-SHA256).
+///
+/// Internal key encapsulation mechanism. It performs no encryption and
+/// must remain internal to the crate.
+pub(crate) mod kem;
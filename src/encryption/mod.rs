@@ -1,12 +1,33 @@
-//! ChaCha20-Poly1305 authenticated encryption (RFC 8439).
+//! Authenticated encryption.
 //!
-//! This module exposes the ChaCha20-Poly1305 AEAD construction by re-exporting
-//! the internal Poly1305-based implementation under a clear, unambiguous name.
+//! This module exposes the crate's AEAD constructions by re-exporting the
+//! internal implementations under clear, unambiguous names:
 //!
-//! The underlying implementation is split internally for clarity and safety,
-//! but users of this module interact only with the high-level AEAD API.
+//! - `chacha20poly1305`: ChaCha20-Poly1305 (RFC 8439)
+//! - `nonce`: nonce sequences bound to ChaCha20-Poly1305 keys
+//! - `padding`: length-hiding padding (Padmé, buckets)
+//! - `chacha20blake2bsiv`: nonce-misuse-resistant ChaCha20-Blake2b-SIV
+//! - `committing`: key-committing wrapper over ChaCha20-Poly1305
+//! - `secretbox`: NaCl-compatible XSalsa20-Poly1305
+//! - `cryptobox` / `sealedbox`: NaCl-compatible public-key encryption
+//! - `hpke`: Hybrid Public Key Encryption (RFC 9180)
+//! - `password`: passphrase-encrypted containers (Argon2id + ChaCha20-Poly1305)
+//! - `age`: age v1 file encryption with X25519 recipients
+//!
+//! The underlying implementations are split internally for clarity and
+//! safety, but users of this module interact only with the high-level AEAD
+//! APIs.
 
+mod commitment;
+mod container;
+mod curve25519xsalsa20poly1305;
+mod fileformat;
+mod hybrid;
+mod pad;
 mod poly1305;
+mod sequence;
+mod siv;
+mod xsalsa20poly1305;
 
 /// ChaCha20-Poly1305 AEAD construction.
 ///
@@ -19,9 +40,130 @@ mod poly1305;
 /// - This module combines:
 ///   - ChaCha20 for encryption
 ///   - Poly1305 for authentication
+/// - Associated data is supported through `encrypt_with_aad` /
+///   `decrypt_with_aad`.
 /// - The API enforces one-time Poly1305 key usage internally.
 /// - Nonce reuse with the same key is catastrophic and must be avoided.
 ///
 /// This re-export intentionally hides the internal Poly1305 structure
 /// and exposes only the AEAD interface.
 pub use poly1305::core as chacha20poly1305;
+
+/// Nonce-managed ChaCha20-Poly1305.
+///
+/// Keys bound to counter or random nonce sequences, so that each sealing
+/// operation consumes a fresh nonce.
+///
+/// # Notes
+///
+/// - Sealed messages carry their nonce and can be opened in any order.
+/// - Sequences report exhaustion instead of wrapping around.
+pub use sequence::core as nonce;
+
+/// Length-hiding padding.
+///
+/// Padmé and fixed-bucket padding with unambiguous ISO/IEC 7816-4 style
+/// markers, removed in constant time after decryption.
+///
+/// # Notes
+///
+/// - Apply `pad` before encryption and `unpad` after decryption, or use
+///   the padding options of the `nonce` keys, `age` and `SecureStream`.
+/// - Padding hides lengths only up to the chosen granularity.
+pub use pad::core as padding;
+
+/// ChaCha20-Blake2b-SIV AEAD construction.
+///
+/// A synthetic-IV mode in which the authentication tag, computed with keyed
+/// Blake2b over the nonce, AAD and plaintext, also selects the ChaCha20
+/// keystream.
+///
+/// # Notes
+///
+/// - Accidental `(key, nonce)` reuse only leaks message equality.
+/// - Intended for writers that cannot guarantee nonce uniqueness.
+/// - Prefer `chacha20poly1305` when nonces are reliably unique, as it
+///   requires a single pass over the data.
+pub use siv::core as chacha20blake2bsiv;
+
+/// Key-committing ChaCha20-Poly1305.
+///
+/// Prepends a Blake2b commitment to the key and nonce so that a ciphertext
+/// only opens under the key that produced it.
+///
+/// # Notes
+///
+/// - Protects multi-key decryption (password guessing, multi-recipient
+///   schemes) against partitioning-oracle attacks.
+/// - Adds 48 bytes of overhead (32-byte commitment, 16-byte tag).
+pub use commitment::core as committing;
+
+/// NaCl / libsodium `crypto_secretbox` (XSalsa20-Poly1305).
+///
+/// Provided for interoperability with data sealed by NaCl or libsodium.
+///
+/// # Notes
+///
+/// - Uses a 192-bit nonce, which can safely be chosen at random.
+/// - Shares its Poly1305 implementation with `chacha20poly1305`.
+/// - Does not support associated data.
+pub use xsalsa20poly1305::core as secretbox;
+
+/// NaCl / libsodium `crypto_box` (X25519 + XSalsa20-Poly1305).
+///
+/// Public-key authenticated encryption between two X25519 keypairs.
+///
+/// # Notes
+///
+/// - The recipient can verify which keypair sent a message.
+/// - The shared key can be precomputed once per peer.
+/// - Small-order peer public keys are rejected.
+pub use curve25519xsalsa20poly1305::core as cryptobox;
+
+/// NaCl / libsodium `crypto_box_seal` anonymous sealed boxes.
+///
+/// Encrypts to a recipient's X25519 public key with a fresh ephemeral
+/// sender keypair, without any prior handshake.
+///
+/// # Notes
+///
+/// - Provides confidentiality and integrity, not sender authentication.
+/// - Adds 48 bytes of overhead (ephemeral public key and tag).
+pub use curve25519xsalsa20poly1305::sealed as sealedbox;
+
+/// Hybrid Public Key Encryption (RFC 9180).
+///
+/// DHKEM(X25519, HKDF-SHA256), HKDF-SHA256 and ChaCha20-Poly1305 in the
+/// base, PSK, auth and auth-PSK modes.
+///
+/// # Notes
+///
+/// - Interoperable with other RFC 9180 implementations of this suite.
+/// - Contexts number their messages; they must be opened in order.
+/// - Exported secrets can key application-level protocols.
+pub use hybrid::core as hpke;
+
+/// Passphrase-encrypted containers.
+///
+/// A versioned, self-describing format combining Argon2id and
+/// ChaCha20-Poly1305, with the whole header authenticated as associated
+/// data.
+///
+/// # Notes
+///
+/// - Reports wrong passphrases separately from corrupt data.
+/// - Containers can be re-sealed to raise their Argon2id costs.
+/// - Adds 93 bytes of overhead (77-byte header, 16-byte tag).
+pub use container::core as password;
+
+/// age v1 file encryption.
+///
+/// Encrypts files to one or more X25519 recipients (`age1...`) and
+/// decrypts them with the matching identities (`AGE-SECRET-KEY-1...`).
+///
+/// # Notes
+///
+/// - Interoperable with the reference age implementations.
+/// - Only the binary format is supported, not ASCII armor.
+/// - Scrypt (passphrase) recipients are not supported.
+pub use fileformat::core as age;
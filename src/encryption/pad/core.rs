@@ -0,0 +1,143 @@
+//! Length-hiding padding schemes.
+//!
+//! Messages are padded with the unambiguous ISO/IEC 7816-4 method: a
+//! single `0x80` marker followed by zero bytes up to the target length.
+//!
+//! ```text
+//! padded = message || 0x80 || 0x00 ... 0x00
+//! ```
+//!
+//! The target length `L'` of an input of length `L = len(message) + 1`
+//! is chosen by the `Padding` scheme:
+//!
+//! ```text
+//! Padmé:     E = floor(log2 L), S = floor(log2 E) + 1
+//!            L' = L rounded up to a multiple of 2^(E - S)
+//! Bucket(n): L' = L rounded up to a multiple of n
+//! ```
+//!
+//! ## Notes
+//!
+//! - Padmé leaks `O(log log L)` bits of the length with at most 12%
+//!   overhead, for any message size.
+//! - Buckets hide lengths completely within a bucket, at a fixed cost.
+//! - `unpad` scans the whole buffer, so its timing does not depend on
+//!   the amount of padding.
+
+/// Padding scheme applied before encryption.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Padding {
+    /// No padding; the message is encrypted as-is.
+    #[default]
+    None,
+    /// Padmé: round up to a length with a short mantissa.
+    Padme,
+    /// Round up to a multiple of the given bucket size (non-zero).
+    Bucket(usize),
+}
+
+/// Errors that can occur while padding or unpadding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaddingError {
+    /// The bucket size is zero.
+    InvalidBucket,
+    /// The input does not end with a valid `0x80 0x00*` padding.
+    InvalidPadding,
+}
+
+/// Returns the padded length of a `len`-byte message, marker included.
+///
+/// # Returns
+///
+/// - `Ok(len)` for [`Padding::None`]
+/// - `Ok(padded_len)` otherwise, always greater than `len`
+/// - `Err(InvalidBucket)` for a zero bucket size
+pub fn padded_len(padding: Padding, len: usize) -> Result<usize, PaddingError> {
+    let min = len + 1;
+
+    match padding {
+        Padding::None => Ok(len),
+        Padding::Padme => Ok(padme(min)),
+        Padding::Bucket(0) => Err(PaddingError::InvalidBucket),
+        Padding::Bucket(size) => Ok(min.div_ceil(size) * size),
+    }
+}
+
+/// Padmé length of an `len`-byte input.
+fn padme(len: usize) -> usize {
+    if len < 2 {
+        return len;
+    }
+
+    let e = len.ilog2();
+    let s = e.ilog2() + 1;
+    let mask = (1usize << (e - s)) - 1;
+
+    (len + mask) & !mask
+}
+
+/// Pads `message` according to `padding`.
+///
+/// # Returns
+///
+/// - `Ok(padded)` of length [`padded_len`]
+/// - `Err(InvalidBucket)` for a zero bucket size
+pub fn pad(padding: Padding, message: &[u8]) -> Result<Vec<u8>, PaddingError> {
+    let len = padded_len(padding, message.len())?;
+
+    let mut out = Vec::with_capacity(len);
+    out.extend_from_slice(message);
+
+    if padding != Padding::None {
+        out.push(0x80);
+        out.resize(len, 0);
+    }
+
+    Ok(out)
+}
+
+/// Returns the length of the message inside a padded buffer.
+///
+/// # Returns
+///
+/// - `Ok(len)` such that `padded[..len]` is the original message
+/// - `Err(InvalidPadding)` if `padded` does not end with `0x80 0x00*`
+///
+/// # Security Notes
+///
+/// - Every byte is inspected and no branch depends on the contents, so
+///   the timing only depends on `padded.len()`.
+pub fn unpad(padded: &[u8]) -> Result<usize, PaddingError> {
+    // All-ones while still inside the trailing zeros.
+    let mut in_zeros = 0xffu8;
+    let mut len = 0usize;
+    let mut invalid = 0u8;
+
+    for (i, &b) in padded.iter().enumerate().rev() {
+        let is_zero = ct_eq(b, 0x00);
+        let is_marker = ct_eq(b, 0x80);
+
+        // The first non-zero byte from the end must be the marker.
+        let found = in_zeros & is_marker;
+        invalid |= in_zeros & !is_zero & !is_marker;
+
+        len |= i & ((found & 1) as usize).wrapping_neg();
+
+        in_zeros &= is_zero;
+    }
+
+    // No marker at all.
+    invalid |= in_zeros;
+
+    if invalid != 0 {
+        return Err(PaddingError::InvalidPadding);
+    }
+
+    Ok(len)
+}
+
+/// Returns `0xff` if `a == b`, `0x00` otherwise, without branching.
+fn ct_eq(a: u8, b: u8) -> u8 {
+    let x = (a ^ b) as u16;
+    (((x.wrapping_sub(1)) >> 8) & 0xff) as u8
+}
@@ -0,0 +1,12 @@
+//! Length-hiding padding.
+//!
+//! Ciphertexts reveal the exact length of their plaintext. This module
+//! rounds messages up to a coarser set of lengths before encryption and
+//! removes the padding in constant time after decryption.
+//!
+//! The implementation is split into:
+//!
+//! - `core`: padding schemes and the public `pad` / `unpad` API
+
+/// Padding public API.
+pub mod core;
@@ -15,21 +15,14 @@
 //!
 //! ## Notes
 //!
-//! - This implementation currently uses an empty AAD (`AAD = []`).
+//! - `encrypt` / `decrypt` use an empty AAD; `encrypt_with_aad` /
+//!   `decrypt_with_aad` authenticate caller-provided associated data.
 //! - The caller must ensure `(key, nonce)` uniqueness.
 //! - Reusing a `(key, nonce)` pair breaks security.
 
 use super::mac::Poly1305;
 use crate::rng::chacha20::{block, xor};
 
-/// Additional Authenticated Data (AAD).
-///
-/// This data is authenticated but not encrypted.
-/// It is currently empty, but the construction fully supports AAD.
-///
-/// In a production API, this should be provided by the caller.
-const AAD: &[u8] = &[];
-
 /// Errors that can occur during ChaCha20-Poly1305 decryption.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Chacha20Poly1305Error {
@@ -41,6 +34,8 @@ pub enum Chacha20Poly1305Error {
 
 /// Encrypts and authenticates a message using ChaCha20-Poly1305.
 ///
+/// This is equivalent to [`encrypt_with_aad`] with an empty AAD.
+///
 /// # Parameters
 ///
 /// - `key`: 256-bit secret key
@@ -53,6 +48,34 @@ pub enum Chacha20Poly1305Error {
 ///
 /// Panics if `plaintext.len() != ciphertext.len()`.
 ///
+/// # Security Notes
+///
+/// - `(key, nonce)` MUST be unique per encryption.
+pub fn encrypt(
+    key: &[u8; 32],
+    nonce: &[u8; 12],
+    plaintext: &[u8],
+    ciphertext: &mut [u8],
+    tag: &mut [u8; 16],
+) {
+    encrypt_with_aad(key, nonce, &[], plaintext, ciphertext, tag);
+}
+
+/// Encrypts a message and authenticates it together with associated data.
+///
+/// # Parameters
+///
+/// - `key`: 256-bit secret key
+/// - `nonce`: 96-bit nonce (IETF variant)
+/// - `aad`: Additional authenticated data (authenticated, not encrypted)
+/// - `plaintext`: Input message to encrypt
+/// - `ciphertext`: Output buffer for encrypted data (same length as `plaintext`)
+/// - `tag`: Output authentication tag (16 bytes)
+///
+/// # Panics
+///
+/// Panics if `plaintext.len() != ciphertext.len()`.
+///
 /// # Algorithm
 ///
 /// 1. Derive the Poly1305 one-time key using `ChaCha20(key, nonce, counter = 0)`
@@ -65,12 +88,13 @@ pub enum Chacha20Poly1305Error {
 ///
 /// # Security Notes
 ///
-/// - This function does not allocate secret material on the heap except
-///   for the MAC buffer.
+/// - The MAC input is streamed into Poly1305; no heap allocation is
+///   performed.
 /// - `(key, nonce)` MUST be unique per encryption.
-pub fn encrypt(
+pub fn encrypt_with_aad(
     key: &[u8; 32],
     nonce: &[u8; 12],
+    aad: &[u8],
     plaintext: &[u8],
     ciphertext: &mut [u8],
     tag: &mut [u8; 16],
@@ -78,35 +102,51 @@ pub fn encrypt(
     assert_eq!(plaintext.len(), ciphertext.len());
 
     // Generate one-time key for Poly1305
-    let block0 = block(key, 0, nonce);
-    let mut otk = [0u8; 32];
-    otk.copy_from_slice(&block0[..32]);
+    let mut otk = one_time_key(key, nonce);
 
     // Encrypt plaintext (ChaCha20 is symmetric)
     xor(key, nonce, 1, plaintext, ciphertext);
 
-    // Build MAC input according to RFC 8439
-    let mut mac_data = Vec::new();
-
-    mac_data.extend_from_slice(AAD);
-    pad16(&mut mac_data);
-
-    mac_data.extend_from_slice(ciphertext);
-    pad16(&mut mac_data);
-
-    mac_data.extend_from_slice(&(AAD.len() as u64).to_le_bytes());
-    mac_data.extend_from_slice(&(ciphertext.len() as u64).to_le_bytes());
+    // Compute authentication tag over AAD and ciphertext
+    *tag = aead_tag(&otk, aad, ciphertext);
 
-    // Compute authentication tag
-    auth(tag, &otk, &mac_data);
+    otk.fill(0);
 }
 
 /// Decrypts and authenticates a message using ChaCha20-Poly1305.
 ///
+/// This is equivalent to [`decrypt_with_aad`] with an empty AAD.
+///
+/// # Parameters
+///
+/// - `key`: 256-bit secret key
+/// - `nonce`: 96-bit nonce (IETF variant)
+/// - `ciphertext`: Encrypted input data
+/// - `tag`: Authentication tag to verify
+/// - `plaintext`: Output buffer for decrypted data
+///
+/// # Returns
+///
+/// - `Ok(())` if authentication succeeds and decryption is successful
+/// - `Err(InvalidLength)` if buffer sizes mismatch
+/// - `Err(AuthenticationFailed)` if tag verification fails
+pub fn decrypt(
+    key: &[u8; 32],
+    nonce: &[u8; 12],
+    ciphertext: &[u8],
+    tag: &[u8; 16],
+    plaintext: &mut [u8],
+) -> Result<(), Chacha20Poly1305Error> {
+    decrypt_with_aad(key, nonce, &[], ciphertext, tag, plaintext)
+}
+
+/// Decrypts a message and verifies it together with associated data.
+///
 /// # Parameters
 ///
 /// - `key`: 256-bit secret key
 /// - `nonce`: 96-bit nonce (IETF variant)
+/// - `aad`: Additional authenticated data used during encryption
 /// - `ciphertext`: Encrypted input data
 /// - `tag`: Authentication tag to verify
 /// - `plaintext`: Output buffer for decrypted data
@@ -120,7 +160,7 @@ pub fn encrypt(
 /// # Algorithm
 ///
 /// 1. Recompute the Poly1305 one-time key
-/// 2. Rebuild the MAC input exactly as in `encrypt`
+/// 2. Rebuild the MAC input exactly as in `encrypt_with_aad`
 /// 3. Verify the authentication tag in constant time
 /// 4. Decrypt the ciphertext if authentication succeeds
 ///
@@ -128,9 +168,10 @@ pub fn encrypt(
 ///
 /// - Decryption is only performed after successful authentication
 /// - Tag comparison is constant-time
-pub fn decrypt(
+pub fn decrypt_with_aad(
     key: &[u8; 32],
     nonce: &[u8; 12],
+    aad: &[u8],
     ciphertext: &[u8],
     tag: &[u8; 16],
     plaintext: &mut [u8],
@@ -139,23 +180,8 @@ pub fn decrypt(
         return Err(Chacha20Poly1305Error::InvalidLength);
     }
 
-    let block0 = block(key, 0, nonce);
-    let mut otk = [0u8; 32];
-    otk.copy_from_slice(&block0[..32]);
-
-    let mut mac_data = Vec::new();
-
-    mac_data.extend_from_slice(AAD);
-    pad16(&mut mac_data);
-
-    mac_data.extend_from_slice(ciphertext);
-    pad16(&mut mac_data);
-
-    mac_data.extend_from_slice(&(AAD.len() as u64).to_le_bytes());
-    mac_data.extend_from_slice(&(ciphertext.len() as u64).to_le_bytes());
-
-    let mut expected_tag = [0u8; 16];
-    auth(&mut expected_tag, &otk, &mac_data);
+    let mut otk = one_time_key(key, nonce);
+    let expected_tag = aead_tag(&otk, aad, ciphertext);
 
     let mut diff = 0u8;
     for i in 0..16 {
@@ -172,15 +198,39 @@ pub fn decrypt(
     Ok(())
 }
 
-/// Pads a buffer with zero bytes until its length is a multiple of 16.
-///
-/// This is required by the Poly1305 input format defined in RFC 8439.
+/// Derives the Poly1305 one-time key from ChaCha20 block 0.
 #[inline(always)]
-fn pad16(buf: &mut Vec<u8>) {
-    let rem = buf.len() % 16;
-    if rem != 0 {
-        buf.resize(buf.len() + (16 - rem), 0);
+fn one_time_key(key: &[u8; 32], nonce: &[u8; 12]) -> [u8; 32] {
+    let mut block0 = block(key, 0, nonce);
+    let mut otk = [0u8; 32];
+    otk.copy_from_slice(&block0[..32]);
+    block0.fill(0);
+
+    otk
+}
+
+/// Computes the RFC 8439 AEAD tag over `aad` and `ciphertext`.
+///
+/// The MAC input `AAD || pad16 || ciphertext || pad16 || len(AAD) ||
+/// len(ciphertext)` is streamed block by block. Trailing partial blocks are
+/// zero-padded to 16 bytes, which is exactly the `pad16` step.
+fn aead_tag(otk: &[u8; 32], aad: &[u8], ciphertext: &[u8]) -> [u8; 16] {
+    let mut mac = Poly1305::new(otk);
+
+    for data in [aad, ciphertext] {
+        for chunk in data.chunks(16) {
+            let mut padded = [0u8; 16];
+            padded[..chunk.len()].copy_from_slice(chunk);
+            mac.update_block(&padded);
+        }
     }
+
+    let mut lengths = [0u8; 16];
+    lengths[..8].copy_from_slice(&(aad.len() as u64).to_le_bytes());
+    lengths[8..].copy_from_slice(&(ciphertext.len() as u64).to_le_bytes());
+    mac.update_block(&lengths);
+
+    mac.finalize()
 }
 
 /// Computes a Poly1305 authentication tag.
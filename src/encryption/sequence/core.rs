@@ -0,0 +1,345 @@
+//! Nonce sequences and nonce-owning ChaCha20-Poly1305 keys.
+//!
+//! A `SealingKey` owns both a 256-bit key and a `NonceSequence`. Each
+//! call to `SealingKey::seal` draws the next nonce from the sequence, so
+//! a nonce can never be supplied twice by mistake.
+//!
+//! ## Sequences
+//!
+//! ```text
+//! CounterNonce: nonce = prefix (4 bytes) || counter (8 bytes, big-endian)
+//! RandomNonce:  nonce = 12 bytes from the CSPRNG
+//! ```
+//!
+//! - Counter nonces never repeat until the 64-bit counter is exhausted.
+//!   The prefix must be unique per sender sharing the same key.
+//! - Random nonces are stateless across restarts, but are limited to
+//!   2^32 messages per key to keep the collision probability negligible.
+//!
+//! ## Sealed format
+//!
+//! ```text
+//! nonce (12 bytes) || ciphertext || tag (16 bytes)
+//! ```
+//!
+//! ## Notes
+//!
+//! - Keys and sequences are deliberately not `Clone`: a copy would replay
+//!   the same nonces.
+//! - Exhaustion is permanent; a new key must be established.
+//! - Both keys can pad messages to hide their length; see the `padding`
+//!   module. The sealing and opening sides must agree on its use.
+
+use crate::encryption::chacha20poly1305::{
+    Chacha20Poly1305Error, decrypt_with_aad, encrypt_with_aad,
+};
+use crate::encryption::padding::{Padding, PaddingError, pad, padded_len, unpad};
+use crate::rng::Csprng;
+
+/// Length of a ChaCha20-Poly1305 nonce.
+pub const NONCE_LEN: usize = 12;
+
+/// Length of the Poly1305 tag.
+pub const TAG_LEN: usize = 16;
+
+/// Total size overhead of a sealed message.
+pub const OVERHEAD: usize = NONCE_LEN + TAG_LEN;
+
+/// Length of the fixed prefix of counter nonces.
+pub const PREFIX_LEN: usize = 4;
+
+/// Maximum number of random nonces drawn under a single key.
+pub const RANDOM_NONCE_LIMIT: u64 = 1 << 32;
+
+/// Errors that can occur when drawing a nonce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonceError {
+    /// The sequence cannot produce any further unique nonce.
+    Exhausted,
+}
+
+/// A source of unique nonces for a single key.
+///
+/// Implementations must never return the same nonce twice and must fail
+/// with [`NonceError::Exhausted`] once uniqueness can no longer be
+/// guaranteed.
+pub trait NonceSequence {
+    /// Returns the next nonce of the sequence.
+    fn advance(&mut self) -> Result<[u8; NONCE_LEN], NonceError>;
+}
+
+/// Counter-based nonce sequence with a fixed prefix.
+///
+/// Produces `prefix || counter` with a big-endian 64-bit counter.
+pub struct CounterNonce {
+    prefix: [u8; PREFIX_LEN],
+    next: Option<u64>,
+}
+
+impl CounterNonce {
+    /// Creates a sequence starting at counter 0.
+    ///
+    /// # Security Notes
+    ///
+    /// - Two senders using the same key must use different prefixes.
+    pub fn new(prefix: [u8; PREFIX_LEN]) -> Self {
+        Self::resume(prefix, 0)
+    }
+
+    /// Creates a sequence with a random prefix starting at counter 0.
+    ///
+    /// Random prefixes avoid coordination between senders, at the cost of
+    /// a 2^-32 collision probability per pair of senders.
+    pub fn with_random_prefix() -> Self {
+        let mut prefix = [0u8; PREFIX_LEN];
+        Csprng::new().fill_bytes(&mut prefix);
+
+        Self::new(prefix)
+    }
+
+    /// Resumes a sequence at a previously saved [`position`](Self::position).
+    ///
+    /// # Security Notes
+    ///
+    /// - The saved position must be persisted *before* the corresponding
+    ///   nonces are used, otherwise a crash may cause reuse.
+    pub fn resume(prefix: [u8; PREFIX_LEN], next: u64) -> Self {
+        Self {
+            prefix,
+            next: Some(next),
+        }
+    }
+
+    /// Returns the counter of the next nonce, or `None` if exhausted.
+    pub fn position(&self) -> Option<u64> {
+        self.next
+    }
+}
+
+impl NonceSequence for CounterNonce {
+    fn advance(&mut self) -> Result<[u8; NONCE_LEN], NonceError> {
+        let counter = self.next.ok_or(NonceError::Exhausted)?;
+        self.next = counter.checked_add(1);
+
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce[..PREFIX_LEN].copy_from_slice(&self.prefix);
+        nonce[PREFIX_LEN..].copy_from_slice(&counter.to_be_bytes());
+
+        Ok(nonce)
+    }
+}
+
+/// Random nonce sequence backed by [`Csprng`].
+///
+/// Each nonce is 96 random bits. The number of nonces is capped so that
+/// the probability of a collision stays below 2^-32.
+pub struct RandomNonce {
+    rng: Csprng,
+    remaining: u64,
+}
+
+impl RandomNonce {
+    /// Creates a sequence allowing [`RANDOM_NONCE_LIMIT`] nonces.
+    pub fn new() -> Self {
+        Self::with_limit(RANDOM_NONCE_LIMIT)
+    }
+
+    /// Creates a sequence allowing at most `limit` nonces.
+    ///
+    /// `limit` is capped at [`RANDOM_NONCE_LIMIT`].
+    pub fn with_limit(limit: u64) -> Self {
+        Self {
+            rng: Csprng::new(),
+            remaining: limit.min(RANDOM_NONCE_LIMIT),
+        }
+    }
+
+    /// Returns the number of nonces that can still be drawn.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+}
+
+impl Default for RandomNonce {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NonceSequence for RandomNonce {
+    fn advance(&mut self) -> Result<[u8; NONCE_LEN], NonceError> {
+        if self.remaining == 0 {
+            return Err(NonceError::Exhausted);
+        }
+        self.remaining -= 1;
+
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng.fill_bytes(&mut nonce);
+
+        Ok(nonce)
+    }
+}
+
+/// A ChaCha20-Poly1305 key bound to a nonce sequence.
+///
+/// The key is wiped when dropped.
+pub struct SealingKey<N: NonceSequence> {
+    key: [u8; 32],
+    nonces: N,
+    padding: Padding,
+}
+
+impl<N: NonceSequence> SealingKey<N> {
+    /// Binds `key` to `nonces`.
+    ///
+    /// # Security Notes
+    ///
+    /// - The same key must not be bound to two sequences that can produce
+    ///   the same nonce (e.g. two counters with the same prefix).
+    pub fn new(key: [u8; 32], nonces: N) -> Self {
+        Self {
+            key,
+            nonces,
+            padding: Padding::None,
+        }
+    }
+
+    /// Pads every message with `padding` before encryption.
+    ///
+    /// # Returns
+    ///
+    /// - `Err(InvalidBucket)` for a zero bucket size
+    pub fn with_padding(mut self, padding: Padding) -> Result<Self, PaddingError> {
+        padded_len(padding, 0)?;
+        self.padding = padding;
+
+        Ok(self)
+    }
+
+    /// Encrypts and authenticates a message under the next nonce.
+    ///
+    /// # Parameters
+    ///
+    /// - `aad`: Additional authenticated data (not encrypted)
+    /// - `plaintext`: Input message to encrypt
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(nonce || ciphertext || tag)`, i.e. `OVERHEAD` bytes plus the
+    ///   (padded) plaintext length
+    /// - `Err(Exhausted)` if the nonce sequence is exhausted
+    pub fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, NonceError> {
+        let nonce = self.nonces.advance()?;
+
+        // The padding was validated by `with_padding`.
+        let mut padded = pad(self.padding, plaintext).unwrap();
+
+        let mut out = vec![0u8; padded.len() + OVERHEAD];
+        out[..NONCE_LEN].copy_from_slice(&nonce);
+
+        let (body, tag) = out[NONCE_LEN..].split_at_mut(padded.len());
+        encrypt_with_aad(
+            &self.key,
+            &nonce,
+            aad,
+            &padded,
+            body,
+            tag.try_into().unwrap(),
+        );
+
+        padded.fill(0);
+
+        Ok(out)
+    }
+
+    /// Returns the underlying nonce sequence, e.g. to save its position.
+    pub fn nonces(&self) -> &N {
+        &self.nonces
+    }
+}
+
+impl<N: NonceSequence> Drop for SealingKey<N> {
+    fn drop(&mut self) {
+        self.key.fill(0);
+    }
+}
+
+/// A ChaCha20-Poly1305 key for opening messages sealed by a [`SealingKey`].
+///
+/// The key is wiped when dropped.
+pub struct OpeningKey {
+    key: [u8; 32],
+    padding: bool,
+}
+
+impl OpeningKey {
+    /// Wraps `key` for decryption.
+    pub fn new(key: [u8; 32]) -> Self {
+        Self {
+            key,
+            padding: false,
+        }
+    }
+
+    /// Removes the padding of messages sealed with
+    /// [`SealingKey::with_padding`].
+    ///
+    /// Any padding scheme can be removed, so the scheme itself need not be
+    /// known.
+    pub fn with_padding(mut self) -> Self {
+        self.padding = true;
+        self
+    }
+
+    /// Verifies and decrypts the output of [`SealingKey::seal`].
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(plaintext)` if the tag (and padding, if enabled) verifies
+    /// - `Err(InvalidLength)` if `sealed` is shorter than `OVERHEAD`
+    /// - `Err(AuthenticationFailed)` otherwise
+    ///
+    /// # Security Notes
+    ///
+    /// - Malformed padding is reported as an authentication failure, so
+    ///   both checks are indistinguishable to the caller.
+    pub fn open(&self, aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>, Chacha20Poly1305Error> {
+        if sealed.len() < OVERHEAD {
+            return Err(Chacha20Poly1305Error::InvalidLength);
+        }
+
+        let (nonce, rest) = sealed.split_at(NONCE_LEN);
+        let (body, tag) = rest.split_at(rest.len() - TAG_LEN);
+
+        let mut plaintext = vec![0u8; body.len()];
+        decrypt_with_aad(
+            &self.key,
+            nonce.try_into().unwrap(),
+            aad,
+            body,
+            tag.try_into().unwrap(),
+            &mut plaintext,
+        )?;
+
+        if self.padding {
+            match unpad(&plaintext) {
+                Ok(len) => {
+                    plaintext[len..].fill(0);
+                    plaintext.truncate(len);
+                }
+                Err(_) => {
+                    plaintext.fill(0);
+                    return Err(Chacha20Poly1305Error::AuthenticationFailed);
+                }
+            }
+        }
+
+        Ok(plaintext)
+    }
+}
+
+impl Drop for OpeningKey {
+    fn drop(&mut self) {
+        self.key.fill(0);
+    }
+}
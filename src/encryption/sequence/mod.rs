@@ -0,0 +1,13 @@
+//! Nonce management for ChaCha20-Poly1305.
+//!
+//! The raw AEAD API takes caller-provided nonces and leaves their
+//! uniqueness to the caller. This module binds a key to a nonce sequence
+//! so that every sealing operation consumes a fresh nonce, making
+//! `(key, nonce)` reuse impossible through the safe API.
+//!
+//! The implementation is split into:
+//!
+//! - `core`: nonce sequences and the `SealingKey` / `OpeningKey` API
+
+/// Nonce sequence public API.
+pub mod core;
@@ -0,0 +1,188 @@
+//! ChaCha20-Blake2b-SIV authenticated encryption.
+//!
+//! This module implements a synthetic-IV AEAD over ChaCha20 and keyed
+//! Blake2b. Unlike ChaCha20-Poly1305, accidental nonce reuse does not break
+//! confidentiality or authenticity: it only leaks whether the same message
+//! was encrypted twice under the same key, nonce and AAD.
+//!
+//! ## Construction
+//!
+//! Two independent subkeys are derived from the 256-bit master key:
+//!
+//! ```text
+//! mac_key || enc_key = Blake2b-512(key = K, "Cryptal SIV subkeys")
+//! ```
+//!
+//! Encryption then proceeds as:
+//!
+//! ```text
+//! T  = Blake2b-128(key = mac_key,
+//!                  nonce || le64(len(AAD)) || le64(len(P)) || AAD || P)
+//! K' = HChaCha20(enc_key, T)
+//! C  = P XOR ChaCha20(K', nonce = 0, counter = 0)
+//! ```
+//!
+//! The output is the ciphertext `C` together with the 16-byte tag `T`.
+//!
+//! ## Design goals
+//!
+//! - Dependency-free, built only from existing crate primitives
+//! - Constant-time authentication check
+//! - Deterministic: the same inputs always yield the same output
+//! - Same buffer-oriented API shape as `chacha20poly1305`
+//!
+//! ## Notes
+//!
+//! - Nonces are still recommended: with unique nonces the construction is
+//!   a regular randomized AEAD and leaks nothing.
+//! - Encryption requires two passes over the plaintext (MAC, then cipher).
+
+use crate::hash::blake2b::core::Blake2b;
+use crate::hash::blake2b_keyed;
+use crate::rng::chacha20::{hchacha20, xor};
+
+/// Domain separation label for subkey derivation.
+const SUBKEY_LABEL: &[u8] = b"Cryptal SIV subkeys";
+
+/// Errors that can occur during ChaCha20-Blake2b-SIV decryption.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SivError {
+    /// The input and output buffers have mismatched lengths.
+    InvalidLength,
+    /// The synthetic IV did not match the decrypted message.
+    AuthenticationFailed,
+}
+
+/// Encrypts and authenticates a message using ChaCha20-Blake2b-SIV.
+///
+/// # Parameters
+///
+/// - `key`: 256-bit secret key
+/// - `nonce`: 96-bit nonce (should be unique, but reuse is tolerated)
+/// - `aad`: Additional authenticated data (not encrypted)
+/// - `plaintext`: Input message to encrypt
+/// - `ciphertext`: Output buffer for encrypted data (same length as `plaintext`)
+/// - `tag`: Output synthetic IV / authentication tag (16 bytes)
+///
+/// # Panics
+///
+/// Panics if `plaintext.len() != ciphertext.len()`.
+///
+/// # Security Notes
+///
+/// - Reusing `(key, nonce)` reveals only whether the `(aad, plaintext)`
+///   pairs are identical.
+/// - The tag is a PRF output over the full input and doubles as the IV.
+pub fn encrypt(
+    key: &[u8; 32],
+    nonce: &[u8; 12],
+    aad: &[u8],
+    plaintext: &[u8],
+    ciphertext: &mut [u8],
+    tag: &mut [u8; 16],
+) {
+    assert_eq!(plaintext.len(), ciphertext.len());
+
+    let (mut mac_key, mut enc_key) = subkeys(key);
+
+    *tag = synthetic_iv(&mac_key, nonce, aad, plaintext);
+
+    let mut stream_key = hchacha20(&enc_key, tag);
+    xor(&stream_key, &[0u8; 12], 0, plaintext, ciphertext);
+
+    mac_key.fill(0);
+    enc_key.fill(0);
+    stream_key.fill(0);
+}
+
+/// Decrypts and authenticates a message using ChaCha20-Blake2b-SIV.
+///
+/// # Parameters
+///
+/// - `key`: 256-bit secret key
+/// - `nonce`: 96-bit nonce used during encryption
+/// - `aad`: Additional authenticated data used during encryption
+/// - `ciphertext`: Encrypted input data
+/// - `tag`: Synthetic IV / authentication tag to verify
+/// - `plaintext`: Output buffer for decrypted data
+///
+/// # Returns
+///
+/// - `Ok(())` if authentication succeeds
+/// - `Err(InvalidLength)` if buffer sizes mismatch
+/// - `Err(AuthenticationFailed)` if the tag does not match
+///
+/// # Security Notes
+///
+/// - In SIV mode the message must be decrypted before it can be
+///   authenticated. On failure, `plaintext` is zeroed before returning so
+///   that unauthenticated data is never released.
+/// - Tag comparison is constant-time.
+pub fn decrypt(
+    key: &[u8; 32],
+    nonce: &[u8; 12],
+    aad: &[u8],
+    ciphertext: &[u8],
+    tag: &[u8; 16],
+    plaintext: &mut [u8],
+) -> Result<(), SivError> {
+    if plaintext.len() != ciphertext.len() {
+        return Err(SivError::InvalidLength);
+    }
+
+    let (mut mac_key, mut enc_key) = subkeys(key);
+
+    let mut stream_key = hchacha20(&enc_key, tag);
+    xor(&stream_key, &[0u8; 12], 0, ciphertext, plaintext);
+
+    let expected_tag = synthetic_iv(&mac_key, nonce, aad, plaintext);
+
+    mac_key.fill(0);
+    enc_key.fill(0);
+    stream_key.fill(0);
+
+    let mut diff = 0u8;
+    for i in 0..16 {
+        diff |= expected_tag[i] ^ tag[i];
+    }
+
+    if diff != 0 {
+        plaintext.fill(0);
+        return Err(SivError::AuthenticationFailed);
+    }
+
+    Ok(())
+}
+
+/// Derives the independent MAC and encryption subkeys from the master key.
+fn subkeys(key: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
+    let mut okm = blake2b_keyed(64, key, SUBKEY_LABEL);
+
+    let mut mac_key = [0u8; 32];
+    let mut enc_key = [0u8; 32];
+    mac_key.copy_from_slice(&okm[..32]);
+    enc_key.copy_from_slice(&okm[32..]);
+
+    okm.fill(0);
+
+    (mac_key, enc_key)
+}
+
+/// Computes the 128-bit synthetic IV over the nonce, AAD and plaintext.
+///
+/// Both lengths are encoded before the variable-length fields so that the
+/// MAC input is an injective encoding of `(nonce, aad, plaintext)`.
+fn synthetic_iv(mac_key: &[u8; 32], nonce: &[u8; 12], aad: &[u8], plaintext: &[u8]) -> [u8; 16] {
+    let mut mac = Blake2b::new(16, mac_key);
+
+    mac.update(nonce);
+    mac.update(&(aad.len() as u64).to_le_bytes());
+    mac.update(&(plaintext.len() as u64).to_le_bytes());
+    mac.update(aad);
+    mac.update(plaintext);
+
+    let mut iv = [0u8; 16];
+    iv.copy_from_slice(&mac.finalize()[..16]);
+
+    iv
+}
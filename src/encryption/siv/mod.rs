@@ -0,0 +1,22 @@
+//! Nonce-misuse-resistant authenticated encryption (synthetic IV).
+//!
+//! This module implements a deterministic, nonce-misuse-resistant AEAD
+//! construction built from the crate's existing primitives:
+//!
+//! - keyed Blake2b as a PRF computing a synthetic IV over the whole input
+//! - HChaCha20 + ChaCha20 as a stream cipher keyed by that synthetic IV
+//!
+//! The structure follows the SIV paradigm (Rogaway–Shrimpton): the tag is
+//! computed first over `(nonce, AAD, plaintext)` and then used as the IV of
+//! the encryption. As a result, repeating a nonce under the same key only
+//! reveals whether two messages (with identical AAD) are equal.
+//!
+//! The implementation is split into:
+//!
+//! - `core`: the public encryption and decryption API
+
+/// ChaCha20-Blake2b-SIV public API.
+///
+/// This module exposes the `encrypt` / `decrypt` pair and the associated
+/// error type. Subkey derivation and tag computation remain internal.
+pub mod core;
@@ -0,0 +1,172 @@
+//! XSalsa20-Poly1305 authenticated encryption (NaCl `crypto_secretbox`).
+//!
+//! This module implements the `secretbox` construction from NaCl, as also
+//! provided by libsodium. It combines:
+//!
+//! - XSalsa20 as a stream cipher with a 192-bit nonce
+//! - Poly1305 as a one-time MAC, keyed from the first 32 keystream bytes
+//!
+//! Data sealed here can be opened by `crypto_secretbox_open_easy` and vice
+//! versa.
+//!
+//! ## Layout
+//!
+//! The combined format (libsodium `crypto_secretbox_easy`) is:
+//!
+//! ```text
+//! tag (16 bytes) || ciphertext
+//! ```
+//!
+//! The detached variants expose the ciphertext and the tag separately.
+//!
+//! ## Notes
+//!
+//! - There is no associated data in `secretbox`.
+//! - The 192-bit nonce is large enough to be generated at random.
+//! - Reusing a `(key, nonce)` pair breaks security.
+
+use super::salsa20::{xsalsa20_prefix, xsalsa20_xor};
+use crate::encryption::chacha20poly1305::auth;
+
+/// Length of a `secretbox` key in bytes.
+pub const KEY_LEN: usize = 32;
+
+/// Length of a `secretbox` nonce in bytes.
+pub const NONCE_LEN: usize = 24;
+
+/// Length of the Poly1305 authentication tag in bytes.
+pub const TAG_LEN: usize = 16;
+
+/// Errors that can occur while opening a `secretbox`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretboxError {
+    /// The input is too short, or the buffers have mismatched lengths.
+    InvalidLength,
+    /// The authentication tag verification failed.
+    AuthenticationFailed,
+}
+
+/// Encrypts and authenticates a message with XSalsa20-Poly1305.
+///
+/// # Parameters
+///
+/// - `key`: 256-bit secret key
+/// - `nonce`: 192-bit nonce
+/// - `plaintext`: Input message to encrypt
+/// - `ciphertext`: Output buffer for encrypted data (same length as `plaintext`)
+/// - `tag`: Output authentication tag (16 bytes)
+///
+/// # Panics
+///
+/// Panics if `plaintext.len() != ciphertext.len()`.
+///
+/// # Algorithm
+///
+/// 1. Derive the XSalsa20 keystream from `(key, nonce)`
+/// 2. Use keystream bytes 0..32 as the Poly1305 one-time key
+/// 3. Encrypt the plaintext with the keystream starting at byte 32
+/// 4. Authenticate the ciphertext with Poly1305
+pub fn seal_detached(
+    key: &[u8; KEY_LEN],
+    nonce: &[u8; NONCE_LEN],
+    plaintext: &[u8],
+    ciphertext: &mut [u8],
+    tag: &mut [u8; TAG_LEN],
+) {
+    assert_eq!(plaintext.len(), ciphertext.len());
+
+    let mut otk = xsalsa20_prefix(key, nonce);
+
+    xsalsa20_xor(key, nonce, 32, plaintext, ciphertext);
+    auth(tag, &otk, ciphertext);
+
+    otk.fill(0);
+}
+
+/// Verifies and decrypts a message produced by [`seal_detached`].
+///
+/// # Returns
+///
+/// - `Ok(())` if authentication succeeds
+/// - `Err(InvalidLength)` if buffer sizes mismatch
+/// - `Err(AuthenticationFailed)` if tag verification fails
+///
+/// # Security Notes
+///
+/// - Decryption is only performed after successful authentication
+/// - Tag comparison is constant-time
+pub fn open_detached(
+    key: &[u8; KEY_LEN],
+    nonce: &[u8; NONCE_LEN],
+    ciphertext: &[u8],
+    tag: &[u8; TAG_LEN],
+    plaintext: &mut [u8],
+) -> Result<(), SecretboxError> {
+    if plaintext.len() != ciphertext.len() {
+        return Err(SecretboxError::InvalidLength);
+    }
+
+    let mut otk = xsalsa20_prefix(key, nonce);
+
+    let mut expected_tag = [0u8; TAG_LEN];
+    auth(&mut expected_tag, &otk, ciphertext);
+
+    otk.fill(0);
+
+    let mut diff = 0u8;
+    for i in 0..TAG_LEN {
+        diff |= expected_tag[i] ^ tag[i];
+    }
+
+    if diff != 0 {
+        return Err(SecretboxError::AuthenticationFailed);
+    }
+
+    xsalsa20_xor(key, nonce, 32, ciphertext, plaintext);
+    Ok(())
+}
+
+/// Encrypts and authenticates a message into the combined format.
+///
+/// This is equivalent to libsodium's `crypto_secretbox_easy` and returns
+/// `tag || ciphertext`, i.e. `plaintext.len() + TAG_LEN` bytes.
+pub fn seal(key: &[u8; KEY_LEN], nonce: &[u8; NONCE_LEN], plaintext: &[u8]) -> Vec<u8> {
+    let mut out = vec![0u8; TAG_LEN + plaintext.len()];
+
+    let (tag, ciphertext) = out.split_at_mut(TAG_LEN);
+    seal_detached(key, nonce, plaintext, ciphertext, tag.try_into().unwrap());
+
+    out
+}
+
+/// Verifies and decrypts a message in the combined format.
+///
+/// This is equivalent to libsodium's `crypto_secretbox_open_easy`.
+///
+/// # Returns
+///
+/// - `Ok(plaintext)` if authentication succeeds
+/// - `Err(InvalidLength)` if `sealed` is shorter than a tag
+/// - `Err(AuthenticationFailed)` if tag verification fails
+pub fn open(
+    key: &[u8; KEY_LEN],
+    nonce: &[u8; NONCE_LEN],
+    sealed: &[u8],
+) -> Result<Vec<u8>, SecretboxError> {
+    if sealed.len() < TAG_LEN {
+        return Err(SecretboxError::InvalidLength);
+    }
+
+    let (tag, ciphertext) = sealed.split_at(TAG_LEN);
+    let mut plaintext = vec![0u8; ciphertext.len()];
+
+    open_detached(
+        key,
+        nonce,
+        ciphertext,
+        tag.try_into().unwrap(),
+        &mut plaintext,
+    )?;
+
+    Ok(plaintext)
+}
@@ -0,0 +1,23 @@
+//! XSalsa20-Poly1305 authenticated encryption (NaCl `secretbox`).
+//!
+//! This module provides interoperability with data protected by NaCl and
+//! libsodium's `crypto_secretbox`.
+//!
+//! The implementation is split into two layers:
+//!
+//! - `salsa20`: Salsa20 block function, HSalsa20 and XSalsa20 keystream
+//! - `core`: the `secretbox` seal / open API built on top of the existing
+//!   Poly1305 MAC
+
+/// Public `secretbox` API.
+///
+/// Exposes combined (`tag || ciphertext`) and detached seal / open
+/// functions together with the associated error type.
+pub mod core;
+
+/// Salsa20 family primitives.
+///
+/// Contains the Salsa20 block function, HSalsa20 subkey derivation and the
+/// XSalsa20 keystream. It performs no authentication and must remain
+/// internal to the crate.
+pub(crate) mod salsa20;
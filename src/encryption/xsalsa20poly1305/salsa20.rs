@@ -0,0 +1,208 @@
+//! Salsa20, HSalsa20 and XSalsa20 core implementation.
+//!
+//! This module provides a low-level, dependency-free implementation of the
+//! Salsa20 family as used by NaCl / libsodium:
+//!
+//! - the Salsa20/20 block function (64-bit nonce, 64-bit block counter)
+//! - HSalsa20, the keyed hash used to derive XSalsa20 subkeys
+//! - XSalsa20, the extended-nonce (192-bit) stream cipher
+//!
+//! Like the ChaCha20 module, it:
+//! - avoids heap allocations
+//! - runs in constant time
+//! - exposes only minimal, crate-internal APIs
+//!
+//! This module **does not** implement authenticated encryption by itself.
+
+/// Salsa20 constant words.
+///
+/// These values correspond to the ASCII string `"expand 32-byte k"`
+/// encoded as little-endian `u32` words. Unlike ChaCha20, Salsa20 places
+/// them on the diagonal of the state (words 0, 5, 10 and 15).
+const SIGMA: [u32; 4] = [
+    0x6170_7865, // "expa"
+    0x3320_646e, // "nd 3"
+    0x7962_2d32, // "2-by"
+    0x6b20_6574, // "te k"
+];
+
+/// Performs one Salsa20 quarter round.
+///
+/// The quarter round updates `b`, `c`, `d` and `a` in turn, each from an
+/// addition of two other words followed by a fixed rotation and an XOR.
+///
+/// The function is branchless and runs in constant time.
+#[inline(always)]
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[b] ^= state[a].wrapping_add(state[d]).rotate_left(7);
+    state[c] ^= state[b].wrapping_add(state[a]).rotate_left(9);
+    state[d] ^= state[c].wrapping_add(state[b]).rotate_left(13);
+    state[a] ^= state[d].wrapping_add(state[c]).rotate_left(18);
+}
+
+/// Applies the full Salsa20 permutation (20 rounds).
+///
+/// The permutation consists of 10 double rounds, each performing:
+/// - 4 column quarter rounds
+/// - 4 row quarter rounds
+fn rounds(state: &mut [u32; 16]) {
+    for _ in 0..10 {
+        // Column rounds
+        quarter_round(state, 0, 4, 8, 12);
+        quarter_round(state, 5, 9, 13, 1);
+        quarter_round(state, 10, 14, 2, 6);
+        quarter_round(state, 15, 3, 7, 11);
+
+        // Row rounds
+        quarter_round(state, 0, 1, 2, 3);
+        quarter_round(state, 5, 6, 7, 4);
+        quarter_round(state, 10, 11, 8, 9);
+        quarter_round(state, 15, 12, 13, 14);
+    }
+}
+
+/// Builds the initial Salsa20 state from a key and a 128-bit input.
+///
+/// The input occupies words 6..10 of the state: for Salsa20 it is the
+/// 64-bit nonce followed by the 64-bit block counter, for HSalsa20 it is
+/// the first 16 bytes of the extended nonce.
+fn init_state(key: &[u8; 32], input: &[u8; 16]) -> [u32; 16] {
+    let word = |b: &[u8]| u32::from_le_bytes(b.try_into().unwrap());
+
+    let mut state = [0u32; 16];
+
+    state[0] = SIGMA[0];
+    state[5] = SIGMA[1];
+    state[10] = SIGMA[2];
+    state[15] = SIGMA[3];
+
+    for i in 0..4 {
+        state[1 + i] = word(&key[4 * i..4 * i + 4]);
+        state[11 + i] = word(&key[16 + 4 * i..16 + 4 * i + 4]);
+        state[6 + i] = word(&input[4 * i..4 * i + 4]);
+    }
+
+    state
+}
+
+/// Generates a single 64-byte Salsa20 keystream block.
+///
+/// # Parameters
+/// - `key`: 256-bit secret key (32 bytes)
+/// - `nonce`: 64-bit nonce
+/// - `counter`: 64-bit block counter
+///
+/// # Returns
+/// A 64-byte keystream block that can be XORed with plaintext or ciphertext.
+///
+/// # Security Notes
+/// - Reusing the same `(key, nonce, counter)` tuple is catastrophic for
+///   security and must be prevented by higher-level protocols.
+pub(crate) fn block(key: &[u8; 32], nonce: &[u8; 8], counter: u64) -> [u8; 64] {
+    let mut input = [0u8; 16];
+    input[..8].copy_from_slice(nonce);
+    input[8..].copy_from_slice(&counter.to_le_bytes());
+
+    let original = init_state(key, &input);
+    let mut state = original;
+
+    rounds(&mut state);
+
+    // Add original state (feed-forward)
+    state.iter_mut().zip(&original).for_each(|(s, o)| {
+        *s = s.wrapping_add(*o);
+    });
+
+    let mut out = [0u8; 64];
+    out.chunks_exact_mut(4)
+        .zip(&state)
+        .for_each(|(chunk, word)| {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        });
+
+    out
+}
+
+/// Derives a 256-bit subkey using the HSalsa20 function.
+///
+/// HSalsa20 runs the Salsa20 permutation without the final feed-forward and
+/// returns the diagonal words (0, 5, 10, 15) followed by words 6..10.
+///
+/// # Parameters
+/// - `key`: 256-bit secret key (32 bytes)
+/// - `input`: 128-bit input (first 16 bytes of an XSalsa20 nonce)
+///
+/// # Returns
+/// A 32-byte subkey suitable for use as a Salsa20 key.
+pub(crate) fn hsalsa20(key: &[u8; 32], input: &[u8; 16]) -> [u8; 32] {
+    let mut state = init_state(key, input);
+
+    rounds(&mut state);
+
+    let mut out = [0u8; 32];
+    for (chunk, &index) in out.chunks_exact_mut(4).zip(&[0, 5, 10, 15, 6, 7, 8, 9]) {
+        chunk.copy_from_slice(&state[index].to_le_bytes());
+    }
+
+    out
+}
+
+/// XORs input data with the XSalsa20 keystream.
+///
+/// The keystream is produced by Salsa20 keyed with
+/// `HSalsa20(key, nonce[0..16])`, using `nonce[16..24]` as the Salsa20
+/// nonce. The first `offset` bytes of the keystream are skipped, which
+/// allows callers to reserve the beginning of block 0 (e.g. for a
+/// Poly1305 one-time key).
+///
+/// # Parameters
+/// - `key`: 256-bit secret key (32 bytes)
+/// - `nonce`: 192-bit extended nonce
+/// - `offset`: Number of keystream bytes to skip
+/// - `input`: Plaintext or ciphertext input
+/// - `output`: Output buffer (must be the same length as `input`)
+///
+/// # Notes
+/// - Encryption and decryption are identical operations.
+/// - This function performs no authentication.
+pub(crate) fn xsalsa20_xor(
+    key: &[u8; 32],
+    nonce: &[u8; 24],
+    offset: usize,
+    input: &[u8],
+    output: &mut [u8],
+) {
+    assert_eq!(input.len(), output.len());
+
+    let mut subkey = hsalsa20(key, nonce[..16].try_into().unwrap());
+    let inner: &[u8; 8] = nonce[16..].try_into().unwrap();
+
+    let mut position = offset;
+    let mut done = 0usize;
+
+    while done < input.len() {
+        let keystream = block(&subkey, inner, (position / 64) as u64);
+        let start = position % 64;
+        let take = (64 - start).min(input.len() - done);
+
+        for i in 0..take {
+            output[done + i] = input[done + i] ^ keystream[start + i];
+        }
+
+        done += take;
+        position += take;
+    }
+
+    subkey.fill(0);
+}
+
+/// Returns the first 32 bytes of the XSalsa20 keystream.
+///
+/// In the NaCl `secretbox` construction these bytes form the Poly1305
+/// one-time key, and the message keystream starts right after them.
+pub(crate) fn xsalsa20_prefix(key: &[u8; 32], nonce: &[u8; 24]) -> [u8; 32] {
+    let mut prefix = [0u8; 32];
+    xsalsa20_xor(key, nonce, 0, &[0u8; 32], &mut prefix);
+
+    prefix
+}
@@ -9,6 +9,12 @@ pub(crate) const BLAKE2B_BLOCK_BYTES: usize = 128;
 /// A 64-byte output corresponds to the full 512-bit hash.
 pub(crate) const BLAKE2B_OUT_MAX: usize = 64;
 
+/// Maximum key size of Blake2b, in bytes.
+///
+/// Keys are padded to a single message block, but RFC 7693 limits them
+/// to 64 bytes.
+pub(crate) const BLAKE2B_KEY_MAX: usize = 64;
+
 /// Number of compression rounds performed by Blake2b.
 ///
 /// Blake2b uses a fixed 12-round compression function as defined in RFC 7693.
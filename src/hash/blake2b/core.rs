@@ -1,4 +1,4 @@
-use super::consts::{BLAKE2B_BLOCK_BYTES, BLAKE2B_OUT_MAX, IV, ROUNDS, SIGMA};
+use super::consts::{BLAKE2B_BLOCK_BYTES, BLAKE2B_KEY_MAX, BLAKE2B_OUT_MAX, IV, ROUNDS, SIGMA};
 use super::utils::{g, load_u64_le, store_u64_le};
 
 /// Blake2b hashing state.
@@ -32,12 +32,14 @@ impl Blake2b {
     ///
     /// `out_len` specifies the desired output length in bytes (up to
     /// `BLAKE2B_OUT_MAX`).
-    /// `key` optionally enables keyed hashing (MAC mode).
+    /// `key` optionally enables keyed hashing (MAC mode) and must not be
+    /// longer than `BLAKE2B_KEY_MAX` bytes.
     ///
     /// When a key is provided, it is processed as the first full block
     /// according to the Blake2b specification.
     pub(crate) fn new(out_len: usize, key: &[u8]) -> Self {
         assert!(out_len <= BLAKE2B_OUT_MAX);
+        assert!(key.len() <= BLAKE2B_KEY_MAX);
 
         let mut h = IV;
         let param = 0x0101_0000u64 ^ ((key.len() as u64) << 8) ^ (out_len as u64);
@@ -51,11 +53,10 @@ impl Blake2b {
         };
 
         if !key.is_empty() {
+            // The key block is only compressed once more input arrives, so
+            // that a keyed hash of an empty message finalizes the key block.
             st.buf[..key.len()].copy_from_slice(key);
             st.buflen = BLAKE2B_BLOCK_BYTES;
-            st.compress(false);
-            st.buf = [0u8; BLAKE2B_BLOCK_BYTES];
-            st.buflen = 0;
         }
 
         st
@@ -64,37 +65,19 @@ impl Blake2b {
     /// Updates the hash state with additional input data.
     ///
     /// This method may be called multiple times with arbitrary input sizes.
-    /// Full blocks are compressed immediately, while partial blocks are
-    /// buffered until enough data is available.
+    /// A full buffered block is only compressed once more input is
+    /// available, since the last block of the message must be compressed
+    /// with the finalization flag set.
     pub(crate) fn update(&mut self, mut input: &[u8]) {
-        if input.is_empty() {
-            return;
-        }
-
-        if self.buflen > 0 {
-            let want = BLAKE2B_BLOCK_BYTES - self.buflen;
-            let take = want.min(input.len());
-            self.buf[self.buflen..self.buflen + take].copy_from_slice(&input[..take]);
-            self.buflen += take;
-            input = &input[take..];
-
+        while !input.is_empty() {
             if self.buflen == BLAKE2B_BLOCK_BYTES {
                 self.compress(false);
-                self.buflen = 0;
             }
-        }
-
-        while input.len() >= BLAKE2B_BLOCK_BYTES {
-            self.buf.copy_from_slice(&input[..BLAKE2B_BLOCK_BYTES]);
-            self.buflen = BLAKE2B_BLOCK_BYTES;
-            self.compress(false);
-            self.buflen = 0;
-            input = &input[BLAKE2B_BLOCK_BYTES..];
-        }
 
-        if !input.is_empty() {
-            self.buf[..input.len()].copy_from_slice(input);
-            self.buflen = input.len();
+            let take = (BLAKE2B_BLOCK_BYTES - self.buflen).min(input.len());
+            self.buf[self.buflen..self.buflen + take].copy_from_slice(&input[..take]);
+            self.buflen += take;
+            input = &input[take..];
         }
     }
 
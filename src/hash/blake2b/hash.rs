@@ -20,6 +20,25 @@ pub fn blake2b(out_len: usize, input: &[u8]) -> [u8; BLAKE2B_OUT_MAX] {
     h.finalize()
 }
 
+/// Computes a keyed Blake2b hash (MAC mode) of the given input.
+///
+/// `out_len` specifies the desired output length in bytes (up to
+/// `BLAKE2B_OUT_MAX`) and `key` must be between 1 and `BLAKE2B_KEY_MAX` bytes
+/// long.
+///
+/// As with [`blake2b`], the full Blake2b output is returned; callers may
+/// truncate the result to `out_len` bytes. The output length and the key
+/// length are both bound into the parameter block, so truncating a longer
+/// output does not yield the same value as requesting a shorter one.
+pub fn blake2b_keyed(out_len: usize, key: &[u8], input: &[u8]) -> [u8; BLAKE2B_OUT_MAX] {
+    assert!(!key.is_empty());
+
+    let mut h = Blake2b::new(out_len, key);
+
+    h.update(input);
+    h.finalize()
+}
+
 /// Computes a Blake2b-based extendable-output hash (XOF).
 ///
 /// This function expands the given input into an output of arbitrary length
@@ -10,14 +10,15 @@
 //! - suitable for identifiers, integrity checks, and higher-level constructions
 //!
 //! This module intentionally exposes **hashing primitives only**.
-//! It does not provide streaming, incremental, or stateful APIs.
+//! Apart from the incremental `Sha512` state, needed to hash large inputs
+//! without buffering them, it does not provide stateful APIs.
 //! More complex constructions (KDFs, MACs, password hashing, etc.) are
 //! expected to be built on top of these functions.
 //!
 //! Currently supported primitives:
 //! - SHA-256
 //! - SHA-512
-//! - Blake2b (fixed-length and extendable-output variants)
+//! - Blake2b (fixed-length, keyed and extendable-output variants)
 //!
 //! Blake2b is provided both as a standard cryptographic hash function
 //! (up to 512-bit output) and as an extendable-output function (XOF),
@@ -27,8 +28,8 @@
 //! The module is structured to allow additional hash functions to be added
 //! in the future without breaking existing users.
 
-mod blake2b;
-mod sha256;
+pub(crate) mod blake2b;
+pub(crate) mod sha256;
 mod sha512;
 
 /// Computes the SHA-256 hash of the given input.
@@ -42,11 +43,23 @@ pub use sha256::core::sha256;
 /// output or higher collision resistance.
 pub use sha512::core::sha512;
 
+/// Incremental SHA-512 hashing state.
+///
+/// Produces the same digest as [`sha512`] for input supplied in several
+/// pieces, e.g. while streaming a file or prehashing for Ed25519ph.
+pub use sha512::core::Sha512;
+
 /// Computes a Blake2b hash with a configurable output length (up to 64 bytes).
 ///
 /// This is the standard Blake2b hash function as defined in RFC 7693.
 pub use blake2b::hash::blake2b;
 
+/// Computes a keyed Blake2b hash with a configurable output length.
+///
+/// This is the keyed (MAC) mode of Blake2b defined in RFC 7693, used as a
+/// building block for authenticated constructions.
+pub use blake2b::hash::blake2b_keyed;
+
 /// Computes a Blake2b-based extendable-output hash (XOF).
 ///
 /// This function expands an input into an arbitrary-length output using
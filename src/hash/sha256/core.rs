@@ -5,6 +5,7 @@
 //!
 //! It provides:
 //! - the compression function operating on 512-bit blocks
+//! - an incremental hashing state for multi-part input
 //! - a complete SHA-256 hashing function for arbitrary-length input
 //!
 //! The implementation is designed to be minimal, explicit, and suitable
@@ -39,6 +40,90 @@ pub fn compress(block: &[u8; 64], state: &mut [u32; 8]) {
     all_rounds(state, w);
 }
 
+/// Incremental SHA-256 hashing state.
+///
+/// This structure allows a message to be hashed in several pieces, which
+/// is required by constructions such as HMAC that hash a prefix followed
+/// by caller-provided data without concatenating them.
+///
+/// Feeding the same bytes through any sequence of `update` calls yields the
+/// same digest as a single call to [`sha256`].
+#[derive(Clone)]
+pub(crate) struct Sha256 {
+    /// Current chaining value.
+    state: [u32; 8],
+
+    /// Buffer for a partial 512-bit block.
+    block: [u8; 64],
+
+    /// Number of bytes currently stored in `block`.
+    buflen: usize,
+
+    /// Total message length in bytes.
+    len: u64,
+}
+
+impl Sha256 {
+    /// Creates a new hashing state initialized with the SHA-256 IV.
+    pub(crate) fn new() -> Self {
+        Self {
+            state: H256_INIT,
+            block: [0u8; 64],
+            buflen: 0,
+            len: 0,
+        }
+    }
+
+    /// Absorbs additional input into the hashing state.
+    pub(crate) fn update(&mut self, mut input: &[u8]) {
+        self.len = self.len.wrapping_add(input.len() as u64);
+
+        while !input.is_empty() {
+            let take = (64 - self.buflen).min(input.len());
+            self.block[self.buflen..self.buflen + take].copy_from_slice(&input[..take]);
+            self.buflen += take;
+            input = &input[take..];
+
+            if self.buflen == 64 {
+                compress(&self.block, &mut self.state);
+                self.buflen = 0;
+            }
+        }
+    }
+
+    /// Applies the final padding and returns the 32-byte digest.
+    pub(crate) fn finalize(mut self) -> [u8; 32] {
+        let rem = self.buflen;
+
+        // Append the padding bit (0x80) and clear the rest of the block
+        self.block[rem] = 0x80;
+        self.block[rem + 1..].fill(0);
+
+        // If there is not enough space for the length field, process this
+        // block and use an additional zeroed block.
+        if rem > 55 {
+            compress(&self.block, &mut self.state);
+            self.block = [0; 64];
+        }
+
+        // Append the message length in bits as a 64-bit big-endian integer
+        let bit_len = self.len << 3;
+        self.block[56..64].copy_from_slice(&bit_len.to_be_bytes());
+
+        // Final compression
+        compress(&self.block, &mut self.state);
+
+        let mut out = [0u8; 32];
+        for (chunk, word) in out.chunks_exact_mut(4).zip(self.state) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+
+        self.block.fill(0);
+
+        out
+    }
+}
+
 /// Computes the SHA-256 hash of the given input.
 ///
 /// This function processes the input message in 512-bit blocks, applies
@@ -55,42 +140,8 @@ pub fn compress(block: &[u8; 64], state: &mut [u32; 8]) {
 /// - Message length is encoded as a 64-bit big-endian integer (in bits).
 /// - No heap allocations are performed.
 pub fn sha256(input: &[u8]) -> U256 {
-    // Initialize hash state
-    let mut state = H256_INIT;
-
-    let mut i = 0;
-    let len = input.len();
-
-    // Process full 512-bit blocks
-    while i + 64 <= len {
-        let block: &[u8; 64] = input[i..i + 64].try_into().unwrap();
-        compress(block, &mut state);
-        i += 64;
-    }
-
-    // Prepare final padded block(s)
-    let mut block = [0u8; 64];
-    let rem = len - i;
-
-    // Copy remaining bytes and append the padding bit (0x80)
-    block[..rem].copy_from_slice(&input[i..]);
-    block[rem] = 0x80;
-
-    // If there is not enough space for the length field, process this block
-    // and use an additional zeroed block.
-    if rem > 55 {
-        compress(&block, &mut state);
-        block = [0; 64];
-    }
-
-    // Append the message length in bits as a 64-bit big-endian integer
-    let bit_len = (len as u64) << 3;
-    let len_bytes = bit_len.to_be_bytes();
-    block[56..64].copy_from_slice(&len_bytes);
-
-    // Final compression
-    compress(&block, &mut state);
+    let mut hasher = Sha256::new();
+    hasher.update(input);
 
-    // Convert final state into a 256-bit value
-    U256::from(state)
+    U256::from(hasher.finalize())
 }
@@ -5,6 +5,7 @@
 //!
 //! It provides:
 //! - the compression function operating on 1024-bit blocks
+//! - an incremental hashing state for streamed input
 //! - a complete SHA-512 hashing function for arbitrary-length input
 //!
 //! The implementation is intentionally minimal, explicit, and designed
@@ -38,6 +39,96 @@ pub fn compress(block: &[u8; 128], state: &mut [u64; 8]) {
     all_rounds(state, w);
 }
 
+/// Incremental SHA-512 hashing state.
+///
+/// This structure allows a message to be hashed as it is read, e.g. to
+/// hash a large file chunk by chunk without buffering it, or to prehash a
+/// message for Ed25519ph.
+///
+/// Feeding the same bytes through any sequence of `update` calls yields the
+/// same digest as a single call to [`sha512`].
+#[derive(Clone)]
+pub struct Sha512 {
+    /// Current chaining value.
+    state: [u64; 8],
+
+    /// Buffer for a partial 1024-bit block.
+    block: [u8; 128],
+
+    /// Number of bytes currently stored in `block`.
+    buflen: usize,
+
+    /// Total message length in bytes.
+    len: u128,
+}
+
+impl Sha512 {
+    /// Creates a new hashing state initialized with the SHA-512 IV.
+    pub fn new() -> Self {
+        Self {
+            state: H512_INIT,
+            block: [0u8; 128],
+            buflen: 0,
+            len: 0,
+        }
+    }
+
+    /// Absorbs additional input into the hashing state.
+    pub fn update(&mut self, mut input: &[u8]) {
+        self.len = self.len.wrapping_add(input.len() as u128);
+
+        while !input.is_empty() {
+            let take = (128 - self.buflen).min(input.len());
+            self.block[self.buflen..self.buflen + take].copy_from_slice(&input[..take]);
+            self.buflen += take;
+            input = &input[take..];
+
+            if self.buflen == 128 {
+                compress(&self.block, &mut self.state);
+                self.buflen = 0;
+            }
+        }
+    }
+
+    /// Applies the final padding and returns the 64-byte digest.
+    pub fn finalize(mut self) -> [u8; 64] {
+        let rem = self.buflen;
+
+        // Append the padding bit (0x80) and clear the rest of the block
+        self.block[rem] = 0x80;
+        self.block[rem + 1..].fill(0);
+
+        // If there is not enough space for the length field, process this
+        // block and use an additional zeroed block.
+        if rem > 111 {
+            compress(&self.block, &mut self.state);
+            self.block = [0; 128];
+        }
+
+        // Append the message length in bits as a 128-bit big-endian integer
+        let bit_len = self.len << 3;
+        self.block[112..128].copy_from_slice(&bit_len.to_be_bytes());
+
+        // Final compression
+        compress(&self.block, &mut self.state);
+
+        let mut out = [0u8; 64];
+        for (chunk, word) in out.chunks_exact_mut(8).zip(self.state) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+
+        self.block.fill(0);
+
+        out
+    }
+}
+
+impl Default for Sha512 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Computes the SHA-512 hash of the given input.
 ///
 /// This function processes the input message in 1024-bit blocks, applies
@@ -55,46 +146,8 @@ pub fn compress(block: &[u8; 128], state: &mut [u64; 8]) {
 /// - The internal state uses 8 × 64-bit words and is serialized in big-endian.
 /// - No heap allocations are performed.
 pub fn sha512(input: &[u8]) -> [u8; 64] {
-    // Initialize hash state
-    let mut state = H512_INIT;
-
-    let mut i = 0;
-    let len = input.len();
-
-    // Process full 1024-bit blocks
-    while i + 128 <= len {
-        let block: &[u8; 128] = input[i..i + 128].try_into().unwrap();
-        compress(block, &mut state);
-        i += 128;
-    }
-
-    // Prepare final padded block(s)
-    let mut block = [0u8; 128];
-    let rem = len - i;
-
-    // Copy remaining bytes and append the padding bit (0x80)
-    block[..rem].copy_from_slice(&input[i..]);
-    block[rem] = 0x80;
-
-    // If there is not enough space for the 128-bit length field,
-    // process this block and use an additional zeroed block.
-    if rem > 111 {
-        compress(&block, &mut state);
-        block = [0; 128];
-    }
-
-    // Append the message length in bits as a 128-bit big-endian integer
-    let bit_len = (len as u128) << 3;
-    block[112..128].copy_from_slice(&bit_len.to_be_bytes());
-
-    // Final compression
-    compress(&block, &mut state);
-
-    // Serialize final state into big-endian bytes
-    let mut out = [0u8; 64];
-    for (i, word) in state.iter().enumerate() {
-        out[i * 8..(i + 1) * 8].copy_from_slice(&word.to_be_bytes());
-    }
+    let mut hasher = Sha512::new();
+    hasher.update(input);
 
-    out
+    hasher.finalize()
 }
@@ -10,12 +10,105 @@
 //! auditability, and predictable behavior.
 
 use super::ct::ConstantTimeEq;
+use super::field::FieldElement;
 use super::group::{GeCached, GeP1, GeP3};
+use super::msm::multiscalar_mul;
 pub use super::scalar::Scalar;
-use crate::hash::sha512;
+use crate::hash::{Sha512, sha512};
 use crate::keys::x25519;
 use crate::rng::Csprng;
 
+/// Errors that can occur while decoding Ed25519 keys and signatures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ed25519Error {
+    /// The input does not have the expected length.
+    InvalidLength,
+    /// The encoding is not a valid curve point.
+    InvalidPublicKey,
+    /// The secret scalar is neither clamped nor reduced modulo `ℓ`.
+    InvalidPrivateKey,
+    /// The context string is longer than `MAX_CONTEXT_LEN` bytes.
+    InvalidContext,
+}
+
+/// Maximum length of an Ed25519ctx / Ed25519ph context string.
+pub const MAX_CONTEXT_LEN: usize = 255;
+
+/// Prefix of the RFC 8032 `dom2` domain separator.
+const DOM2_PREFIX: &[u8] = b"SigEd25519 no Ed25519 collisions";
+
+/// Acceptance rules applied when verifying a signature.
+///
+/// Implementations agree on honestly generated signatures but differ on
+/// crafted edge cases (non-canonical encodings, small-order components).
+/// Systems where several nodes must reach the same verdict, such as
+/// consensus code, should pick one policy explicitly.
+///
+/// All policies reject `S ≥ ℓ` and encodings that do not decode to a point.
+///
+/// | Check                          | `Strict` | `Cofactorless` | `Zip215` |
+/// |--------------------------------|----------|----------------|----------|
+/// | Non-canonical `A` encoding     | reject   | accept         | accept   |
+/// | Non-canonical `R` encoding     | reject   | reject         | accept   |
+/// | Small-order `A` or `R`         | reject   | accept         | accept   |
+/// | Equation                       | `S·B = R + k·A` | `S·B = R + k·A` | `8·S·B = 8·R + 8·k·A` |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerificationPolicy {
+    /// RFC 8032 cofactorless equation, as checked by [`verify`].
+    #[default]
+    Cofactorless,
+    /// Cofactorless equation with canonical, non-small-order `A` and `R`
+    /// (the libsodium and `verify_strict` rules). Prevents a signature
+    /// from being valid for several messages or keys.
+    Strict,
+    /// ZIP-215 rules: cofactored equation, any decodable encoding accepted.
+    /// Agrees with batch verification, see [`verify_batch`].
+    Zip215,
+}
+
+/// The RFC 8032 signature variant, which determines the `dom2` prefix
+/// hashed before the nonce and challenge inputs.
+#[derive(Clone, Copy)]
+pub(crate) enum Variant<'a> {
+    /// PureEdDSA (Ed25519): no prefix.
+    Pure,
+    /// Ed25519ctx: `dom2(0, context)`.
+    Context(&'a [u8]),
+    /// Ed25519ph: `dom2(1, context)`.
+    Prehash(&'a [u8]),
+}
+
+impl Variant<'_> {
+    /// Returns `false` if the context string is too long.
+    fn is_valid(self) -> bool {
+        match self {
+            Variant::Pure => true,
+            Variant::Context(c) | Variant::Prehash(c) => c.len() <= MAX_CONTEXT_LEN,
+        }
+    }
+
+    /// Returns the length of `dom2(phflag, context)`.
+    fn dom_len(self) -> usize {
+        match self {
+            Variant::Pure => 0,
+            Variant::Context(c) | Variant::Prehash(c) => DOM2_PREFIX.len() + 2 + c.len(),
+        }
+    }
+
+    /// Absorbs `dom2(phflag, context)` into `hasher`.
+    fn absorb(self, hasher: &mut Sha512) {
+        let (flag, context) = match self {
+            Variant::Pure => return,
+            Variant::Context(c) => (0u8, c),
+            Variant::Prehash(c) => (1u8, c),
+        };
+
+        hasher.update(DOM2_PREFIX);
+        hasher.update(&[flag, context.len() as u8]);
+        hasher.update(context);
+    }
+}
+
 /// An Ed25519 public key.
 ///
 /// This type wraps the canonical 32-byte compressed encoding of a curve
@@ -23,7 +116,7 @@ use crate::rng::Csprng;
 /// y-coordinate together with a sign bit for x, as defined in RFC 8032.
 ///
 /// A `PublicKey` is immutable and copyable.
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PublicKey([u8; 32]);
 
 impl PublicKey {
@@ -35,6 +128,113 @@ impl PublicKey {
     pub fn to_bytes(&self) -> [u8; 32] {
         self.0
     }
+
+    /// Decodes a public key from its 32-byte compressed encoding.
+    ///
+    /// The encoding is decompressed to check that it denotes a point on
+    /// the curve.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(public)` if the encoding is a valid curve point
+    /// - `Err(InvalidPublicKey)` otherwise
+    pub fn from_bytes(bytes: [u8; 32]) -> Result<Self, Ed25519Error> {
+        let (_, status) = GeP3::decompress(&bytes);
+        if status != 0 {
+            return Err(Ed25519Error::InvalidPublicKey);
+        }
+
+        Ok(Self(bytes))
+    }
+
+    /// Converts the key to the X25519 public key of the same secret.
+    ///
+    /// The Edwards `y` coordinate is mapped to the Montgomery `u`
+    /// coordinate with the birational map `u = (1 + y) / (1 − y)`, as done
+    /// by libsodium's `crypto_sign_ed25519_pk_to_curve25519`.
+    ///
+    /// ## Notes
+    ///
+    /// - The result equals `x25519::public_key` applied to
+    ///   [`PrivateKey::to_montgomery`].
+    /// - A small-order key maps to a small-order `u` (the identity to
+    ///   `u = 0`), for which X25519 yields an all-zero shared secret.
+    pub fn to_montgomery(self) -> [u8; 32] {
+        let y = FieldElement::from_bytes(&self.0);
+        let one = FieldElement::ONE;
+
+        ((one + y) * (one - y).invert()).to_bytes()
+    }
+}
+
+/// An Ed25519 public key prepared for repeated verification.
+///
+/// [`verify`] decompresses the public key and builds a table of its
+/// multiples on every call. A `VerifyingKey` does this once, so checking
+/// many signatures from the same signer skips that work (roughly 5–10% of
+/// a verification).
+///
+/// ```text
+/// let key = VerifyingKey::new(public);
+/// for (signature, message) in items {
+///     key.verify(signature, message);
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct VerifyingKey {
+    /// The encoded key, hashed into every challenge.
+    public: PublicKey,
+
+    /// Odd multiples `−A, −3A, …, −15A`.
+    table: [GeCached; 8],
+
+    /// Whether the strict policy rejects the key (non-canonical encoding
+    /// or small order).
+    weak: bool,
+}
+
+impl VerifyingKey {
+    /// Decompresses `public` and precomputes its multiples.
+    ///
+    /// Every [`PublicKey`] is a valid point, checked by
+    /// [`PublicKey::from_bytes`], so this cannot fail.
+    pub fn new(public: PublicKey) -> Self {
+        let (minus_a, status) = GeP3::decompress(&public.0);
+        debug_assert_eq!(status, 0);
+
+        Self {
+            public,
+            table: minus_a.odd_multiples(),
+            weak: !is_canonical_encoding(&public.0) || has_small_order(&minus_a),
+        }
+    }
+
+    /// Returns the public key this key was built from.
+    pub fn public_key(&self) -> PublicKey {
+        self.public
+    }
+
+    /// Verifies a signature, with the same rules as [`verify`].
+    pub fn verify(&self, signature: Signature, message: &[u8]) -> bool {
+        self.verify_with_policy(signature, message, VerificationPolicy::Cofactorless)
+    }
+
+    /// Verifies a signature under an explicit policy, with the same rules
+    /// as [`verify_with_policy`].
+    pub fn verify_with_policy(
+        &self,
+        signature: Signature,
+        message: &[u8],
+        policy: VerificationPolicy,
+    ) -> bool {
+        verify_variant(Variant::Pure, policy, signature, message, self)
+    }
+}
+
+impl From<PublicKey> for VerifyingKey {
+    fn from(public: PublicKey) -> Self {
+        Self::new(public)
+    }
 }
 
 /// An Ed25519 private key.
@@ -45,14 +245,65 @@ impl PublicKey {
 ///   generate deterministic nonces during signing.
 ///
 /// This structure corresponds to the expanded private key described
-/// in RFC 8032 rather than the raw 32-byte seed.
+/// in RFC 8032 rather than the raw 32-byte seed. A key expanded with
+/// [`PrivateKey::from_seed`] also keeps its seed, which key file formats
+/// (PKCS#8, OpenSSH) store instead of the expanded form.
 #[derive(Clone, Copy)]
 pub struct PrivateKey {
     scalar: Scalar,
     prefix: [u8; 32],
+    seed: Option<[u8; 32]>,
 }
 
 impl PrivateKey {
+    /// Expands a 32-byte seed into a private key, as specified by RFC 8032.
+    ///
+    /// The seed is hashed with SHA-512; the first half is clamped into the
+    /// secret scalar and the second half becomes the nonce prefix.
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        let digest = sha512(&seed);
+
+        let mut a_bytes: [u8; 32] = digest[..32].try_into().unwrap();
+        a_bytes[0] &= 248;
+        a_bytes[31] &= 63;
+        a_bytes[31] |= 64;
+
+        Self {
+            scalar: Scalar::from_bytes(&a_bytes),
+            prefix: digest[32..].try_into().unwrap(),
+            seed: Some(seed),
+        }
+    }
+
+    /// Decodes an expanded private key produced by [`PrivateKey::to_bytes`].
+    ///
+    /// The scalar must either be clamped (as produced by seed expansion)
+    /// or reduced modulo `ℓ` (as produced by [`add_scalar`]).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(private)` if the scalar has one of the accepted forms
+    /// - `Err(InvalidPrivateKey)` otherwise
+    pub fn from_bytes(bytes: [u8; 64]) -> Result<Self, Ed25519Error> {
+        let scalar = Scalar::from_bytes(&bytes[..32]);
+
+        let clamped = (bytes[0] & 7) == 0 && (bytes[31] & 0xc0) == 0x40;
+        if !clamped && !scalar.is_canonical() {
+            return Err(Ed25519Error::InvalidPrivateKey);
+        }
+
+        Ok(Self {
+            scalar,
+            prefix: bytes[32..].try_into().unwrap(),
+            seed: None,
+        })
+    }
+
+    /// Returns the public key matching this private key.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey(GeP3::from_scalar_mul(self.scalar).to_bytes())
+    }
+
     /// Returns the secret scalar component of the private key.
     ///
     /// This value is used internally for scalar multiplication on the curve.
@@ -62,6 +313,40 @@ impl PrivateKey {
         self.scalar
     }
 
+    /// Converts the key to the X25519 secret of the same scalar.
+    ///
+    /// For a key expanded from a seed, this is the clamped scalar, as
+    /// returned by libsodium's `crypto_sign_ed25519_sk_to_curve25519`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(secret)` if the scalar is clamped
+    /// - `Err(InvalidPrivateKey)` if it was reduced modulo `ℓ` (see
+    ///   [`add_scalar`]): X25519 clamping would change it. [`exchange`]
+    ///   supports such keys.
+    pub fn to_montgomery(&self) -> Result<[u8; 32], Ed25519Error> {
+        let bytes = self.scalar.to_bytes();
+
+        if (bytes[0] & 7) != 0 || (bytes[31] & 0xc0) != 0x40 {
+            return Err(Ed25519Error::InvalidPrivateKey);
+        }
+
+        Ok(bytes)
+    }
+
+    /// Returns the RFC 8032 seed this key was expanded from.
+    ///
+    /// Returns `None` for keys decoded from their expanded form with
+    /// [`PrivateKey::from_bytes`] or modified by [`add_scalar`], which no
+    /// longer derive from a seed.
+    ///
+    /// Key formats that store the seed rather than the expanded key (PKCS#8,
+    /// OpenSSH, minisign, signify, JWK) cannot encode such keys and report
+    /// a `MissingSeed` error.
+    pub fn seed(&self) -> Option<[u8; 32]> {
+        self.seed
+    }
+
     /// Returns the nonce prefix associated with this private key.
     ///
     /// The prefix is used as part of the deterministic nonce derivation
@@ -96,7 +381,7 @@ impl PrivateKey {
 ///
 /// Together, these form the standard 64-byte Ed25519 signature as defined
 /// in RFC 8032.
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Signature([u8; 64]);
 
 impl Signature {
@@ -109,6 +394,17 @@ impl Signature {
         Self(bytes)
     }
 
+    /// Constructs a signature from a slice, which must be 64 bytes long.
+    ///
+    /// As with [`Signature::from_bytes`], validity is only checked during
+    /// verification.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, Ed25519Error> {
+        bytes
+            .try_into()
+            .map(Self)
+            .map_err(|_| Ed25519Error::InvalidLength)
+    }
+
     /// Returns the raw byte encoding of this signature.
     ///
     /// The output is compatible with standard Ed25519 implementations
@@ -145,20 +441,173 @@ pub fn generate_keypair() -> (PublicKey, PrivateKey) {
     let mut seed = [0u8; 32];
     Csprng::new().fill_bytes(&mut seed);
 
-    let digest = sha512(&seed);
+    let keypair = keypair_from_seed(seed);
+    seed.fill(0);
+
+    keypair
+}
+
+/// Derives the Ed25519 keypair of a 32-byte seed.
+///
+/// This is the deterministic part of [`generate_keypair`]: the same seed
+/// always yields the same keypair, which allows identities to be stored
+/// as a seed and reloaded, and RFC 8032 test vectors to be reproduced.
+///
+/// # Security Notes
+///
+/// - The seed is the secret key: it must be uniformly random and kept
+///   confidential.
+pub fn keypair_from_seed(seed: [u8; 32]) -> (PublicKey, PrivateKey) {
+    let private = PrivateKey::from_seed(seed);
+
+    (private.public_key(), private)
+}
+
+/// Computes the challenge `k = H(dom || R || A || M) mod ℓ`.
+fn challenge(variant: Variant, r: &[u8], public: &[u8; 32], message: &[u8]) -> Scalar {
+    let mut hasher = Sha512::new();
+    variant.absorb(&mut hasher);
+    hasher.update(r);
+    hasher.update(public);
+    hasher.update(message);
+
+    Scalar::reduce(hasher.finalize())
+}
+
+/// Signs `message` (or its prehash) under the given variant.
+///
+/// With `noise`, the nonce is hedged as in draft-irtf-cfrg-det-sigs-with-noise:
+/// `r = H(dom || Z || pad || prefix || pad' || M)`, where `pad` zero-fills
+/// the SHA-512 block holding `dom || Z` and `pad'` the one holding
+/// `prefix`.
+pub(crate) fn sign_variant(
+    variant: Variant,
+    noise: Option<&[u8; 32]>,
+    message: &[u8],
+    public: PublicKey,
+    private: PrivateKey,
+) -> Signature {
+    let a = private.scalar();
+
+    let prefix = private.prefix();
+
+    let mut hasher = Sha512::new();
+    variant.absorb(&mut hasher);
+    match noise {
+        None => hasher.update(&prefix),
+        Some(noise) => {
+            let zeros = [0u8; 128];
+            let head = variant.dom_len() + noise.len();
+
+            hasher.update(noise);
+            hasher.update(&zeros[..head.next_multiple_of(128) - head]);
+            hasher.update(&prefix);
+            hasher.update(&zeros[..128 - prefix.len()]);
+        }
+    }
+    hasher.update(message);
+
+    let r = Scalar::reduce(hasher.finalize());
+
+    let r_bytes = GeP3::from_scalar_mul(r).to_bytes();
+
+    let k = challenge(variant, &r_bytes, &public.0, message);
+
+    let s = Scalar::from_mul_sum(k, a, r).0;
+
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&r_bytes);
+    signature[32..].copy_from_slice(&s);
+
+    Signature(signature)
+}
+
+/// Verifies `message` (or its prehash) under the given variant and policy.
+fn verify_variant(
+    variant: Variant,
+    policy: VerificationPolicy,
+    signature: Signature,
+    message: &[u8],
+    key: &VerifyingKey,
+) -> bool {
+    if !variant.is_valid() {
+        return false;
+    }
+
+    let r: &[u8; 32] = signature.0[..32].try_into().unwrap();
+    let s = Scalar(signature.0[32..].try_into().unwrap());
+
+    if !s.is_canonical() {
+        return false;
+    }
+
+    if policy == VerificationPolicy::Strict && (key.weak || !is_canonical_encoding(r)) {
+        return false;
+    }
+
+    let h = challenge(variant, r, &key.public.0, message);
+
+    let cofactorless = || {
+        GeP3::double_scalar_mul(&key.table, h, s)
+            .to_bytes()
+            .ct_eq(r)
+    };
+
+    match policy {
+        VerificationPolicy::Cofactorless => cofactorless(),
+        VerificationPolicy::Strict => {
+            let (minus_r, ok) = GeP3::decompress(r);
+
+            ok == 0 && !has_small_order(&minus_r) && cofactorless()
+        }
+        VerificationPolicy::Zip215 => {
+            let (minus_r, ok) = GeP3::decompress(r);
+            if ok != 0 {
+                return false;
+            }
+
+            // S · B − h · A − R
+            let sb_minus_ha = GeP3::from_gep2(&GeP3::double_scalar_mul(&key.table, h, s));
+            let check =
+                GeP3::from_gep1(&GeP1::from_sum(&sb_minus_ha, &GeCached::from_p3(&minus_r)));
+
+            has_small_order(&check)
+        }
+    }
+}
+
+/// Returns `true` if `8 · P` is the identity.
+fn has_small_order(point: &GeP3) -> bool {
+    let mut check = GeP3::from_gep1(&point.double());
+
+    for _ in 0..2 {
+        check = GeP3::from_gep1(&check.double());
+    }
+
+    // The identity is (0 : Z : Z : 0).
+    check.x.is_non_zero() == 0 && (check.y - check.z).is_non_zero() == 0
+}
+
+/// Returns `true` if `bytes` is the canonical encoding of its point:
+/// `y < p`, and the sign bit is clear when `x = 0`.
+fn is_canonical_encoding(bytes: &[u8; 32]) -> bool {
+    let mut y = *bytes;
+    y[31] &= 0x7f;
 
-    let mut a_bytes: [u8; 32] = digest[..32].try_into().unwrap();
-    a_bytes[0] &= 248;
-    a_bytes[31] &= 63;
-    a_bytes[31] |= 64;
-    let scalar = Scalar::from_bytes(&a_bytes);
+    // The only 255-bit values not reduced modulo p are p..2^255 - 1.
+    let reduced = !(y[0] >= 0xed && y[1..31].iter().all(|&b| b == 0xff) && y[31] == 0x7f);
 
-    let prefix: [u8; 32] = digest[32..].try_into().unwrap();
+    // x = 0 exactly for y = 1 and y = -1.
+    let mut one = [0u8; 32];
+    one[0] = 1;
 
-    let public = PublicKey(GeP3::from_scalar_mul(scalar).to_bytes());
-    let private = PrivateKey { scalar, prefix };
+    let mut minus_one = [0xffu8; 32];
+    minus_one[0] = 0xec;
+    minus_one[31] = 0x7f;
 
-    (public, private)
+    let negative_zero = bytes[31] >> 7 == 1 && (y == one || y == minus_one);
+
+    reduced && !negative_zero
 }
 
 /// Computes an Ed25519 signature over a message.
@@ -184,31 +633,42 @@ pub fn generate_keypair() -> (PublicKey, PrivateKey) {
 /// This implementation mirrors the structure of the reference
 /// Ed25519 implementations and avoids side-channel leakage by
 /// relying on constant-time primitives.
+///
+/// The message is streamed twice through an incremental SHA-512 state and
+/// never copied, so memory use does not depend on its length.
 pub fn sign(message: &[u8], public: PublicKey, private: PrivateKey) -> Signature {
-    let a = private.scalar();
-
-    let mut r_input = Vec::with_capacity(32 + message.len());
-    r_input.extend_from_slice(&private.prefix());
-    r_input.extend_from_slice(message);
-
-    let r = Scalar::reduce(sha512(&r_input));
-
-    let r_bytes = GeP3::from_scalar_mul(r).to_bytes();
-
-    let mut k_input = Vec::with_capacity(64 + message.len());
-    k_input.extend_from_slice(&r_bytes);
-    k_input.extend_from_slice(&public.to_bytes());
-    k_input.extend_from_slice(message);
+    sign_variant(Variant::Pure, None, message, public, private)
+}
 
-    let k = Scalar::reduce(sha512(&k_input));
+/// Computes a hedged Ed25519 signature, mixing fresh randomness into the
+/// nonce (draft-irtf-cfrg-det-sigs-with-noise).
+///
+/// A deterministic nonce lets an attacker who can inject a fault during
+/// signing (e.g. corrupt `k` in one of two signatures of the same message)
+/// recover the secret scalar from the two results. Hedging adds 32 random
+/// bytes from the `Csprng` to the nonce input, each secret zero-padded to
+/// a full SHA-512 block:
+///
+/// ```text
+/// r = H(Z || 0^96 || prefix || 0^96 || M)
+/// ```
+///
+/// The signature has the standard format and verifies with [`verify`].
+///
+/// # Security Notes
+///
+/// - Signing the same message twice yields different signatures.
+/// - If the randomness is weak, the nonce still depends on the secret
+///   prefix and the message, so security falls back to that of [`sign`].
+pub fn sign_hedged(message: &[u8], public: PublicKey, private: PrivateKey) -> Signature {
+    let mut noise = [0u8; 32];
+    Csprng::new().fill_bytes(&mut noise);
 
-    let s = Scalar::from_mul_sum(k, a, r).0;
+    let signature = sign_variant(Variant::Pure, Some(&noise), message, public, private);
 
-    let mut signature = [0u8; 64];
-    signature[..32].copy_from_slice(&r_bytes);
-    signature[32..].copy_from_slice(&s);
+    noise.fill(0);
 
-    Signature(signature)
+    signature
 }
 
 /// Verifies an Ed25519 signature.
@@ -218,7 +678,7 @@ pub fn sign(message: &[u8], public: PublicKey, private: PrivateKey) -> Signature
 ///
 /// Verification proceeds as follows:
 ///
-/// - Ensure the scalar `S` is canonically encoded
+/// - Ensure the scalar `S` is canonically encoded (`S < ℓ`)
 /// - Decompress the public key `A`
 /// - Compute `h = H(R || A || M) mod ℓ`
 /// - Check that:
@@ -233,28 +693,250 @@ pub fn sign(message: &[u8], public: PublicKey, private: PrivateKey) -> Signature
 /// This implementation mirrors the reference Ed25519 verification logic
 /// and avoids side-channel leakage by relying exclusively on
 /// constant-time group and scalar operations.
+///
+/// The message is streamed through an incremental SHA-512 state and never
+/// copied; no heap allocation takes place.
+///
+/// See [`verify_with_policy`] for the strict and ZIP-215 rules.
 pub fn verify(signature: Signature, message: &[u8], public: PublicKey) -> bool {
-    if (signature.0[63] & 0b1110_0000) != 0 {
-        return false;
+    verify_variant(
+        Variant::Pure,
+        VerificationPolicy::Cofactorless,
+        signature,
+        message,
+        &VerifyingKey::new(public),
+    )
+}
+
+/// Verifies an Ed25519 signature under an explicit [`VerificationPolicy`].
+///
+/// [`verify`] is equivalent to `VerificationPolicy::Cofactorless`.
+///
+/// # Returns
+///
+/// - `true` if the signature is valid under `policy`
+/// - `false` otherwise
+pub fn verify_with_policy(
+    signature: Signature,
+    message: &[u8],
+    public: PublicKey,
+    policy: VerificationPolicy,
+) -> bool {
+    verify_variant(
+        Variant::Pure,
+        policy,
+        signature,
+        message,
+        &VerifyingKey::new(public),
+    )
+}
+
+/// Computes an Ed25519ctx signature (RFC 8032, section 5.1).
+///
+/// Ed25519ctx binds the signature to a context string, so that a signature
+/// produced for one protocol or purpose cannot be replayed in another. The
+/// nonce and challenge hashes are prefixed with `dom2(0, context)`.
+///
+/// # Returns
+///
+/// - `Ok(signature)` on success
+/// - `Err(InvalidContext)` if `context` exceeds `MAX_CONTEXT_LEN` bytes
+///
+/// # Notes
+///
+/// - RFC 8032 recommends a non-empty context; plain [`sign`] should be
+///   used when no context is needed.
+pub fn sign_ctx(
+    message: &[u8],
+    context: &[u8],
+    public: PublicKey,
+    private: PrivateKey,
+) -> Result<Signature, Ed25519Error> {
+    let variant = Variant::Context(context);
+    if !variant.is_valid() {
+        return Err(Ed25519Error::InvalidContext);
     }
 
-    let (a, ok) = GeP3::decompress(&public.to_bytes());
-    if ok != 0 {
-        return false;
+    Ok(sign_variant(variant, None, message, public, private))
+}
+
+/// Verifies an Ed25519ctx signature made with the same context string.
+///
+/// Returns `false` for an invalid signature or an over-long context.
+pub fn verify_ctx(signature: Signature, message: &[u8], context: &[u8], public: PublicKey) -> bool {
+    verify_variant(
+        Variant::Context(context),
+        VerificationPolicy::Cofactorless,
+        signature,
+        message,
+        &VerifyingKey::new(public),
+    )
+}
+
+/// Computes an Ed25519ph signature (RFC 8032, section 5.1).
+///
+/// Ed25519ph signs the SHA-512 digest of the message instead of the message
+/// itself, so that arbitrarily large inputs can be streamed through
+/// `prehash` without being buffered. The nonce and challenge hashes are
+/// prefixed with `dom2(1, context)`.
+///
+/// # Parameters
+///
+/// - `prehash`: a [`Sha512`] state that has absorbed the whole message
+/// - `context`: a context string of at most `MAX_CONTEXT_LEN` bytes
+///   (possibly empty)
+///
+/// # Returns
+///
+/// - `Ok(signature)` on success
+/// - `Err(InvalidContext)` if `context` is too long
+///
+/// # Security Notes
+///
+/// - Ed25519ph signatures are not interchangeable with Ed25519 ones; the
+///   verifier must use [`verify_prehashed`].
+/// - Unlike PureEdDSA, the scheme relies on the collision resistance of
+///   SHA-512.
+pub fn sign_prehashed(
+    prehash: Sha512,
+    context: &[u8],
+    public: PublicKey,
+    private: PrivateKey,
+) -> Result<Signature, Ed25519Error> {
+    let variant = Variant::Prehash(context);
+    if !variant.is_valid() {
+        return Err(Ed25519Error::InvalidContext);
     }
 
-    let mut h_input = Vec::with_capacity(64 + message.len());
-    h_input.extend_from_slice(&signature.0[..32]); // R
-    h_input.extend_from_slice(&public.to_bytes()); // A
-    h_input.extend_from_slice(message);
+    Ok(sign_variant(
+        variant,
+        None,
+        &prehash.finalize(),
+        public,
+        private,
+    ))
+}
 
-    let h = Scalar::reduce(sha512(&h_input));
+/// Verifies an Ed25519ph signature against a streamed message.
+///
+/// `prehash` must have absorbed the whole message. Returns `false` for an
+/// invalid signature or an over-long context.
+pub fn verify_prehashed(
+    signature: Signature,
+    prehash: Sha512,
+    context: &[u8],
+    public: PublicKey,
+) -> bool {
+    verify_variant(
+        Variant::Prehash(context),
+        VerificationPolicy::Cofactorless,
+        signature,
+        &prehash.finalize(),
+        &VerifyingKey::new(public),
+    )
+}
 
-    let s = Scalar(signature.0[32..].try_into().unwrap());
+/// Verifies a batch of Ed25519 signatures at once.
+///
+/// Each entry is a `(signature, message, public key)` triple. Instead of
+/// checking `S · B == R + h · A` for every entry, a single random linear
+/// combination is checked:
+///
+/// ```text
+/// 8 · ( (Σ z_i · S_i) · B − Σ z_i · R_i − Σ (z_i · h_i) · A_i ) == 0
+/// ```
+///
+/// where the `z_i` are fresh random 128-bit scalars. The sum is computed
+/// with a single multi-scalar multiplication; the gain over separate
+/// verifications grows with the batch size (about 2.5x for a thousand
+/// signatures).
+///
+/// If the combined check fails, every entry is verified individually under
+/// [`VerificationPolicy::Zip215`] to identify the invalid ones, so the
+/// result is that of ZIP-215 verification either way.
+///
+/// # Returns
+///
+/// - `Ok(())` if all signatures are valid (or the batch is empty)
+/// - `Err(indices)` with the indices of the invalid signatures
+///
+/// # Security Notes
+///
+/// - A forged entry passes the combined check with probability at most
+///   2^-128.
+/// - The combined check is cofactored: an entry that [`verify`] rejects
+///   only because of small-order components or a non-canonical `R` is
+///   accepted. Honest signatures are accepted by every policy.
+/// - Verification handles public data only and runs in variable time.
+pub fn verify_batch(items: &[(Signature, &[u8], PublicKey)]) -> Result<(), Vec<usize>> {
+    if items.len() > 1 && batch_holds(items) {
+        return Ok(());
+    }
+
+    let invalid: Vec<usize> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, (signature, message, public))| {
+            !verify_with_policy(*signature, message, *public, VerificationPolicy::Zip215)
+        })
+        .map(|(i, _)| i)
+        .collect();
+
+    if invalid.is_empty() {
+        Ok(())
+    } else {
+        Err(invalid)
+    }
+}
+
+/// Checks the combined batch equation.
+///
+/// `GeP3::decompress` returns negated points, so the sum below is
+/// `Σ z_i · S_i · B − Σ z_i · R_i − Σ z_i · h_i · A_i`.
+fn batch_holds(items: &[(Signature, &[u8], PublicKey)]) -> bool {
+    let mut rng = Csprng::new();
 
-    let r_check = a.double_scalar_mul(h, s).to_bytes();
+    let mut scalars = Vec::with_capacity(2 * items.len());
+    let mut points = Vec::with_capacity(2 * items.len());
+    let mut base = Scalar([0u8; 32]);
 
-    r_check.ct_eq((&signature.0[..32]).try_into().unwrap())
+    for (signature, message, public) in items {
+        let s = Scalar(signature.0[32..].try_into().unwrap());
+        if !s.is_canonical() {
+            return false;
+        }
+
+        let (minus_a, ok) = GeP3::decompress(&public.0);
+        if ok != 0 {
+            return false;
+        }
+
+        let (minus_r, ok) = GeP3::decompress(signature.0[..32].try_into().unwrap());
+        if ok != 0 {
+            return false;
+        }
+
+        let h = challenge(Variant::Pure, &signature.0[..32], &public.0, message);
+
+        let mut z = Scalar([0u8; 32]);
+        rng.fill_bytes(&mut z.0[..16]);
+
+        base = Scalar::from_mul_sum(z, s, base);
+
+        scalars.push(z);
+        points.push(minus_r);
+
+        scalars.push(Scalar::from_mul_sum(z, h, Scalar([0u8; 32])));
+        points.push(minus_a);
+    }
+
+    let sum = multiscalar_mul(&scalars, &points);
+    let check = GeP3::from_gep1(&GeP1::from_sum(
+        &sum,
+        &GeCached::from_p3(&GeP3::from_scalar_mul(base)),
+    ));
+
+    has_small_order(&check)
 }
 
 /// Adds a scalar to an Ed25519 keypair and/or public key.
@@ -296,6 +978,7 @@ pub fn add_scalar(
     match (private_key, public_key) {
         (Some(private), Some(public)) => {
             private.scalar = Scalar::from_mul_sum(one, Scalar(n), private.scalar);
+            private.seed = None;
 
             let mut buf = [0u8; 64];
             buf[..32].copy_from_slice(&private.prefix);
@@ -308,6 +991,7 @@ pub fn add_scalar(
 
         (Some(private), None) => {
             private.scalar = Scalar::from_mul_sum(one, Scalar(n), private.scalar);
+            private.seed = None;
 
             let mut buf = [0u8; 64];
             buf[..32].copy_from_slice(&private.prefix);
@@ -334,16 +1018,20 @@ pub fn add_scalar(
 
 /// Computes a Diffie–Hellman shared secret using X25519.
 ///
-/// This function is a convenience wrapper around the X25519 key
-/// agreement implementation provided by the `x25519` module.
+/// Both keys are converted to their Montgomery form (see
+/// [`PublicKey::to_montgomery`] and [`PrivateKey::to_montgomery`]), so two
+/// parties holding Ed25519 keypairs derive the same secret:
+///
+/// ```text
+/// exchange(a, B) == exchange(b, A) == u(a · b · G)
+/// ```
 ///
-/// It derives a shared secret from:
-/// - the secret scalar contained in an Ed25519 private key, and
-/// - a peer public key represented as a 32-byte Curve25519
-///   Montgomery coordinate.
+/// For keys expanded from a seed the result equals `x25519::exchange` on
+/// the converted keys. Keys whose scalar was reduced by [`add_scalar`] are
+/// used unclamped through the same Montgomery ladder.
 ///
 /// All algorithmic details and security properties are documented
 /// in the `x25519` module.
 pub fn exchange(private: &PrivateKey, public: &PublicKey) -> [u8; 32] {
-    x25519::exchange(&private.scalar().to_bytes(), &public.to_bytes())
+    x25519::ladder(&private.scalar().to_bytes(), &public.to_montgomery())
 }
@@ -403,6 +403,7 @@ impl GeP2 {
 ///
 /// This type is the primary working representation for group
 /// operations in Ed25519.
+#[derive(Clone, Copy)]
 pub(crate) struct GeP3 {
     pub(crate) x: FieldElement,
     pub(crate) y: FieldElement,
@@ -421,45 +422,46 @@ impl GeP3 {
         t: FieldElement::ZERO,
     };
 
+    /// Returns the odd multiples `1·P, 3·P, …, 15·P` used by
+    /// [`GeP3::double_scalar_mul`].
+    ///
+    /// Callers verifying many signatures under the same key compute this
+    /// table once.
+    pub(crate) fn odd_multiples(&self) -> [GeCached; 8] {
+        let mut ai = [GeCached::ZERO; 8];
+
+        ai[0] = GeCached::from_p3(self);
+
+        let a2 = GeP3::from_gep1(&self.double());
+
+        for j in 1..8 {
+            let t = GeP1::from_sum(&a2, &ai[j - 1]);
+            ai[j] = GeCached::from_p3(&GeP3::from_gep1(&t));
+        }
+
+        ai
+    }
+
     /// Computes a double scalar multiplication:
     ///
     /// ```text
-    /// r = a * self + b * B
+    /// r = a * P + b * B
     /// ```
     ///
-    /// where `B` is the standard Ed25519 base point.
+    /// where `B` is the standard Ed25519 base point and `ai` holds the odd
+    /// multiples of `P` returned by [`GeP3::odd_multiples`].
     ///
     /// This method implements a **sliding-window algorithm** using:
     /// - signed window representations (`slide`)
-    /// - precomputed odd multiples of `self`
+    /// - precomputed odd multiples of `P`
     /// - precomputed table entries for the base point
     ///
     /// The computation is performed in constant time with respect
     /// to the scalar values.
-    pub(crate) fn double_scalar_mul(&self, a: Scalar, b: Scalar) -> GeP2 {
-        let mut ai = [
-            GeCached::ZERO,
-            GeCached::ZERO,
-            GeCached::ZERO,
-            GeCached::ZERO,
-            GeCached::ZERO,
-            GeCached::ZERO,
-            GeCached::ZERO,
-            GeCached::ZERO,
-        ];
-
+    pub(crate) fn double_scalar_mul(ai: &[GeCached; 8], a: Scalar, b: Scalar) -> GeP2 {
         let aslide = a.slide();
         let bslide = b.slide();
 
-        ai[0] = GeCached::from_p3(self);
-
-        let a2 = GeP3::from_gep1(&self.double());
-
-        for j in 1..8 {
-            let t = GeP1::from_sum(&a2, &ai[j - 1]);
-            ai[j] = GeCached::from_p3(&GeP3::from_gep1(&t));
-        }
-
         let mut r = GeP2::ONE;
         let mut started = false;
 
@@ -513,11 +515,26 @@ impl GeP3 {
         Self { x, y, z, t }
     }
 
+    /// Converts a point from projective `(P2)` coordinates into extended
+    /// `(P3)` coordinates, recovering `T = X · Y / Z` without inversion:
+    ///
+    /// ```text
+    /// (X : Y : Z) -> (X · Z : Y · Z : Z² : X · Y)
+    /// ```
+    pub(crate) fn from_gep2(g: &GeP2) -> Self {
+        let x = g.x * g.z;
+        let y = g.y * g.z;
+        let z = g.z.square();
+        let t = g.x * g.y;
+
+        Self { x, y, z, t }
+    }
+
     /// Encodes the point into its compressed 32-byte representation.
     ///
     /// The `y` coordinate is serialized in little-endian form,
     /// and the least significant bit of `x` is stored as the sign bit.
-    pub(crate) fn to_bytes(&self) -> [u8; 32] {
+    pub(crate) fn to_bytes(self) -> [u8; 32] {
         let recip = self.z.invert();
         let x = self.x * recip;
         let y = self.y * recip;
@@ -717,6 +734,7 @@ impl GeP3 {
 ///
 /// This layout matches the one used in the Ed25519 reference
 /// implementations (ref10 / orlp).
+#[derive(Clone, Copy)]
 pub struct GeCached {
     /// Precomputed value `y + x`.
     pub(crate) yplusx: FieldElement,
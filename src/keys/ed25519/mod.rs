@@ -35,6 +35,7 @@
 /// This module exposes the public-facing interface:
 /// - key pair generation,
 /// - message signing and signature verification,
+/// - batch signature verification and cached verifying keys,
 /// - scalar-based key updates,
 /// - Diffie–Hellman–style key exchange built on Curve25519.
 ///
@@ -75,6 +76,14 @@ pub(crate) mod field;
 /// - cached and precomputed representations for efficiency.
 pub(crate) mod group;
 
+/// Multi-scalar multiplication.
+///
+/// Implements Pippenger's bucket method to compute sums of many scalar
+/// multiples at once, as needed by batch signature verification.
+///
+/// Runs in variable time and must only be used with public inputs.
+pub(crate) mod msm;
+
 /// Scalar arithmetic.
 ///
 /// Implements arithmetic modulo the Ed25519 group order ℓ.
@@ -93,5 +102,10 @@ pub(crate) mod scalar;
 /// All values are derived from the Ed25519 specification.
 pub(crate) mod table;
 
+/// Tests of internal signing paths that the public API cannot reach, such
+/// as hedged nonces built from fixed noise.
+#[cfg(test)]
+mod tests;
+
 // Re-export the public API at the `ed25519` level.
 pub use core::*;
@@ -88,6 +88,13 @@ use crate::keys::ed25519::field::{load_3, load_4};
 
 use std::array;
 
+/// The group order `ℓ = 2^252 + 27742317777372353535851937790883648493`,
+/// little-endian.
+const L: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+];
+
 /// A 256-bit scalar used in Ed25519 operations.
 ///
 /// This type represents integers modulo the Ed25519 group order `ℓ`,
@@ -128,6 +135,20 @@ impl Scalar {
         self.0
     }
 
+    /// Returns `true` if the scalar is strictly less than `ℓ`, i.e. in
+    /// canonical reduced form.
+    ///
+    /// The comparison is a full-width subtraction and runs in constant time.
+    pub(crate) fn is_canonical(&self) -> bool {
+        let mut borrow = 0i16;
+
+        for (a, l) in self.0.iter().zip(L.iter()) {
+            borrow = (*a as i16 - *l as i16 + borrow) >> 8;
+        }
+
+        borrow != 0
+    }
+
     /// Reduces a 512-bit integer modulo the Ed25519 scalar field order `ℓ`.
     ///
     /// This function takes a 64-byte (512-bit) input and reduces it modulo
@@ -263,7 +284,7 @@ impl Scalar {
 
         let s12 = s[12];
         for (sx, coeff) in s.iter_mut().take(6).zip(coeffs.iter()) {
-            *sx = s12 * coeff;
+            *sx += s12 * coeff;
         }
         s[12] = 0;
 
@@ -280,7 +301,7 @@ impl Scalar {
 
         let s12 = s[12];
         for (sx, coeff) in s.iter_mut().take(6).zip(coeffs.iter()) {
-            *sx = s12 * coeff;
+            *sx += s12 * coeff;
         }
 
         for index in 0..11 {
@@ -0,0 +1,55 @@
+//! Hedged signatures with fixed noise.
+//!
+//! The expected signatures were computed with libsodium's scalar and point
+//! primitives from `r = SHA-512(dom || Z || pad || prefix || pad' || M)`,
+//! with the RFC 8032 test 1 key and `Z = 0x01..=0x20`.
+
+use super::core::{Variant, keypair_from_seed, sign_variant, verify, verify_ctx};
+
+const SEED: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+const MESSAGE: &[u8] = b"hedged";
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn noise() -> [u8; 32] {
+    std::array::from_fn(|i| i as u8 + 1)
+}
+
+#[test]
+fn hedged_nonce_layout() {
+    let (public, private) = keypair_from_seed(unhex(SEED).try_into().unwrap());
+
+    // r = H(Z || 0^96 || prefix || 0^96 || M)
+    let signature = sign_variant(Variant::Pure, Some(&noise()), MESSAGE, public, private);
+    assert_eq!(
+        signature.to_bytes().to_vec(),
+        unhex(
+            "d74884b598f580ce6116a54db2fcfbcd8b58166d04ece0ba3cb54bc27842a729\
+             a0bc3264cac0ecd55c85df05ddc32ce1b79b31864cdcb6027dd30e7f0fc7bc09"
+        )
+    );
+    assert!(verify(signature, MESSAGE, public));
+
+    // r = H(dom2(0, "foo") || Z || 0^59 || prefix || 0^96 || M)
+    let context = b"foo";
+    let signature = sign_variant(
+        Variant::Context(context),
+        Some(&noise()),
+        MESSAGE,
+        public,
+        private,
+    );
+    assert_eq!(
+        signature.to_bytes().to_vec(),
+        unhex(
+            "9bf222faf09a01ba47e9e853b0a6cc63b8013517ff76189af9f2a40a55b42509\
+             61ceff84d99c7a5283289b95338e9716368c77766e06147338cddb1f395ed403"
+        )
+    );
+    assert!(verify_ctx(signature, MESSAGE, context, public));
+}
@@ -0,0 +1,577 @@
+//! minisign Ed25519 key and signature files.
+//!
+//! Keys use the `signify` layout of an untrusted comment line followed by
+//! one Base64 line; signatures add a trusted comment and a second, global
+//! signature:
+//!
+//! ```text
+//! public key:  "Ed" || key id (8) || public key (32)
+//! secret key:  "Ed" || KDF ("Sc", or 0 0 if unencrypted) || "B2"
+//!              || salt (32) || opslimit (u64 LE) || memlimit (u64 LE)
+//!              || key id (8) || seed || public key (64) || checksum (32)
+//!
+//! untrusted comment: <comment>
+//! base64("ED" || key id (8) || Ed25519(BLAKE2b-512(message)))
+//! trusted comment: <trusted comment>
+//! base64(Ed25519(signature || trusted comment))
+//! ```
+//!
+//! Legacy signatures use the algorithm `Ed` and sign the message itself.
+//!
+//! The secret key checksum is BLAKE2b-256 over the algorithm, key id and
+//! key. When encrypted, the last 104 bytes are XORed with scrypt output
+//! whose parameters are derived from `opslimit` and `memlimit` as in
+//! libsodium's `crypto_pwhash_scryptsalsa208sha256`.
+
+use crate::derivation::{ScryptParams, scrypt};
+use crate::encoding::base64::{self, Variant};
+use crate::hash::blake2b;
+use crate::keys::ed25519::{self, PrivateKey, PublicKey, Signature};
+use crate::keys::signify::file::{self, COMMENT_PREFIX};
+use crate::rng::Csprng;
+
+/// Signature algorithm of keys and legacy signatures.
+const LEGACY_ALG: &[u8; 2] = b"Ed";
+
+/// Signature algorithm of prehashed signatures.
+const PREHASHED_ALG: &[u8; 2] = b"ED";
+
+/// KDF identifier of scrypt.
+const KDF_SCRYPT: &[u8; 2] = b"Sc";
+
+/// KDF identifier of unencrypted secret keys.
+const KDF_NONE: &[u8; 2] = &[0, 0];
+
+/// Checksum identifier of BLAKE2b-256.
+const CHECKSUM_ALG: &[u8; 2] = b"B2";
+
+/// Prefix of the trusted comment line.
+const TRUSTED_PREFIX: &str = "trusted comment: ";
+
+/// Untrusted comment written by `minisign -S`.
+const SIGNATURE_COMMENT: &str = "signature from minisign secret key";
+
+/// Encoded lengths of the structures.
+const PUBLIC_KEY_LEN: usize = 42;
+const SECRET_KEY_LEN: usize = 158;
+const SIGNATURE_LEN: usize = 74;
+
+/// Offset and length of the encrypted part of a secret key.
+const SECRET_OFFSET: usize = 54;
+const SECRET_LEN: usize = 104;
+
+/// Errors that can occur while handling minisign files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinisignError {
+    /// A comment line, Base64 payload or structure length is malformed.
+    InvalidEncoding,
+    /// The signature algorithm, KDF or checksum algorithm is unknown.
+    UnsupportedAlgorithm,
+    /// The scrypt limits exceed the defaults or do not yield valid
+    /// parameters.
+    InvalidKdfParams,
+    /// A comment spans several lines.
+    InvalidComment,
+    /// The secret key is encrypted and no password was given.
+    PasswordRequired,
+    /// The checksum of the decrypted secret key does not match.
+    WrongPassword,
+    /// The key material is invalid or inconsistent.
+    InvalidKey,
+    /// The Ed25519 private key has no seed.
+    MissingSeed,
+    /// The signature was made by another key.
+    KeyMismatch,
+    /// The signature or the trusted comment signature does not verify.
+    InvalidSignature,
+}
+
+/// Cost limits of the scrypt KDF protecting secret keys, as accepted by
+/// libsodium's `crypto_pwhash_scryptsalsa208sha256`.
+///
+/// Limits above the defaults are rejected, so that a key file cannot
+/// demand more than about 1 GiB of memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScryptLimits {
+    /// Computation limit.
+    pub opslimit: u64,
+    /// Memory limit in bytes.
+    pub memlimit: u64,
+}
+
+impl Default for ScryptLimits {
+    /// The "sensitive" limits used by `minisign -G`: 2²⁵ operations and
+    /// 1 GiB of memory.
+    fn default() -> Self {
+        Self {
+            opslimit: 1 << 25,
+            memlimit: 1 << 30,
+        }
+    }
+}
+
+impl ScryptLimits {
+    /// Derives the scrypt parameters, following libsodium's `pickparams`,
+    /// with `p` capped so that the `p` input blocks fit in `memlimit`.
+    fn params(&self) -> Result<ScryptParams, MinisignError> {
+        let max = Self::default();
+        if self.opslimit > max.opslimit || self.memlimit > max.memlimit {
+            return Err(MinisignError::InvalidKdfParams);
+        }
+
+        let opslimit = self.opslimit.max(32768);
+        let r = 8u32;
+
+        let log2 = |max_n: u64| (1u8..63).find(|&l| 1u64 << l > max_n / 2).unwrap_or(63);
+
+        if opslimit < self.memlimit / 32 {
+            Ok(ScryptParams {
+                log_n: log2(opslimit / (r as u64 * 4)),
+                r,
+                p: 1,
+            })
+        } else {
+            let log_n = log2(self.memlimit / (r as u64 * 128));
+            let max_rp = ((opslimit / 4) >> log_n).min(0x3fff_ffff);
+            let max_p = (self.memlimit / (r as u64 * 128)).max(1);
+
+            Ok(ScryptParams {
+                log_n,
+                r,
+                p: (max_rp / r as u64).min(max_p) as u32,
+            })
+        }
+    }
+}
+
+/// A minisign public key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinisignPublicKey {
+    /// Random identifier shared with the secret key and its signatures.
+    pub key_id: [u8; 8],
+    /// The Ed25519 verification key.
+    pub public: PublicKey,
+}
+
+/// A minisign secret key.
+#[derive(Clone, Copy)]
+pub struct MinisignSecretKey {
+    /// Random identifier shared with the public key and signatures.
+    pub key_id: [u8; 8],
+    /// The Ed25519 signing key.
+    pub private: PrivateKey,
+}
+
+impl MinisignPublicKey {
+    /// Encodes the key as the Base64 string accepted by `minisign -P`.
+    pub fn to_base64(&self) -> String {
+        let mut data = Vec::with_capacity(PUBLIC_KEY_LEN);
+        data.extend_from_slice(LEGACY_ALG);
+        data.extend_from_slice(&self.key_id);
+        data.extend_from_slice(&self.public.to_bytes());
+
+        base64::encode(&data, Variant::Standard)
+    }
+
+    /// Decodes a Base64 public key, as passed to `minisign -P`.
+    pub fn from_base64(input: &str) -> Result<Self, MinisignError> {
+        let data = base64::decode(input.trim(), Variant::Standard)
+            .map_err(|_| MinisignError::InvalidEncoding)?;
+
+        Self::from_data(&data)
+    }
+
+    /// Decodes the binary public key structure.
+    fn from_data(data: &[u8]) -> Result<Self, MinisignError> {
+        if data.len() != PUBLIC_KEY_LEN {
+            return Err(MinisignError::InvalidEncoding);
+        }
+
+        if &data[..2] != LEGACY_ALG {
+            return Err(MinisignError::UnsupportedAlgorithm);
+        }
+
+        let public = PublicKey::from_bytes(data[10..].try_into().unwrap())
+            .map_err(|_| MinisignError::InvalidKey)?;
+
+        Ok(Self {
+            key_id: data[2..10].try_into().unwrap(),
+            public,
+        })
+    }
+
+    /// Encodes the key as a `minisign.pub` file, with the comment
+    /// `minisign public key <KEY ID>` written by `minisign -G`.
+    pub fn encode(&self) -> String {
+        let mut out = String::with_capacity(128);
+        file::push_line(
+            &mut out,
+            COMMENT_PREFIX,
+            &format!("minisign public key {}", key_id_hex(&self.key_id)),
+        );
+        file::push_line(&mut out, "", &self.to_base64());
+
+        out
+    }
+
+    /// Decodes a `minisign.pub` file.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok((key, comment))` on success
+    /// - `Err(MinisignError)` if the file is malformed
+    pub fn decode(input: &str) -> Result<(Self, String), MinisignError> {
+        let (comment, data, rest) = file::read(input).ok_or(MinisignError::InvalidEncoding)?;
+        if !rest.trim().is_empty() {
+            return Err(MinisignError::InvalidEncoding);
+        }
+
+        Ok((Self::from_data(&data)?, comment.to_string()))
+    }
+
+    /// Verifies a minisign signature file over `message`, as
+    /// `minisign -V`.
+    ///
+    /// Both prehashed and legacy signatures are accepted. The trusted
+    /// comment is only returned once its own signature has been checked.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(trusted_comment)` if both signatures are valid
+    /// - `Err(KeyMismatch)` if the signature was made by another key
+    /// - `Err(InvalidSignature)` if either signature does not verify
+    /// - `Err(MinisignError)` if the file is malformed
+    pub fn verify(&self, signature: &str, message: &[u8]) -> Result<String, MinisignError> {
+        let (_, data, rest) = file::read(signature).ok_or(MinisignError::InvalidEncoding)?;
+
+        let (trusted, rest) = file::next_line(rest)
+            .and_then(|(line, rest)| Some((line.strip_prefix(TRUSTED_PREFIX)?, rest)))
+            .ok_or(MinisignError::InvalidEncoding)?;
+
+        let (global, rest) = file::next_line(rest).ok_or(MinisignError::InvalidEncoding)?;
+        let global = base64::decode(global, Variant::Standard)
+            .map_err(|_| MinisignError::InvalidEncoding)?;
+        let global = Signature::from_slice(&global).map_err(|_| MinisignError::InvalidEncoding)?;
+
+        if data.len() != SIGNATURE_LEN || !rest.trim().is_empty() {
+            return Err(MinisignError::InvalidEncoding);
+        }
+
+        let prehashed = match &data[..2] {
+            alg if alg == PREHASHED_ALG => true,
+            alg if alg == LEGACY_ALG => false,
+            _ => return Err(MinisignError::UnsupportedAlgorithm),
+        };
+
+        if data[2..10] != self.key_id {
+            return Err(MinisignError::KeyMismatch);
+        }
+
+        let signature = Signature::from_slice(&data[10..]).unwrap();
+
+        let valid = if prehashed {
+            ed25519::verify(signature, &blake2b(64, message), self.public)
+        } else {
+            ed25519::verify(signature, message, self.public)
+        };
+
+        if !valid || !ed25519::verify(global, &global_data(&signature, trusted), self.public) {
+            return Err(MinisignError::InvalidSignature);
+        }
+
+        Ok(trusted.to_string())
+    }
+}
+
+impl MinisignSecretKey {
+    /// Generates a new key with a random key id, as `minisign -G`.
+    pub fn generate() -> Self {
+        let mut key_id = [0u8; 8];
+        Csprng::new().fill_bytes(&mut key_id);
+
+        let (_, private) = ed25519::generate_keypair();
+
+        Self { key_id, private }
+    }
+
+    /// Returns the matching public key.
+    pub fn public_key(&self) -> MinisignPublicKey {
+        MinisignPublicKey {
+            key_id: self.key_id,
+            public: self.private.public_key(),
+        }
+    }
+
+    /// Encodes the key as a secret key file, encrypted with the default
+    /// [`ScryptLimits`] if a password is given.
+    ///
+    /// `minisign -G` uses the comment `minisign encrypted secret key`.
+    pub fn encode(&self, password: Option<&[u8]>, comment: &str) -> Result<String, MinisignError> {
+        self.encode_with_limits(password, comment, ScryptLimits::default())
+    }
+
+    /// Encodes the key as a secret key file, encrypted with the given
+    /// scrypt limits if a password is given.
+    ///
+    /// Without a password, the key is stored in the clear like
+    /// `minisign -G -W`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(file)` on success
+    /// - `Err(MissingSeed)` if the key was not expanded from a seed
+    /// - `Err(InvalidKdfParams)` or `Err(InvalidComment)` for invalid
+    ///   arguments
+    pub fn encode_with_limits(
+        &self,
+        password: Option<&[u8]>,
+        comment: &str,
+        limits: ScryptLimits,
+    ) -> Result<String, MinisignError> {
+        if !file::is_single_line(comment) {
+            return Err(MinisignError::InvalidComment);
+        }
+
+        let mut seed = self.private.seed().ok_or(MinisignError::MissingSeed)?;
+
+        let mut secret = [0u8; SECRET_LEN];
+        secret[..8].copy_from_slice(&self.key_id);
+        secret[8..40].copy_from_slice(&seed);
+        secret[40..72].copy_from_slice(&self.private.public_key().to_bytes());
+        seed.fill(0);
+
+        let checksum = checksum(&secret[..72]);
+        secret[72..].copy_from_slice(&checksum);
+
+        let mut data = Vec::with_capacity(SECRET_KEY_LEN);
+        data.extend_from_slice(LEGACY_ALG);
+
+        let result = match password {
+            Some(password) => {
+                let mut salt = [0u8; 32];
+                Csprng::new().fill_bytes(&mut salt);
+
+                data.extend_from_slice(KDF_SCRYPT);
+                data.extend_from_slice(CHECKSUM_ALG);
+                data.extend_from_slice(&salt);
+                data.extend_from_slice(&limits.opslimit.to_le_bytes());
+                data.extend_from_slice(&limits.memlimit.to_le_bytes());
+
+                apply_mask(&mut secret, password, &salt, limits)
+            }
+            None => {
+                data.extend_from_slice(KDF_NONE);
+                data.extend_from_slice(CHECKSUM_ALG);
+                data.extend_from_slice(&[0u8; 48]);
+
+                Ok(())
+            }
+        };
+
+        data.extend_from_slice(&secret);
+        let out = result.map(|()| file::write(comment, &data));
+
+        secret.fill(0);
+        data.fill(0);
+
+        out
+    }
+
+    /// Decodes a secret key file, decrypting it with `password` if needed.
+    ///
+    /// The password is ignored for unencrypted keys.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok((key, comment))` on success
+    /// - `Err(PasswordRequired)` if the key is encrypted and no password
+    ///   was given
+    /// - `Err(WrongPassword)` if the decrypted key fails its checksum
+    /// - `Err(MinisignError)` if the file is malformed
+    ///
+    /// # Security Notes
+    ///
+    /// - The scrypt limits are read from the file, and rejected with
+    ///   `InvalidKdfParams` if they exceed the default [`ScryptLimits`].
+    pub fn decode(input: &str, password: Option<&[u8]>) -> Result<(Self, String), MinisignError> {
+        let (comment, mut data, rest) = file::read(input).ok_or(MinisignError::InvalidEncoding)?;
+
+        let result = if data.len() != SECRET_KEY_LEN || !rest.trim().is_empty() {
+            Err(MinisignError::InvalidEncoding)
+        } else {
+            Self::from_data(&data, password)
+        };
+
+        data.fill(0);
+
+        Ok((result?, comment.to_string()))
+    }
+
+    /// Decodes the binary secret key structure.
+    fn from_data(data: &[u8], password: Option<&[u8]>) -> Result<Self, MinisignError> {
+        if &data[..2] != LEGACY_ALG || &data[4..6] != CHECKSUM_ALG {
+            return Err(MinisignError::UnsupportedAlgorithm);
+        }
+
+        let mut secret: [u8; SECRET_LEN] = data[SECRET_OFFSET..].try_into().unwrap();
+
+        let encrypted = match &data[2..4] {
+            kdf if kdf == KDF_SCRYPT => true,
+            kdf if kdf == KDF_NONE => false,
+            _ => return Err(MinisignError::UnsupportedAlgorithm),
+        };
+
+        if encrypted {
+            let password = password.ok_or(MinisignError::PasswordRequired)?;
+            let limits = ScryptLimits {
+                opslimit: u64::from_le_bytes(data[38..46].try_into().unwrap()),
+                memlimit: u64::from_le_bytes(data[46..54].try_into().unwrap()),
+            };
+
+            apply_mask(&mut secret, password, &data[6..38], limits)?;
+        }
+
+        let valid = checksum(&secret[..72]) == secret[72..];
+        let key_id: [u8; 8] = secret[..8].try_into().unwrap();
+        let private = PrivateKey::from_seed(secret[8..40].try_into().unwrap());
+        let consistent = private.public_key().to_bytes()[..] == secret[40..72];
+        secret.fill(0);
+
+        if !valid {
+            return Err(if encrypted {
+                MinisignError::WrongPassword
+            } else {
+                MinisignError::InvalidKey
+            });
+        }
+
+        if !consistent {
+            return Err(MinisignError::InvalidKey);
+        }
+
+        Ok(Self { key_id, private })
+    }
+
+    /// Signs `message` with a prehashed signature, as `minisign -S`.
+    ///
+    /// The trusted comment is signed together with the signature;
+    /// `minisign -S` writes `timestamp:<unix time>\tfile:<name>\thashed`.
+    /// An empty untrusted comment is replaced by the default
+    /// `signature from minisign secret key`.
+    pub fn sign(
+        &self,
+        message: &[u8],
+        untrusted_comment: &str,
+        trusted_comment: &str,
+    ) -> Result<String, MinisignError> {
+        self.sign_variant(
+            PREHASHED_ALG,
+            &blake2b(64, message),
+            untrusted_comment,
+            trusted_comment,
+        )
+    }
+
+    /// Signs `message` with a legacy signature over the message itself,
+    /// as `minisign -S -l`.
+    ///
+    /// Prefer [`MinisignSecretKey::sign`]; legacy signatures exist for
+    /// verifiers predating prehashing.
+    pub fn sign_legacy(
+        &self,
+        message: &[u8],
+        untrusted_comment: &str,
+        trusted_comment: &str,
+    ) -> Result<String, MinisignError> {
+        self.sign_variant(LEGACY_ALG, message, untrusted_comment, trusted_comment)
+    }
+
+    /// Builds a signature file over `signed` with algorithm `alg`.
+    fn sign_variant(
+        &self,
+        alg: &[u8; 2],
+        signed: &[u8],
+        untrusted_comment: &str,
+        trusted_comment: &str,
+    ) -> Result<String, MinisignError> {
+        if !file::is_single_line(untrusted_comment) || !file::is_single_line(trusted_comment) {
+            return Err(MinisignError::InvalidComment);
+        }
+
+        let public = self.private.public_key();
+        let signature = ed25519::sign(signed, public, self.private);
+        let global = ed25519::sign(
+            &global_data(&signature, trusted_comment),
+            public,
+            self.private,
+        );
+
+        let mut data = Vec::with_capacity(SIGNATURE_LEN);
+        data.extend_from_slice(alg);
+        data.extend_from_slice(&self.key_id);
+        data.extend_from_slice(&signature.to_bytes());
+
+        let comment = if untrusted_comment.is_empty() {
+            SIGNATURE_COMMENT
+        } else {
+            untrusted_comment
+        };
+
+        let mut out = file::write(comment, &data);
+        file::push_line(&mut out, TRUSTED_PREFIX, trusted_comment);
+        file::push_line(
+            &mut out,
+            "",
+            &base64::encode(&global.to_bytes(), Variant::Standard),
+        );
+
+        Ok(out)
+    }
+}
+
+/// Formats a key id as minisign does: the little-endian integer in
+/// uppercase hexadecimal.
+fn key_id_hex(key_id: &[u8; 8]) -> String {
+    format!("{:016X}", u64::from_le_bytes(*key_id))
+}
+
+/// Data covered by the global signature: `signature || trusted comment`.
+fn global_data(signature: &Signature, trusted_comment: &str) -> Vec<u8> {
+    let mut data = Vec::with_capacity(64 + trusted_comment.len());
+    data.extend_from_slice(&signature.to_bytes());
+    data.extend_from_slice(trusted_comment.as_bytes());
+
+    data
+}
+
+/// BLAKE2b-256 checksum of `"Ed" || key id || key`.
+fn checksum(keynum_sk: &[u8]) -> [u8; 32] {
+    let mut input = [0u8; 74];
+    input[..2].copy_from_slice(LEGACY_ALG);
+    input[2..].copy_from_slice(keynum_sk);
+
+    let digest = blake2b(32, &input);
+    input.fill(0);
+
+    digest[..32].try_into().unwrap()
+}
+
+/// XORs the encrypted part of a secret key with scrypt output.
+fn apply_mask(
+    secret: &mut [u8; SECRET_LEN],
+    password: &[u8],
+    salt: &[u8],
+    limits: ScryptLimits,
+) -> Result<(), MinisignError> {
+    let mut mask = [0u8; SECRET_LEN];
+    scrypt(password, salt, &limits.params()?, &mut mask)
+        .map_err(|_| MinisignError::InvalidKdfParams)?;
+
+    for (s, m) in secret.iter_mut().zip(&mask) {
+        *s ^= m;
+    }
+
+    mask.fill(0);
+
+    Ok(())
+}
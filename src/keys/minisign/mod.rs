@@ -0,0 +1,24 @@
+//! minisign keys and signatures.
+//!
+//! This module reads and writes the Ed25519 key and signature files of
+//! minisign, and signs and verifies detached signatures compatible with
+//! `minisign -S` / `minisign -V`:
+//!
+//! - public keys (`minisign.pub` files and bare Base64 keys),
+//! - secret keys, optionally encrypted with a password through scrypt,
+//! - prehashed (BLAKE2b-512) and legacy signatures, each carrying a
+//!   signed trusted comment.
+//!
+//! The file layout is the one of OpenBSD `signify`, see
+//! [`keys::signify`](crate::keys::signify).
+//!
+//! ## Structure
+//!
+//! - `core`  
+//!   Key and signature encodings, signing, verification and the error
+//!   type.
+
+mod core;
+
+// Re-export the public API at the `minisign` level.
+pub use core::*;
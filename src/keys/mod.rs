@@ -40,5 +40,21 @@
 //! It provides constant-time scalar multiplication on Montgomery
 //! coordinates and is intended for shared-secret derivation and
 //! key exchange, not for signatures.
+//!
+//! ## Key formats
+//!
+//! The `pkcs8` module encodes Ed25519 and X25519 keys as PKCS#8 private
+//! keys and SubjectPublicKeyInfo public keys (RFC 8410), in DER or PEM.
+//!
+//! The `openssh` module handles `ssh-ed25519` public keys, unencrypted
+//! `openssh-key-v1` private keys and SSHSIG file signatures.
+//!
+//! The `signify` and `minisign` modules read and write the key and
+//! detached signature files of OpenBSD `signify` and minisign, including
+//! password-encrypted secret keys.
 pub mod ed25519;
+pub mod minisign;
+pub mod openssh;
+pub mod pkcs8;
+pub mod signify;
 pub mod x25519;
@@ -0,0 +1,27 @@
+//! OpenSSH key formats and SSHSIG signatures.
+//!
+//! This module reads and writes Ed25519 keys in the formats used by
+//! OpenSSH, and signs and verifies files like `ssh-keygen -Y`:
+//!
+//! - `ssh-ed25519` public keys (`.pub` files, `authorized_keys` lines),
+//! - unencrypted `openssh-key-v1` private keys,
+//! - `SSH SIGNATURE` blocks (SSHSIG, SHA-512).
+//!
+//! ## Structure
+//!
+//! - `core`  
+//!   Public and private key encodings, and the error type.
+//!
+//! - `sshsig`  
+//!   Creation and verification of SSHSIG signatures.
+//!
+//! - `wire`  
+//!   The SSH `uint32` / `string` encoding shared by both.
+
+mod core;
+mod sshsig;
+mod wire;
+
+// Re-export the public API at the `openssh` level.
+pub use core::*;
+pub use sshsig::*;
@@ -0,0 +1,72 @@
+//! SSH wire encoding (RFC 4251, section 5).
+//!
+//! Only the two types used by key and signature blobs: `uint32` (4 bytes,
+//! big-endian) and `string` (a `uint32` length followed by the bytes).
+
+use super::core::OpenSshError;
+
+/// Sequential reader over an SSH-encoded buffer.
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Reads `len` raw bytes.
+    pub(crate) fn bytes(&mut self, len: usize) -> Result<&'a [u8], OpenSshError> {
+        if self.data.len() < len {
+            return Err(OpenSshError::InvalidEncoding);
+        }
+
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+
+        Ok(head)
+    }
+
+    /// Reads a `uint32`.
+    pub(crate) fn u32(&mut self) -> Result<u32, OpenSshError> {
+        Ok(u32::from_be_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    /// Reads a `string`.
+    pub(crate) fn string(&mut self) -> Result<&'a [u8], OpenSshError> {
+        let len = self.u32()? as usize;
+        self.bytes(len)
+    }
+
+    /// Reads a `string` of exactly `N` bytes.
+    pub(crate) fn array<const N: usize>(&mut self) -> Result<[u8; N], OpenSshError> {
+        self.string()?
+            .try_into()
+            .map_err(|_| OpenSshError::InvalidKey)
+    }
+
+    /// Returns the unread bytes.
+    pub(crate) fn rest(self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Checks that the whole buffer has been read.
+    pub(crate) fn finish(self) -> Result<(), OpenSshError> {
+        if self.data.is_empty() {
+            Ok(())
+        } else {
+            Err(OpenSshError::InvalidEncoding)
+        }
+    }
+}
+
+/// Appends a `uint32`.
+pub(crate) fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+/// Appends a `string`.
+pub(crate) fn put_string(out: &mut Vec<u8>, value: &[u8]) {
+    put_u32(out, value.len() as u32);
+    out.extend_from_slice(value);
+}
@@ -0,0 +1,309 @@
+//! PKCS#8 and SubjectPublicKeyInfo encodings of Curve25519 keys.
+//!
+//! RFC 8410 identifies both curves by an object identifier with no
+//! parameters and stores the raw 32-byte keys:
+//!
+//! ```text
+//! PrivateKeyInfo ::= SEQUENCE {
+//!     version             INTEGER (0 or 1),
+//!     algorithm           SEQUENCE { OBJECT IDENTIFIER },
+//!     privateKey          OCTET STRING { OCTET STRING (32 bytes) },
+//!     attributes      [0] IMPLICIT Attributes OPTIONAL,
+//!     publicKey       [1] IMPLICIT BIT STRING OPTIONAL   -- version 1 only
+//! }
+//!
+//! SubjectPublicKeyInfo ::= SEQUENCE {
+//!     algorithm           SEQUENCE { OBJECT IDENTIFIER },
+//!     subjectPublicKey    BIT STRING (32 bytes)
+//! }
+//! ```
+//!
+//! The Ed25519 private key is the RFC 8032 seed; the X25519 private key is
+//! the 32-byte secret, stored unclamped.
+
+use crate::encoding::der::{self, DerError, Reader};
+use crate::encoding::pem::{self, PemError};
+use crate::keys::{ed25519, x25519};
+
+/// PEM label of PKCS#8 private keys.
+pub const PRIVATE_KEY_LABEL: &str = "PRIVATE KEY";
+
+/// PEM label of SubjectPublicKeyInfo public keys.
+pub const PUBLIC_KEY_LABEL: &str = "PUBLIC KEY";
+
+/// Contents of the Ed25519 object identifier, `1.3.101.112`.
+const ED25519_OID: [u8; 3] = [0x2b, 0x65, 0x70];
+
+/// Contents of the X25519 object identifier, `1.3.101.110`.
+const X25519_OID: [u8; 3] = [0x2b, 0x65, 0x6e];
+
+/// Tag of the optional `[0] IMPLICIT Attributes` field.
+const ATTRIBUTES: u8 = 0xa0;
+
+/// Tag of the optional `[1] IMPLICIT BIT STRING` public key field.
+const PUBLIC_KEY: u8 = 0x81;
+
+/// Errors that can occur while decoding PKCS#8 and SPKI keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pkcs8Error {
+    /// The DER structure is malformed.
+    Der(DerError),
+    /// The PEM armor is malformed.
+    Pem(PemError),
+    /// The PEM label does not match the expected structure.
+    UnexpectedLabel,
+    /// The version is neither 0 nor 1, or 0 with a public key.
+    UnsupportedVersion,
+    /// The algorithm is neither Ed25519 nor X25519, or has parameters.
+    UnsupportedAlgorithm,
+    /// The key material has the wrong length or is not a valid key.
+    InvalidKey,
+    /// The embedded public key does not match the private key.
+    PublicKeyMismatch,
+    /// The Ed25519 private key has no seed.
+    MissingSeed,
+}
+
+impl From<DerError> for Pkcs8Error {
+    fn from(err: DerError) -> Self {
+        Pkcs8Error::Der(err)
+    }
+}
+
+/// A private key stored in a PKCS#8 container.
+///
+/// X25519 secrets are wiped when the container is dropped.
+pub enum PrivateKeyInfo {
+    /// An Ed25519 signing key.
+    Ed25519(ed25519::PrivateKey),
+    /// An X25519 secret.
+    X25519([u8; 32]),
+}
+
+/// A public key stored in a SubjectPublicKeyInfo container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublicKeyInfo {
+    /// An Ed25519 verification key.
+    Ed25519(ed25519::PublicKey),
+    /// An X25519 public key (Montgomery `u` coordinate).
+    X25519([u8; 32]),
+}
+
+impl PrivateKeyInfo {
+    /// Returns the public key matching this private key.
+    pub fn public_key(&self) -> PublicKeyInfo {
+        match self {
+            PrivateKeyInfo::Ed25519(private) => PublicKeyInfo::Ed25519(private.public_key()),
+            PrivateKeyInfo::X25519(secret) => PublicKeyInfo::X25519(x25519::public_key(secret)),
+        }
+    }
+
+    /// Encodes the key as a version 0 PKCS#8 structure, without the
+    /// optional public key, as OpenSSL does.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(der)` on success
+    /// - `Err(MissingSeed)` for an Ed25519 key without a seed
+    pub fn to_der(&self) -> Result<Vec<u8>, Pkcs8Error> {
+        let (oid, mut secret) = match self {
+            PrivateKeyInfo::Ed25519(private) => {
+                (ED25519_OID, private.seed().ok_or(Pkcs8Error::MissingSeed)?)
+            }
+            PrivateKeyInfo::X25519(secret) => (X25519_OID, *secret),
+        };
+
+        let mut key = Vec::with_capacity(34);
+        der::write(der::OCTET_STRING, &secret, &mut key);
+
+        let mut body = Vec::with_capacity(46);
+        der::write(der::INTEGER, &[0], &mut body);
+        write_algorithm(&oid, &mut body);
+        der::write(der::OCTET_STRING, &key, &mut body);
+
+        let mut out = Vec::with_capacity(48);
+        der::write(der::SEQUENCE, &body, &mut out);
+
+        secret.fill(0);
+        key.fill(0);
+        body.fill(0);
+
+        Ok(out)
+    }
+
+    /// Decodes a PKCS#8 structure (version 0 or 1).
+    ///
+    /// Attributes are ignored. A version 1 public key, if present, must
+    /// match the private key.
+    pub fn from_der(input: &[u8]) -> Result<Self, Pkcs8Error> {
+        let mut outer = Reader::new(input);
+        let mut reader = Reader::new(outer.read(der::SEQUENCE)?);
+        outer.finish()?;
+
+        let version = match reader.read(der::INTEGER)? {
+            [0] => 0,
+            [1] => 1,
+            _ => return Err(Pkcs8Error::UnsupportedVersion),
+        };
+
+        let oid = read_algorithm(&mut reader)?;
+
+        let mut key = Reader::new(reader.read(der::OCTET_STRING)?);
+        let secret: [u8; 32] = key
+            .read(der::OCTET_STRING)?
+            .try_into()
+            .map_err(|_| Pkcs8Error::InvalidKey)?;
+        key.finish()?;
+
+        reader.read_optional(ATTRIBUTES)?;
+
+        let public = reader.read_optional(PUBLIC_KEY)?;
+        reader.finish()?;
+
+        if version == 0 && public.is_some() {
+            return Err(Pkcs8Error::UnsupportedVersion);
+        }
+
+        let info = if oid == ED25519_OID {
+            PrivateKeyInfo::Ed25519(ed25519::PrivateKey::from_seed(secret))
+        } else {
+            PrivateKeyInfo::X25519(secret)
+        };
+
+        if let Some(public) = public {
+            let public = PublicKeyInfo::from_bit_string(oid, public)?;
+            if public != info.public_key() {
+                return Err(Pkcs8Error::PublicKeyMismatch);
+            }
+        }
+
+        Ok(info)
+    }
+
+    /// Encodes the key as a `PRIVATE KEY` PEM block.
+    pub fn to_pem(&self) -> Result<String, Pkcs8Error> {
+        let mut der = self.to_der()?;
+        let out = pem::encode(PRIVATE_KEY_LABEL, &der);
+
+        der.fill(0);
+
+        Ok(out)
+    }
+
+    /// Decodes a `PRIVATE KEY` PEM block.
+    pub fn from_pem(input: &str) -> Result<Self, Pkcs8Error> {
+        let (label, mut der) = pem::decode(input).map_err(Pkcs8Error::Pem)?;
+
+        let result = if label == PRIVATE_KEY_LABEL {
+            Self::from_der(&der)
+        } else {
+            Err(Pkcs8Error::UnexpectedLabel)
+        };
+
+        der.fill(0);
+
+        result
+    }
+}
+
+impl Drop for PrivateKeyInfo {
+    fn drop(&mut self) {
+        if let PrivateKeyInfo::X25519(secret) = self {
+            secret.fill(0);
+        }
+    }
+}
+
+impl PublicKeyInfo {
+    /// Encodes the key as a SubjectPublicKeyInfo structure.
+    pub fn to_der(&self) -> Vec<u8> {
+        let (oid, key) = match self {
+            PublicKeyInfo::Ed25519(public) => (ED25519_OID, public.to_bytes()),
+            PublicKeyInfo::X25519(public) => (X25519_OID, *public),
+        };
+
+        let mut bits = Vec::with_capacity(33);
+        bits.push(0);
+        bits.extend_from_slice(&key);
+
+        let mut body = Vec::with_capacity(42);
+        write_algorithm(&oid, &mut body);
+        der::write(der::BIT_STRING, &bits, &mut body);
+
+        let mut out = Vec::with_capacity(44);
+        der::write(der::SEQUENCE, &body, &mut out);
+
+        out
+    }
+
+    /// Decodes a SubjectPublicKeyInfo structure.
+    pub fn from_der(input: &[u8]) -> Result<Self, Pkcs8Error> {
+        let mut outer = Reader::new(input);
+        let mut reader = Reader::new(outer.read(der::SEQUENCE)?);
+        outer.finish()?;
+
+        let oid = read_algorithm(&mut reader)?;
+        let bits = reader.read(der::BIT_STRING)?;
+        reader.finish()?;
+
+        Self::from_bit_string(oid, bits)
+    }
+
+    /// Encodes the key as a `PUBLIC KEY` PEM block.
+    pub fn to_pem(&self) -> String {
+        pem::encode(PUBLIC_KEY_LABEL, &self.to_der())
+    }
+
+    /// Decodes a `PUBLIC KEY` PEM block.
+    pub fn from_pem(input: &str) -> Result<Self, Pkcs8Error> {
+        let (label, der) = pem::decode(input).map_err(Pkcs8Error::Pem)?;
+        if label != PUBLIC_KEY_LABEL {
+            return Err(Pkcs8Error::UnexpectedLabel);
+        }
+
+        Self::from_der(&der)
+    }
+
+    /// Decodes the contents of a public key BIT STRING: no unused bits,
+    /// then the 32-byte key.
+    fn from_bit_string(oid: [u8; 3], bits: &[u8]) -> Result<Self, Pkcs8Error> {
+        let key: [u8; 32] = match bits {
+            [0, key @ ..] => key.try_into().map_err(|_| Pkcs8Error::InvalidKey)?,
+            _ => return Err(Pkcs8Error::InvalidKey),
+        };
+
+        if oid == ED25519_OID {
+            ed25519::PublicKey::from_bytes(key)
+                .map(PublicKeyInfo::Ed25519)
+                .map_err(|_| Pkcs8Error::InvalidKey)
+        } else {
+            Ok(PublicKeyInfo::X25519(key))
+        }
+    }
+}
+
+/// Appends `AlgorithmIdentifier ::= SEQUENCE { oid }` to `out`.
+fn write_algorithm(oid: &[u8; 3], out: &mut Vec<u8>) {
+    let mut algorithm = Vec::with_capacity(5);
+    der::write(der::OBJECT_IDENTIFIER, oid, &mut algorithm);
+    der::write(der::SEQUENCE, &algorithm, out);
+}
+
+/// Reads an `AlgorithmIdentifier` and returns its OID, which must be
+/// Ed25519 or X25519 with absent parameters.
+fn read_algorithm(reader: &mut Reader) -> Result<[u8; 3], Pkcs8Error> {
+    let mut algorithm = Reader::new(reader.read(der::SEQUENCE)?);
+    let oid = algorithm.read(der::OBJECT_IDENTIFIER)?;
+
+    if !algorithm.is_empty() {
+        return Err(Pkcs8Error::UnsupportedAlgorithm);
+    }
+
+    if oid == ED25519_OID {
+        Ok(ED25519_OID)
+    } else if oid == X25519_OID {
+        Ok(X25519_OID)
+    } else {
+        Err(Pkcs8Error::UnsupportedAlgorithm)
+    }
+}
@@ -0,0 +1,344 @@
+//! `signify` Ed25519 key and signature files.
+//!
+//! Every file is an untrusted comment line followed by one Base64 line
+//! holding a fixed-size structure:
+//!
+//! ```text
+//! public key:  "Ed" || key id (8) || public key (32)
+//! secret key:  "Ed" || "BK" || uint32 rounds (big endian) || salt (16)
+//!              || checksum (8) || key id (8) || seed || public key (64)
+//! signature:   "Ed" || key id (8) || Ed25519 signature (64)
+//! ```
+//!
+//! The random key id ties signatures to the key that made them. The
+//! secret key checksum is the first 8 bytes of SHA-512 over the plain
+//! 64-byte key; when `rounds` is not zero, that key is XORed with
+//! `bcrypt_pbkdf(password, salt, rounds)`. Signatures cover the message
+//! itself.
+
+use super::file;
+use crate::derivation::bcrypt_pbkdf;
+use crate::hash::sha512;
+use crate::keys::ed25519::{self, PrivateKey, PublicKey, Signature};
+use crate::rng::Csprng;
+
+/// Public key algorithm identifier of Ed25519.
+const PKALG: &[u8; 2] = b"Ed";
+
+/// KDF identifier of bcrypt_pbkdf.
+const KDFALG: &[u8; 2] = b"BK";
+
+/// bcrypt_pbkdf rounds used by `signify` for encrypted keys.
+const KDF_ROUNDS: u32 = 42;
+
+/// Largest number of bcrypt_pbkdf rounds accepted in a secret key file.
+pub const MAX_KDF_ROUNDS: u32 = 4 * KDF_ROUNDS;
+
+/// Encoded lengths of the three structures.
+const PUBLIC_KEY_LEN: usize = 42;
+const SECRET_KEY_LEN: usize = 104;
+const SIGNATURE_LEN: usize = 74;
+
+/// Errors that can occur while handling `signify` files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignifyError {
+    /// The comment line, Base64 payload or structure length is malformed.
+    InvalidEncoding,
+    /// The key or signature algorithm is not Ed25519, or the KDF is not
+    /// bcrypt_pbkdf.
+    UnsupportedAlgorithm,
+    /// The bcrypt_pbkdf rounds exceed [`MAX_KDF_ROUNDS`].
+    InvalidKdfParams,
+    /// A comment spans several lines.
+    InvalidComment,
+    /// The secret key is encrypted and no password was given.
+    PasswordRequired,
+    /// The password is empty.
+    EmptyPassword,
+    /// The checksum of the decrypted secret key does not match.
+    WrongPassword,
+    /// The key material is invalid or inconsistent.
+    InvalidKey,
+    /// The Ed25519 private key has no seed.
+    MissingSeed,
+    /// The signature was made by another key.
+    KeyMismatch,
+    /// The signature does not verify.
+    InvalidSignature,
+}
+
+/// A `signify` public key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignifyPublicKey {
+    /// Random identifier shared with the secret key and its signatures.
+    pub key_id: [u8; 8],
+    /// The Ed25519 verification key.
+    pub public: PublicKey,
+}
+
+/// A `signify` secret key.
+#[derive(Clone, Copy)]
+pub struct SignifySecretKey {
+    /// Random identifier shared with the public key and signatures.
+    pub key_id: [u8; 8],
+    /// The Ed25519 signing key.
+    pub private: PrivateKey,
+}
+
+impl SignifyPublicKey {
+    /// Encodes the key as a `.pub` file.
+    ///
+    /// `signify -G` uses the comment `signify public key`.
+    pub fn encode(&self, comment: &str) -> Result<String, SignifyError> {
+        if !file::is_single_line(comment) {
+            return Err(SignifyError::InvalidComment);
+        }
+
+        let mut data = Vec::with_capacity(PUBLIC_KEY_LEN);
+        data.extend_from_slice(PKALG);
+        data.extend_from_slice(&self.key_id);
+        data.extend_from_slice(&self.public.to_bytes());
+
+        Ok(file::write(comment, &data))
+    }
+
+    /// Decodes a `.pub` file.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok((key, comment))` on success
+    /// - `Err(SignifyError)` if the file is malformed
+    pub fn decode(input: &str) -> Result<(Self, String), SignifyError> {
+        let (comment, data) = read_file(input, PUBLIC_KEY_LEN)?;
+
+        let public = PublicKey::from_bytes(data[10..].try_into().unwrap())
+            .map_err(|_| SignifyError::InvalidKey)?;
+
+        let key = Self {
+            key_id: data[2..10].try_into().unwrap(),
+            public,
+        };
+
+        Ok((key, comment.to_string()))
+    }
+
+    /// Verifies a detached `.sig` file over `message`, as `signify -V`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the signature is valid
+    /// - `Err(KeyMismatch)` if it was made by another key
+    /// - `Err(InvalidSignature)` if it does not verify
+    /// - `Err(SignifyError)` if the file is malformed
+    pub fn verify(&self, signature: &str, message: &[u8]) -> Result<(), SignifyError> {
+        let (_, data) = read_file(signature, SIGNATURE_LEN)?;
+
+        if data[2..10] != self.key_id {
+            return Err(SignifyError::KeyMismatch);
+        }
+
+        // The length was checked by `read_file`.
+        let signature = Signature::from_slice(&data[10..]).unwrap();
+
+        if ed25519::verify(signature, message, self.public) {
+            Ok(())
+        } else {
+            Err(SignifyError::InvalidSignature)
+        }
+    }
+}
+
+impl SignifySecretKey {
+    /// Generates a new key with a random key id, as `signify -G`.
+    pub fn generate() -> Self {
+        let mut key_id = [0u8; 8];
+        Csprng::new().fill_bytes(&mut key_id);
+
+        let (_, private) = ed25519::generate_keypair();
+
+        Self { key_id, private }
+    }
+
+    /// Returns the matching public key.
+    pub fn public_key(&self) -> SignifyPublicKey {
+        SignifyPublicKey {
+            key_id: self.key_id,
+            public: self.private.public_key(),
+        }
+    }
+
+    /// Encodes the key as a `.sec` file.
+    ///
+    /// With a password, the key is encrypted with 42 rounds of
+    /// bcrypt_pbkdf like `signify -G`; without one, it is stored in the
+    /// clear like `signify -G -n`. `signify -G` uses the comment
+    /// `signify secret key`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(file)` on success
+    /// - `Err(MissingSeed)` if the key was not expanded from a seed
+    /// - `Err(EmptyPassword)` or `Err(InvalidComment)` for invalid
+    ///   arguments
+    pub fn encode(&self, password: Option<&[u8]>, comment: &str) -> Result<String, SignifyError> {
+        if !file::is_single_line(comment) {
+            return Err(SignifyError::InvalidComment);
+        }
+
+        let mut seed = self.private.seed().ok_or(SignifyError::MissingSeed)?;
+
+        let mut secret = [0u8; 64];
+        secret[..32].copy_from_slice(&seed);
+        secret[32..].copy_from_slice(&self.private.public_key().to_bytes());
+        seed.fill(0);
+
+        let mut salt = [0u8; 16];
+        Csprng::new().fill_bytes(&mut salt);
+
+        let checksum: [u8; 8] = sha512(&secret)[..8].try_into().unwrap();
+
+        let rounds = if password.is_some() { KDF_ROUNDS } else { 0 };
+        let mut mask = derive_mask(password, &salt, rounds)?;
+
+        let mut data = Vec::with_capacity(SECRET_KEY_LEN);
+        data.extend_from_slice(PKALG);
+        data.extend_from_slice(KDFALG);
+        data.extend_from_slice(&rounds.to_be_bytes());
+        data.extend_from_slice(&salt);
+        data.extend_from_slice(&checksum);
+        data.extend_from_slice(&self.key_id);
+        data.extend(secret.iter().zip(&mask).map(|(s, m)| s ^ m));
+
+        let out = file::write(comment, &data);
+
+        secret.fill(0);
+        mask.fill(0);
+        data.fill(0);
+
+        Ok(out)
+    }
+
+    /// Decodes a `.sec` file, decrypting it with `password` if needed.
+    ///
+    /// The password is ignored for unencrypted keys.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok((key, comment))` on success
+    /// - `Err(PasswordRequired)` if the key is encrypted and no password
+    ///   was given
+    /// - `Err(WrongPassword)` if the decrypted key fails its checksum
+    /// - `Err(SignifyError)` if the file is malformed
+    ///
+    /// # Security Notes
+    ///
+    /// - The bcrypt_pbkdf rounds are read from the file, and rejected with
+    ///   `InvalidKdfParams` above [`MAX_KDF_ROUNDS`].
+    pub fn decode(input: &str, password: Option<&[u8]>) -> Result<(Self, String), SignifyError> {
+        let (comment, mut data) = read_file(input, SECRET_KEY_LEN)?;
+        let result = Self::from_data(&data, password);
+        data.fill(0);
+
+        Ok((result?, comment.to_string()))
+    }
+
+    /// Decodes the binary secret key structure.
+    fn from_data(data: &[u8], password: Option<&[u8]>) -> Result<Self, SignifyError> {
+        if &data[2..4] != KDFALG {
+            return Err(SignifyError::UnsupportedAlgorithm);
+        }
+
+        let rounds = u32::from_be_bytes(data[4..8].try_into().unwrap());
+        let salt = &data[8..24];
+        let checksum = &data[24..32];
+        let key_id: [u8; 8] = data[32..40].try_into().unwrap();
+
+        if rounds > MAX_KDF_ROUNDS {
+            return Err(SignifyError::InvalidKdfParams);
+        }
+
+        if rounds != 0 && password.is_none() {
+            return Err(SignifyError::PasswordRequired);
+        }
+
+        let mut mask = derive_mask(password, salt, rounds)?;
+        let mut secret = [0u8; 64];
+        for (s, (d, m)) in secret.iter_mut().zip(data[40..].iter().zip(&mask)) {
+            *s = d ^ m;
+        }
+        mask.fill(0);
+
+        let valid = sha512(&secret)[..8] == *checksum;
+        let private = PrivateKey::from_seed(secret[..32].try_into().unwrap());
+        let consistent = private.public_key().to_bytes()[..] == secret[32..];
+        secret.fill(0);
+
+        if !valid {
+            return Err(if rounds == 0 {
+                SignifyError::InvalidKey
+            } else {
+                SignifyError::WrongPassword
+            });
+        }
+
+        if !consistent {
+            return Err(SignifyError::InvalidKey);
+        }
+
+        Ok(Self { key_id, private })
+    }
+
+    /// Signs `message`, returning a detached `.sig` file as `signify -S`.
+    ///
+    /// `signify -S` uses the comment `verify with <public key file>`.
+    pub fn sign(&self, message: &[u8], comment: &str) -> Result<String, SignifyError> {
+        if !file::is_single_line(comment) {
+            return Err(SignifyError::InvalidComment);
+        }
+
+        let signature = ed25519::sign(message, self.private.public_key(), self.private);
+
+        let mut data = Vec::with_capacity(SIGNATURE_LEN);
+        data.extend_from_slice(PKALG);
+        data.extend_from_slice(&self.key_id);
+        data.extend_from_slice(&signature.to_bytes());
+
+        Ok(file::write(comment, &data))
+    }
+}
+
+/// Returns the XOR mask of the secret key: zero when `rounds` is zero,
+/// `bcrypt_pbkdf(password, salt, rounds)` otherwise.
+fn derive_mask(
+    password: Option<&[u8]>,
+    salt: &[u8],
+    rounds: u32,
+) -> Result<[u8; 64], SignifyError> {
+    let mut mask = [0u8; 64];
+
+    if let (Some(password), 1..) = (password, rounds) {
+        if password.is_empty() {
+            return Err(SignifyError::EmptyPassword);
+        }
+
+        // The salt and output lengths are fixed and valid.
+        bcrypt_pbkdf(password, salt, rounds, &mut mask).unwrap();
+    }
+
+    Ok(mask)
+}
+
+/// Reads a complete file whose payload is `len` bytes of Ed25519 data.
+fn read_file(input: &str, len: usize) -> Result<(&str, Vec<u8>), SignifyError> {
+    let (comment, data, rest) = file::read(input).ok_or(SignifyError::InvalidEncoding)?;
+
+    if data.len() != len || !rest.trim().is_empty() {
+        return Err(SignifyError::InvalidEncoding);
+    }
+
+    if &data[..2] != PKALG {
+        return Err(SignifyError::UnsupportedAlgorithm);
+    }
+
+    Ok((comment, data))
+}
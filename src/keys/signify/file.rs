@@ -0,0 +1,66 @@
+//! The `signify` file layout, also used by minisign:
+//!
+//! ```text
+//! untrusted comment: <comment>
+//! <base64 payload>
+//! ```
+//!
+//! Both lines end with a newline. The comment is not authenticated.
+
+use crate::encoding::base64::{self, Variant};
+
+/// Prefix of the first line of every file.
+pub(crate) const COMMENT_PREFIX: &str = "untrusted comment: ";
+
+/// Returns whether `comment` fits on a single line.
+pub(crate) fn is_single_line(comment: &str) -> bool {
+    !comment.contains(['\n', '\r'])
+}
+
+/// Appends `<prefix><text>\n` to `out`.
+pub(crate) fn push_line(out: &mut String, prefix: &str, text: &str) {
+    out.push_str(prefix);
+    out.push_str(text);
+    out.push('\n');
+}
+
+/// Encodes `data` with its untrusted comment.
+///
+/// The caller checks that the comment is a single line.
+pub(crate) fn write(comment: &str, data: &[u8]) -> String {
+    let mut out = String::with_capacity(COMMENT_PREFIX.len() + comment.len() + data.len() * 2);
+    push_line(&mut out, COMMENT_PREFIX, comment);
+    push_line(&mut out, "", &base64::encode(data, Variant::Standard));
+
+    out
+}
+
+/// Splits off the next line of `input`, without its LF or CRLF ending.
+///
+/// Returns `None` at the end of the input.
+pub(crate) fn next_line(input: &str) -> Option<(&str, &str)> {
+    if input.is_empty() {
+        return None;
+    }
+
+    let (line, rest) = input.split_once('\n').unwrap_or((input, ""));
+
+    Some((line.strip_suffix('\r').unwrap_or(line), rest))
+}
+
+/// Decodes the comment line and the Base64 line at the start of `input`.
+///
+/// # Returns
+///
+/// - `Some((comment, data, rest))` with the input following both lines
+/// - `None` if the comment prefix is missing or the payload is not
+///   Base64
+pub(crate) fn read(input: &str) -> Option<(&str, Vec<u8>, &str)> {
+    let (comment, rest) = next_line(input)?;
+    let comment = comment.strip_prefix(COMMENT_PREFIX)?;
+
+    let (encoded, rest) = next_line(rest)?;
+    let data = base64::decode(encoded, Variant::Standard).ok()?;
+
+    Some((comment, data, rest))
+}
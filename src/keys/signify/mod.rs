@@ -0,0 +1,26 @@
+//! OpenBSD `signify` keys and signatures.
+//!
+//! This module reads and writes the Ed25519 key and signature files of
+//! `signify(1)`, and signs and verifies detached signatures compatible with
+//! `signify -S` / `signify -V`:
+//!
+//! - public keys (`.pub` files),
+//! - secret keys, optionally encrypted with a password through
+//!   bcrypt_pbkdf,
+//! - detached signatures (`.sig` files).
+//!
+//! ## Structure
+//!
+//! - `core`  
+//!   Key and signature encodings, signing, verification and the error
+//!   type.
+//!
+//! - `file`  
+//!   The two-line `untrusted comment:` / Base64 layout, shared with the
+//!   `minisign` module.
+
+mod core;
+pub(crate) mod file;
+
+// Re-export the public API at the `signify` level.
+pub use core::*;
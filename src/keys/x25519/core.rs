@@ -1,4 +1,36 @@
 use crate::keys::ed25519::field::FieldElement;
+use crate::rng::Csprng;
+
+/// The X25519 base point (Montgomery u-coordinate `9`).
+///
+/// Multiplying this point by a private scalar yields the corresponding
+/// public key, as specified in RFC 7748.
+pub const BASEPOINT: [u8; 32] = [
+    9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+/// Generates a fresh X25519 keypair.
+///
+/// The private key is a 32-byte random string drawn from a CSPRNG. It is
+/// stored unclamped, exactly like RFC 7748 and libsodium private keys;
+/// clamping is applied on every use by [`exchange`].
+///
+/// Returns `(public, private)`, where `public` is the Montgomery
+/// u-coordinate of `private · 9`.
+pub fn generate_keypair() -> ([u8; 32], [u8; 32]) {
+    let mut private = [0u8; 32];
+    Csprng::new().fill_bytes(&mut private);
+
+    (public_key(&private), private)
+}
+
+/// Computes the X25519 public key associated with a private key.
+///
+/// This is the scalar multiplication of the base point [`BASEPOINT`] by
+/// the (clamped) private scalar.
+pub fn public_key(private: &[u8; 32]) -> [u8; 32] {
+    exchange(private, &BASEPOINT)
+}
 
 /// X25519 key exchange (RFC 7748 compatible).
 ///
@@ -9,7 +41,8 @@ use crate::keys::ed25519::field::FieldElement;
 ///
 /// - `private`: Local secret key material. The scalar is clamped as specified
 ///   by RFC 7748 before scalar multiplication.
-/// - `public`: Peer public key as a 32-byte Montgomery u-coordinate.
+/// - `public`: Peer public key as a 32-byte Montgomery u-coordinate. The most
+///   significant bit is ignored, as required by RFC 7748.
 ///
 /// ## Algorithm
 ///
@@ -41,13 +74,21 @@ pub fn exchange(private: &[u8; 32], public: &[u8; 32]) -> [u8; 32] {
     e[31] &= 127;
     e[31] |= 64;
 
-    let mut x1 = FieldElement::from_bytes(public);
+    let shared = ladder(&e, public);
+
+    e.fill(0);
+
+    shared
+}
 
-    // RFC 7748 decoding: u = (u + 1) / (1 - u)
-    let one = FieldElement::ONE;
-    let tmp0 = x1 + one;
-    let tmp1 = (one - x1).invert();
-    x1 = tmp0 * tmp1;
+/// Runs the Montgomery ladder over bits 0..=254 of `scalar`, without
+/// clamping.
+///
+/// Used by Ed25519 key exchange, whose secret scalar may already be reduced
+/// modulo `ℓ` and would be altered by clamping.
+pub(crate) fn ladder(scalar: &[u8; 32], public: &[u8; 32]) -> [u8; 32] {
+    let e = scalar;
+    let x1 = FieldElement::from_bytes(public);
 
     // Montgomery ladder ---
     let mut x2 = FieldElement::ONE;
@@ -70,20 +111,20 @@ pub fn exchange(private: &[u8; 32], public: &[u8; 32]) -> [u8; 32] {
         x2 = x2 + z2;
         z2 = x3 + z3;
 
-        let z3_new = tmp0 * x2;
-        let z2_new = z2 * tmp1;
+        z3 = tmp0 * x2;
+        z2 = z2 * tmp1;
 
         let tmp0 = tmp1.square();
         let tmp1 = x2.square();
 
-        x3 = z3_new + z2_new;
-        z2 = z3_new - z2_new;
+        x3 = z3 + z2;
+        z2 = z3 - z2;
         x2 = tmp1 * tmp0;
 
         let tmp1 = tmp1 - tmp0;
-        z2 = z2.square();
-        let mut z3 = tmp1.mul121666();
         x3 = x3.square();
+        z3 = tmp1.mul121666();
+        z2 = z2.square();
         let tmp0 = tmp0 + z3;
 
         z3 = x1 * z2;
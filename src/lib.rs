@@ -25,6 +25,10 @@
 //!   internal hashing. They are **not** designed to protect low-entropy,
 //!   human-provided secrets.
 //!
+//! - `encoding`  
+//!   Encodings (Base64, Bech32, DER, PEM, JSON) used by interoperable key,
+//!   file and token formats.
+//!
 //! - `primitives`  
 //!   Fixed-size, low-level cryptographic primitives such as `U256` and
 //!   `U512`. These types provide explicit, predictable semantics and are
@@ -68,6 +72,9 @@
 //!   - reconstruct identities across multiple devices without storing keys
 //!   - optionally impose a one-time computational cost (e.g. anti-Sybil)
 //!
+//!   scrypt and bcrypt_pbkdf are provided to open key files written by
+//!   other tools (minisign, signify).
+//!
 //!   Derivation functions are **never used in network hot paths** and are
 //!   strictly local to the machine performing the derivation.
 //!
@@ -85,6 +92,13 @@
 //!   storage, networking, or policy decisions. Those concerns are handled
 //!   at higher layers of the Nebula stack.
 //!
+//! - `protocol`  
+//!   Complete cryptographic protocols (e.g. Noise handshakes, JOSE
+//!   tokens) built on top of the primitives above.
+//!
+//!   Protocols own their state machines and exchange opaque byte
+//!   messages; networking and storage remain the caller's concern.
+//!
 //! # Design goals
 //!
 //! - Clear separation between hashing, key derivation, and key material
@@ -100,9 +114,11 @@
 mod utils;
 
 pub mod derivation;
+pub mod encoding;
 pub mod encryption;
 pub mod hash;
 pub mod keys;
 pub mod primitives;
+pub mod protocol;
 pub mod recovery;
 pub mod rng;
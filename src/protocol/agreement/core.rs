@@ -0,0 +1,607 @@
+//! X3DH key agreement with signed prekeys.
+//!
+//! Identity keys are Ed25519 keys: they sign the responder's prekeys and,
+//! mapped to their Montgomery form, take part in the X25519 exchanges.
+//! Prekeys are plain X25519 keys.
+//!
+//! ## Key agreement
+//!
+//! ```text
+//! DH1 = X25519(IK_A, SPK_B)
+//! DH2 = X25519(EK_A, IK_B)
+//! DH3 = X25519(EK_A, SPK_B)
+//! DH4 = X25519(EK_A, OPK_B)          (if a one-time prekey is used)
+//!
+//! SK = HKDF-SHA256(salt = 0^32, ikm = 0xFF^32 || DH1 || DH2 || DH3 [|| DH4],
+//!                  info = "Cryptal X3DH")
+//! AD = IK_A || IK_B
+//! ```
+//!
+//! The signed prekey signature covers `"Cryptal X3DH prekey" || id || SPK`.
+//!
+//! ## Encodings
+//!
+//! ```text
+//! bundle  = version (1) || flags (1) || IK (32) || SPK id (4) || SPK (32)
+//!           || signature (64) [|| OPK id (4) || OPK (32)]
+//! message = version (1) || flags (1) || IK_A (32) || EK_A (32)
+//!           || SPK id (4) [|| OPK id (4)]
+//! ```
+//!
+//! Bit 0 of `flags` signals a one-time prekey; ids are big-endian.
+//!
+//! ## Notes
+//!
+//! - `SK` and `AD` are meant to initialize a Double Ratchet session, with
+//!   the signed prekey as the responder's first ratchet key.
+//! - The responder must delete a one-time prekey once it has been used.
+//! - Identity keys are not authenticated by this module: they must be
+//!   verified out of band (e.g. by comparing fingerprints).
+
+use crate::derivation::hkdf::core::hkdf;
+use crate::keys::{ed25519, x25519};
+
+/// Length of X25519 and Ed25519 public keys.
+pub const KEY_LEN: usize = 32;
+
+/// Length of an Ed25519 signature.
+pub const SIGNATURE_LEN: usize = 64;
+
+/// Encoding format version.
+const VERSION: u8 = 1;
+
+/// Flag signalling a one-time prekey.
+const FLAG_ONE_TIME: u8 = 0x01;
+
+/// Length of an encoded bundle without a one-time prekey.
+const BUNDLE_LEN: usize = 2 + KEY_LEN + 4 + KEY_LEN + SIGNATURE_LEN;
+
+/// Length of an encoded initial message without a one-time prekey id.
+const MESSAGE_LEN: usize = 2 + KEY_LEN + KEY_LEN + 4;
+
+/// HKDF info string for the shared secret.
+const INFO: &[u8] = b"Cryptal X3DH";
+
+/// Domain separation label for signed prekey signatures.
+const PREKEY_LABEL: &[u8] = b"Cryptal X3DH prekey";
+
+/// Errors that can occur during an X3DH key agreement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum X3dhError {
+    /// The signed prekey signature does not verify.
+    InvalidSignature,
+    /// The bundle is malformed or unsupported, or its identity key is not
+    /// a curve point.
+    InvalidBundle,
+    /// The initial message is malformed or unsupported, or its identity
+    /// key is not a curve point.
+    InvalidMessage,
+    /// A public key yields the all-zero shared secret.
+    WeakPublicKey,
+    /// The message references prekeys that were not supplied.
+    UnknownPreKey,
+    /// The identity public key does not match its private key.
+    KeyMismatch,
+}
+
+/// A long-term Ed25519 identity key pair.
+#[derive(Clone, Copy)]
+pub struct IdentityKeyPair {
+    public: ed25519::PublicKey,
+    private: ed25519::PrivateKey,
+}
+
+impl IdentityKeyPair {
+    /// Generates a fresh random identity.
+    pub fn generate() -> Self {
+        let (public, private) = ed25519::generate_keypair();
+        Self { public, private }
+    }
+
+    /// Wraps an existing Ed25519 key pair.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(identity)` on success
+    /// - `Err(KeyMismatch)` if `public` is not the public key of `private`
+    pub fn from_keypair(
+        public: ed25519::PublicKey,
+        private: ed25519::PrivateKey,
+    ) -> Result<Self, X3dhError> {
+        if public.to_bytes() != private.public_key().to_bytes() {
+            return Err(X3dhError::KeyMismatch);
+        }
+
+        Ok(Self { public, private })
+    }
+
+    /// Returns the Ed25519 public key.
+    pub fn public_key(&self) -> [u8; 32] {
+        self.public.to_bytes()
+    }
+
+    /// Computes `X25519(IK, public)` with the identity scalar.
+    ///
+    /// The scalar is used unclamped, as in [`ed25519::exchange`], so keys
+    /// reduced modulo `ℓ` by [`ed25519::add_scalar`] keep matching their
+    /// public key.
+    fn exchange(&self, public: &[u8; 32]) -> [u8; 32] {
+        let mut scalar = self.private.scalar().to_bytes();
+        let shared = x25519::ladder(&scalar, public);
+        scalar.fill(0);
+        shared
+    }
+}
+
+/// A medium-term X25519 prekey, signed by an identity.
+///
+/// The private key is wiped when the prekey is dropped.
+pub struct SignedPreKey {
+    id: u32,
+    public: [u8; 32],
+    private: [u8; 32],
+    signature: [u8; 64],
+}
+
+impl SignedPreKey {
+    /// Generates a fresh signed prekey with the given id.
+    pub fn generate(identity: &IdentityKeyPair, id: u32) -> Self {
+        let (_, private) = x25519::generate_keypair();
+        Self::from_private(identity, id, private)
+    }
+
+    /// Rebuilds a signed prekey from its stored private key.
+    ///
+    /// Ed25519 signatures are deterministic, so the signature is identical
+    /// to the one originally published.
+    pub fn from_private(identity: &IdentityKeyPair, id: u32, private: [u8; 32]) -> Self {
+        let public = x25519::public_key(&private);
+        let signature = ed25519::sign(
+            &prekey_message(id, &public),
+            identity.public,
+            identity.private,
+        );
+
+        Self {
+            id,
+            public,
+            private,
+            signature: signature.to_bytes(),
+        }
+    }
+
+    /// Returns the prekey id.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the X25519 public key.
+    pub fn public_key(&self) -> [u8; 32] {
+        self.public
+    }
+
+    /// Returns the X25519 private key.
+    ///
+    /// The returned value is secret key material.
+    pub fn private_key(&self) -> [u8; 32] {
+        self.private
+    }
+
+    /// Returns the identity signature over the prekey.
+    pub fn signature(&self) -> [u8; 64] {
+        self.signature
+    }
+}
+
+impl Drop for SignedPreKey {
+    fn drop(&mut self) {
+        self.private.fill(0);
+    }
+}
+
+/// A single-use X25519 prekey.
+///
+/// The private key is wiped when the prekey is dropped.
+pub struct OneTimePreKey {
+    id: u32,
+    public: [u8; 32],
+    private: [u8; 32],
+}
+
+impl OneTimePreKey {
+    /// Generates a fresh one-time prekey with the given id.
+    pub fn generate(id: u32) -> Self {
+        let (_, private) = x25519::generate_keypair();
+        Self::from_private(id, private)
+    }
+
+    /// Rebuilds a one-time prekey from its stored private key.
+    pub fn from_private(id: u32, private: [u8; 32]) -> Self {
+        Self {
+            id,
+            public: x25519::public_key(&private),
+            private,
+        }
+    }
+
+    /// Returns the prekey id.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the X25519 public key.
+    pub fn public_key(&self) -> [u8; 32] {
+        self.public
+    }
+
+    /// Returns the X25519 private key.
+    ///
+    /// The returned value is secret key material.
+    pub fn private_key(&self) -> [u8; 32] {
+        self.private
+    }
+}
+
+impl Drop for OneTimePreKey {
+    fn drop(&mut self) {
+        self.private.fill(0);
+    }
+}
+
+/// The public keys a responder publishes for initiators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreKeyBundle {
+    /// Ed25519 identity key.
+    pub identity_key: [u8; 32],
+    /// Id of the signed prekey.
+    pub signed_prekey_id: u32,
+    /// X25519 signed prekey.
+    pub signed_prekey: [u8; 32],
+    /// Identity signature over the signed prekey.
+    pub signature: [u8; 64],
+    /// Optional one-time prekey, as `(id, public key)`.
+    pub one_time_prekey: Option<(u32, [u8; 32])>,
+}
+
+impl PreKeyBundle {
+    /// Assembles a bundle from the responder's keys.
+    pub fn new(
+        identity: &IdentityKeyPair,
+        signed_prekey: &SignedPreKey,
+        one_time_prekey: Option<&OneTimePreKey>,
+    ) -> Self {
+        Self {
+            identity_key: identity.public_key(),
+            signed_prekey_id: signed_prekey.id,
+            signed_prekey: signed_prekey.public,
+            signature: signed_prekey.signature,
+            one_time_prekey: one_time_prekey.map(|k| (k.id, k.public)),
+        }
+    }
+
+    /// Verifies the signed prekey signature.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the signature is valid
+    /// - `Err(InvalidBundle)` if the identity key is not a curve point
+    /// - `Err(InvalidSignature)` if the signature does not verify
+    pub fn verify(&self) -> Result<(), X3dhError> {
+        let identity = ed25519::PublicKey::from_bytes(self.identity_key)
+            .map_err(|_| X3dhError::InvalidBundle)?;
+
+        let valid = ed25519::verify(
+            ed25519::Signature::from_bytes(self.signature),
+            &prekey_message(self.signed_prekey_id, &self.signed_prekey),
+            identity,
+        );
+
+        if valid {
+            Ok(())
+        } else {
+            Err(X3dhError::InvalidSignature)
+        }
+    }
+
+    /// Encodes the bundle.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(BUNDLE_LEN + 4 + KEY_LEN);
+
+        out.push(VERSION);
+        out.push(if self.one_time_prekey.is_some() {
+            FLAG_ONE_TIME
+        } else {
+            0
+        });
+        out.extend_from_slice(&self.identity_key);
+        out.extend_from_slice(&self.signed_prekey_id.to_be_bytes());
+        out.extend_from_slice(&self.signed_prekey);
+        out.extend_from_slice(&self.signature);
+
+        if let Some((id, key)) = self.one_time_prekey {
+            out.extend_from_slice(&id.to_be_bytes());
+            out.extend_from_slice(&key);
+        }
+
+        out
+    }
+
+    /// Decodes a bundle.
+    ///
+    /// The signature is not checked here; see [`PreKeyBundle::verify`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, X3dhError> {
+        let one_time = match bytes.get(..2) {
+            Some([VERSION, 0]) => false,
+            Some([VERSION, FLAG_ONE_TIME]) => true,
+            _ => return Err(X3dhError::InvalidBundle),
+        };
+
+        let expected = if one_time {
+            BUNDLE_LEN + 4 + KEY_LEN
+        } else {
+            BUNDLE_LEN
+        };
+
+        if bytes.len() != expected {
+            return Err(X3dhError::InvalidBundle);
+        }
+
+        Ok(Self {
+            identity_key: bytes[2..34].try_into().unwrap(),
+            signed_prekey_id: u32::from_be_bytes(bytes[34..38].try_into().unwrap()),
+            signed_prekey: bytes[38..70].try_into().unwrap(),
+            signature: bytes[70..134].try_into().unwrap(),
+            one_time_prekey: one_time.then(|| {
+                (
+                    u32::from_be_bytes(bytes[134..138].try_into().unwrap()),
+                    bytes[138..170].try_into().unwrap(),
+                )
+            }),
+        })
+    }
+}
+
+/// The message an initiator sends so the responder can derive the secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InitialMessage {
+    /// Ed25519 identity key of the initiator.
+    pub identity_key: [u8; 32],
+    /// X25519 ephemeral key of the initiator.
+    pub ephemeral_key: [u8; 32],
+    /// Id of the signed prekey that was used.
+    pub signed_prekey_id: u32,
+    /// Id of the one-time prekey that was used, if any.
+    pub one_time_prekey_id: Option<u32>,
+}
+
+impl InitialMessage {
+    /// Encodes the message.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(MESSAGE_LEN + 4);
+
+        out.push(VERSION);
+        out.push(if self.one_time_prekey_id.is_some() {
+            FLAG_ONE_TIME
+        } else {
+            0
+        });
+        out.extend_from_slice(&self.identity_key);
+        out.extend_from_slice(&self.ephemeral_key);
+        out.extend_from_slice(&self.signed_prekey_id.to_be_bytes());
+
+        if let Some(id) = self.one_time_prekey_id {
+            out.extend_from_slice(&id.to_be_bytes());
+        }
+
+        out
+    }
+
+    /// Decodes a message.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, X3dhError> {
+        let one_time = match bytes.get(..2) {
+            Some([VERSION, 0]) => false,
+            Some([VERSION, FLAG_ONE_TIME]) => true,
+            _ => return Err(X3dhError::InvalidMessage),
+        };
+
+        let expected = if one_time {
+            MESSAGE_LEN + 4
+        } else {
+            MESSAGE_LEN
+        };
+
+        if bytes.len() != expected {
+            return Err(X3dhError::InvalidMessage);
+        }
+
+        Ok(Self {
+            identity_key: bytes[2..34].try_into().unwrap(),
+            ephemeral_key: bytes[34..66].try_into().unwrap(),
+            signed_prekey_id: u32::from_be_bytes(bytes[66..70].try_into().unwrap()),
+            one_time_prekey_id: one_time
+                .then(|| u32::from_be_bytes(bytes[70..74].try_into().unwrap())),
+        })
+    }
+}
+
+/// The result of a key agreement.
+///
+/// The shared secret is wiped when the agreement is dropped.
+pub struct Agreement {
+    shared_secret: [u8; 32],
+    associated_data: [u8; 64],
+}
+
+impl Agreement {
+    /// Returns the shared secret `SK`.
+    pub fn shared_secret(&self) -> &[u8; 32] {
+        &self.shared_secret
+    }
+
+    /// Returns the associated data `AD = IK_A || IK_B`.
+    pub fn associated_data(&self) -> &[u8; 64] {
+        &self.associated_data
+    }
+}
+
+impl Drop for Agreement {
+    fn drop(&mut self) {
+        self.shared_secret.fill(0);
+    }
+}
+
+/// Builds the message signed for a prekey.
+fn prekey_message(id: u32, public: &[u8; 32]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(PREKEY_LABEL.len() + 4 + KEY_LEN);
+    message.extend_from_slice(PREKEY_LABEL);
+    message.extend_from_slice(&id.to_be_bytes());
+    message.extend_from_slice(public);
+    message
+}
+
+/// Accumulates DH outputs and derives the agreement.
+struct KeyMaterial(Vec<u8>);
+
+impl KeyMaterial {
+    fn new() -> Self {
+        let mut ikm = Vec::with_capacity(KEY_LEN * 5);
+        ikm.extend_from_slice(&[0xFF; 32]);
+        Self(ikm)
+    }
+
+    /// Mixes in `X25519(private, public)`, rejecting the all-zero output
+    /// of low-order points.
+    fn exchange(&mut self, private: &[u8; 32], public: &[u8; 32]) -> Result<(), X3dhError> {
+        self.mix(x25519::exchange(private, public))
+    }
+
+    /// Mixes in a DH output, rejecting the all-zero output of low-order
+    /// points.
+    fn mix(&mut self, mut shared: [u8; 32]) -> Result<(), X3dhError> {
+        let mut acc = 0u8;
+        for b in shared {
+            acc |= b;
+        }
+
+        self.0.extend_from_slice(&shared);
+        shared.fill(0);
+
+        if acc == 0 {
+            return Err(X3dhError::WeakPublicKey);
+        }
+
+        Ok(())
+    }
+
+    fn derive(&self, initiator: &[u8; 32], responder: &[u8; 32]) -> Agreement {
+        let mut shared_secret = [0u8; 32];
+        hkdf(&[0u8; 32], &self.0, INFO, &mut shared_secret).unwrap();
+
+        let mut associated_data = [0u8; 64];
+        associated_data[..32].copy_from_slice(initiator);
+        associated_data[32..].copy_from_slice(responder);
+
+        Agreement {
+            shared_secret,
+            associated_data,
+        }
+    }
+}
+
+impl Drop for KeyMaterial {
+    fn drop(&mut self) {
+        self.0.fill(0);
+    }
+}
+
+/// Runs the initiator side of X3DH against a responder's bundle.
+///
+/// # Returns
+///
+/// - `Ok((message, agreement))`, where `message` must be sent to the
+///   responder
+/// - `Err(InvalidBundle)` if the identity key is not a curve point
+/// - `Err(InvalidSignature)` if the signed prekey signature does not verify
+/// - `Err(WeakPublicKey)` if a bundle key is a low-order point
+///
+/// # Security Notes
+///
+/// The bundle's identity key must be authenticated by the caller;
+/// otherwise the agreement may be with an impostor.
+pub fn initiate(
+    identity: &IdentityKeyPair,
+    bundle: &PreKeyBundle,
+) -> Result<(InitialMessage, Agreement), X3dhError> {
+    bundle.verify()?;
+
+    let responder_identity = ed25519::PublicKey::from_bytes(bundle.identity_key)
+        .map_err(|_| X3dhError::InvalidBundle)?
+        .to_montgomery();
+    let (ephemeral_key, mut ephemeral) = x25519::generate_keypair();
+
+    let mut ikm = KeyMaterial::new();
+    let result = ikm
+        .mix(identity.exchange(&bundle.signed_prekey))
+        .and_then(|_| ikm.exchange(&ephemeral, &responder_identity))
+        .and_then(|_| ikm.exchange(&ephemeral, &bundle.signed_prekey))
+        .and_then(|_| match &bundle.one_time_prekey {
+            Some((_, key)) => ikm.exchange(&ephemeral, key),
+            None => Ok(()),
+        });
+
+    ephemeral.fill(0);
+    result?;
+
+    let message = InitialMessage {
+        identity_key: identity.public_key(),
+        ephemeral_key,
+        signed_prekey_id: bundle.signed_prekey_id,
+        one_time_prekey_id: bundle.one_time_prekey.map(|(id, _)| id),
+    };
+
+    Ok((
+        message,
+        ikm.derive(&message.identity_key, &bundle.identity_key),
+    ))
+}
+
+/// Runs the responder side of X3DH for a received initial message.
+///
+/// `one_time_prekey` must be the prekey referenced by the message, if any.
+///
+/// # Returns
+///
+/// - `Ok(agreement)` on success
+/// - `Err(UnknownPreKey)` if the message references other prekeys
+/// - `Err(InvalidMessage)` if the initiator identity is not a curve point
+/// - `Err(WeakPublicKey)` if a message key is a low-order point
+pub fn respond(
+    identity: &IdentityKeyPair,
+    signed_prekey: &SignedPreKey,
+    one_time_prekey: Option<&OneTimePreKey>,
+    message: &InitialMessage,
+) -> Result<Agreement, X3dhError> {
+    if message.signed_prekey_id != signed_prekey.id
+        || message.one_time_prekey_id != one_time_prekey.map(|k| k.id)
+    {
+        return Err(X3dhError::UnknownPreKey);
+    }
+
+    let initiator_identity = ed25519::PublicKey::from_bytes(message.identity_key)
+        .map_err(|_| X3dhError::InvalidMessage)?
+        .to_montgomery();
+
+    let mut ikm = KeyMaterial::new();
+    let result = ikm
+        .exchange(&signed_prekey.private, &initiator_identity)
+        .and_then(|_| ikm.mix(identity.exchange(&message.ephemeral_key)))
+        .and_then(|_| ikm.exchange(&signed_prekey.private, &message.ephemeral_key))
+        .and_then(|_| match one_time_prekey {
+            Some(key) => ikm.exchange(&key.private, &message.ephemeral_key),
+            None => Ok(()),
+        });
+
+    result?;
+
+    Ok(ikm.derive(&message.identity_key, &identity.public_key()))
+}
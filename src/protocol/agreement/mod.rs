@@ -0,0 +1,14 @@
+//! X3DH key agreement.
+//!
+//! This module implements the Extended Triple Diffie-Hellman (X3DH) key
+//! agreement used by the Signal protocol to establish a shared secret with
+//! a peer that may be offline:
+//!
+//! <https://signal.org/docs/specifications/x3dh/>
+//!
+//! The responder publishes a prekey bundle in advance; the initiator uses
+//! it to derive a shared secret and sends a single initial message, from
+//! which the responder derives the same secret later.
+
+/// Public X3DH API.
+pub mod core;
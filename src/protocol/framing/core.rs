@@ -0,0 +1,395 @@
+//! Length-framed ChaCha20-Poly1305 streams.
+//!
+//! Each message is sent as a single frame:
+//!
+//! ```text
+//! header = type (1) || length (4, big-endian)
+//! frame  = header || ChaCha20-Poly1305(key, nonce, aad = header, payload)
+//! ```
+//!
+//! Nonces are 64-bit big-endian counters (see `encryption::nonce`), one
+//! sequence per direction, and both parties derive one key per direction
+//! from the session key:
+//!
+//! ```text
+//! key_i = HKDF-SHA256(salt = "", ikm = session key, info = "Cryptal SecureStream initiator")
+//! key_r = HKDF-SHA256(salt = "", ikm = session key, info = "Cryptal SecureStream responder")
+//! ```
+//!
+//! Every `rekey_interval` frames, each direction replaces its key with
+//! `HMAC-SHA256(key, "Cryptal SecureStream rekey")`, without any signalling.
+//!
+//! ## Security properties
+//!
+//! - Lengths and frame types are authenticated as associated data.
+//! - Implicit counter nonces make replayed, reordered or dropped frames
+//!   fail authentication.
+//! - The sender ends the stream with an authenticated close frame, so an
+//!   end of stream without it is reported as truncation.
+//!
+//! ## Notes
+//!
+//! - The underlying stream must be blocking: a failed read or write leaves
+//!   the framing out of sync, so that direction is disabled afterwards.
+//! - The rekey interval must be the same on both ends.
+//! - Data frames can be padded to hide message lengths (see
+//!   `encryption::padding`); padding must be enabled on both ends.
+
+use std::io::{self, Read, Write};
+
+use crate::derivation::hkdf::core::{hkdf, hmac_sha256};
+use crate::encryption::chacha20poly1305::{decrypt_with_aad, encrypt_with_aad};
+use crate::encryption::nonce::{CounterNonce, NONCE_LEN, NonceSequence, TAG_LEN};
+use crate::encryption::padding::{Padding, PaddingError, pad, padded_len, unpad};
+
+/// Length of a frame header.
+pub const HEADER_LEN: usize = 5;
+
+/// Maximum length of a single message.
+pub const MAX_MESSAGE_LEN: usize = 1 << 24;
+
+/// Default number of frames between two rekeys.
+pub const DEFAULT_REKEY_INTERVAL: u64 = 1 << 16;
+
+/// Frame carrying application data.
+const FRAME_DATA: u8 = 0;
+
+/// Frame ending the stream.
+const FRAME_CLOSE: u8 = 1;
+
+/// HKDF info string for the initiator's sending key.
+const INITIATOR_INFO: &[u8] = b"Cryptal SecureStream initiator";
+
+/// HKDF info string for the responder's sending key.
+const RESPONDER_INFO: &[u8] = b"Cryptal SecureStream responder";
+
+/// HMAC message used to rekey a direction.
+const REKEY_LABEL: &[u8] = b"Cryptal SecureStream rekey";
+
+/// Errors that can occur while using a secure stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamError {
+    /// The underlying stream failed.
+    Io(io::ErrorKind),
+    /// The message exceeds `MAX_MESSAGE_LEN`.
+    MessageTooLong,
+    /// A frame is malformed or of an unknown type.
+    InvalidFrame,
+    /// A frame failed authentication (tampered, replayed or reordered).
+    DecryptionFailed,
+    /// The stream ended without a close frame.
+    Truncated,
+    /// The stream was closed by this side.
+    Closed,
+    /// A previous error disabled this direction.
+    InvalidState,
+    /// The nonce counter is exhausted.
+    NonceExhausted,
+}
+
+impl From<io::Error> for StreamError {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::UnexpectedEof => StreamError::Truncated,
+            kind => StreamError::Io(kind),
+        }
+    }
+}
+
+/// The side of the session a stream endpoint plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// The party that initiated the session.
+    Initiator,
+    /// The party that accepted the session.
+    Responder,
+}
+
+/// Whether a direction can still be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Open,
+    Closed,
+    Failed,
+}
+
+/// Key and nonce sequence of one direction.
+struct Direction {
+    key: [u8; 32],
+    nonces: CounterNonce,
+    rekey_interval: u64,
+    status: Status,
+}
+
+impl Direction {
+    fn new(session_key: &[u8; 32], info: &[u8]) -> Self {
+        let mut key = [0u8; 32];
+        hkdf(&[], session_key, info, &mut key).unwrap();
+
+        Self {
+            key,
+            nonces: CounterNonce::new([0; 4]),
+            rekey_interval: DEFAULT_REKEY_INTERVAL,
+            status: Status::Open,
+        }
+    }
+
+    /// Returns the nonce of the next frame, rekeying first when an
+    /// interval boundary is reached.
+    fn next_nonce(&mut self) -> Result<[u8; NONCE_LEN], StreamError> {
+        let position = self.nonces.position().ok_or(StreamError::NonceExhausted)?;
+
+        if position != 0 && position.is_multiple_of(self.rekey_interval) {
+            let mut next = hmac_sha256(&self.key, REKEY_LABEL);
+            self.key.copy_from_slice(&next);
+            next.fill(0);
+        }
+
+        self.nonces
+            .advance()
+            .map_err(|_| StreamError::NonceExhausted)
+    }
+}
+
+impl Drop for Direction {
+    fn drop(&mut self) {
+        self.key.fill(0);
+    }
+}
+
+/// An encrypted, message-oriented channel over a byte stream.
+pub struct SecureStream<T: Read + Write> {
+    inner: T,
+    send: Direction,
+    recv: Direction,
+    padding: Padding,
+}
+
+impl<T: Read + Write> SecureStream<T> {
+    /// Wraps `inner` using a 32-byte session key shared with the peer.
+    ///
+    /// # Parameters
+    ///
+    /// - `inner`: the underlying blocking stream
+    /// - `session_key`: a uniformly random key known to both parties
+    /// - `role`: this side of the session; the peer must use the other
+    pub fn new(inner: T, session_key: &[u8; 32], role: Role) -> Self {
+        let initiator = Direction::new(session_key, INITIATOR_INFO);
+        let responder = Direction::new(session_key, RESPONDER_INFO);
+
+        let (send, recv) = match role {
+            Role::Initiator => (initiator, responder),
+            Role::Responder => (responder, initiator),
+        };
+
+        Self {
+            inner,
+            send,
+            recv,
+            padding: Padding::None,
+        }
+    }
+
+    /// Sets the number of frames between two rekeys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frames` is zero.
+    pub fn with_rekey_interval(mut self, frames: u64) -> Self {
+        assert!(frames > 0, "rekey interval must be non-zero");
+
+        self.send.rekey_interval = frames;
+        self.recv.rekey_interval = frames;
+        self
+    }
+
+    /// Pads every sent message with `padding`, and removes the padding of
+    /// received messages.
+    ///
+    /// The peer must enable padding as well, though not necessarily with
+    /// the same scheme.
+    ///
+    /// # Returns
+    ///
+    /// - `Err(InvalidBucket)` for a zero bucket size
+    pub fn with_padding(mut self, padding: Padding) -> Result<Self, PaddingError> {
+        padded_len(padding, 0)?;
+        self.padding = padding;
+
+        Ok(self)
+    }
+
+    /// Returns a reference to the underlying stream.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying stream.
+    ///
+    /// Reading from or writing to it directly corrupts the framing.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Unwraps the underlying stream.
+    pub fn into_inner(self) -> T {
+        let Self { inner, .. } = self;
+        inner
+    }
+
+    /// Encrypts and sends one message, then flushes the stream.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` on success
+    /// - `Err(MessageTooLong)` if the message, once padded, exceeds
+    ///   `MAX_MESSAGE_LEN`
+    /// - `Err(Closed)` if [`close`](Self::close) was called
+    /// - `Err(Io(_))` if the underlying stream fails
+    pub fn send(&mut self, message: &[u8]) -> Result<(), StreamError> {
+        if message.len() > MAX_MESSAGE_LEN {
+            return Err(StreamError::MessageTooLong);
+        }
+
+        if self.padding == Padding::None {
+            return self.write_frame(FRAME_DATA, message);
+        }
+
+        // The padding was validated by `with_padding`.
+        let mut padded = pad(self.padding, message).unwrap();
+
+        let result = if padded.len() > MAX_MESSAGE_LEN {
+            Err(StreamError::MessageTooLong)
+        } else {
+            self.write_frame(FRAME_DATA, &padded)
+        };
+        padded.fill(0);
+
+        result
+    }
+
+    /// Sends the close frame and flushes the stream.
+    ///
+    /// The peer's [`recv`](Self::recv) then returns `Ok(None)`. No message
+    /// can be sent afterwards, but messages can still be received.
+    pub fn close(&mut self) -> Result<(), StreamError> {
+        self.write_frame(FRAME_CLOSE, &[])?;
+        self.send.status = Status::Closed;
+
+        Ok(())
+    }
+
+    /// Receives and decrypts the next message.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(message))` for each message, in order
+    /// - `Ok(None)` once the peer has closed the stream
+    /// - `Err(Truncated)` if the stream ends without a close frame
+    /// - `Err(DecryptionFailed)` if a frame was tampered with, replayed,
+    ///   reordered or dropped
+    /// - `Err(InvalidFrame)` if padding is enabled and a message is not
+    ///   padded
+    ///
+    /// # Security Notes
+    ///
+    /// - After any error, every further call fails with `InvalidState`.
+    pub fn recv(&mut self) -> Result<Option<Vec<u8>>, StreamError> {
+        match self.recv.status {
+            Status::Open => {}
+            Status::Closed => return Ok(None),
+            Status::Failed => return Err(StreamError::InvalidState),
+        }
+
+        let result = self.read_frame();
+
+        self.recv.status = match result {
+            Ok(Some(_)) => Status::Open,
+            Ok(None) => Status::Closed,
+            Err(_) => Status::Failed,
+        };
+
+        result
+    }
+
+    fn write_frame(&mut self, kind: u8, payload: &[u8]) -> Result<(), StreamError> {
+        match self.send.status {
+            Status::Open => {}
+            Status::Closed => return Err(StreamError::Closed),
+            Status::Failed => return Err(StreamError::InvalidState),
+        }
+
+        let nonce = self.send.next_nonce()?;
+
+        let mut frame = vec![0u8; HEADER_LEN + payload.len() + TAG_LEN];
+        frame[0] = kind;
+        frame[1..HEADER_LEN].copy_from_slice(&(payload.len() as u32).to_be_bytes());
+
+        let (header, body) = frame.split_at_mut(HEADER_LEN);
+        let (ciphertext, tag) = body.split_at_mut(payload.len());
+        encrypt_with_aad(
+            &self.send.key,
+            &nonce,
+            header,
+            payload,
+            ciphertext,
+            tag.try_into().unwrap(),
+        );
+
+        let result = self
+            .inner
+            .write_all(&frame)
+            .and_then(|_| self.inner.flush());
+
+        if let Err(err) = result {
+            self.send.status = Status::Failed;
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+
+    fn read_frame(&mut self) -> Result<Option<Vec<u8>>, StreamError> {
+        let mut header = [0u8; HEADER_LEN];
+        self.inner.read_exact(&mut header)?;
+
+        let len = u32::from_be_bytes(header[1..].try_into().unwrap()) as usize;
+        if len > MAX_MESSAGE_LEN {
+            return Err(StreamError::InvalidFrame);
+        }
+
+        let mut body = vec![0u8; len + TAG_LEN];
+        self.inner.read_exact(&mut body)?;
+
+        let nonce = self.recv.next_nonce()?;
+        let (ciphertext, tag) = body.split_at(len);
+
+        let mut message = vec![0u8; len];
+        decrypt_with_aad(
+            &self.recv.key,
+            &nonce,
+            &header,
+            ciphertext,
+            tag.try_into().unwrap(),
+            &mut message,
+        )
+        .map_err(|_| StreamError::DecryptionFailed)?;
+
+        match (header[0], len) {
+            (FRAME_DATA, _) if self.padding != Padding::None => match unpad(&message) {
+                Ok(len) => {
+                    message[len..].fill(0);
+                    message.truncate(len);
+                    Ok(Some(message))
+                }
+                Err(_) => {
+                    message.fill(0);
+                    Err(StreamError::InvalidFrame)
+                }
+            },
+            (FRAME_DATA, _) => Ok(Some(message)),
+            (FRAME_CLOSE, 0) => Ok(None),
+            _ => Err(StreamError::InvalidFrame),
+        }
+    }
+}
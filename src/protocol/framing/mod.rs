@@ -0,0 +1,9 @@
+//! Encrypted framed transport.
+//!
+//! This module wraps a byte stream implementing `std::io::Read` and
+//! `std::io::Write` (e.g. a `TcpStream`) into an encrypted, message-oriented
+//! channel keyed by a session key established beforehand, e.g. with Noise
+//! or X3DH.
+
+/// Public secure stream API.
+pub mod core;
@@ -0,0 +1,146 @@
+//! Noise `CipherState` over ChaCha20-Poly1305.
+
+use super::core::NoiseError;
+use crate::encryption::chacha20poly1305::{decrypt_with_aad, encrypt_with_aad};
+
+/// Length of the Poly1305 tag.
+const TAG_LEN: usize = 16;
+
+/// Nonce reserved for rekeying; never used to encrypt messages.
+const REKEY_NONCE: u64 = u64::MAX;
+
+/// A ChaCha20-Poly1305 key with its 64-bit message counter.
+///
+/// A state without a key passes data through unchanged, as required
+/// during the first messages of a handshake.
+pub(crate) struct CipherState {
+    key: Option<[u8; 32]>,
+    n: u64,
+}
+
+impl CipherState {
+    /// Creates a state without a key.
+    pub(crate) fn empty() -> Self {
+        Self { key: None, n: 0 }
+    }
+
+    /// Creates a state with `key` and a zero counter.
+    pub(crate) fn new(key: [u8; 32]) -> Self {
+        Self {
+            key: Some(key),
+            n: 0,
+        }
+    }
+
+    /// Returns whether a key is set.
+    pub(crate) fn has_key(&self) -> bool {
+        self.key.is_some()
+    }
+
+    /// Encodes a counter as a ChaCha20-Poly1305 nonce (32 zero bits,
+    /// then the little-endian counter).
+    fn nonce(n: u64) -> [u8; 12] {
+        let mut nonce = [0u8; 12];
+        nonce[4..].copy_from_slice(&n.to_le_bytes());
+        nonce
+    }
+
+    /// Encrypts `plaintext` with `ad` and increments the counter.
+    pub(crate) fn encrypt_with_ad(
+        &mut self,
+        ad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, NoiseError> {
+        let Some(key) = &self.key else {
+            return Ok(plaintext.to_vec());
+        };
+
+        if self.n == REKEY_NONCE {
+            return Err(NoiseError::NonceExhausted);
+        }
+
+        let mut out = vec![0u8; plaintext.len() + TAG_LEN];
+        let (body, tag) = out.split_at_mut(plaintext.len());
+        encrypt_with_aad(
+            key,
+            &Self::nonce(self.n),
+            ad,
+            plaintext,
+            body,
+            tag.try_into().unwrap(),
+        );
+
+        self.n += 1;
+
+        Ok(out)
+    }
+
+    /// Decrypts `ciphertext` with `ad` and increments the counter.
+    ///
+    /// The counter is left unchanged if authentication fails.
+    pub(crate) fn decrypt_with_ad(
+        &mut self,
+        ad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, NoiseError> {
+        let Some(key) = &self.key else {
+            return Ok(ciphertext.to_vec());
+        };
+
+        if self.n == REKEY_NONCE {
+            return Err(NoiseError::NonceExhausted);
+        }
+
+        if ciphertext.len() < TAG_LEN {
+            return Err(NoiseError::InvalidMessage);
+        }
+
+        let (body, tag) = ciphertext.split_at(ciphertext.len() - TAG_LEN);
+        let mut out = vec![0u8; body.len()];
+
+        decrypt_with_aad(
+            key,
+            &Self::nonce(self.n),
+            ad,
+            body,
+            tag.try_into().unwrap(),
+            &mut out,
+        )
+        .map_err(|_| NoiseError::DecryptionFailed)?;
+
+        self.n += 1;
+
+        Ok(out)
+    }
+
+    /// Replaces the key with `ENCRYPT(k, 2^64 - 1, "", zeros)[..32]`.
+    ///
+    /// The counter is not reset.
+    pub(crate) fn rekey(&mut self) {
+        let Some(key) = &mut self.key else {
+            return;
+        };
+
+        let mut next = [0u8; 32];
+        let mut tag = [0u8; TAG_LEN];
+        encrypt_with_aad(
+            key,
+            &Self::nonce(REKEY_NONCE),
+            &[],
+            &[0u8; 32],
+            &mut next,
+            &mut tag,
+        );
+
+        key.copy_from_slice(&next);
+        next.fill(0);
+    }
+}
+
+impl Drop for CipherState {
+    fn drop(&mut self) {
+        if let Some(key) = &mut self.key {
+            key.fill(0);
+        }
+    }
+}
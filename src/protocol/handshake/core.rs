@@ -0,0 +1,652 @@
+//! Noise Protocol Framework handshakes (revision 34).
+//!
+//! This module implements the `N`, `NK`, `XX` and `IK` handshake patterns
+//! with the `25519` DH function, the `ChaChaPoly` cipher and either the
+//! `SHA256` or `BLAKE2b` hash function, optionally with `psk` modifiers.
+//!
+//! ## Patterns
+//!
+//! ```text
+//! N:             NK:            XX:                  IK:
+//!   <- s           <- s           -> e                 <- s
+//!   ...            ...            <- e, ee, s, es      ...
+//!   -> e, es       -> e, es       -> s, se             -> e, es, s, ss
+//!                  <- e, ee                            <- e, ee, se
+//! ```
+//!
+//! ## Usage
+//!
+//! 1. Build `NoiseParams` from a protocol name such as
+//!    `Noise_XX_25519_ChaChaPoly_SHA256`.
+//! 2. Create a `HandshakeState` as initiator or responder and exchange
+//!    messages with `write_message` / `read_message` in turn.
+//! 3. Once `HandshakeState::is_finished`, convert it into a
+//!    `TransportState` to exchange encrypted transport messages.
+//!
+//! ## Notes
+//!
+//! - Messages are limited to 65535 bytes, as required by Noise.
+//! - A handshake must be abandoned after any error while reading.
+//! - Transport messages must be delivered in order; each direction has
+//!   its own 64-bit counter.
+//! - Both sides can rekey a direction with `rekey_outgoing` /
+//!   `rekey_incoming`, which must be coordinated by the application.
+
+use super::cipher::CipherState;
+use super::pattern::{Token, with_psks};
+use super::symmetric::SymmetricState;
+use crate::keys::x25519;
+
+/// Length of X25519 public keys and shared secrets (`DHLEN`).
+pub const DH_LEN: usize = 32;
+
+/// Length of the Poly1305 tag added to encrypted fields.
+pub const TAG_LEN: usize = 16;
+
+/// Length of pre-shared keys.
+pub const PSK_LEN: usize = 32;
+
+/// Maximum length of a Noise message.
+pub const MAX_MESSAGE_LEN: usize = 65535;
+
+/// Errors that can occur during a Noise handshake or transport.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoiseError {
+    /// The protocol name or parameters are not supported.
+    UnsupportedProtocol,
+    /// A static key or pre-shared key required by the pattern is missing.
+    MissingKey,
+    /// The operation is not allowed in the current state (wrong turn,
+    /// unfinished or failed handshake, one-way transport).
+    InvalidState,
+    /// The message would exceed `MAX_MESSAGE_LEN`.
+    MessageTooLong,
+    /// The message is truncated or malformed.
+    InvalidMessage,
+    /// An encrypted field failed authentication.
+    DecryptionFailed,
+    /// The message counter is exhausted; the session must be replaced.
+    NonceExhausted,
+}
+
+/// Supported handshake patterns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandshakePattern {
+    /// One-way pattern to a known responder.
+    N,
+    /// Anonymous initiator, known responder.
+    NK,
+    /// Mutual authentication with static keys transmitted encrypted.
+    XX,
+    /// Mutual authentication with a known responder, in one round trip.
+    IK,
+}
+
+/// Supported hash functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashChoice {
+    /// SHA-256 (`HASHLEN` = 32).
+    Sha256,
+    /// BLAKE2b-512 (`HASHLEN` = 64).
+    Blake2b,
+}
+
+/// A complete Noise protocol: pattern, PSK modifiers and hash function.
+///
+/// The DH function is always `25519` and the cipher `ChaChaPoly`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoiseParams {
+    pattern: HandshakePattern,
+    psks: Vec<u8>,
+    hash: HashChoice,
+}
+
+impl NoiseParams {
+    /// Creates parameters without PSK modifiers.
+    pub fn new(pattern: HandshakePattern, hash: HashChoice) -> Self {
+        Self {
+            pattern,
+            psks: Vec::new(),
+            hash,
+        }
+    }
+
+    /// Adds a `psk<position>` modifier.
+    ///
+    /// # Returns
+    ///
+    /// - `Err(UnsupportedProtocol)` if `position` exceeds the number of
+    ///   messages of the pattern, or is not greater than the previous one
+    pub fn with_psk(mut self, position: u8) -> Result<Self, NoiseError> {
+        let messages = self.pattern.messages().len() as u8;
+
+        if position > messages || self.psks.last().is_some_and(|&last| position <= last) {
+            return Err(NoiseError::UnsupportedProtocol);
+        }
+
+        self.psks.push(position);
+
+        Ok(self)
+    }
+
+    /// Parses a protocol name such as `Noise_IKpsk2_25519_ChaChaPoly_BLAKE2b`.
+    pub fn parse(name: &str) -> Result<Self, NoiseError> {
+        let parts: Vec<&str> = name.split('_').collect();
+
+        let ["Noise", handshake, "25519", "ChaChaPoly", hash] = parts[..] else {
+            return Err(NoiseError::UnsupportedProtocol);
+        };
+
+        let hash = match hash {
+            "SHA256" => HashChoice::Sha256,
+            "BLAKE2b" => HashChoice::Blake2b,
+            _ => return Err(NoiseError::UnsupportedProtocol),
+        };
+
+        // The pattern name is the leading run of uppercase letters.
+        let split = handshake
+            .find(|c: char| !c.is_ascii_uppercase())
+            .unwrap_or(handshake.len());
+        let (pattern, modifiers) = handshake.split_at(split);
+
+        let pattern = match pattern {
+            "N" => HandshakePattern::N,
+            "NK" => HandshakePattern::NK,
+            "XX" => HandshakePattern::XX,
+            "IK" => HandshakePattern::IK,
+            _ => return Err(NoiseError::UnsupportedProtocol),
+        };
+
+        let mut params = Self::new(pattern, hash);
+
+        if !modifiers.is_empty() {
+            for modifier in modifiers.split('+') {
+                let position = modifier
+                    .strip_prefix("psk")
+                    .filter(|n| n.len() == 1)
+                    .and_then(|n| n.parse::<u8>().ok())
+                    .ok_or(NoiseError::UnsupportedProtocol)?;
+
+                params = params.with_psk(position)?;
+            }
+        }
+
+        Ok(params)
+    }
+
+    /// Returns the canonical protocol name.
+    pub fn name(&self) -> String {
+        let pattern = match self.pattern {
+            HandshakePattern::N => "N",
+            HandshakePattern::NK => "NK",
+            HandshakePattern::XX => "XX",
+            HandshakePattern::IK => "IK",
+        };
+
+        let modifiers: Vec<String> = self.psks.iter().map(|p| format!("psk{p}")).collect();
+
+        let hash = match self.hash {
+            HashChoice::Sha256 => "SHA256",
+            HashChoice::Blake2b => "BLAKE2b",
+        };
+
+        format!(
+            "Noise_{pattern}{}_25519_ChaChaPoly_{hash}",
+            modifiers.join("+")
+        )
+    }
+
+    /// Returns the handshake pattern.
+    pub fn pattern(&self) -> HandshakePattern {
+        self.pattern
+    }
+
+    /// Returns the hash function.
+    pub fn hash(&self) -> HashChoice {
+        self.hash
+    }
+}
+
+/// Keys and prologue of one handshake participant.
+///
+/// Fields not required by the pattern are ignored.
+#[derive(Default)]
+pub struct HandshakeConfig<'a> {
+    /// Data both parties must agree on, authenticated by the handshake.
+    pub prologue: &'a [u8],
+    /// Local static X25519 private key.
+    pub local_static: Option<[u8; 32]>,
+    /// Remote static X25519 public key, for patterns where it is known
+    /// in advance.
+    pub remote_static: Option<[u8; 32]>,
+    /// Pre-shared keys, one per `psk` modifier, in order.
+    pub psks: &'a [[u8; PSK_LEN]],
+    /// Fixed ephemeral X25519 private key.
+    ///
+    /// Only intended for test vectors; a fresh random ephemeral key is
+    /// generated when `None`.
+    pub local_ephemeral: Option<[u8; 32]>,
+}
+
+/// An X25519 key pair whose private half is wiped on drop.
+struct KeyPair {
+    private: [u8; 32],
+    public: [u8; 32],
+}
+
+impl KeyPair {
+    fn new(private: [u8; 32]) -> Self {
+        Self {
+            public: x25519::public_key(&private),
+            private,
+        }
+    }
+}
+
+impl Drop for KeyPair {
+    fn drop(&mut self) {
+        self.private.fill(0);
+    }
+}
+
+/// A Noise handshake in progress.
+pub struct HandshakeState {
+    symmetric: SymmetricState,
+    pattern: HandshakePattern,
+    initiator: bool,
+    s: Option<KeyPair>,
+    e: Option<KeyPair>,
+    fixed_ephemeral: Option<[u8; 32]>,
+    rs: Option<[u8; 32]>,
+    re: Option<[u8; 32]>,
+    psks: Vec<[u8; PSK_LEN]>,
+    psk_mode: bool,
+    messages: Vec<Vec<Token>>,
+    index: usize,
+    failed: bool,
+}
+
+impl HandshakeState {
+    /// Starts a handshake as the initiator.
+    ///
+    /// # Returns
+    ///
+    /// - `Err(MissingKey)` if the pattern requires a key or PSK that is
+    ///   not in `config`
+    pub fn initiator(params: &NoiseParams, config: HandshakeConfig) -> Result<Self, NoiseError> {
+        Self::new(params, config, true)
+    }
+
+    /// Starts a handshake as the responder.
+    ///
+    /// # Returns
+    ///
+    /// - `Err(MissingKey)` if the pattern requires a key or PSK that is
+    ///   not in `config`
+    pub fn responder(params: &NoiseParams, config: HandshakeConfig) -> Result<Self, NoiseError> {
+        Self::new(params, config, false)
+    }
+
+    fn new(
+        params: &NoiseParams,
+        config: HandshakeConfig,
+        initiator: bool,
+    ) -> Result<Self, NoiseError> {
+        let pattern = params.pattern;
+
+        let needs_static = !initiator || pattern.initiator_static();
+        let needs_remote = initiator && pattern.responder_static_known();
+
+        if (needs_static && config.local_static.is_none())
+            || (needs_remote && config.remote_static.is_none())
+            || config.psks.len() != params.psks.len()
+        {
+            return Err(NoiseError::MissingKey);
+        }
+
+        let mut symmetric = SymmetricState::new(params.hash, params.name().as_bytes());
+        symmetric.mix_hash(config.prologue);
+
+        let s = config
+            .local_static
+            .filter(|_| needs_static)
+            .map(KeyPair::new);
+        let rs = config.remote_static.filter(|_| needs_remote);
+
+        // Pre-message: the responder's static key.
+        if pattern.responder_static_known() {
+            match (&s, &rs) {
+                (Some(s), _) if !initiator => symmetric.mix_hash(&s.public),
+                (_, Some(rs)) => symmetric.mix_hash(rs),
+                _ => unreachable!(),
+            }
+        }
+
+        Ok(Self {
+            symmetric,
+            pattern,
+            initiator,
+            s,
+            e: None,
+            fixed_ephemeral: config.local_ephemeral,
+            rs,
+            re: None,
+            psks: config.psks.to_vec(),
+            psk_mode: !params.psks.is_empty(),
+            messages: with_psks(pattern, &params.psks),
+            index: 0,
+            failed: false,
+        })
+    }
+
+    /// Returns whether all handshake messages have been processed.
+    pub fn is_finished(&self) -> bool {
+        self.index == self.messages.len()
+    }
+
+    /// Returns whether it is this party's turn to write a message.
+    pub fn is_my_turn(&self) -> bool {
+        !self.is_finished() && self.index.is_multiple_of(2) == self.initiator
+    }
+
+    /// Returns the current handshake hash.
+    ///
+    /// Once the handshake is finished, this value uniquely identifies the
+    /// session and can be used for channel binding.
+    pub fn handshake_hash(&self) -> &[u8] {
+        self.symmetric.handshake_hash()
+    }
+
+    /// Returns the remote static public key, if known.
+    pub fn remote_static(&self) -> Option<[u8; 32]> {
+        self.rs
+    }
+
+    /// Computes the exact length of the next message for `payload_len`.
+    fn message_len(&self, tokens: &[Token], payload_len: usize) -> usize {
+        let mut has_key = self.symmetric.has_key();
+        let mut len = payload_len;
+
+        for token in tokens {
+            match token {
+                Token::E => {
+                    len += DH_LEN;
+                    has_key |= self.psk_mode;
+                }
+                Token::S => len += DH_LEN + if has_key { TAG_LEN } else { 0 },
+                _ => has_key = true,
+            }
+        }
+
+        len + if has_key { TAG_LEN } else { 0 }
+    }
+
+    /// Performs the DH of a `ee`/`es`/`se`/`ss` token and mixes it in.
+    fn mix_dh(&mut self, token: Token) -> Result<(), NoiseError> {
+        // Which local and remote keys are used, from the initiator's view.
+        let (local_static, remote_static) = match token {
+            Token::Ee => (false, false),
+            Token::Es => (false, true),
+            Token::Se => (true, false),
+            _ => (true, true),
+        };
+
+        // The responder mirrors the initiator's keys.
+        let (local_static, remote_static) = if self.initiator {
+            (local_static, remote_static)
+        } else {
+            (remote_static, local_static)
+        };
+
+        let local = if local_static { &self.s } else { &self.e };
+        let remote = if remote_static { self.rs } else { self.re };
+
+        let (Some(local), Some(remote)) = (local, remote) else {
+            return Err(NoiseError::InvalidState);
+        };
+
+        let mut shared = x25519::exchange(&local.private, &remote);
+        self.symmetric.mix_key(&shared);
+        shared.fill(0);
+
+        Ok(())
+    }
+
+    /// Writes the next handshake message carrying `payload`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(message)` to send to the peer
+    /// - `Err(InvalidState)` if it is not this party's turn
+    /// - `Err(MessageTooLong)` if the message would exceed
+    ///   `MAX_MESSAGE_LEN`; the state is left unchanged
+    pub fn write_message(&mut self, payload: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        if self.failed || !self.is_my_turn() {
+            return Err(NoiseError::InvalidState);
+        }
+
+        let tokens = self.messages[self.index].clone();
+
+        if self.message_len(&tokens, payload.len()) > MAX_MESSAGE_LEN {
+            return Err(NoiseError::MessageTooLong);
+        }
+
+        let mut out = Vec::new();
+
+        for token in tokens {
+            match token {
+                Token::E => {
+                    let e = match self.fixed_ephemeral.take() {
+                        Some(private) => KeyPair::new(private),
+                        None => KeyPair::new(x25519::generate_keypair().1),
+                    };
+
+                    out.extend_from_slice(&e.public);
+                    self.symmetric.mix_hash(&e.public);
+                    if self.psk_mode {
+                        self.symmetric.mix_key(&e.public);
+                    }
+
+                    self.e = Some(e);
+                }
+                Token::S => {
+                    let public = self.s.as_ref().ok_or(NoiseError::MissingKey)?.public;
+                    out.extend(self.symmetric.encrypt_and_hash(&public)?);
+                }
+                Token::Psk => self.mix_psk(),
+                dh => self.mix_dh(dh)?,
+            }
+        }
+
+        out.extend(self.symmetric.encrypt_and_hash(payload)?);
+        self.index += 1;
+
+        Ok(out)
+    }
+
+    /// Reads the next handshake message and returns its payload.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(payload)` on success
+    /// - `Err(InvalidState)` if it is not the peer's turn
+    /// - `Err(MessageTooLong)` / `Err(InvalidMessage)` for malformed input
+    /// - `Err(DecryptionFailed)` if an encrypted field does not
+    ///   authenticate
+    ///
+    /// After any error other than `InvalidState`, the handshake is failed
+    /// and every further call returns `InvalidState`.
+    pub fn read_message(&mut self, message: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        if self.failed || self.is_finished() || self.is_my_turn() {
+            return Err(NoiseError::InvalidState);
+        }
+
+        let result = self.read_tokens(message);
+        self.failed = result.is_err();
+
+        result
+    }
+
+    fn read_tokens(&mut self, message: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        if message.len() > MAX_MESSAGE_LEN {
+            return Err(NoiseError::MessageTooLong);
+        }
+
+        let tokens = self.messages[self.index].clone();
+        let mut rest = message;
+
+        for token in tokens {
+            match token {
+                Token::E => {
+                    let re = take(&mut rest, DH_LEN)?;
+                    self.symmetric.mix_hash(re);
+                    if self.psk_mode {
+                        self.symmetric.mix_key(re);
+                    }
+
+                    self.re = Some(re.try_into().unwrap());
+                }
+                Token::S => {
+                    let len = DH_LEN + if self.symmetric.has_key() { TAG_LEN } else { 0 };
+                    let rs = self.symmetric.decrypt_and_hash(take(&mut rest, len)?)?;
+
+                    self.rs = Some(rs.try_into().unwrap());
+                }
+                Token::Psk => self.mix_psk(),
+                dh => self.mix_dh(dh)?,
+            }
+        }
+
+        let payload = self.symmetric.decrypt_and_hash(rest)?;
+        self.index += 1;
+
+        Ok(payload)
+    }
+
+    /// Mixes the next pre-shared key.
+    fn mix_psk(&mut self) {
+        let mut psk = self.psks.remove(0);
+        self.symmetric.mix_key_and_hash(&psk);
+        psk.fill(0);
+    }
+
+    /// Completes the handshake and returns the transport state.
+    ///
+    /// # Returns
+    ///
+    /// - `Err(InvalidState)` if the handshake is not finished
+    pub fn into_transport(self) -> Result<TransportState, NoiseError> {
+        if self.failed || !self.is_finished() {
+            return Err(NoiseError::InvalidState);
+        }
+
+        let (c1, c2) = self.symmetric.split();
+        let (send, recv) = if self.initiator { (c1, c2) } else { (c2, c1) };
+
+        Ok(TransportState {
+            send,
+            recv,
+            handshake_hash: self.symmetric.handshake_hash().to_vec(),
+            remote_static: self.rs,
+            can_send: self.initiator || !self.pattern.is_one_way(),
+            can_recv: !self.initiator || !self.pattern.is_one_way(),
+        })
+    }
+}
+
+/// Takes the next `len` bytes of `rest`.
+fn take<'a>(rest: &mut &'a [u8], len: usize) -> Result<&'a [u8], NoiseError> {
+    if rest.len() < len {
+        return Err(NoiseError::InvalidMessage);
+    }
+
+    let (head, tail) = rest.split_at(len);
+    *rest = tail;
+
+    Ok(head)
+}
+
+impl Drop for HandshakeState {
+    fn drop(&mut self) {
+        for psk in &mut self.psks {
+            psk.fill(0);
+        }
+        if let Some(e) = &mut self.fixed_ephemeral {
+            e.fill(0);
+        }
+    }
+}
+
+/// An established Noise session.
+pub struct TransportState {
+    send: CipherState,
+    recv: CipherState,
+    handshake_hash: Vec<u8>,
+    remote_static: Option<[u8; 32]>,
+    can_send: bool,
+    can_recv: bool,
+}
+
+impl TransportState {
+    /// Encrypts a transport message.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(message)` of `payload.len() + TAG_LEN` bytes
+    /// - `Err(InvalidState)` for the responder of a one-way pattern
+    /// - `Err(MessageTooLong)` if the message would exceed
+    ///   `MAX_MESSAGE_LEN`
+    /// - `Err(NonceExhausted)` after 2^64 - 1 messages
+    pub fn write_message(&mut self, payload: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        if !self.can_send {
+            return Err(NoiseError::InvalidState);
+        }
+
+        if payload.len() + TAG_LEN > MAX_MESSAGE_LEN {
+            return Err(NoiseError::MessageTooLong);
+        }
+
+        self.send.encrypt_with_ad(&[], payload)
+    }
+
+    /// Decrypts a transport message.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(payload)` on success
+    /// - `Err(InvalidState)` for the initiator of a one-way pattern
+    /// - `Err(MessageTooLong)` / `Err(InvalidMessage)` for malformed input
+    /// - `Err(DecryptionFailed)` if the message does not authenticate, or
+    ///   was replayed, reordered or dropped
+    pub fn read_message(&mut self, message: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        if !self.can_recv {
+            return Err(NoiseError::InvalidState);
+        }
+
+        if message.len() > MAX_MESSAGE_LEN {
+            return Err(NoiseError::MessageTooLong);
+        }
+
+        self.recv.decrypt_with_ad(&[], message)
+    }
+
+    /// Rekeys the sending direction.
+    pub fn rekey_outgoing(&mut self) {
+        self.send.rekey();
+    }
+
+    /// Rekeys the receiving direction.
+    pub fn rekey_incoming(&mut self) {
+        self.recv.rekey();
+    }
+
+    /// Returns the final handshake hash.
+    pub fn handshake_hash(&self) -> &[u8] {
+        &self.handshake_hash
+    }
+
+    /// Returns the remote static public key, if the pattern provided one.
+    pub fn remote_static(&self) -> Option<[u8; 32]> {
+        self.remote_static
+    }
+}
@@ -0,0 +1,99 @@
+//! Noise hash functions, HMAC and HKDF.
+//!
+//! Noise defines its own HKDF on top of HMAC with chaining keys of
+//! `HASHLEN` bytes:
+//!
+//! ```text
+//! temp = HMAC(ck, ikm)
+//! out1 = HMAC(temp, 0x01)
+//! out2 = HMAC(temp, out1 || 0x02)
+//! out3 = HMAC(temp, out2 || 0x03)
+//! ```
+
+use super::core::HashChoice;
+use crate::hash::blake2b::core::Blake2b;
+use crate::hash::sha256::core::Sha256;
+
+impl HashChoice {
+    /// Output length of the hash function (`HASHLEN`).
+    pub(crate) fn hash_len(self) -> usize {
+        match self {
+            HashChoice::Sha256 => 32,
+            HashChoice::Blake2b => 64,
+        }
+    }
+
+    /// Internal block length of the hash function (`BLOCKLEN`).
+    fn block_len(self) -> usize {
+        match self {
+            HashChoice::Sha256 => 64,
+            HashChoice::Blake2b => 128,
+        }
+    }
+
+    /// Hashes the concatenation of `parts`.
+    pub(crate) fn hash(self, parts: &[&[u8]]) -> Vec<u8> {
+        match self {
+            HashChoice::Sha256 => {
+                let mut state = Sha256::new();
+                for part in parts {
+                    state.update(part);
+                }
+                state.finalize().to_vec()
+            }
+            HashChoice::Blake2b => {
+                let mut state = Blake2b::new(64, &[]);
+                for part in parts {
+                    state.update(part);
+                }
+                state.finalize().to_vec()
+            }
+        }
+    }
+
+    /// Computes HMAC over the concatenation of `parts`.
+    pub(crate) fn hmac(self, key: &[u8], parts: &[&[u8]]) -> Vec<u8> {
+        let mut k = vec![0u8; self.block_len()];
+
+        if key.len() > k.len() {
+            let digest = self.hash(&[key]);
+            k[..digest.len()].copy_from_slice(&digest);
+        } else {
+            k[..key.len()].copy_from_slice(key);
+        }
+
+        let mut ipad: Vec<u8> = k.iter().map(|b| b ^ 0x36).collect();
+        let mut opad: Vec<u8> = k.iter().map(|b| b ^ 0x5c).collect();
+        k.fill(0);
+
+        let mut inner_parts = vec![&ipad[..]];
+        inner_parts.extend_from_slice(parts);
+
+        let mut inner = self.hash(&inner_parts);
+        let out = self.hash(&[&opad, &inner]);
+
+        ipad.fill(0);
+        opad.fill(0);
+        inner.fill(0);
+
+        out
+    }
+
+    /// Noise HKDF returning `outputs` (2 or 3) values of `HASHLEN` bytes.
+    pub(crate) fn hkdf(self, chaining_key: &[u8], ikm: &[u8], outputs: usize) -> Vec<Vec<u8>> {
+        let mut temp = self.hmac(chaining_key, &[ikm]);
+        let mut out: Vec<Vec<u8>> = Vec::with_capacity(outputs);
+
+        for i in 1..=outputs as u8 {
+            let next = match out.last() {
+                Some(prev) => self.hmac(&temp, &[prev, &[i]]),
+                None => self.hmac(&temp, &[&[i]]),
+            };
+            out.push(next);
+        }
+
+        temp.fill(0);
+
+        out
+    }
+}
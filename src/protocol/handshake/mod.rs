@@ -0,0 +1,29 @@
+//! Noise Protocol Framework.
+//!
+//! This module implements Noise handshakes over X25519, ChaCha20-Poly1305
+//! and SHA-256 or BLAKE2b, following revision 34 of the specification:
+//!
+//! <https://noiseprotocol.org/noise.html>
+//!
+//! The implementation is split into:
+//!
+//! - `hash`: hash functions, HMAC and the Noise HKDF
+//! - `cipher`: the `CipherState` (key and message counter)
+//! - `symmetric`: the `SymmetricState` (chaining key and handshake hash)
+//! - `pattern`: handshake patterns and PSK modifiers
+//! - `core`: the public handshake and transport API
+
+/// Public Noise API.
+pub mod core;
+
+/// Noise `CipherState`.
+pub(crate) mod cipher;
+
+/// Noise hash functions, HMAC and HKDF.
+pub(crate) mod hash;
+
+/// Handshake message patterns.
+pub(crate) mod pattern;
+
+/// Noise `SymmetricState`.
+pub(crate) mod symmetric;
@@ -0,0 +1,64 @@
+//! Handshake patterns and PSK modifiers.
+
+use super::core::HandshakePattern;
+
+/// A token of a handshake message pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Token {
+    E,
+    S,
+    Ee,
+    Es,
+    Se,
+    Ss,
+    Psk,
+}
+
+impl HandshakePattern {
+    /// Returns whether the responder's static key is a pre-message.
+    pub(crate) fn responder_static_known(self) -> bool {
+        matches!(
+            self,
+            HandshakePattern::N | HandshakePattern::NK | HandshakePattern::IK
+        )
+    }
+
+    /// Returns whether only the initiator can send transport messages.
+    pub(crate) fn is_one_way(self) -> bool {
+        self == HandshakePattern::N
+    }
+
+    /// Returns whether the initiator needs a static key.
+    pub(crate) fn initiator_static(self) -> bool {
+        matches!(self, HandshakePattern::XX | HandshakePattern::IK)
+    }
+
+    /// Returns the message patterns, without PSK modifiers.
+    pub(crate) fn messages(self) -> Vec<Vec<Token>> {
+        use Token::*;
+
+        match self {
+            HandshakePattern::N => vec![vec![E, Es]],
+            HandshakePattern::NK => vec![vec![E, Es], vec![E, Ee]],
+            HandshakePattern::XX => vec![vec![E], vec![E, Ee, S, Es], vec![S, Se]],
+            HandshakePattern::IK => vec![vec![E, Es, S, Ss], vec![E, Ee, Se]],
+        }
+    }
+}
+
+/// Returns the message patterns with `psk` tokens inserted.
+///
+/// `psk0` is placed at the start of the first message, `pskN` at the end
+/// of message `N`. Positions must already be validated.
+pub(crate) fn with_psks(pattern: HandshakePattern, psks: &[u8]) -> Vec<Vec<Token>> {
+    let mut messages = pattern.messages();
+
+    for &position in psks {
+        match position {
+            0 => messages[0].insert(0, Token::Psk),
+            n => messages[n as usize - 1].push(Token::Psk),
+        }
+    }
+
+    messages
+}
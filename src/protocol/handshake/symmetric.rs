@@ -0,0 +1,110 @@
+//! Noise `SymmetricState`: chaining key, handshake hash and cipher.
+
+use super::cipher::CipherState;
+use super::core::{HashChoice, NoiseError};
+
+/// Symmetric state of a handshake in progress.
+pub(crate) struct SymmetricState {
+    hash: HashChoice,
+    ck: Vec<u8>,
+    h: Vec<u8>,
+    cipher: CipherState,
+}
+
+/// Truncates a `HASHLEN`-byte output to a 32-byte cipher key.
+fn cipher_key(output: &[u8]) -> [u8; 32] {
+    output[..32].try_into().unwrap()
+}
+
+impl SymmetricState {
+    /// `InitializeSymmetric(protocol_name)`.
+    pub(crate) fn new(hash: HashChoice, protocol_name: &[u8]) -> Self {
+        let h = if protocol_name.len() <= hash.hash_len() {
+            let mut h = vec![0u8; hash.hash_len()];
+            h[..protocol_name.len()].copy_from_slice(protocol_name);
+            h
+        } else {
+            hash.hash(&[protocol_name])
+        };
+
+        Self {
+            hash,
+            ck: h.clone(),
+            h,
+            cipher: CipherState::empty(),
+        }
+    }
+
+    /// Returns whether a cipher key has been established.
+    pub(crate) fn has_key(&self) -> bool {
+        self.cipher.has_key()
+    }
+
+    /// Returns the handshake hash `h`.
+    pub(crate) fn handshake_hash(&self) -> &[u8] {
+        &self.h
+    }
+
+    /// `MixKey(ikm)`.
+    pub(crate) fn mix_key(&mut self, ikm: &[u8]) {
+        let mut out = self.hash.hkdf(&self.ck, ikm, 2);
+
+        self.ck.copy_from_slice(&out[0]);
+        self.cipher = CipherState::new(cipher_key(&out[1]));
+
+        out.iter_mut().for_each(|o| o.fill(0));
+    }
+
+    /// `MixHash(data)`.
+    pub(crate) fn mix_hash(&mut self, data: &[u8]) {
+        self.h = self.hash.hash(&[&self.h, data]);
+    }
+
+    /// `MixKeyAndHash(ikm)`, used for pre-shared keys.
+    pub(crate) fn mix_key_and_hash(&mut self, ikm: &[u8]) {
+        let mut out = self.hash.hkdf(&self.ck, ikm, 3);
+
+        self.ck.copy_from_slice(&out[0]);
+        self.mix_hash(&out[1]);
+        self.cipher = CipherState::new(cipher_key(&out[2]));
+
+        out.iter_mut().for_each(|o| o.fill(0));
+    }
+
+    /// `EncryptAndHash(plaintext)`.
+    pub(crate) fn encrypt_and_hash(&mut self, plaintext: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        let ciphertext = self.cipher.encrypt_with_ad(&self.h, plaintext)?;
+        self.mix_hash(&ciphertext);
+
+        Ok(ciphertext)
+    }
+
+    /// `DecryptAndHash(ciphertext)`.
+    pub(crate) fn decrypt_and_hash(&mut self, ciphertext: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        let plaintext = self.cipher.decrypt_with_ad(&self.h, ciphertext)?;
+        self.mix_hash(ciphertext);
+
+        Ok(plaintext)
+    }
+
+    /// `Split()`, returning the initiator-to-responder and
+    /// responder-to-initiator cipher states.
+    pub(crate) fn split(&self) -> (CipherState, CipherState) {
+        let mut out = self.hash.hkdf(&self.ck, &[], 2);
+
+        let states = (
+            CipherState::new(cipher_key(&out[0])),
+            CipherState::new(cipher_key(&out[1])),
+        );
+
+        out.iter_mut().for_each(|o| o.fill(0));
+
+        states
+    }
+}
+
+impl Drop for SymmetricState {
+    fn drop(&mut self) {
+        self.ck.fill(0);
+    }
+}
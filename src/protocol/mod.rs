@@ -0,0 +1,68 @@
+//! Cryptographic protocols.
+//!
+//! This module builds complete, interoperable protocols on top of the
+//! crate's primitives: key agreement, hashing and authenticated
+//! encryption. Each protocol manages its own state machine, so that
+//! callers exchange opaque messages instead of assembling primitives by
+//! hand.
+//!
+//! # Noise
+//!
+//! The `noise` submodule implements the Noise Protocol Framework with the
+//! `N`, `NK`, `XX` and `IK` handshake patterns, the `25519` DH function,
+//! the `ChaChaPoly` cipher and the `SHA256` or `BLAKE2b` hash functions.
+//!
+//! A handshake authenticates the parties and establishes a transport
+//! session with one cipher per direction, which can be rekeyed.
+//!
+//! # Double Ratchet
+//!
+//! The `doubleratchet` submodule implements the Double Ratchet algorithm
+//! for asynchronous messaging. Every message is encrypted under a fresh
+//! key, and each round trip mixes in a new X25519 exchange, providing
+//! forward secrecy and post-compromise security.
+//!
+//! Sessions tolerate out-of-order delivery, can optionally encrypt
+//! message headers, and can be serialized for storage.
+//!
+//! # X3DH
+//!
+//! The `x3dh` submodule implements the Extended Triple Diffie-Hellman key
+//! agreement. A responder publishes a bundle of Ed25519-signed X25519
+//! prekeys, which lets an initiator establish a shared secret (e.g. to
+//! start a Double Ratchet session) while the responder is offline.
+//!
+//! # Secure streams
+//!
+//! The `securestream` submodule turns any blocking `Read + Write` stream
+//! into an encrypted, message-oriented channel once a session key exists.
+//! Frames are length-prefixed and authenticated under counter nonces, and
+//! keys are rotated periodically.
+//!
+//! # JOSE
+//!
+//! The `jose` submodule implements JSON Web Signatures and Tokens with
+//! the `EdDSA` algorithm, OKP JSON Web Keys, and JSON Web Encryption with
+//! `ECDH-ES` over X25519 and ChaCha20-Poly1305 (`C20P` / `XC20P`), for
+//! exchanging tokens signed by Ed25519 identities with web services.
+//! JWTs are validated against `exp`, `nbf`, `aud` and `iss`.
+//!
+//! Apart from secure streams, this module is agnostic to networking:
+//! messages are produced and consumed as byte vectors, and transport is
+//! left to the caller.
+
+mod agreement;
+mod framing;
+mod handshake;
+mod ratchet;
+mod token;
+
+pub use handshake::core as noise;
+
+pub use ratchet::core as doubleratchet;
+
+pub use agreement::core as x3dh;
+
+pub use framing::core as securestream;
+
+pub use token::core as jose;
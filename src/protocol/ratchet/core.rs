@@ -0,0 +1,476 @@
+//! Double Ratchet sessions.
+//!
+//! This module implements the Double Ratchet algorithm as specified by
+//! Signal, with X25519 for the DH ratchet, HKDF-SHA256 / HMAC-SHA256 for
+//! the root and chain KDFs, and ChaCha20-Poly1305 for messages.
+//!
+//! ## Messages
+//!
+//! ```text
+//! header  = DHs public key (32) || PN (4) || N (4)
+//! message = header || ChaCha20-Poly1305(mk, AD || header, plaintext)
+//! ```
+//!
+//! With header encryption, the header is replaced by
+//! `nonce (12) || ChaCha20-Poly1305(HK, header)`, hiding the ratchet
+//! public keys and counters from observers.
+//!
+//! ## Initialization
+//!
+//! Both parties start from a 32-byte shared secret (e.g. from X3DH). The
+//! initiator also needs the responder's ratchet public key, and the
+//! responder the matching private key. The responder cannot send until it
+//! has received a first message.
+//!
+//! ## Notes
+//!
+//! - Out-of-order messages are supported: up to `MAX_SKIP` message keys
+//!   per chain are stored, and at most `MAX_STORED_KEYS` overall (the
+//!   oldest are evicted first).
+//! - Decryption is transactional: a failed decryption leaves the session
+//!   unchanged.
+//! - The serialized state contains secret keys and must be protected at
+//!   rest (e.g. with the `password` container).
+
+use super::kdf::{
+    HEADER_NONCE_LEN, TAG_LEN, decrypt, decrypt_header, encrypt, encrypt_header, kdf_ck, kdf_rk,
+};
+use super::state::{HeaderKeys, SkippedKey, State};
+use crate::keys::x25519;
+
+/// Maximum number of message keys skipped within a single chain.
+pub const MAX_SKIP: u32 = 1000;
+
+/// Maximum number of skipped message keys stored by a session.
+pub const MAX_STORED_KEYS: usize = 2000;
+
+/// Length of a plaintext message header.
+pub const HEADER_LEN: usize = 40;
+
+/// Length of an encrypted message header.
+pub const ENCRYPTED_HEADER_LEN: usize = HEADER_NONCE_LEN + HEADER_LEN + TAG_LEN;
+
+/// Errors that can occur while using a Double Ratchet session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RatchetError {
+    /// The responder cannot send before receiving a first message.
+    NoSendingChain,
+    /// A chain reached its maximum number of messages.
+    ChainExhausted,
+    /// A public key yields the all-zero shared secret.
+    WeakPublicKey,
+    /// The message is truncated or malformed.
+    InvalidMessage,
+    /// The message (or its header) failed authentication, or was replayed.
+    DecryptionFailed,
+    /// The message would require skipping more than `MAX_SKIP` keys.
+    TooManySkipped,
+    /// The serialized session is malformed or unsupported.
+    InvalidSession,
+}
+
+/// A decoded message header.
+struct Header {
+    dh: [u8; 32],
+    pn: u32,
+    n: u32,
+}
+
+impl Header {
+    fn to_bytes(&self) -> [u8; HEADER_LEN] {
+        let mut out = [0u8; HEADER_LEN];
+        out[..32].copy_from_slice(&self.dh);
+        out[32..36].copy_from_slice(&self.pn.to_be_bytes());
+        out[36..].copy_from_slice(&self.n.to_be_bytes());
+        out
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            dh: bytes[..32].try_into().unwrap(),
+            pn: u32::from_be_bytes(bytes[32..36].try_into().unwrap()),
+            n: u32::from_be_bytes(bytes[36..40].try_into().unwrap()),
+        }
+    }
+}
+
+/// Computes X25519, rejecting the all-zero output of low-order points.
+fn exchange(private: &[u8; 32], public: &[u8; 32]) -> Result<[u8; 32], RatchetError> {
+    let shared = x25519::exchange(private, public);
+
+    let mut acc = 0u8;
+    for b in shared {
+        acc |= b;
+    }
+
+    if acc == 0 {
+        return Err(RatchetError::WeakPublicKey);
+    }
+
+    Ok(shared)
+}
+
+/// A Double Ratchet session with one peer.
+pub struct Session {
+    state: State,
+}
+
+impl Session {
+    /// Starts a session as the initiator (the first party to send).
+    ///
+    /// # Parameters
+    ///
+    /// - `shared_secret`: 32-byte secret agreed with the responder
+    /// - `remote_public`: the responder's ratchet public key
+    pub fn initiator(
+        shared_secret: &[u8; 32],
+        remote_public: &[u8; 32],
+    ) -> Result<Self, RatchetError> {
+        Self::start_initiator(shared_secret, remote_public, None)
+    }
+
+    /// Starts a session as the responder.
+    ///
+    /// # Parameters
+    ///
+    /// - `shared_secret`: 32-byte secret agreed with the initiator
+    /// - `private`: the ratchet private key whose public key the initiator
+    ///   uses
+    pub fn responder(shared_secret: &[u8; 32], private: &[u8; 32]) -> Self {
+        Self::start_responder(shared_secret, private, None)
+    }
+
+    /// Starts a session with header encryption as the initiator.
+    ///
+    /// `header_key` and `next_header_key` are additional shared secrets,
+    /// agreed with the responder together with `shared_secret`.
+    pub fn initiator_with_header_encryption(
+        shared_secret: &[u8; 32],
+        remote_public: &[u8; 32],
+        header_key: &[u8; 32],
+        next_header_key: &[u8; 32],
+    ) -> Result<Self, RatchetError> {
+        Self::start_initiator(
+            shared_secret,
+            remote_public,
+            Some((header_key, next_header_key)),
+        )
+    }
+
+    /// Starts a session with header encryption as the responder.
+    ///
+    /// `header_key` and `next_header_key` must match the initiator's.
+    pub fn responder_with_header_encryption(
+        shared_secret: &[u8; 32],
+        private: &[u8; 32],
+        header_key: &[u8; 32],
+        next_header_key: &[u8; 32],
+    ) -> Self {
+        Self::start_responder(shared_secret, private, Some((header_key, next_header_key)))
+    }
+
+    fn start_initiator(
+        shared_secret: &[u8; 32],
+        remote_public: &[u8; 32],
+        header_keys: Option<(&[u8; 32], &[u8; 32])>,
+    ) -> Result<Self, RatchetError> {
+        let (dhs_public, dhs) = x25519::generate_keypair();
+
+        let mut shared = exchange(&dhs, remote_public)?;
+        let (rk, cks, nhks) = kdf_rk(shared_secret, &shared);
+        shared.fill(0);
+
+        Ok(Self {
+            state: State {
+                dhs,
+                dhs_public,
+                dhr: Some(*remote_public),
+                rk,
+                cks: Some(cks),
+                ckr: None,
+                ns: 0,
+                nr: 0,
+                pn: 0,
+                header: header_keys.map(|(hk, nhk)| HeaderKeys {
+                    hks: Some(*hk),
+                    hkr: None,
+                    nhks,
+                    nhkr: *nhk,
+                }),
+                skipped: Vec::new(),
+            },
+        })
+    }
+
+    fn start_responder(
+        shared_secret: &[u8; 32],
+        private: &[u8; 32],
+        header_keys: Option<(&[u8; 32], &[u8; 32])>,
+    ) -> Self {
+        Self {
+            state: State {
+                dhs: *private,
+                dhs_public: x25519::public_key(private),
+                dhr: None,
+                rk: *shared_secret,
+                cks: None,
+                ckr: None,
+                ns: 0,
+                nr: 0,
+                pn: 0,
+                header: header_keys.map(|(hk, nhk)| HeaderKeys {
+                    hks: None,
+                    hkr: None,
+                    nhks: *nhk,
+                    nhkr: *hk,
+                }),
+                skipped: Vec::new(),
+            },
+        }
+    }
+
+    /// Encrypts a message, advancing the sending chain.
+    ///
+    /// # Parameters
+    ///
+    /// - `plaintext`: message to encrypt
+    /// - `ad`: associated data (e.g. both identity keys), authenticated
+    ///   but not transmitted
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(message)` to send to the peer
+    /// - `Err(NoSendingChain)` if the responder has not received a
+    ///   message yet
+    /// - `Err(ChainExhausted)` after 2^32 - 1 messages in one chain
+    pub fn encrypt(&mut self, plaintext: &[u8], ad: &[u8]) -> Result<Vec<u8>, RatchetError> {
+        let st = &mut self.state;
+
+        let ck = st.cks.ok_or(RatchetError::NoSendingChain)?;
+        if st.ns == u32::MAX {
+            return Err(RatchetError::ChainExhausted);
+        }
+
+        let (next, mut mk) = kdf_ck(&ck);
+        st.cks = Some(next);
+
+        let header = Header {
+            dh: st.dhs_public,
+            pn: st.pn,
+            n: st.ns,
+        }
+        .to_bytes();
+        st.ns += 1;
+
+        let header = match &st.header {
+            // A sending chain always comes with a sending header key.
+            Some(keys) => encrypt_header(keys.hks.as_ref().unwrap(), &header),
+            None => header.to_vec(),
+        };
+
+        let mut out = header.clone();
+        out.extend(encrypt(&mk, &[ad, &header], plaintext));
+        mk.fill(0);
+
+        Ok(out)
+    }
+
+    /// Decrypts a message, advancing the ratchet as needed.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(plaintext)` on success
+    /// - `Err(InvalidMessage)` if the message is truncated
+    /// - `Err(DecryptionFailed)` if it does not authenticate, or its key
+    ///   was already used
+    /// - `Err(TooManySkipped)` if it is too far ahead of the chain
+    /// - `Err(WeakPublicKey)` if it carries a low-order ratchet key
+    ///
+    /// On error, the session is left unchanged.
+    pub fn decrypt(&mut self, message: &[u8], ad: &[u8]) -> Result<Vec<u8>, RatchetError> {
+        let header_len = match self.state.header {
+            Some(_) => ENCRYPTED_HEADER_LEN,
+            None => HEADER_LEN,
+        };
+
+        if message.len() < header_len + TAG_LEN {
+            return Err(RatchetError::InvalidMessage);
+        }
+
+        let (raw_header, body) = message.split_at(header_len);
+
+        let mut st = self.state.clone();
+        let plaintext = Self::decrypt_with(&mut st, raw_header, body, ad)?;
+        self.state = st;
+
+        Ok(plaintext)
+    }
+
+    fn decrypt_with(
+        st: &mut State,
+        raw_header: &[u8],
+        body: &[u8],
+        ad: &[u8],
+    ) -> Result<Vec<u8>, RatchetError> {
+        let ad: [&[u8]; 2] = [ad, raw_header];
+
+        // Previously skipped message.
+        if let Some((index, mk)) = Self::find_skipped(st, raw_header) {
+            let plaintext = decrypt(&mk, &ad, body)?;
+            st.skipped.remove(index);
+            return Ok(plaintext);
+        }
+
+        let (header, dh_ratchet) = match &st.header {
+            None => {
+                let header = Header::from_bytes(raw_header);
+                let ratchet = st.dhr != Some(header.dh);
+                (header, ratchet)
+            }
+            Some(keys) => {
+                let current = keys.hkr.and_then(|hk| decrypt_header(&hk, raw_header));
+
+                match current {
+                    Some(header) => (Header::from_bytes(&header), false),
+                    None => match decrypt_header(&keys.nhkr, raw_header) {
+                        Some(header) => (Header::from_bytes(&header), true),
+                        None => return Err(RatchetError::DecryptionFailed),
+                    },
+                }
+            }
+        };
+
+        if dh_ratchet {
+            Self::skip_message_keys(st, header.pn)?;
+            Self::dh_ratchet(st, &header.dh)?;
+        }
+
+        Self::skip_message_keys(st, header.n)?;
+
+        // Without a DH ratchet step, the header may still name the
+        // initiator's first remote key, which has no receiving chain yet.
+        let ck = st.ckr.ok_or(RatchetError::DecryptionFailed)?;
+        let (next, mut mk) = kdf_ck(&ck);
+        st.ckr = Some(next);
+        st.nr = st.nr.checked_add(1).ok_or(RatchetError::ChainExhausted)?;
+
+        let result = decrypt(&mk, &ad, body);
+        mk.fill(0);
+
+        result
+    }
+
+    /// Looks up the stored key of a skipped message.
+    fn find_skipped(st: &State, raw_header: &[u8]) -> Option<(usize, [u8; 32])> {
+        match &st.header {
+            None => {
+                let header = Header::from_bytes(raw_header);
+
+                st.skipped
+                    .iter()
+                    .position(|s| s.id == header.dh && s.n == header.n)
+                    .map(|i| (i, st.skipped[i].mk))
+            }
+            Some(_) => st.skipped.iter().enumerate().find_map(|(i, s)| {
+                let header = Header::from_bytes(&decrypt_header(&s.id, raw_header)?);
+                (header.n == s.n).then_some((i, s.mk))
+            }),
+        }
+    }
+
+    /// Stores the message keys of the receiving chain up to `until`.
+    fn skip_message_keys(st: &mut State, until: u32) -> Result<(), RatchetError> {
+        if until.saturating_sub(st.nr) > MAX_SKIP {
+            return Err(RatchetError::TooManySkipped);
+        }
+
+        let Some(mut ck) = st.ckr else {
+            return Ok(());
+        };
+
+        let id = match &st.header {
+            Some(keys) => keys.hkr.unwrap(),
+            None => st.dhr.unwrap(),
+        };
+
+        while st.nr < until {
+            let (next, mk) = kdf_ck(&ck);
+            ck = next;
+
+            if st.skipped.len() == MAX_STORED_KEYS {
+                st.skipped.remove(0).mk.fill(0);
+            }
+            st.skipped.push(SkippedKey { id, n: st.nr, mk });
+
+            st.nr += 1;
+        }
+
+        st.ckr = Some(ck);
+
+        Ok(())
+    }
+
+    /// Performs a DH ratchet step with the peer's new ratchet key.
+    fn dh_ratchet(st: &mut State, dh: &[u8; 32]) -> Result<(), RatchetError> {
+        st.pn = st.ns;
+        st.ns = 0;
+        st.nr = 0;
+        st.dhr = Some(*dh);
+
+        if let Some(keys) = &mut st.header {
+            keys.hks = Some(keys.nhks);
+            keys.hkr = Some(keys.nhkr);
+        }
+
+        let mut shared = exchange(&st.dhs, dh)?;
+        let (rk, ckr, nhkr) = kdf_rk(&st.rk, &shared);
+        st.rk = rk;
+        st.ckr = Some(ckr);
+
+        let (public, private) = x25519::generate_keypair();
+        st.dhs = private;
+        st.dhs_public = public;
+
+        shared = exchange(&st.dhs, dh)?;
+        let (rk, cks, nhks) = kdf_rk(&st.rk, &shared);
+        shared.fill(0);
+        st.rk = rk;
+        st.cks = Some(cks);
+
+        if let Some(keys) = &mut st.header {
+            keys.nhkr = nhkr;
+            keys.nhks = nhks;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the current ratchet public key.
+    pub fn public_key(&self) -> [u8; 32] {
+        self.state.dhs_public
+    }
+
+    /// Returns the number of stored skipped message keys.
+    pub fn skipped_keys(&self) -> usize {
+        self.state.skipped.len()
+    }
+
+    /// Serializes the session state.
+    ///
+    /// The output contains secret keys.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.state.to_bytes()
+    }
+
+    /// Restores a session serialized with [`Session::to_bytes`].
+    ///
+    /// # Security Notes
+    ///
+    /// - Restoring an older copy of a session replays its message keys;
+    ///   only the latest state should be kept.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RatchetError> {
+        Ok(Self {
+            state: State::from_bytes(bytes, MAX_STORED_KEYS)?,
+        })
+    }
+}
@@ -0,0 +1,128 @@
+//! Key derivation and message encryption for the Double Ratchet.
+//!
+//! ```text
+//! KDF_RK(rk, dh)  = HKDF-SHA256(salt = rk, ikm = dh, info = ROOT_INFO)
+//!                   -> rk' (32) || ck (32) [|| nhk (32)]
+//! KDF_CK(ck)      = mk = HMAC-SHA256(ck, 0x01), ck' = HMAC-SHA256(ck, 0x02)
+//! ENCRYPT(mk, m)  = ChaCha20-Poly1305(key, nonce, ad, m), where
+//!                   key || nonce = HKDF-SHA256(salt = 0, ikm = mk,
+//!                                              info = MESSAGE_INFO)
+//! ```
+
+use super::core::RatchetError;
+use crate::derivation::hkdf::core::{hkdf, hmac_sha256};
+use crate::encryption::chacha20poly1305::{decrypt_with_aad, encrypt_with_aad};
+use crate::rng::Csprng;
+
+/// HKDF info for root key derivation.
+const ROOT_INFO: &[u8] = b"Cryptal DoubleRatchet root";
+
+/// HKDF info for message key expansion.
+const MESSAGE_INFO: &[u8] = b"Cryptal DoubleRatchet message";
+
+/// Length of the Poly1305 tag.
+pub(crate) const TAG_LEN: usize = 16;
+
+/// Length of the random nonce prepended to encrypted headers.
+pub(crate) const HEADER_NONCE_LEN: usize = 12;
+
+/// Root KDF step, returning the new root key, a chain key and a next
+/// header key.
+pub(crate) fn kdf_rk(rk: &[u8; 32], dh: &[u8; 32]) -> ([u8; 32], [u8; 32], [u8; 32]) {
+    let mut okm = [0u8; 96];
+    hkdf(rk, dh, ROOT_INFO, &mut okm).unwrap();
+
+    let out = (
+        okm[..32].try_into().unwrap(),
+        okm[32..64].try_into().unwrap(),
+        okm[64..].try_into().unwrap(),
+    );
+    okm.fill(0);
+
+    out
+}
+
+/// Chain KDF step, returning the next chain key and a message key.
+pub(crate) fn kdf_ck(ck: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
+    (hmac_sha256(ck, &[0x02]), hmac_sha256(ck, &[0x01]))
+}
+
+/// Expands a message key into a ChaCha20-Poly1305 key and nonce.
+fn message_key(mk: &[u8; 32]) -> ([u8; 32], [u8; 12]) {
+    let mut okm = [0u8; 44];
+    hkdf(&[0u8; 32], mk, MESSAGE_INFO, &mut okm).unwrap();
+
+    let out = (okm[..32].try_into().unwrap(), okm[32..].try_into().unwrap());
+    okm.fill(0);
+
+    out
+}
+
+/// Encrypts a message under a single-use message key.
+pub(crate) fn encrypt(mk: &[u8; 32], ad: &[&[u8]], plaintext: &[u8]) -> Vec<u8> {
+    let (mut key, nonce) = message_key(mk);
+    let ad = ad.concat();
+
+    let mut out = vec![0u8; plaintext.len() + TAG_LEN];
+    let (body, tag) = out.split_at_mut(plaintext.len());
+    encrypt_with_aad(&key, &nonce, &ad, plaintext, body, tag.try_into().unwrap());
+    key.fill(0);
+
+    out
+}
+
+/// Decrypts a message under a single-use message key.
+pub(crate) fn decrypt(
+    mk: &[u8; 32],
+    ad: &[&[u8]],
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, RatchetError> {
+    if ciphertext.len() < TAG_LEN {
+        return Err(RatchetError::InvalidMessage);
+    }
+
+    let (mut key, nonce) = message_key(mk);
+    let ad = ad.concat();
+
+    let (body, tag) = ciphertext.split_at(ciphertext.len() - TAG_LEN);
+    let mut out = vec![0u8; body.len()];
+    let result = decrypt_with_aad(&key, &nonce, &ad, body, tag.try_into().unwrap(), &mut out);
+    key.fill(0);
+
+    result.map_err(|_| RatchetError::DecryptionFailed)?;
+
+    Ok(out)
+}
+
+/// Encrypts a serialized header under a header key with a random nonce.
+pub(crate) fn encrypt_header(hk: &[u8; 32], header: &[u8]) -> Vec<u8> {
+    let mut nonce = [0u8; HEADER_NONCE_LEN];
+    Csprng::new().fill_bytes(&mut nonce);
+
+    let mut out = vec![0u8; HEADER_NONCE_LEN + header.len() + TAG_LEN];
+    out[..HEADER_NONCE_LEN].copy_from_slice(&nonce);
+
+    let (body, tag) = out[HEADER_NONCE_LEN..].split_at_mut(header.len());
+    encrypt_with_aad(hk, &nonce, &[], header, body, tag.try_into().unwrap());
+
+    out
+}
+
+/// Decrypts an encrypted header, returning `None` on failure.
+pub(crate) fn decrypt_header(hk: &[u8; 32], encrypted: &[u8]) -> Option<Vec<u8>> {
+    let (nonce, rest) = encrypted.split_at(HEADER_NONCE_LEN);
+    let (body, tag) = rest.split_at(rest.len() - TAG_LEN);
+
+    let mut out = vec![0u8; body.len()];
+    decrypt_with_aad(
+        hk,
+        nonce.try_into().unwrap(),
+        &[],
+        body,
+        tag.try_into().unwrap(),
+        &mut out,
+    )
+    .ok()?;
+
+    Some(out)
+}
@@ -0,0 +1,22 @@
+//! Double Ratchet algorithm.
+//!
+//! This module implements the Double Ratchet used by the Signal protocol
+//! for asynchronous messaging, providing forward secrecy and
+//! post-compromise security:
+//!
+//! <https://signal.org/docs/specifications/doubleratchet/>
+//!
+//! The implementation is split into:
+//!
+//! - `kdf`: root and chain KDFs, message and header encryption
+//! - `state`: the session state and its serialization
+//! - `core`: the public `Session` API
+
+/// Public Double Ratchet API.
+pub mod core;
+
+/// Double Ratchet KDFs and AEAD.
+pub(crate) mod kdf;
+
+/// Double Ratchet session state.
+pub(crate) mod state;
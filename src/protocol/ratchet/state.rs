@@ -0,0 +1,240 @@
+//! Double Ratchet session state and its serialization.
+//!
+//! ```text
+//! version (1) || flags (1) || DHs (32) || DHr? || RK (32) || CKs? || CKr?
+//! || Ns (4) || Nr (4) || PN (4)
+//! [|| HKs? || HKr? || NHKs (32) || NHKr (32)]      if header encryption
+//! || count (4) || count * (id (32) || N (4) || MK (32))
+//!
+//! X? = 0x00, or 0x01 || X (32)
+//! ```
+//!
+//! Integers are big-endian. The skipped-key `id` is the ratchet public key
+//! of the chain, or its header key when headers are encrypted.
+
+use super::core::RatchetError;
+use crate::keys::x25519;
+
+/// Current serialization format version.
+const VERSION: u8 = 1;
+
+/// Flag set when header encryption is enabled.
+const FLAG_HEADER_ENCRYPTION: u8 = 0x01;
+
+/// Header keys of a session with header encryption.
+#[derive(Clone)]
+pub(crate) struct HeaderKeys {
+    pub(crate) hks: Option<[u8; 32]>,
+    pub(crate) hkr: Option<[u8; 32]>,
+    pub(crate) nhks: [u8; 32],
+    pub(crate) nhkr: [u8; 32],
+}
+
+/// A stored message key for a skipped message.
+#[derive(Clone)]
+pub(crate) struct SkippedKey {
+    pub(crate) id: [u8; 32],
+    pub(crate) n: u32,
+    pub(crate) mk: [u8; 32],
+}
+
+/// Complete ratchet state.
+#[derive(Clone)]
+pub(crate) struct State {
+    pub(crate) dhs: [u8; 32],
+    pub(crate) dhs_public: [u8; 32],
+    pub(crate) dhr: Option<[u8; 32]>,
+    pub(crate) rk: [u8; 32],
+    pub(crate) cks: Option<[u8; 32]>,
+    pub(crate) ckr: Option<[u8; 32]>,
+    pub(crate) ns: u32,
+    pub(crate) nr: u32,
+    pub(crate) pn: u32,
+    pub(crate) header: Option<HeaderKeys>,
+    pub(crate) skipped: Vec<SkippedKey>,
+}
+
+impl Drop for State {
+    fn drop(&mut self) {
+        self.dhs.fill(0);
+        self.rk.fill(0);
+
+        for key in [&mut self.cks, &mut self.ckr].into_iter().flatten() {
+            key.fill(0);
+        }
+
+        if let Some(header) = &mut self.header {
+            for key in [&mut header.hks, &mut header.hkr].into_iter().flatten() {
+                key.fill(0);
+            }
+            header.nhks.fill(0);
+            header.nhkr.fill(0);
+        }
+
+        for skipped in &mut self.skipped {
+            skipped.mk.fill(0);
+        }
+    }
+}
+
+fn put_optional(out: &mut Vec<u8>, key: &Option<[u8; 32]>) {
+    match key {
+        Some(key) => {
+            out.push(1);
+            out.extend_from_slice(key);
+        }
+        None => out.push(0),
+    }
+}
+
+/// Cursor over serialized state.
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn bytes(&mut self, len: usize) -> Result<&[u8], RatchetError> {
+        if self.0.len() < len {
+            return Err(RatchetError::InvalidSession);
+        }
+
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+
+        Ok(head)
+    }
+
+    fn byte(&mut self) -> Result<u8, RatchetError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn key(&mut self) -> Result<[u8; 32], RatchetError> {
+        Ok(self.bytes(32)?.try_into().unwrap())
+    }
+
+    fn u32(&mut self) -> Result<u32, RatchetError> {
+        Ok(u32::from_be_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn optional(&mut self) -> Result<Option<[u8; 32]>, RatchetError> {
+        match self.byte()? {
+            0 => Ok(None),
+            1 => Ok(Some(self.key()?)),
+            _ => Err(RatchetError::InvalidSession),
+        }
+    }
+}
+
+impl State {
+    /// Checks the invariants the ratchet relies on: a receiving chain
+    /// comes with the peer's ratchet key, and with header encryption each
+    /// chain comes with its header key.
+    fn is_consistent(&self) -> bool {
+        if self.ckr.is_some() && self.dhr.is_none() {
+            return false;
+        }
+
+        match &self.header {
+            None => true,
+            Some(keys) => {
+                keys.hks.is_some() == self.cks.is_some() && keys.hkr.is_some() == self.ckr.is_some()
+            }
+        }
+    }
+
+    /// Serializes the state.
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![VERSION];
+        out.push(if self.header.is_some() {
+            FLAG_HEADER_ENCRYPTION
+        } else {
+            0
+        });
+
+        out.extend_from_slice(&self.dhs);
+        put_optional(&mut out, &self.dhr);
+        out.extend_from_slice(&self.rk);
+        put_optional(&mut out, &self.cks);
+        put_optional(&mut out, &self.ckr);
+
+        for n in [self.ns, self.nr, self.pn] {
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+
+        if let Some(header) = &self.header {
+            put_optional(&mut out, &header.hks);
+            put_optional(&mut out, &header.hkr);
+            out.extend_from_slice(&header.nhks);
+            out.extend_from_slice(&header.nhkr);
+        }
+
+        out.extend_from_slice(&(self.skipped.len() as u32).to_be_bytes());
+        for skipped in &self.skipped {
+            out.extend_from_slice(&skipped.id);
+            out.extend_from_slice(&skipped.n.to_be_bytes());
+            out.extend_from_slice(&skipped.mk);
+        }
+
+        out
+    }
+
+    /// Parses a serialized state.
+    pub(crate) fn from_bytes(bytes: &[u8], max_skipped: usize) -> Result<Self, RatchetError> {
+        let mut r = Reader(bytes);
+
+        if r.byte()? != VERSION {
+            return Err(RatchetError::InvalidSession);
+        }
+
+        let flags = r.byte()?;
+        if flags & !FLAG_HEADER_ENCRYPTION != 0 {
+            return Err(RatchetError::InvalidSession);
+        }
+
+        let dhs = r.key()?;
+
+        let mut state = State {
+            dhs,
+            dhs_public: x25519::public_key(&dhs),
+            dhr: r.optional()?,
+            rk: r.key()?,
+            cks: r.optional()?,
+            ckr: r.optional()?,
+            ns: r.u32()?,
+            nr: r.u32()?,
+            pn: r.u32()?,
+            header: None,
+            skipped: Vec::new(),
+        };
+
+        if flags & FLAG_HEADER_ENCRYPTION != 0 {
+            state.header = Some(HeaderKeys {
+                hks: r.optional()?,
+                hkr: r.optional()?,
+                nhks: r.key()?,
+                nhkr: r.key()?,
+            });
+        }
+
+        if !state.is_consistent() {
+            return Err(RatchetError::InvalidSession);
+        }
+
+        let count = r.u32()? as usize;
+        if count > max_skipped {
+            return Err(RatchetError::InvalidSession);
+        }
+
+        for _ in 0..count {
+            state.skipped.push(SkippedKey {
+                id: r.key()?,
+                n: r.u32()?,
+                mk: r.key()?,
+            });
+        }
+
+        if !r.0.is_empty() {
+            return Err(RatchetError::InvalidSession);
+        }
+
+        Ok(state)
+    }
+}
@@ -0,0 +1,145 @@
+//! JOSE with Ed25519 and X25519 (RFC 7515, RFC 7516, RFC 7519, RFC 8037).
+//!
+//! ## Signatures and tokens
+//!
+//! `sign_jws` and `verify_jws` produce and check compact JWS
+//! (`header.payload.signature`) with the `EdDSA` algorithm.
+//! `sign_jwt` and `verify_jwt` add JWT `Claims` on top, validated
+//! against a `Validation` policy (`exp`, `nbf`, `aud`, `iss`).
+//!
+//! ## Encryption
+//!
+//! `encrypt_jwe` and `decrypt_jwe` produce and open compact JWE with
+//! direct key agreement (`ECDH-ES`) to an X25519 public key. The content
+//! key is derived with the Concat KDF of RFC 7518 and encrypts the
+//! payload with ChaCha20-Poly1305 (`C20P`) or XChaCha20-Poly1305
+//! (`XC20P`):
+//!
+//! ```text
+//! BASE64URL(header) . "" . BASE64URL(iv) . BASE64URL(ciphertext) . BASE64URL(tag)
+//! ```
+//!
+//! ## Keys
+//!
+//! `Jwk` converts Ed25519 and X25519 keys to and from OKP JSON Web Keys,
+//! and computes their RFC 7638 thumbprints.
+//!
+//! ## Notes
+//!
+//! - Only the algorithms above are accepted; in particular `none` and
+//!   HMAC algorithms are rejected, so a token cannot downgrade its own
+//!   verification.
+//! - Headers listing `crit` extensions or a `zip` compression are
+//!   rejected.
+//! - JSON with duplicate member names is rejected.
+
+pub use super::jwe::{ContentEncryption, JweHeader, decrypt_jwe, encrypt_jwe};
+pub use super::jwk::{Jwk, JwkKey};
+pub use super::jws::{JwsHeader, decode_jws_header, sign_jws, verify_jws};
+pub use super::jwt::{Claims, Validation, sign_jwt, verify_jwt};
+
+use crate::encoding::base64::{self, Variant};
+use crate::encoding::json::{JsonError, Value};
+
+/// Errors that can occur while producing or consuming JOSE objects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoseError {
+    /// The compact serialization or a base64url part is malformed.
+    InvalidFormat,
+    /// A header, key or claims set is not valid JSON.
+    InvalidJson(JsonError),
+    /// A header member is missing or has the wrong type.
+    InvalidHeader,
+    /// The algorithm, encryption, key type or curve is not supported.
+    UnsupportedAlgorithm,
+    /// The header requires an extension (`crit`) or compression (`zip`).
+    UnsupportedExtension,
+    /// The key material is invalid or inconsistent.
+    InvalidKey,
+    /// The Ed25519 private key has no seed.
+    MissingSeed,
+    /// The signature does not verify.
+    InvalidSignature,
+    /// A public key yields the all-zero X25519 shared secret.
+    WeakPublicKey,
+    /// The ciphertext failed authentication.
+    DecryptionFailed,
+    /// A registered claim has the wrong type, or a required claim is
+    /// missing.
+    InvalidClaims,
+    /// The token is expired (`exp`).
+    Expired,
+    /// The token is not valid yet (`nbf`).
+    NotYetValid,
+    /// The token is not intended for this audience (`aud`).
+    InvalidAudience,
+    /// The token was not issued by the expected issuer (`iss`).
+    InvalidIssuer,
+}
+
+impl From<JsonError> for JoseError {
+    fn from(err: JsonError) -> Self {
+        JoseError::InvalidJson(err)
+    }
+}
+
+/// Encodes `data` as unpadded base64url.
+pub(crate) fn b64(data: &[u8]) -> String {
+    base64::encode(data, Variant::UrlSafeNoPad)
+}
+
+/// Decodes unpadded base64url.
+pub(crate) fn unb64(input: &str) -> Result<Vec<u8>, JoseError> {
+    base64::decode(input, Variant::UrlSafeNoPad).map_err(|_| JoseError::InvalidFormat)
+}
+
+/// Decodes a base64url-encoded JSON object.
+pub(crate) fn decode_object(input: &str) -> Result<Value, JoseError> {
+    let bytes = unb64(input)?;
+    let text = String::from_utf8(bytes).map_err(|_| JoseError::InvalidFormat)?;
+
+    let value = Value::parse(&text)?;
+    if value.as_object().is_none() {
+        return Err(JoseError::InvalidHeader);
+    }
+
+    Ok(value)
+}
+
+/// Returns the optional string member `name` of `object`.
+pub(crate) fn optional_str(object: &Value, name: &str) -> Result<Option<String>, JoseError> {
+    match object.get(name) {
+        None => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        Some(_) => Err(JoseError::InvalidHeader),
+    }
+}
+
+/// Returns the required string member `name` of `object`.
+pub(crate) fn required_str<'a>(object: &'a Value, name: &str) -> Result<&'a str, JoseError> {
+    object
+        .get(name)
+        .and_then(Value::as_str)
+        .ok_or(JoseError::InvalidHeader)
+}
+
+/// Rejects headers that require extensions or compression, neither of
+/// which is supported.
+pub(crate) fn reject_extensions(header: &Value) -> Result<(), JoseError> {
+    if header.get("crit").is_some() || header.get("zip").is_some() {
+        return Err(JoseError::UnsupportedExtension);
+    }
+
+    Ok(())
+}
+
+/// Appends `"name": value` to `members` if `value` is set.
+pub(crate) fn push_optional(
+    members: &mut Vec<(String, Value)>,
+    name: &str,
+    value: &Option<String>,
+) {
+    if let Some(value) = value {
+        members.push((name.to_string(), Value::from(value.as_str())));
+    }
+}
@@ -0,0 +1,332 @@
+//! Compact JWE with `ECDH-ES` over X25519 (RFC 7516, RFC 7518, RFC 8037).
+//!
+//! The sender generates an ephemeral X25519 key, published as the `epk`
+//! header member, and derives the content encryption key directly from
+//! the shared secret `Z` with the Concat KDF:
+//!
+//! ```text
+//! CEK = SHA-256(00000001 || Z || len(enc) || enc || len(apu) || apu
+//!               || len(apv) || apv || 00000100)
+//! ```
+//!
+//! where lengths are 32-bit big-endian. The encrypted key part of the
+//! compact form is empty, and the AAD is the encoded protected header.
+//! `XC20P` derives a ChaCha20-Poly1305 subkey from the first 16 bytes of
+//! its 24-byte IV with HChaCha20, as in XChaCha20-Poly1305.
+
+use super::core::{
+    JoseError, b64, decode_object, optional_str, push_optional, reject_extensions, required_str,
+    unb64,
+};
+use super::jwk::{Jwk, JwkKey};
+use crate::encoding::json::Value;
+use crate::encryption::chacha20poly1305::{decrypt_with_aad, encrypt_with_aad};
+use crate::hash::sha256;
+use crate::keys::x25519;
+use crate::rng::Csprng;
+use crate::rng::chacha20::hchacha20;
+
+/// The JWE key management algorithm name.
+const ALG: &str = "ECDH-ES";
+
+/// Length of Poly1305 tags.
+const TAG_LEN: usize = 16;
+
+/// Content encryption algorithms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncryption {
+    /// ChaCha20-Poly1305 with a 96-bit IV.
+    C20P,
+    /// XChaCha20-Poly1305 with a 192-bit IV.
+    XC20P,
+}
+
+impl ContentEncryption {
+    /// Returns the `enc` header value.
+    pub fn name(self) -> &'static str {
+        match self {
+            ContentEncryption::C20P => "C20P",
+            ContentEncryption::XC20P => "XC20P",
+        }
+    }
+
+    /// Parses an `enc` header value.
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "C20P" => Some(ContentEncryption::C20P),
+            "XC20P" => Some(ContentEncryption::XC20P),
+            _ => None,
+        }
+    }
+
+    /// Returns the IV length in bytes.
+    fn iv_len(self) -> usize {
+        match self {
+            ContentEncryption::C20P => 12,
+            ContentEncryption::XC20P => 24,
+        }
+    }
+
+    /// Returns the ChaCha20-Poly1305 key and nonce for `cek` and `iv`.
+    fn key_and_nonce(self, cek: &[u8; 32], iv: &[u8]) -> ([u8; 32], [u8; 12]) {
+        let mut nonce = [0u8; 12];
+
+        match self {
+            ContentEncryption::C20P => {
+                nonce.copy_from_slice(iv);
+                (*cek, nonce)
+            }
+            ContentEncryption::XC20P => {
+                let mut input = [0u8; 16];
+                input.copy_from_slice(&iv[..16]);
+                nonce[4..].copy_from_slice(&iv[16..]);
+                (hchacha20(cek, &input), nonce)
+            }
+        }
+    }
+}
+
+/// Members of a JWE protected header.
+///
+/// `alg` is always `ECDH-ES`, and `epk` is generated by [`encrypt_jwe`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JweHeader {
+    /// Content encryption algorithm (`enc`).
+    pub enc: ContentEncryption,
+    /// Identifier of the recipient key (`kid`).
+    pub kid: Option<String>,
+    /// Media type of the whole object (`typ`).
+    pub typ: Option<String>,
+    /// Media type of the plaintext (`cty`), e.g. `JWT` for nested tokens.
+    pub cty: Option<String>,
+    /// Agreement PartyUInfo (`apu`), mixed into the key derivation.
+    pub apu: Vec<u8>,
+    /// Agreement PartyVInfo (`apv`), mixed into the key derivation.
+    pub apv: Vec<u8>,
+}
+
+impl JweHeader {
+    /// Returns a header with the given content encryption and no optional
+    /// members.
+    pub fn new(enc: ContentEncryption) -> Self {
+        Self {
+            enc,
+            kid: None,
+            typ: None,
+            cty: None,
+            apu: Vec::new(),
+            apv: Vec::new(),
+        }
+    }
+
+    /// Builds the JSON header with the ephemeral public key `epk`.
+    fn to_value(&self, epk: [u8; 32]) -> Value {
+        let epk = Jwk {
+            key: JwkKey::X25519 {
+                public: epk,
+                private: None,
+            },
+            kid: None,
+        };
+
+        let mut members = vec![
+            ("alg".to_string(), Value::from(ALG)),
+            ("enc".to_string(), Value::from(self.enc.name())),
+        ];
+        push_optional(&mut members, "kid", &self.kid);
+        push_optional(&mut members, "typ", &self.typ);
+        push_optional(&mut members, "cty", &self.cty);
+
+        // Encoding a public JWK cannot fail.
+        members.push(("epk".to_string(), epk.to_value().unwrap_or(Value::Null)));
+
+        if !self.apu.is_empty() {
+            members.push(("apu".to_string(), Value::from(b64(&self.apu))));
+        }
+        if !self.apv.is_empty() {
+            members.push(("apv".to_string(), Value::from(b64(&self.apv))));
+        }
+
+        Value::Object(members)
+    }
+
+    /// Reads the header and its ephemeral public key.
+    fn from_value(header: &Value) -> Result<(Self, [u8; 32]), JoseError> {
+        if required_str(header, "alg")? != ALG {
+            return Err(JoseError::UnsupportedAlgorithm);
+        }
+
+        let enc = ContentEncryption::from_name(required_str(header, "enc")?)
+            .ok_or(JoseError::UnsupportedAlgorithm)?;
+
+        reject_extensions(header)?;
+
+        let epk = Jwk::from_value(header.get("epk").ok_or(JoseError::InvalidHeader)?)?;
+        let epk = match epk.key {
+            JwkKey::X25519 {
+                public,
+                private: None,
+            } => public,
+            JwkKey::X25519 { .. } => return Err(JoseError::InvalidKey),
+            JwkKey::Ed25519 { .. } => return Err(JoseError::UnsupportedAlgorithm),
+        };
+
+        let party_info = |name| match optional_str(header, name)? {
+            Some(encoded) => unb64(&encoded),
+            None => Ok(Vec::new()),
+        };
+
+        let parsed = Self {
+            enc,
+            kid: optional_str(header, "kid")?,
+            typ: optional_str(header, "typ")?,
+            cty: optional_str(header, "cty")?,
+            apu: party_info("apu")?,
+            apv: party_info("apv")?,
+        };
+
+        Ok((parsed, epk))
+    }
+}
+
+/// Encrypts `plaintext` to the X25519 public key `recipient` as a compact
+/// JWE.
+///
+/// # Returns
+///
+/// - `Ok(token)` on success
+/// - `Err(WeakPublicKey)` if `recipient` is a low-order point
+pub fn encrypt_jwe(
+    plaintext: &[u8],
+    header: &JweHeader,
+    recipient: &[u8; 32],
+) -> Result<String, JoseError> {
+    let (epk, mut ephemeral) = x25519::generate_keypair();
+    let shared = agree(&ephemeral, recipient);
+    ephemeral.fill(0);
+    let mut cek = derive_key(&shared?, header);
+
+    let mut token = b64(header.to_value(epk).to_json().as_bytes());
+
+    let mut iv = vec![0u8; header.enc.iv_len()];
+    Csprng::new().fill_bytes(&mut iv);
+
+    let (mut key, nonce) = header.enc.key_and_nonce(&cek, &iv);
+    cek.fill(0);
+
+    let mut ciphertext = vec![0u8; plaintext.len()];
+    let mut tag = [0u8; TAG_LEN];
+    encrypt_with_aad(
+        &key,
+        &nonce,
+        token.as_bytes(),
+        plaintext,
+        &mut ciphertext,
+        &mut tag,
+    );
+    key.fill(0);
+
+    for part in ["", &b64(&iv), &b64(&ciphertext), &b64(&tag)] {
+        token.push('.');
+        token.push_str(part);
+    }
+
+    Ok(token)
+}
+
+/// Decrypts a compact JWE encrypted to the X25519 key `private`.
+///
+/// # Returns
+///
+/// - `Ok((header, plaintext))` on success
+/// - `Err(UnsupportedAlgorithm)` if `alg` is not `ECDH-ES` or `enc` is
+///   not `C20P` or `XC20P`
+/// - `Err(DecryptionFailed)` if the ciphertext or header was tampered with,
+///   or was not encrypted to `private`
+/// - `Err(JoseError)` if the token is malformed
+pub fn decrypt_jwe(token: &str, private: &[u8; 32]) -> Result<(JweHeader, Vec<u8>), JoseError> {
+    let mut parts = token.split('.');
+
+    let (encoded, iv, ciphertext, tag) = match (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) {
+        (Some(header), Some(""), Some(iv), Some(ciphertext), Some(tag), None) => {
+            (header, iv, ciphertext, tag)
+        }
+        _ => return Err(JoseError::InvalidFormat),
+    };
+
+    let (header, epk) = JweHeader::from_value(&decode_object(encoded)?)?;
+
+    let iv = unb64(iv)?;
+    let ciphertext = unb64(ciphertext)?;
+    let tag: [u8; TAG_LEN] = unb64(tag)?
+        .as_slice()
+        .try_into()
+        .map_err(|_| JoseError::InvalidFormat)?;
+
+    if iv.len() != header.enc.iv_len() {
+        return Err(JoseError::InvalidFormat);
+    }
+
+    let mut cek = derive_key(&agree(private, &epk)?, &header);
+    let (mut key, nonce) = header.enc.key_and_nonce(&cek, &iv);
+    cek.fill(0);
+
+    let mut plaintext = vec![0u8; ciphertext.len()];
+    let result = decrypt_with_aad(
+        &key,
+        &nonce,
+        encoded.as_bytes(),
+        &ciphertext,
+        &tag,
+        &mut plaintext,
+    );
+    key.fill(0);
+
+    if result.is_err() {
+        plaintext.fill(0);
+        return Err(JoseError::DecryptionFailed);
+    }
+
+    Ok((header, plaintext))
+}
+
+/// Computes the X25519 shared secret, rejecting the all-zero output of
+/// low-order points.
+fn agree(private: &[u8; 32], public: &[u8; 32]) -> Result<[u8; 32], JoseError> {
+    let mut shared = x25519::exchange(private, public);
+
+    if shared.iter().fold(0u8, |acc, &b| acc | b) == 0 {
+        shared.fill(0);
+        return Err(JoseError::WeakPublicKey);
+    }
+
+    Ok(shared)
+}
+
+/// Derives the 256-bit content encryption key with the single-round
+/// Concat KDF of RFC 7518, Section 4.6.2.
+fn derive_key(shared: &[u8; 32], header: &JweHeader) -> [u8; 32] {
+    let enc = header.enc.name().as_bytes();
+
+    let mut input =
+        Vec::with_capacity(4 + 32 + 12 + enc.len() + header.apu.len() + header.apv.len() + 4);
+    input.extend_from_slice(&1u32.to_be_bytes());
+    input.extend_from_slice(shared);
+    for field in [enc, &header.apu, &header.apv] {
+        input.extend_from_slice(&(field.len() as u32).to_be_bytes());
+        input.extend_from_slice(field);
+    }
+    input.extend_from_slice(&256u32.to_be_bytes());
+
+    let key = sha256(&input).into();
+    input.fill(0);
+
+    key
+}
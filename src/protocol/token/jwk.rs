@@ -0,0 +1,256 @@
+//! OKP JSON Web Keys (RFC 8037) and thumbprints (RFC 7638).
+//!
+//! ```text
+//! {"kty":"OKP","crv":"Ed25519","x":BASE64URL(public key),"d":BASE64URL(seed)}
+//! {"kty":"OKP","crv":"X25519","x":BASE64URL(public key),"d":BASE64URL(secret)}
+//! ```
+//!
+//! The private member `d` is optional. Unknown members (`use`, `alg`,
+//! `key_ops`, ...) are ignored when decoding.
+
+use super::core::{JoseError, b64, optional_str, push_optional, required_str, unb64};
+use crate::encoding::json::Value;
+use crate::hash::sha256;
+use crate::keys::{ed25519, x25519};
+
+/// Key type of Curve25519 keys.
+const KTY: &str = "OKP";
+
+/// Curve names.
+const ED25519: &str = "Ed25519";
+const X25519: &str = "X25519";
+
+/// Key material of an OKP JSON Web Key.
+///
+/// X25519 secrets are wiped when the key is dropped.
+#[derive(Clone)]
+pub enum JwkKey {
+    /// An Ed25519 key, with its signing key if private.
+    Ed25519 {
+        /// The verification key.
+        public: ed25519::PublicKey,
+        /// The signing key, for private JWKs.
+        private: Option<ed25519::PrivateKey>,
+    },
+    /// An X25519 key, with its secret if private.
+    X25519 {
+        /// The public key (Montgomery `u` coordinate).
+        public: [u8; 32],
+        /// The secret, for private JWKs.
+        private: Option<[u8; 32]>,
+    },
+}
+
+impl Drop for JwkKey {
+    fn drop(&mut self) {
+        if let JwkKey::X25519 {
+            private: Some(secret),
+            ..
+        } = self
+        {
+            secret.fill(0);
+        }
+    }
+}
+
+/// An OKP JSON Web Key, with an optional key id.
+#[derive(Clone)]
+pub struct Jwk {
+    /// The key material.
+    pub key: JwkKey,
+    /// The `kid` member.
+    pub kid: Option<String>,
+}
+
+impl Jwk {
+    /// Returns a private Ed25519 JWK.
+    pub fn from_ed25519(private: ed25519::PrivateKey) -> Self {
+        Self {
+            key: JwkKey::Ed25519 {
+                public: private.public_key(),
+                private: Some(private),
+            },
+            kid: None,
+        }
+    }
+
+    /// Returns a private X25519 JWK.
+    pub fn from_x25519(secret: [u8; 32]) -> Self {
+        Self {
+            key: JwkKey::X25519 {
+                public: x25519::public_key(&secret),
+                private: Some(secret),
+            },
+            kid: None,
+        }
+    }
+
+    /// Returns the same key without its private part.
+    pub fn to_public(&self) -> Self {
+        let key = match &self.key {
+            JwkKey::Ed25519 { public, .. } => JwkKey::Ed25519 {
+                public: *public,
+                private: None,
+            },
+            JwkKey::X25519 { public, .. } => JwkKey::X25519 {
+                public: *public,
+                private: None,
+            },
+        };
+
+        Self {
+            key,
+            kid: self.kid.clone(),
+        }
+    }
+
+    /// Returns whether the key holds private material.
+    pub fn is_private(&self) -> bool {
+        match &self.key {
+            JwkKey::Ed25519 { private, .. } => private.is_some(),
+            JwkKey::X25519 { private, .. } => private.is_some(),
+        }
+    }
+
+    /// Encodes the key as JSON, including the private part if present.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(json)` on success
+    /// - `Err(MissingSeed)` for an Ed25519 key without a seed
+    pub fn to_json(&self) -> Result<String, JoseError> {
+        Ok(self.to_value()?.to_json())
+    }
+
+    /// Builds the JSON object of the key.
+    pub(crate) fn to_value(&self) -> Result<Value, JoseError> {
+        let (crv, public, mut secret) = match &self.key {
+            JwkKey::Ed25519 { public, private } => {
+                let seed = match private {
+                    Some(private) => Some(private.seed().ok_or(JoseError::MissingSeed)?),
+                    None => None,
+                };
+                (ED25519, public.to_bytes(), seed)
+            }
+            JwkKey::X25519 { public, private } => (X25519, *public, *private),
+        };
+
+        let mut members = vec![
+            ("kty".to_string(), Value::from(KTY)),
+            ("crv".to_string(), Value::from(crv)),
+            ("x".to_string(), Value::from(b64(&public))),
+        ];
+
+        if let Some(secret) = secret.as_mut() {
+            members.push(("d".to_string(), Value::from(b64(secret))));
+            secret.fill(0);
+        }
+
+        push_optional(&mut members, "kid", &self.kid);
+
+        Ok(Value::Object(members))
+    }
+
+    /// Decodes an OKP key from JSON.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(jwk)` on success
+    /// - `Err(UnsupportedAlgorithm)` for other key types or curves
+    /// - `Err(InvalidKey)` if `x` or `d` is malformed, or `d` does not
+    ///   match `x`
+    pub fn from_json(input: &str) -> Result<Self, JoseError> {
+        Self::from_value(&Value::parse(input)?)
+    }
+
+    /// Decodes an OKP key from a JSON object.
+    pub(crate) fn from_value(value: &Value) -> Result<Self, JoseError> {
+        if value.as_object().is_none() {
+            return Err(JoseError::InvalidHeader);
+        }
+
+        if required_str(value, "kty")? != KTY {
+            return Err(JoseError::UnsupportedAlgorithm);
+        }
+
+        let crv = required_str(value, "crv")?;
+        if crv != ED25519 && crv != X25519 {
+            return Err(JoseError::UnsupportedAlgorithm);
+        }
+
+        let public = key_bytes(value, "x")?.ok_or(JoseError::InvalidKey)?;
+        let mut secret = key_bytes(value, "d")?;
+
+        let key = match crv {
+            ED25519 => {
+                let public =
+                    ed25519::PublicKey::from_bytes(public).map_err(|_| JoseError::InvalidKey)?;
+                let private = secret.map(ed25519::PrivateKey::from_seed);
+
+                if private.is_some_and(|private| private.public_key() != public) {
+                    return Err(JoseError::InvalidKey);
+                }
+
+                JwkKey::Ed25519 { public, private }
+            }
+            X25519 => {
+                if secret.is_some_and(|secret| x25519::public_key(&secret) != public) {
+                    return Err(JoseError::InvalidKey);
+                }
+
+                JwkKey::X25519 {
+                    public,
+                    private: secret,
+                }
+            }
+            _ => return Err(JoseError::UnsupportedAlgorithm),
+        };
+
+        if let Some(secret) = secret.as_mut() {
+            secret.fill(0);
+        }
+
+        Ok(Self {
+            key,
+            kid: optional_str(value, "kid")?,
+        })
+    }
+
+    /// Computes the RFC 7638 thumbprint: the base64url SHA-256 of the
+    /// required public members in lexicographic order.
+    ///
+    /// The thumbprint is the same for the public and private forms of a
+    /// key and is commonly used as its `kid`.
+    pub fn thumbprint(&self) -> String {
+        let (crv, public) = match &self.key {
+            JwkKey::Ed25519 { public, .. } => (ED25519, public.to_bytes()),
+            JwkKey::X25519 { public, .. } => (X25519, *public),
+        };
+
+        let canonical = Value::Object(vec![
+            ("crv".to_string(), Value::from(crv)),
+            ("kty".to_string(), Value::from(KTY)),
+            ("x".to_string(), Value::from(b64(&public))),
+        ]);
+
+        let digest: [u8; 32] = sha256(canonical.to_json().as_bytes()).into();
+
+        b64(&digest)
+    }
+}
+
+/// Decodes the optional 32-byte base64url member `name`.
+fn key_bytes(value: &Value, name: &str) -> Result<Option<[u8; 32]>, JoseError> {
+    let Some(encoded) = optional_str(value, name)? else {
+        return Ok(None);
+    };
+
+    let mut bytes = unb64(&encoded).map_err(|_| JoseError::InvalidKey)?;
+    let key = bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| JoseError::InvalidKey);
+    bytes.fill(0);
+
+    key.map(Some)
+}
@@ -0,0 +1,112 @@
+//! Compact JWS with the `EdDSA` algorithm (RFC 7515, RFC 8037).
+//!
+//! ```text
+//! signing input = BASE64URL(header) || "." || BASE64URL(payload)
+//! token         = signing input || "." || BASE64URL(Ed25519(signing input))
+//! ```
+
+use super::core::{
+    JoseError, b64, decode_object, optional_str, push_optional, reject_extensions, required_str,
+    unb64,
+};
+use crate::encoding::json::Value;
+use crate::keys::ed25519::{self, PrivateKey, PublicKey, Signature};
+
+/// The JWS algorithm name of Ed25519 signatures.
+const ALG: &str = "EdDSA";
+
+/// Optional members of a JWS protected header.
+///
+/// The `alg` member is always `EdDSA`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JwsHeader {
+    /// Media type of the whole object (`typ`), e.g. `JWT`.
+    pub typ: Option<String>,
+    /// Identifier of the signing key (`kid`).
+    pub kid: Option<String>,
+}
+
+impl JwsHeader {
+    /// Builds the JSON header.
+    fn to_value(&self) -> Value {
+        let mut members = vec![("alg".to_string(), Value::from(ALG))];
+        push_optional(&mut members, "typ", &self.typ);
+        push_optional(&mut members, "kid", &self.kid);
+
+        Value::Object(members)
+    }
+
+    /// Reads the header, which must use `EdDSA` and no extensions.
+    fn from_value(header: &Value) -> Result<Self, JoseError> {
+        if required_str(header, "alg")? != ALG {
+            return Err(JoseError::UnsupportedAlgorithm);
+        }
+
+        reject_extensions(header)?;
+
+        Ok(Self {
+            typ: optional_str(header, "typ")?,
+            kid: optional_str(header, "kid")?,
+        })
+    }
+}
+
+/// Signs `payload` as a compact JWS.
+pub fn sign_jws(payload: &[u8], header: &JwsHeader, private: &PrivateKey) -> String {
+    let mut token = b64(header.to_value().to_json().as_bytes());
+    token.push('.');
+    token.push_str(&b64(payload));
+
+    let signature = ed25519::sign(token.as_bytes(), private.public_key(), *private);
+
+    token.push('.');
+    token.push_str(&b64(&signature.to_bytes()));
+
+    token
+}
+
+/// Decodes the header of a compact JWS **without** verifying it.
+///
+/// Useful to select the verification key by `kid`; the result must not be
+/// trusted until [`verify_jws`] succeeds.
+pub fn decode_jws_header(token: &str) -> Result<JwsHeader, JoseError> {
+    let (header, _, _) = split(token)?;
+
+    JwsHeader::from_value(&decode_object(header)?)
+}
+
+/// Verifies a compact JWS signed with `EdDSA`.
+///
+/// # Returns
+///
+/// - `Ok((header, payload))` if the signature is valid
+/// - `Err(UnsupportedAlgorithm)` if `alg` is not `EdDSA`
+/// - `Err(InvalidSignature)` if the signature does not verify
+/// - `Err(JoseError)` if the token is malformed
+pub fn verify_jws(token: &str, public: &PublicKey) -> Result<(JwsHeader, Vec<u8>), JoseError> {
+    let (header, payload, signature) = split(token)?;
+
+    let parsed = JwsHeader::from_value(&decode_object(header)?)?;
+    let payload_bytes = unb64(payload)?;
+    let signature =
+        Signature::from_slice(&unb64(signature)?).map_err(|_| JoseError::InvalidFormat)?;
+
+    // The signing input is the token up to the last dot.
+    let signed = &token[..header.len() + 1 + payload.len()];
+
+    if !ed25519::verify(signature, signed.as_bytes(), *public) {
+        return Err(JoseError::InvalidSignature);
+    }
+
+    Ok((parsed, payload_bytes))
+}
+
+/// Splits a compact JWS into its three parts.
+fn split(token: &str) -> Result<(&str, &str, &str), JoseError> {
+    let mut parts = token.split('.');
+
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(header), Some(payload), Some(signature), None) => Ok((header, payload, signature)),
+        _ => Err(JoseError::InvalidFormat),
+    }
+}
@@ -0,0 +1,266 @@
+//! JSON Web Tokens (RFC 7519) signed with `EdDSA`.
+//!
+//! A JWT is a compact JWS whose payload is a JSON claims set and whose
+//! header carries `"typ":"JWT"`. Time claims (`exp`, `nbf`, `iat`) are
+//! NumericDates: seconds since the Unix epoch.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::core::JoseError;
+use super::jws::{JwsHeader, sign_jws, verify_jws};
+use crate::encoding::json::{MAX_SAFE_INTEGER, Value};
+use crate::keys::ed25519::{PrivateKey, PublicKey};
+
+/// Header `typ` of JWTs.
+const TYP: &str = "JWT";
+
+/// Names of the registered claims held by [`Claims`] fields.
+const REGISTERED: [&str; 7] = ["iss", "sub", "aud", "exp", "nbf", "iat", "jti"];
+
+/// A JWT claims set.
+///
+/// Registered claims have dedicated fields; any other claim is kept in
+/// `custom`, in order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Claims {
+    /// Issuer (`iss`).
+    pub iss: Option<String>,
+    /// Subject (`sub`).
+    pub sub: Option<String>,
+    /// Audience (`aud`); encoded as a string when it has one element.
+    pub aud: Vec<String>,
+    /// Expiration time (`exp`).
+    pub exp: Option<i64>,
+    /// Not-before time (`nbf`).
+    pub nbf: Option<i64>,
+    /// Issued-at time (`iat`).
+    pub iat: Option<i64>,
+    /// Token identifier (`jti`).
+    pub jti: Option<String>,
+    /// Private and public claims other than the registered ones.
+    ///
+    /// Entries named like a registered claim are ignored when encoding.
+    pub custom: Vec<(String, Value)>,
+}
+
+impl Claims {
+    /// Returns the custom claim `name`.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.custom
+            .iter()
+            .find(|(claim, _)| claim == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Encodes the claims set as JSON.
+    pub fn to_json(&self) -> String {
+        let mut members = Vec::new();
+
+        let mut push = |name: &str, value: Value| members.push((name.to_string(), value));
+
+        if let Some(iss) = &self.iss {
+            push("iss", Value::from(iss.as_str()));
+        }
+        if let Some(sub) = &self.sub {
+            push("sub", Value::from(sub.as_str()));
+        }
+        match self.aud.as_slice() {
+            [] => {}
+            [aud] => push("aud", Value::from(aud.as_str())),
+            aud => push(
+                "aud",
+                Value::Array(aud.iter().map(|a| Value::from(a.as_str())).collect()),
+            ),
+        }
+        if let Some(exp) = self.exp {
+            push("exp", Value::from(exp));
+        }
+        if let Some(nbf) = self.nbf {
+            push("nbf", Value::from(nbf));
+        }
+        if let Some(iat) = self.iat {
+            push("iat", Value::from(iat));
+        }
+        if let Some(jti) = &self.jti {
+            push("jti", Value::from(jti.as_str()));
+        }
+
+        for (name, value) in &self.custom {
+            if !REGISTERED.contains(&name.as_str()) {
+                push(name, value.clone());
+            }
+        }
+
+        Value::Object(members).to_json()
+    }
+
+    /// Decodes a JSON claims set.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(claims)` on success
+    /// - `Err(InvalidClaims)` if the input is not a JSON object, or a
+    ///   registered claim has the wrong type
+    /// - `Err(InvalidJson)` if the input is not valid JSON
+    pub fn from_json(input: &str) -> Result<Self, JoseError> {
+        let value = Value::parse(input)?;
+        let members = value.as_object().ok_or(JoseError::InvalidClaims)?;
+
+        let mut claims = Claims::default();
+
+        for (name, value) in members {
+            match name.as_str() {
+                "iss" => claims.iss = Some(string_claim(value)?),
+                "sub" => claims.sub = Some(string_claim(value)?),
+                "jti" => claims.jti = Some(string_claim(value)?),
+                "exp" => claims.exp = Some(date_claim(value)?),
+                "nbf" => claims.nbf = Some(date_claim(value)?),
+                "iat" => claims.iat = Some(date_claim(value)?),
+                "aud" => {
+                    claims.aud = match value {
+                        Value::String(aud) => vec![aud.clone()],
+                        Value::Array(items) => {
+                            items.iter().map(string_claim).collect::<Result<_, _>>()?
+                        }
+                        _ => return Err(JoseError::InvalidClaims),
+                    }
+                }
+                _ => claims.custom.push((name.clone(), value.clone())),
+            }
+        }
+
+        Ok(claims)
+    }
+}
+
+/// Rules a JWT must satisfy to be accepted by [`verify_jwt`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Validation {
+    /// Current time, in seconds since the Unix epoch.
+    pub now: i64,
+    /// Tolerated clock skew, in seconds, for `exp` and `nbf`.
+    pub leeway: i64,
+    /// Audience this verifier identifies as. A token with an `aud` claim
+    /// is rejected unless it lists this value.
+    pub audience: Option<String>,
+    /// Required issuer, if any.
+    pub issuer: Option<String>,
+    /// Whether tokens without `exp` are rejected.
+    pub require_exp: bool,
+}
+
+impl Validation {
+    /// Returns the default rules at time `now`: no leeway, no audience or
+    /// issuer, and `exp` required.
+    pub fn new(now: i64) -> Self {
+        Self {
+            now,
+            leeway: 0,
+            audience: None,
+            issuer: None,
+            require_exp: true,
+        }
+    }
+
+    /// Returns the default rules at the current system time.
+    pub fn at_current_time() -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as i64);
+
+        Self::new(now)
+    }
+
+    /// Checks `claims` against the rules.
+    fn check(&self, claims: &Claims) -> Result<(), JoseError> {
+        match claims.exp {
+            Some(exp) if self.now.saturating_sub(self.leeway) >= exp => {
+                return Err(JoseError::Expired);
+            }
+            None if self.require_exp => return Err(JoseError::InvalidClaims),
+            _ => {}
+        }
+
+        if let Some(nbf) = claims.nbf
+            && self.now.saturating_add(self.leeway) < nbf
+        {
+            return Err(JoseError::NotYetValid);
+        }
+
+        if !claims.aud.is_empty() {
+            let accepted = self
+                .audience
+                .as_ref()
+                .is_some_and(|audience| claims.aud.contains(audience));
+
+            if !accepted {
+                return Err(JoseError::InvalidAudience);
+            }
+        }
+
+        if let Some(issuer) = &self.issuer
+            && claims.iss.as_ref() != Some(issuer)
+        {
+            return Err(JoseError::InvalidIssuer);
+        }
+
+        Ok(())
+    }
+}
+
+/// Signs `claims` as a JWT, with header `{"alg":"EdDSA","typ":"JWT"}` and
+/// an optional `kid`.
+pub fn sign_jwt(claims: &Claims, kid: Option<&str>, private: &PrivateKey) -> String {
+    let header = JwsHeader {
+        typ: Some(TYP.to_string()),
+        kid: kid.map(str::to_string),
+    };
+
+    sign_jws(claims.to_json().as_bytes(), &header, private)
+}
+
+/// Verifies a JWT signature and validates its claims.
+///
+/// A `typ` header, if present, must be `JWT`.
+///
+/// # Returns
+///
+/// - `Ok(claims)` if the signature is valid and the claims satisfy
+///   `validation`
+/// - `Err(Expired)`, `Err(NotYetValid)`, `Err(InvalidAudience)` or
+///   `Err(InvalidIssuer)` if a claim check fails
+/// - `Err(JoseError)` if the token is malformed or the signature invalid
+pub fn verify_jwt(
+    token: &str,
+    public: &PublicKey,
+    validation: &Validation,
+) -> Result<Claims, JoseError> {
+    let (header, payload) = verify_jws(token, public)?;
+
+    if header.typ.is_some_and(|typ| !typ.eq_ignore_ascii_case(TYP)) {
+        return Err(JoseError::InvalidHeader);
+    }
+
+    let payload = String::from_utf8(payload).map_err(|_| JoseError::InvalidClaims)?;
+    let claims = Claims::from_json(&payload)?;
+
+    validation.check(&claims)?;
+
+    Ok(claims)
+}
+
+/// Reads a string claim.
+fn string_claim(value: &Value) -> Result<String, JoseError> {
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or(JoseError::InvalidClaims)
+}
+
+/// Reads a NumericDate claim, rounding fractional seconds down.
+fn date_claim(value: &Value) -> Result<i64, JoseError> {
+    match value.as_f64() {
+        Some(n) if n.abs() <= MAX_SAFE_INTEGER => Ok(n.floor() as i64),
+        _ => Err(JoseError::InvalidClaims),
+    }
+}
@@ -0,0 +1,33 @@
+//! JOSE: JSON Web Keys, Signatures, Tokens and Encryption.
+//!
+//! This module implements the JOSE structures needed to issue and consume
+//! tokens with the crate's Curve25519 keys:
+//!
+//! <https://www.rfc-editor.org/rfc/rfc7515> (JWS),
+//! <https://www.rfc-editor.org/rfc/rfc7516> (JWE),
+//! <https://www.rfc-editor.org/rfc/rfc7518> (Concat KDF),
+//! <https://www.rfc-editor.org/rfc/rfc7519> (JWT),
+//! <https://www.rfc-editor.org/rfc/rfc8037> (OKP keys and EdDSA).
+//!
+//! The implementation is split into:
+//!
+//! - `jwk`: OKP JSON Web Keys (Ed25519 and X25519) and thumbprints
+//! - `jws`: compact JWS with the `EdDSA` algorithm
+//! - `jwt`: JWT claims and their validation
+//! - `jwe`: compact JWE with `ECDH-ES` over X25519 and `C20P` / `XC20P`
+//! - `core`: the error type, shared encoding helpers and the public API
+
+/// Public JOSE API.
+pub mod core;
+
+/// OKP JSON Web Keys.
+pub(crate) mod jwk;
+
+/// Compact JWS signatures.
+pub(crate) mod jws;
+
+/// JSON Web Tokens.
+pub(crate) mod jwt;
+
+/// Compact JWE encryption.
+pub(crate) mod jwe;
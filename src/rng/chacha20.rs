@@ -141,6 +141,57 @@ pub(crate) fn block(key: &[u8; 32], counter: u32, nonce: &[u8; 12]) -> [u8; 64]
     out
 }
 
+/// Derives a 256-bit subkey using the HChaCha20 function.
+///
+/// HChaCha20 runs the ChaCha20 permutation over the key and a 128-bit input
+/// without the final feed-forward, and returns state words 0..4 and 12..16.
+/// It is the key-derivation step used by XChaCha20 and by constructions
+/// that bind a keystream to a long, non-secret value (e.g. a synthetic IV).
+///
+/// # Parameters
+/// - `key`: 256-bit secret key (32 bytes)
+/// - `input`: 128-bit input (nonce prefix or synthetic IV)
+///
+/// # Returns
+/// A 32-byte subkey suitable for use as a ChaCha20 key.
+///
+/// # Security Notes
+/// - The output is a PRF of `(key, input)` and must be treated as secret.
+pub(crate) fn hchacha20(key: &[u8; 32], input: &[u8; 16]) -> [u8; 32] {
+    let mut state = [0u32; 16];
+
+    state[0..4].copy_from_slice(&CHACHA20_CONSTANTS);
+
+    state[4..12]
+        .iter_mut()
+        .zip(key.chunks_exact(4))
+        .for_each(|(s, k)| {
+            *s = u32::from_le_bytes(k.try_into().unwrap());
+        });
+
+    state[12..16]
+        .iter_mut()
+        .zip(input.chunks_exact(4))
+        .for_each(|(s, n)| {
+            *s = u32::from_le_bytes(n.try_into().unwrap());
+        });
+
+    rounds(&mut state);
+
+    // No feed-forward: output the first and last rows of the state
+    let mut out = [0u8; 32];
+    out[..16]
+        .chunks_exact_mut(4)
+        .zip(&state[0..4])
+        .for_each(|(chunk, word)| chunk.copy_from_slice(&word.to_le_bytes()));
+    out[16..]
+        .chunks_exact_mut(4)
+        .zip(&state[12..16])
+        .for_each(|(chunk, word)| chunk.copy_from_slice(&word.to_le_bytes()));
+
+    out
+}
+
 /// XORs input data with the ChaCha20 keystream.
 ///
 /// This function implements the ChaCha20 stream cipher by generating
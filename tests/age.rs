@@ -0,0 +1,250 @@
+use std::fs;
+use std::path::Path;
+
+use cryptal::encryption::age::{
+    AgeError, Identity, Recipient, decrypt, decrypt_with_padding, encrypt, encrypt_with_padding,
+};
+use cryptal::encryption::padding::{Padding, pad};
+use cryptal::hash::sha256;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// A parsed testkit vector.
+struct Vector {
+    expect: String,
+    payload: Option<String>,
+    identities: Vec<Identity>,
+    file: Vec<u8>,
+}
+
+fn load(path: &Path) -> Vector {
+    let data = fs::read(path).unwrap();
+    let split = data.windows(2).position(|w| w == b"\n\n").unwrap();
+
+    let mut vector = Vector {
+        expect: String::new(),
+        payload: None,
+        identities: Vec::new(),
+        file: data[split + 2..].to_vec(),
+    };
+
+    for line in std::str::from_utf8(&data[..split]).unwrap().lines() {
+        let (key, value) = line.split_once(": ").unwrap();
+
+        match key {
+            "expect" => vector.expect = value.to_string(),
+            "payload" => vector.payload = Some(value.to_string()),
+            "identity" => vector.identities.push(Identity::parse(value).unwrap()),
+            _ => {}
+        }
+    }
+
+    vector
+}
+
+#[test]
+fn age_testkit() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/testdata/age");
+    let mut count = 0;
+
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some() {
+            continue;
+        }
+
+        let name = path.file_name().unwrap().to_str().unwrap().to_string();
+        let vector = load(&path);
+        let result = decrypt(&vector.identities, &vector.file);
+
+        match vector.expect.as_str() {
+            "success" => {
+                let plaintext = result.unwrap_or_else(|e| panic!("{name}: {e:?}"));
+                assert_eq!(
+                    Some(hex(&<[u8; 32]>::from(sha256(&plaintext)))),
+                    vector.payload,
+                    "{name}"
+                );
+            }
+            "no match" => assert_eq!(result, Err(AgeError::NoMatchingIdentity), "{name}"),
+            "HMAC failure" => assert_eq!(result, Err(AgeError::InvalidMac), "{name}"),
+            "payload failure" => assert_eq!(result, Err(AgeError::InvalidPayload), "{name}"),
+            "header failure" => assert!(
+                matches!(
+                    result,
+                    Err(AgeError::InvalidHeader) | Err(AgeError::UnsupportedVersion)
+                ),
+                "{name}: {result:?}"
+            ),
+            other => panic!("{name}: unknown expectation {other}"),
+        }
+
+        count += 1;
+    }
+
+    assert_eq!(count, 52);
+}
+
+#[test]
+fn age_version_unsupported() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/testdata/age/version_unsupported");
+    let vector = load(&path);
+
+    assert_eq!(
+        decrypt(&vector.identities, &vector.file),
+        Err(AgeError::UnsupportedVersion)
+    );
+}
+
+#[test]
+fn age_key_encoding() {
+    let identity = Identity::parse(
+        "AGE-SECRET-KEY-1XMWWC06LY3EE5RYTXM9MFLAZ2U56JJJ36S0MYPDRWSVLUL66MV4QX3S7F6",
+    )
+    .unwrap();
+
+    assert_eq!(
+        identity.encode(),
+        "AGE-SECRET-KEY-1XMWWC06LY3EE5RYTXM9MFLAZ2U56JJJ36S0MYPDRWSVLUL66MV4QX3S7F6"
+    );
+
+    let recipient = identity.to_public();
+    assert_eq!(Recipient::parse(&recipient.encode()), Ok(recipient));
+}
+
+#[test]
+fn age_invalid_keys() {
+    let identity = "AGE-SECRET-KEY-1XMWWC06LY3EE5RYTXM9MFLAZ2U56JJJ36S0MYPDRWSVLUL66MV4QX3S7F6";
+    let recipient = Identity::parse(identity).unwrap().to_public().encode();
+
+    // Wrong case.
+    assert!(Identity::parse(&identity.to_ascii_lowercase()).is_err());
+    assert_eq!(
+        Recipient::parse(&recipient.to_ascii_uppercase()),
+        Err(AgeError::InvalidKey)
+    );
+
+    // Wrong HRP.
+    assert!(Identity::parse(&recipient.to_ascii_uppercase()).is_err());
+    assert_eq!(
+        Recipient::parse(&identity.to_ascii_lowercase()),
+        Err(AgeError::InvalidKey)
+    );
+
+    // Bad checksum.
+    let mut corrupted = recipient.clone();
+    let last = if corrupted.ends_with('q') { "p" } else { "q" };
+    corrupted.replace_range(corrupted.len() - 1.., last);
+    assert_eq!(Recipient::parse(&corrupted), Err(AgeError::InvalidKey));
+}
+
+#[test]
+fn age_roundtrip() {
+    let identity = Identity::generate();
+    let recipient = identity.to_public();
+
+    // Empty, single-chunk, exactly one chunk and multi-chunk payloads.
+    for len in [0, 1, 1000, 65536, 65537, 200_000] {
+        let plaintext: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+
+        let file = encrypt(&[recipient], &plaintext).unwrap();
+        assert!(file.starts_with(b"age-encryption.org/v1\n-> X25519 "));
+
+        assert_eq!(
+            decrypt(std::slice::from_ref(&identity), &file).unwrap(),
+            plaintext
+        );
+    }
+}
+
+#[test]
+fn age_padding() {
+    let identity = Identity::generate();
+    let recipient = identity.to_public();
+    let identities = [identity];
+
+    // Padding ending exactly at, or spilling over, a chunk boundary.
+    for len in [0, 1000, 65535, 65536, 200_000] {
+        let plaintext: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+
+        for padding in [Padding::Padme, Padding::Bucket(65536)] {
+            let file = encrypt_with_padding(&[recipient], &plaintext, padding).unwrap();
+
+            assert_eq!(decrypt_with_padding(&identities, &file).unwrap(), plaintext);
+
+            // Other implementations see the padded plaintext.
+            assert_eq!(
+                decrypt(&identities, &file).unwrap(),
+                pad(padding, &plaintext).unwrap()
+            );
+        }
+    }
+
+    assert_eq!(
+        encrypt_with_padding(&[recipient], b"hello", Padding::Bucket(0)),
+        Err(AgeError::InvalidPadding)
+    );
+
+    let unpadded = encrypt(&[recipient], b"hello").unwrap();
+    assert_eq!(
+        decrypt_with_padding(&identities, &unpadded),
+        Err(AgeError::InvalidPayload)
+    );
+}
+
+#[test]
+fn age_multiple_recipients() {
+    let alice = Identity::generate();
+    let bob = Identity::generate();
+    let eve = Identity::generate();
+
+    let file = encrypt(&[alice.to_public(), bob.to_public()], b"hello").unwrap();
+
+    assert_eq!(decrypt(&[alice], &file).unwrap(), b"hello");
+    assert_eq!(decrypt(&[eve, bob], &file).unwrap(), b"hello");
+}
+
+#[test]
+fn age_no_recipients() {
+    assert_eq!(encrypt(&[], b"hello"), Err(AgeError::NoRecipients));
+}
+
+#[test]
+fn age_wrong_identity() {
+    let file = encrypt(&[Identity::generate().to_public()], b"hello").unwrap();
+
+    assert_eq!(
+        decrypt(&[Identity::generate()], &file),
+        Err(AgeError::NoMatchingIdentity)
+    );
+    assert_eq!(decrypt(&[], &file), Err(AgeError::NoMatchingIdentity));
+}
+
+#[test]
+fn age_tampering() {
+    let identity = Identity::generate();
+    let file = encrypt(&[identity.to_public()], b"hello").unwrap();
+    let identities = [identity];
+
+    // Header MAC.
+    let mac = file.windows(4).position(|w| w == b"--- ").unwrap() + 4;
+    let mut tampered = file.clone();
+    tampered[mac] = if tampered[mac] == b'A' { b'B' } else { b'A' };
+    assert!(decrypt(&identities, &tampered).is_err());
+
+    // Payload.
+    let mut tampered = file.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    assert_eq!(
+        decrypt(&identities, &tampered),
+        Err(AgeError::InvalidPayload)
+    );
+
+    // Truncation.
+    assert_eq!(
+        decrypt(&identities, &file[..file.len() - 1]),
+        Err(AgeError::InvalidPayload)
+    );
+}
@@ -0,0 +1,71 @@
+use cryptal::derivation::{BcryptPbkdfError, bcrypt_pbkdf};
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn bcrypt_pbkdf_reference_vector() {
+    let mut out = [0u8; 32];
+    bcrypt_pbkdf(b"password", b"salt", 4, &mut out).unwrap();
+
+    assert_eq!(
+        out.to_vec(),
+        unhex("5bbf0cc293587f1c3635555c27796598d47e579071bf427e9d8fbe842aba34d9")
+    );
+}
+
+#[test]
+fn bcrypt_pbkdf_openssh_key_derivation() {
+    // Key and IV of an `ssh-keygen -N secret -a 16` private key, which
+    // decrypt its aes256-ctr private section.
+    let salt = unhex("8070edf015ab9c502e520f6b3dcae5b6");
+    let mut out = [0u8; 48];
+    bcrypt_pbkdf(b"secret", &salt, 16, &mut out).unwrap();
+
+    assert_eq!(
+        out.to_vec(),
+        unhex(
+            "6fe28fed267b4fbe2f6b913aa961bfdfba98d94ab903713fccff7af5344dba15\
+             c5a62cf1e34885201bbc7e1f1aa6db89"
+        )
+    );
+}
+
+#[test]
+fn bcrypt_pbkdf_interleaves_output() {
+    // Output bytes are spread across blocks, so a shorter output is not a
+    // prefix of a longer one.
+    let mut short = [0u8; 32];
+    let mut long = [0u8; 64];
+    bcrypt_pbkdf(b"password", b"salt", 2, &mut short).unwrap();
+    bcrypt_pbkdf(b"password", b"salt", 2, &mut long).unwrap();
+
+    assert_ne!(short[..], long[..32]);
+    assert_eq!(short[0], long[0]);
+}
+
+#[test]
+fn bcrypt_pbkdf_rejects_invalid_arguments() {
+    let mut out = [0u8; 32];
+
+    assert_eq!(
+        bcrypt_pbkdf(b"password", b"salt", 0, &mut out),
+        Err(BcryptPbkdfError::InvalidRounds)
+    );
+    assert_eq!(
+        bcrypt_pbkdf(b"", b"salt", 1, &mut out),
+        Err(BcryptPbkdfError::EmptyInput)
+    );
+    assert_eq!(
+        bcrypt_pbkdf(b"password", b"", 1, &mut out),
+        Err(BcryptPbkdfError::EmptyInput)
+    );
+    assert_eq!(
+        bcrypt_pbkdf(b"password", b"salt", 1, &mut [0u8; 1025]),
+        Err(BcryptPbkdfError::InvalidOutputLength)
+    );
+}
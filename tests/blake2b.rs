@@ -1,4 +1,4 @@
-use cryptal::hash::blake2b;
+use cryptal::hash::{blake2b, blake2b_keyed};
 
 fn blake2b_512_test(input: &[u8]) -> [u8; 64] {
     let got = blake2b(64, input);
@@ -143,3 +143,60 @@ fn blake2b_large_boundary_10k() {
     let buf = vec![0x55u8; 10_000];
     let _ = blake2b_512_test(&buf);
 }
+
+#[test]
+fn blake2b_full_final_block_vector() {
+    let out = [
+        0x86, 0x59, 0x39, 0xe1, 0x20, 0xe6, 0x80, 0x54, 0x38, 0x47, 0x88, 0x41, 0xaf, 0xb7, 0x39,
+        0xae, 0x42, 0x50, 0xcf, 0x37, 0x26, 0x53, 0x07, 0x8a, 0x06, 0x5c, 0xdc, 0xff, 0xfc, 0xa4,
+        0xca, 0xf7, 0x98, 0xe6, 0xd4, 0x62, 0xb6, 0x5d, 0x65, 0x8f, 0xc1, 0x65, 0x78, 0x26, 0x40,
+        0xed, 0xed, 0x70, 0x96, 0x34, 0x49, 0xae, 0x15, 0x00, 0xfb, 0x0f, 0x24, 0x98, 0x1d, 0x77,
+        0x27, 0xe2, 0x2c, 0x41,
+    ];
+
+    expect_blake2b_512_eq(&[0u8; 128], &out);
+}
+
+#[test]
+fn blake2b_keyed_empty_vector() {
+    let key: Vec<u8> = (0..64).collect();
+
+    let out = [
+        0x10, 0xeb, 0xb6, 0x77, 0x00, 0xb1, 0x86, 0x8e, 0xfb, 0x44, 0x17, 0x98, 0x7a, 0xcf, 0x46,
+        0x90, 0xae, 0x9d, 0x97, 0x2f, 0xb7, 0xa5, 0x90, 0xc2, 0xf0, 0x28, 0x71, 0x79, 0x9a, 0xaa,
+        0x47, 0x86, 0xb5, 0xe9, 0x96, 0xe8, 0xf0, 0xf4, 0xeb, 0x98, 0x1f, 0xc2, 0x14, 0xb0, 0x05,
+        0xf4, 0x2d, 0x2f, 0xf4, 0x23, 0x34, 0x99, 0x39, 0x16, 0x53, 0xdf, 0x7a, 0xef, 0xcb, 0xc1,
+        0x3f, 0xc5, 0x15, 0x68,
+    ];
+
+    assert_eq!(blake2b_keyed(64, &key, &[]), out);
+}
+
+#[test]
+fn blake2b_keyed_two_blocks_vector() {
+    let key: Vec<u8> = (0..64).collect();
+    let input: Vec<u8> = (0..=255).collect();
+
+    let out = [
+        0xb7, 0x20, 0x71, 0xe0, 0x96, 0x27, 0x7e, 0xde, 0xbb, 0x8e, 0xe5, 0x13, 0x4d, 0xd3, 0x71,
+        0x49, 0x96, 0x30, 0x7b, 0xa3, 0xa5, 0x5a, 0xa4, 0x73, 0x3d, 0x41, 0x2a, 0xbb, 0xe2, 0x8e,
+        0x90, 0x9e, 0x10, 0xe5, 0x7e, 0x6f, 0xbf, 0xb4, 0xef, 0x53, 0xb3, 0xb9, 0x60, 0x51, 0x82,
+        0x94, 0xff, 0x88, 0x9a, 0x90, 0x82, 0x92, 0x54, 0x41, 0x2e, 0x2a, 0x60, 0xb8, 0x5a, 0xdd,
+        0x07, 0xa3, 0x67, 0x4f,
+    ];
+
+    assert_eq!(blake2b_keyed(64, &key, &input), out);
+}
+
+#[test]
+fn blake2b_keyed_short_output() {
+    let out = blake2b_keyed(16, b"key", b"abc");
+
+    assert_eq!(
+        &out[..16],
+        &[
+            0x2c, 0xf0, 0x2a, 0xe1, 0x6a, 0xc2, 0x25, 0xee, 0x73, 0x1f, 0x9d, 0x35, 0x9d, 0x8a,
+            0x1c, 0x38
+        ]
+    );
+}
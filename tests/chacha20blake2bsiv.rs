@@ -0,0 +1,164 @@
+use cryptal::encryption::chacha20blake2bsiv::{SivError, decrypt, encrypt};
+
+fn key() -> [u8; 32] {
+    let mut key = [0u8; 32];
+    for (i, b) in key.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    key
+}
+
+fn nonce() -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    for (i, b) in nonce.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    nonce
+}
+
+#[test]
+fn test_siv_known_answer() {
+    let plaintext = b"The quick brown fox jumps over the lazy dog";
+
+    let mut ciphertext = vec![0u8; plaintext.len()];
+    let mut tag = [0u8; 16];
+
+    encrypt(
+        &key(),
+        &nonce(),
+        b"header",
+        plaintext,
+        &mut ciphertext,
+        &mut tag,
+    );
+
+    let expected_ciphertext: [u8; 43] = [
+        0x9d, 0x50, 0x13, 0x90, 0xe3, 0xef, 0x9d, 0xe1, 0x90, 0x2f, 0x42, 0xe8, 0xe1, 0x5c, 0x71,
+        0xcd, 0xcb, 0xb2, 0xf7, 0xe4, 0x17, 0x41, 0x94, 0x4f, 0xa9, 0xe8, 0x8a, 0xa4, 0x84, 0x4a,
+        0x87, 0xc0, 0x90, 0xd5, 0x48, 0x54, 0xd8, 0x83, 0xa7, 0x5a, 0xd9, 0xa2, 0x26,
+    ];
+    let expected_tag: [u8; 16] = [
+        0x52, 0x89, 0xea, 0xf6, 0xa8, 0xde, 0xf4, 0xed, 0xa1, 0xac, 0x5f, 0x0b, 0x89, 0x57, 0xbb,
+        0x46,
+    ];
+
+    assert_eq!(ciphertext, expected_ciphertext, "ciphertext mismatch");
+    assert_eq!(tag, expected_tag, "tag mismatch");
+}
+
+#[test]
+fn test_siv_roundtrip() {
+    let plaintext = b"nonce reuse only leaks equality";
+
+    let mut ciphertext = vec![0u8; plaintext.len()];
+    let mut tag = [0u8; 16];
+    encrypt(
+        &key(),
+        &nonce(),
+        b"aad",
+        plaintext,
+        &mut ciphertext,
+        &mut tag,
+    );
+
+    let mut decrypted = vec![0u8; plaintext.len()];
+    let res = decrypt(&key(), &nonce(), b"aad", &ciphertext, &tag, &mut decrypted);
+
+    assert!(res.is_ok(), "decryption should succeed");
+    assert_eq!(decrypted, plaintext, "plaintext mismatch");
+}
+
+#[test]
+fn test_siv_empty_message() {
+    let mut tag = [0u8; 16];
+    encrypt(&key(), &nonce(), b"only aad", &[], &mut [], &mut tag);
+
+    assert!(decrypt(&key(), &nonce(), b"only aad", &[], &tag, &mut []).is_ok());
+    assert_eq!(
+        decrypt(&key(), &nonce(), b"other aad", &[], &tag, &mut []),
+        Err(SivError::AuthenticationFailed)
+    );
+}
+
+#[test]
+fn test_siv_nonce_reuse_leaks_only_equality() {
+    let a = b"attack at dawn!!";
+    let b = b"attack at dusk!!";
+
+    let mut ct_a1 = [0u8; 16];
+    let mut ct_a2 = [0u8; 16];
+    let mut ct_b = [0u8; 16];
+    let (mut tag_a1, mut tag_a2, mut tag_b) = ([0u8; 16], [0u8; 16], [0u8; 16]);
+
+    encrypt(&key(), &nonce(), &[], a, &mut ct_a1, &mut tag_a1);
+    encrypt(&key(), &nonce(), &[], a, &mut ct_a2, &mut tag_a2);
+    encrypt(&key(), &nonce(), &[], b, &mut ct_b, &mut tag_b);
+
+    // Identical messages under a repeated nonce are recognizable...
+    assert_eq!(ct_a1, ct_a2);
+    assert_eq!(tag_a1, tag_a2);
+
+    // ...but distinct messages use unrelated keystreams, so XORing the
+    // ciphertexts does not reveal the XOR of the plaintexts.
+    assert_ne!(tag_a1, tag_b);
+
+    let ct_xor: Vec<u8> = ct_a1.iter().zip(&ct_b).map(|(x, y)| x ^ y).collect();
+    let pt_xor: Vec<u8> = a.iter().zip(b).map(|(x, y)| x ^ y).collect();
+    assert_ne!(ct_xor, pt_xor);
+}
+
+#[test]
+fn test_siv_rejects_tampering() {
+    let plaintext = b"integrity matters";
+
+    let mut ciphertext = vec![0u8; plaintext.len()];
+    let mut tag = [0u8; 16];
+    encrypt(
+        &key(),
+        &nonce(),
+        b"aad",
+        plaintext,
+        &mut ciphertext,
+        &mut tag,
+    );
+
+    let mut out = vec![0u8; plaintext.len()];
+
+    let mut bad_ct = ciphertext.clone();
+    bad_ct[3] ^= 1;
+    assert_eq!(
+        decrypt(&key(), &nonce(), b"aad", &bad_ct, &tag, &mut out),
+        Err(SivError::AuthenticationFailed)
+    );
+    assert!(out.iter().all(|&b| b == 0), "plaintext must be wiped");
+
+    let mut bad_tag = tag;
+    bad_tag[15] ^= 0x80;
+    assert_eq!(
+        decrypt(&key(), &nonce(), b"aad", &ciphertext, &bad_tag, &mut out),
+        Err(SivError::AuthenticationFailed)
+    );
+
+    let mut bad_nonce = nonce();
+    bad_nonce[0] ^= 1;
+    assert_eq!(
+        decrypt(&key(), &bad_nonce, b"aad", &ciphertext, &tag, &mut out),
+        Err(SivError::AuthenticationFailed)
+    );
+
+    let mut bad_key = key();
+    bad_key[31] ^= 1;
+    assert_eq!(
+        decrypt(&bad_key, &nonce(), b"aad", &ciphertext, &tag, &mut out),
+        Err(SivError::AuthenticationFailed)
+    );
+}
+
+#[test]
+fn test_siv_invalid_length() {
+    let mut out = [0u8; 4];
+    assert_eq!(
+        decrypt(&key(), &nonce(), &[], &[0u8; 5], &[0u8; 16], &mut out),
+        Err(SivError::InvalidLength)
+    );
+}
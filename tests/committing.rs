@@ -0,0 +1,99 @@
+use cryptal::encryption::committing::{CommittingError, OVERHEAD, open, seal};
+
+const NONCE: [u8; 12] = [0x24; 12];
+
+#[test]
+fn test_committing_roundtrip() {
+    let key = [0x11u8; 32];
+    let plaintext = b"commit to the key, then encrypt";
+
+    let sealed = seal(&key, &NONCE, b"aad", plaintext);
+    assert_eq!(sealed.len(), plaintext.len() + OVERHEAD);
+
+    let opened = open(&key, &NONCE, b"aad", &sealed).unwrap();
+    assert_eq!(opened, plaintext);
+}
+
+#[test]
+fn test_committing_empty_plaintext() {
+    let key = [0x22u8; 32];
+
+    let sealed = seal(&key, &NONCE, &[], &[]);
+    assert_eq!(sealed.len(), OVERHEAD);
+
+    assert_eq!(open(&key, &NONCE, &[], &sealed).unwrap(), Vec::<u8>::new());
+}
+
+#[test]
+fn test_committing_rejects_other_key() {
+    let key = [0x33u8; 32];
+    let mut other = key;
+    other[0] ^= 1;
+
+    let sealed = seal(&key, &NONCE, &[], b"partitioning oracle");
+
+    assert_eq!(
+        open(&other, &NONCE, &[], &sealed),
+        Err(CommittingError::AuthenticationFailed)
+    );
+}
+
+#[test]
+fn test_committing_binds_commitment_to_key() {
+    let key_a = [0x44u8; 32];
+    let key_b = [0x55u8; 32];
+
+    // Graft the commitment for key B onto a ciphertext produced under key A:
+    // the commitment now matches key B, but the body does not open under it.
+    let sealed_a = seal(&key_a, &NONCE, &[], b"message under key a");
+    let sealed_b = seal(&key_b, &NONCE, &[], b"message under key b");
+
+    let mut forged = sealed_a.clone();
+    forged[..32].copy_from_slice(&sealed_b[..32]);
+
+    assert_eq!(
+        open(&key_b, &NONCE, &[], &forged),
+        Err(CommittingError::AuthenticationFailed)
+    );
+    assert_eq!(
+        open(&key_a, &NONCE, &[], &forged),
+        Err(CommittingError::AuthenticationFailed)
+    );
+}
+
+#[test]
+fn test_committing_rejects_tampering() {
+    let key = [0x66u8; 32];
+    let sealed = seal(&key, &NONCE, b"header", b"tamper evident");
+
+    for i in [0, 31, 32, sealed.len() - 1] {
+        let mut bad = sealed.clone();
+        bad[i] ^= 0x01;
+        assert_eq!(
+            open(&key, &NONCE, b"header", &bad),
+            Err(CommittingError::AuthenticationFailed)
+        );
+    }
+
+    assert_eq!(
+        open(&key, &NONCE, b"other", &sealed),
+        Err(CommittingError::AuthenticationFailed)
+    );
+
+    let mut bad_nonce = NONCE;
+    bad_nonce[11] ^= 1;
+    assert_eq!(
+        open(&key, &bad_nonce, b"header", &sealed),
+        Err(CommittingError::AuthenticationFailed)
+    );
+}
+
+#[test]
+fn test_committing_rejects_short_input() {
+    let key = [0x77u8; 32];
+
+    assert_eq!(
+        open(&key, &NONCE, &[], &[0u8; OVERHEAD - 1]),
+        Err(CommittingError::InvalidLength)
+    );
+}
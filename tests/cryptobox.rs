@@ -0,0 +1,157 @@
+use cryptal::encryption::cryptobox::{self, CryptoBoxError};
+use cryptal::encryption::sealedbox;
+use cryptal::keys::x25519::{generate_keypair, public_key};
+
+/// RFC 7748 section 6.1 private keys, also used by the NaCl test suite.
+const ALICE_PRIVATE: [u8; 32] = [
+    0x77, 0x07, 0x6d, 0x0a, 0x73, 0x18, 0xa5, 0x7d, 0x3c, 0x16, 0xc1, 0x72, 0x51, 0xb2, 0x66, 0x45,
+    0xdf, 0x4c, 0x2f, 0x87, 0xeb, 0xc0, 0x99, 0x2a, 0xb1, 0x77, 0xfb, 0xa5, 0x1d, 0xb9, 0x2c, 0x2a,
+];
+
+const BOB_PRIVATE: [u8; 32] = [
+    0x5d, 0xab, 0x08, 0x7e, 0x62, 0x4a, 0x8a, 0x4b, 0x79, 0xe1, 0x7f, 0x8b, 0x83, 0x80, 0x0e, 0xe6,
+    0x6f, 0x3b, 0xb1, 0x29, 0x26, 0x18, 0xb6, 0xfd, 0x1c, 0x2f, 0x8b, 0x27, 0xff, 0x88, 0xe0, 0xeb,
+];
+
+const NONCE: [u8; 24] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+    0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+];
+
+const MESSAGE: &[u8] = b"crypto_box interoperability test";
+
+/// Output of libsodium `crypto_box_easy(MESSAGE, NONCE, bob_pk, alice_sk)`.
+const BOXED: [u8; 48] = [
+    0x04, 0x6c, 0x2b, 0xd6, 0x55, 0x87, 0xf8, 0x92, 0x2a, 0x53, 0x2b, 0x1d, 0x3e, 0x8d, 0x23, 0x7c,
+    0x66, 0x3c, 0x29, 0xd5, 0x6f, 0x19, 0x0a, 0x1f, 0x2f, 0xff, 0xb8, 0x17, 0xcd, 0xfe, 0xdd, 0xac,
+    0x8e, 0xb2, 0xbc, 0x92, 0x14, 0x85, 0xc5, 0xe1, 0x13, 0xa7, 0x5e, 0x1a, 0x32, 0x4f, 0x9d, 0x68,
+];
+
+/// Output of libsodium `crypto_box_seal(b"sealed to a node", bob_pk)`.
+const SEALED: [u8; 64] = [
+    0xbd, 0xd7, 0x78, 0x17, 0x31, 0x56, 0x9e, 0xb2, 0xeb, 0x08, 0xf6, 0xee, 0xc7, 0xdd, 0x2c, 0x08,
+    0x0f, 0xe6, 0x04, 0xb3, 0x27, 0x1f, 0xcf, 0x83, 0x54, 0xac, 0xd9, 0x7f, 0x2a, 0xa0, 0xdf, 0x55,
+    0xf4, 0xb3, 0xa0, 0xe1, 0xc2, 0xcc, 0x86, 0xa9, 0xe6, 0x2d, 0xcb, 0x6b, 0x2f, 0xd5, 0x3f, 0xba,
+    0x1d, 0xeb, 0x94, 0xf8, 0xf5, 0x8f, 0xc4, 0x24, 0x4f, 0xf7, 0x72, 0x4e, 0x50, 0xbe, 0xcd, 0xbf,
+];
+
+#[test]
+fn test_cryptobox_precompute_nacl() {
+    // The NaCl test suite's `secretbox` key is the `crypto_box_beforenm`
+    // output for these two keypairs.
+    let expected = [
+        0x1b, 0x27, 0x55, 0x64, 0x73, 0xe9, 0x85, 0xd4, 0x62, 0xcd, 0x51, 0x19, 0x7a, 0x9a, 0x46,
+        0xc7, 0x60, 0x09, 0x54, 0x9e, 0xac, 0x64, 0x74, 0xf2, 0x06, 0xc4, 0xee, 0x08, 0x44, 0xf6,
+        0x83, 0x89,
+    ];
+
+    let alice = cryptobox::precompute(&public_key(&BOB_PRIVATE), &ALICE_PRIVATE).unwrap();
+    let bob = cryptobox::precompute(&public_key(&ALICE_PRIVATE), &BOB_PRIVATE).unwrap();
+
+    assert_eq!(alice, expected);
+    assert_eq!(bob, expected);
+}
+
+#[test]
+fn test_cryptobox_libsodium_vector() {
+    let bob_public = public_key(&BOB_PRIVATE);
+    let alice_public = public_key(&ALICE_PRIVATE);
+
+    let boxed = cryptobox::seal(&NONCE, &bob_public, &ALICE_PRIVATE, MESSAGE).unwrap();
+    assert_eq!(boxed, BOXED);
+
+    let opened = cryptobox::open(&NONCE, &alice_public, &BOB_PRIVATE, &BOXED).unwrap();
+    assert_eq!(opened, MESSAGE);
+}
+
+#[test]
+fn test_cryptobox_tampering_detected() {
+    let alice_public = public_key(&ALICE_PRIVATE);
+
+    let mut boxed = BOXED;
+    boxed[20] ^= 1;
+
+    assert_eq!(
+        cryptobox::open(&NONCE, &alice_public, &BOB_PRIVATE, &boxed),
+        Err(CryptoBoxError::AuthenticationFailed)
+    );
+    assert_eq!(
+        cryptobox::open(&NONCE, &alice_public, &BOB_PRIVATE, &BOXED[..15]),
+        Err(CryptoBoxError::InvalidLength)
+    );
+}
+
+#[test]
+fn test_cryptobox_wrong_sender() {
+    let (mallory_public, _) = generate_keypair();
+
+    assert_eq!(
+        cryptobox::open(&NONCE, &mallory_public, &BOB_PRIVATE, &BOXED),
+        Err(CryptoBoxError::AuthenticationFailed)
+    );
+}
+
+#[test]
+fn test_cryptobox_rejects_small_order_key() {
+    assert_eq!(
+        cryptobox::seal(&NONCE, &[0u8; 32], &ALICE_PRIVATE, MESSAGE),
+        Err(CryptoBoxError::WeakPublicKey)
+    );
+
+    let mut one = [0u8; 32];
+    one[0] = 1;
+    assert_eq!(
+        cryptobox::precompute(&one, &ALICE_PRIVATE),
+        Err(CryptoBoxError::WeakPublicKey)
+    );
+}
+
+#[test]
+fn test_sealedbox_libsodium_vector() {
+    let bob_public = public_key(&BOB_PRIVATE);
+
+    let opened = sealedbox::open(&bob_public, &BOB_PRIVATE, &SEALED).unwrap();
+    assert_eq!(opened, b"sealed to a node");
+}
+
+#[test]
+fn test_sealedbox_roundtrip() {
+    let (public, private) = generate_keypair();
+
+    for len in [0usize, 1, 64, 1000] {
+        let message = vec![0x5a; len];
+
+        let sealed = sealedbox::seal(&public, &message).unwrap();
+        assert_eq!(sealed.len(), len + sealedbox::OVERHEAD);
+
+        let opened = sealedbox::open(&public, &private, &sealed).unwrap();
+        assert_eq!(opened, message);
+    }
+}
+
+#[test]
+fn test_sealedbox_is_randomized() {
+    let (public, _) = generate_keypair();
+
+    let a = sealedbox::seal(&public, b"same message").unwrap();
+    let b = sealedbox::seal(&public, b"same message").unwrap();
+
+    assert_ne!(a, b);
+}
+
+#[test]
+fn test_sealedbox_wrong_recipient() {
+    let (public, _) = generate_keypair();
+    let (other_public, other_private) = generate_keypair();
+
+    let sealed = sealedbox::seal(&public, b"not for you").unwrap();
+
+    assert_eq!(
+        sealedbox::open(&other_public, &other_private, &sealed),
+        Err(CryptoBoxError::AuthenticationFailed)
+    );
+    assert_eq!(
+        sealedbox::open(&public, &other_private, &sealed[..47]),
+        Err(CryptoBoxError::InvalidLength)
+    );
+}
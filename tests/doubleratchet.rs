@@ -0,0 +1,272 @@
+use cryptal::keys::x25519::generate_keypair;
+use cryptal::protocol::doubleratchet::{
+    ENCRYPTED_HEADER_LEN, HEADER_LEN, MAX_SKIP, RatchetError, Session,
+};
+
+const SK: [u8; 32] = [0x11; 32];
+const AD: &[u8] = b"alice-id || bob-id";
+
+fn pair() -> (Session, Session) {
+    let (bob_public, bob_private) = generate_keypair();
+
+    (
+        Session::initiator(&SK, &bob_public).unwrap(),
+        Session::responder(&SK, &bob_private),
+    )
+}
+
+fn pair_with_header_encryption() -> (Session, Session) {
+    let (bob_public, bob_private) = generate_keypair();
+    let (hka, nhkb) = ([0x22; 32], [0x33; 32]);
+
+    (
+        Session::initiator_with_header_encryption(&SK, &bob_public, &hka, &nhkb).unwrap(),
+        Session::responder_with_header_encryption(&SK, &bob_private, &hka, &nhkb),
+    )
+}
+
+fn conversation(alice: &mut Session, bob: &mut Session) {
+    for round in 0..5u8 {
+        for i in 0..3u8 {
+            let m = alice.encrypt(&[round, i], AD).unwrap();
+            assert_eq!(bob.decrypt(&m, AD).unwrap(), [round, i]);
+        }
+
+        let m = bob.encrypt(&[round], AD).unwrap();
+        assert_eq!(alice.decrypt(&m, AD).unwrap(), [round]);
+    }
+}
+
+#[test]
+fn ratchet_conversation() {
+    let (mut alice, mut bob) = pair();
+    conversation(&mut alice, &mut bob);
+
+    let (mut alice, mut bob) = pair_with_header_encryption();
+    conversation(&mut alice, &mut bob);
+}
+
+#[test]
+fn ratchet_message_layout() {
+    let (mut alice, mut bob) = pair();
+    let m = alice.encrypt(b"hello", AD).unwrap();
+
+    assert_eq!(m.len(), HEADER_LEN + 5 + 16);
+    assert_eq!(m[..32], alice.public_key());
+    bob.decrypt(&m, AD).unwrap();
+
+    let (mut alice, _) = pair_with_header_encryption();
+    let m = alice.encrypt(b"hello", AD).unwrap();
+
+    assert_eq!(m.len(), ENCRYPTED_HEADER_LEN + 5 + 16);
+    assert_ne!(m[..32], alice.public_key());
+}
+
+#[test]
+fn ratchet_public_key_changes() {
+    let (mut alice, mut bob) = pair();
+    let first = alice.public_key();
+
+    let m = alice.encrypt(b"1", AD).unwrap();
+    bob.decrypt(&m, AD).unwrap();
+    let m = bob.encrypt(b"2", AD).unwrap();
+    alice.decrypt(&m, AD).unwrap();
+
+    assert_ne!(alice.public_key(), first);
+}
+
+#[test]
+fn ratchet_responder_cannot_send_first() {
+    let (_, mut bob) = pair();
+    assert_eq!(bob.encrypt(b"hi", AD), Err(RatchetError::NoSendingChain));
+}
+
+fn out_of_order(alice: &mut Session, bob: &mut Session) {
+    let m0 = alice.encrypt(b"m0", AD).unwrap();
+    let m1 = alice.encrypt(b"m1", AD).unwrap();
+    let m2 = alice.encrypt(b"m2", AD).unwrap();
+
+    assert_eq!(bob.decrypt(&m2, AD).unwrap(), b"m2");
+    assert_eq!(bob.skipped_keys(), 2);
+
+    // Bob replies, advancing the ratchet, then Alice sends a new chain.
+    let r = bob.encrypt(b"r", AD).unwrap();
+    assert_eq!(alice.decrypt(&r, AD).unwrap(), b"r");
+    let m3 = alice.encrypt(b"m3", AD).unwrap();
+    assert_eq!(bob.decrypt(&m3, AD).unwrap(), b"m3");
+
+    // Messages of the old chain are still readable, exactly once.
+    assert_eq!(bob.decrypt(&m0, AD).unwrap(), b"m0");
+    assert_eq!(bob.decrypt(&m1, AD).unwrap(), b"m1");
+    assert_eq!(bob.skipped_keys(), 0);
+
+    assert_eq!(bob.decrypt(&m1, AD), Err(RatchetError::DecryptionFailed));
+    assert_eq!(bob.decrypt(&m3, AD), Err(RatchetError::DecryptionFailed));
+}
+
+#[test]
+fn ratchet_out_of_order() {
+    let (mut alice, mut bob) = pair();
+    out_of_order(&mut alice, &mut bob);
+
+    let (mut alice, mut bob) = pair_with_header_encryption();
+    out_of_order(&mut alice, &mut bob);
+}
+
+#[test]
+fn ratchet_skip_limit() {
+    let (mut alice, mut bob) = pair();
+
+    for _ in 0..=MAX_SKIP {
+        alice.encrypt(b"lost", AD).unwrap();
+    }
+    let m = alice.encrypt(b"too far", AD).unwrap();
+
+    assert_eq!(bob.decrypt(&m, AD), Err(RatchetError::TooManySkipped));
+    assert_eq!(bob.skipped_keys(), 0);
+}
+
+#[test]
+fn ratchet_failed_decryption_leaves_state_unchanged() {
+    let (mut alice, mut bob) = pair_with_header_encryption();
+
+    let m0 = alice.encrypt(b"m0", AD).unwrap();
+    let m1 = alice.encrypt(b"m1", AD).unwrap();
+
+    let mut tampered = m1.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    assert_eq!(
+        bob.decrypt(&tampered, AD),
+        Err(RatchetError::DecryptionFailed)
+    );
+    assert_eq!(
+        bob.decrypt(&m1, b"other ad"),
+        Err(RatchetError::DecryptionFailed)
+    );
+    assert_eq!(bob.skipped_keys(), 0);
+
+    assert_eq!(bob.decrypt(&m0, AD).unwrap(), b"m0");
+    assert_eq!(bob.decrypt(&m1, AD).unwrap(), b"m1");
+
+    assert_eq!(
+        bob.decrypt(&m1[..40], AD),
+        Err(RatchetError::InvalidMessage)
+    );
+}
+
+#[test]
+fn ratchet_rejects_foreign_messages() {
+    let (mut alice, _) = pair();
+    let (_, mut eve) = pair();
+
+    let m = alice.encrypt(b"secret", AD).unwrap();
+    assert_eq!(eve.decrypt(&m, AD), Err(RatchetError::DecryptionFailed));
+}
+
+#[test]
+fn ratchet_rejects_weak_keys() {
+    assert_eq!(
+        Session::initiator(&SK, &[0; 32]).err(),
+        Some(RatchetError::WeakPublicKey)
+    );
+
+    let (_, mut bob) = pair();
+    let mut m = vec![0u8; HEADER_LEN + 16];
+    m[..32].fill(0);
+
+    assert_eq!(bob.decrypt(&m, AD), Err(RatchetError::WeakPublicKey));
+}
+
+#[test]
+fn ratchet_serialization() {
+    for (mut alice, mut bob) in [pair(), pair_with_header_encryption()] {
+        let m0 = alice.encrypt(b"m0", AD).unwrap();
+        let m1 = alice.encrypt(b"m1", AD).unwrap();
+        bob.decrypt(&m1, AD).unwrap();
+
+        let mut bob = Session::from_bytes(&bob.to_bytes()).unwrap();
+        let mut alice = Session::from_bytes(&alice.to_bytes()).unwrap();
+
+        assert_eq!(bob.skipped_keys(), 1);
+        assert_eq!(bob.decrypt(&m0, AD).unwrap(), b"m0");
+        conversation(&mut alice, &mut bob);
+    }
+}
+
+#[test]
+fn ratchet_invalid_serialization() {
+    let (alice, _) = pair();
+    let bytes = alice.to_bytes();
+
+    assert!(Session::from_bytes(&[]).is_err());
+    assert!(Session::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+    let mut longer = bytes.clone();
+    longer.push(0);
+    assert_eq!(
+        Session::from_bytes(&longer).err(),
+        Some(RatchetError::InvalidSession)
+    );
+
+    let mut version = bytes.clone();
+    version[0] = 2;
+    assert_eq!(
+        Session::from_bytes(&version).err(),
+        Some(RatchetError::InvalidSession)
+    );
+}
+
+/// Replaces the absent optional key at `offset` of a serialized session.
+fn with_key_at(bytes: &[u8], offset: usize) -> Vec<u8> {
+    assert_eq!(bytes[offset], 0);
+
+    let mut out = bytes[..offset].to_vec();
+    out.push(1);
+    out.extend_from_slice(&[0x44; 32]);
+    out.extend_from_slice(&bytes[offset + 1..]);
+    out
+}
+
+#[test]
+fn ratchet_rejects_inconsistent_sessions() {
+    // A fresh responder has no remote key, sending or receiving chain:
+    // version, flags, DHs, DHr? at 34, RK, CKs? at 67, CKr? at 68.
+    let (_, bob) = pair();
+    let bytes = bob.to_bytes();
+    assert!(Session::from_bytes(&bytes).is_ok());
+
+    // Receiving chain without a remote ratchet key.
+    assert_eq!(
+        Session::from_bytes(&with_key_at(&bytes, 68)).err(),
+        Some(RatchetError::InvalidSession)
+    );
+
+    // With header encryption, HKs? and HKr? follow Ns, Nr and PN.
+    let (_, bob) = pair_with_header_encryption();
+    let bytes = bob.to_bytes();
+    assert!(Session::from_bytes(&bytes).is_ok());
+
+    // Sending chain without a sending header key.
+    assert_eq!(
+        Session::from_bytes(&with_key_at(&bytes, 67)).err(),
+        Some(RatchetError::InvalidSession)
+    );
+
+    // Receiving chain and remote key without a receiving header key.
+    let crafted = with_key_at(&with_key_at(&bytes, 34), 100);
+    assert_eq!(
+        Session::from_bytes(&crafted).err(),
+        Some(RatchetError::InvalidSession)
+    );
+}
+
+#[test]
+fn ratchet_initiator_rejects_initial_remote_key() {
+    let (bob_public, _) = generate_keypair();
+    let mut alice = Session::initiator(&SK, &bob_public).unwrap();
+
+    let mut m = vec![0u8; HEADER_LEN + 16];
+    m[..32].copy_from_slice(&bob_public);
+
+    assert_eq!(alice.decrypt(&m, AD), Err(RatchetError::DecryptionFailed));
+}
@@ -1,6 +1,46 @@
+use cryptal::hash::Sha512;
 use cryptal::keys::ed25519::{
-    Scalar, Signature, add_scalar, exchange, generate_keypair, sign, verify,
+    Ed25519Error, MAX_CONTEXT_LEN, PrivateKey, PublicKey, Scalar, Signature, VerificationPolicy,
+    VerifyingKey, add_scalar, exchange, generate_keypair, keypair_from_seed, sign, sign_ctx,
+    sign_hedged, sign_prehashed, verify, verify_batch, verify_ctx, verify_prehashed,
+    verify_with_policy,
 };
+use cryptal::keys::x25519;
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// RFC 8032, section 7.1: (secret key, public key, message, signature).
+const RFC8032: [(&str, &str, &str, &str); 4] = [
+    (
+        "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        "",
+        "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+    ),
+    (
+        "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+        "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+        "72",
+        "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+    ),
+    (
+        "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+        "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+        "af82",
+        "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+    ),
+    (
+        "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42",
+        "ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf",
+        "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+        "dc2a4459e7369633a52b1bf277839a00201009a3efbf3ecb69bea2186c26b58909351fc9ac90b3ecfdfbc7c66431e0303dca179c138ac17ad9bef1177331a704",
+    ),
+];
 
 #[test]
 fn test_sign_and_verify() {
@@ -46,3 +86,513 @@ fn test_key_exchange() {
 
     assert_eq!(alice_shared, bob_shared);
 }
+
+#[test]
+fn test_montgomery_conversion_vector() {
+    // libsodium `ed25519_convert` test vector.
+    let seed = unhex("421151a459faeade3d247115f94aedae42318124095afabe4d1451a559faedee");
+    let (public, private) = keypair_from_seed(seed.try_into().unwrap());
+
+    let secret = private.to_montgomery().unwrap();
+    assert_eq!(
+        secret.to_vec(),
+        unhex("8052030376d47112be7f73ed7a019293dd12ad910b654455798b4667d73de166")
+    );
+    assert_eq!(
+        public.to_montgomery().to_vec(),
+        unhex("f1814f0e8ff1043d8a44d25babff3cedcae6c22c3edaa48f857ae70de2baae50")
+    );
+    assert_eq!(x25519::public_key(&secret), public.to_montgomery());
+}
+
+#[test]
+fn test_key_exchange_with_x25519_peer() {
+    let (ed_public, ed_private) = generate_keypair();
+    let (x_public, x_private) = x25519::generate_keypair();
+
+    let ed_shared = x25519::exchange(&ed_private.to_montgomery().unwrap(), &x_public);
+    let x_shared = x25519::exchange(&x_private, &ed_public.to_montgomery());
+
+    assert_eq!(ed_shared, x_shared);
+    assert_ne!(ed_shared, [0u8; 32]);
+}
+
+#[test]
+fn test_key_exchange_after_add_scalar() {
+    let (mut alice_public, mut alice_private) = generate_keypair();
+    let (bob_public, bob_private) = generate_keypair();
+
+    add_scalar(
+        Some(&mut alice_public),
+        Some(&mut alice_private),
+        Scalar::from_bytes(&[42u8; 32]),
+    );
+
+    assert_eq!(
+        alice_private.to_montgomery(),
+        Err(Ed25519Error::InvalidPrivateKey)
+    );
+    assert_eq!(
+        exchange(&alice_private, &bob_public),
+        exchange(&bob_private, &alice_public)
+    );
+}
+
+#[test]
+fn test_rfc8032_vectors() {
+    for (secret, public, message, signature) in RFC8032 {
+        let seed: [u8; 32] = unhex(secret).try_into().unwrap();
+        let (pk, sk) = keypair_from_seed(seed);
+
+        assert_eq!(pk.to_bytes().to_vec(), unhex(public));
+        assert_eq!(
+            PublicKey::from_bytes(pk.to_bytes()).unwrap(),
+            sk.public_key()
+        );
+
+        let message = unhex(message);
+        let sig = sign(&message, pk, sk);
+
+        assert_eq!(sig.to_bytes().to_vec(), unhex(signature));
+        assert_eq!(Signature::from_slice(&unhex(signature)), Ok(sig));
+        assert!(verify(sig, &message, pk));
+    }
+}
+
+#[test]
+fn test_keypair_from_seed_is_deterministic() {
+    let (pk1, sk1) = keypair_from_seed([7; 32]);
+    let (pk2, sk2) = keypair_from_seed([7; 32]);
+
+    assert_eq!(pk1, pk2);
+    assert_eq!(sk1.to_bytes(), sk2.to_bytes());
+    assert_eq!(PrivateKey::from_seed([7; 32]).to_bytes(), sk1.to_bytes());
+
+    let (pk3, _) = keypair_from_seed([8; 32]);
+    assert_ne!(pk1, pk3);
+}
+
+#[test]
+fn test_private_key_roundtrip() {
+    let (public, private) = generate_keypair();
+    let message = b"persisted identity";
+
+    let restored = PrivateKey::from_bytes(private.to_bytes()).unwrap();
+    assert_eq!(restored.to_bytes(), private.to_bytes());
+    assert!(verify(sign(message, public, restored), message, public));
+
+    // Keys tweaked with `add_scalar` hold a reduced scalar.
+    let (mut public, mut private) = (public, private);
+    add_scalar(
+        Some(&mut public),
+        Some(&mut private),
+        Scalar::from_bytes(&[3u8; 32]),
+    );
+
+    let restored = PrivateKey::from_bytes(private.to_bytes()).unwrap();
+    assert_eq!(restored.public_key(), public);
+    assert!(verify(sign(message, public, restored), message, public));
+}
+
+#[test]
+fn test_private_key_rejects_invalid_scalars() {
+    let (_, private) = generate_keypair();
+
+    // Low bits set and bit 254 cleared: neither clamped nor reduced.
+    let mut bytes = private.to_bytes();
+    bytes[0] |= 1;
+    bytes[31] = 0x80;
+    assert_eq!(
+        PrivateKey::from_bytes(bytes).err(),
+        Some(Ed25519Error::InvalidPrivateKey)
+    );
+
+    let mut bytes = [0xffu8; 64];
+    bytes[0] = 0xf8;
+    assert_eq!(
+        PrivateKey::from_bytes(bytes).err(),
+        Some(Ed25519Error::InvalidPrivateKey)
+    );
+}
+
+#[test]
+fn test_public_key_rejects_invalid_points() {
+    let (public, _) = generate_keypair();
+    assert_eq!(PublicKey::from_bytes(public.to_bytes()), Ok(public));
+
+    // y = 2 is not the y-coordinate of a curve point.
+    let mut bytes = [0u8; 32];
+    bytes[0] = 2;
+    assert_eq!(
+        PublicKey::from_bytes(bytes),
+        Err(Ed25519Error::InvalidPublicKey)
+    );
+}
+
+#[test]
+fn test_signature_from_slice() {
+    assert_eq!(
+        Signature::from_slice(&[0u8; 63]),
+        Err(Ed25519Error::InvalidLength)
+    );
+    assert_eq!(
+        Signature::from_slice(&[0u8; 65]),
+        Err(Ed25519Error::InvalidLength)
+    );
+    assert_eq!(
+        Signature::from_slice(&[1u8; 64]),
+        Ok(Signature::from_bytes([1u8; 64]))
+    );
+}
+
+/// RFC 8032, section 7.2: (secret key, message, context, signature).
+const RFC8032_CTX: [(&str, &str, &str, &str); 4] = [
+    (
+        "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+        "f726936d19c800494e3fdaff20b276a8",
+        "666f6f",
+        "55a4cc2f70a54e04288c5f4cd1e45a7bb520b36292911876cada7323198dd87a8b36950b95130022907a7fb7c4e9b2d5f6cca685a587b4b21f4b888e4e7edb0d",
+    ),
+    (
+        "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+        "f726936d19c800494e3fdaff20b276a8",
+        "626172",
+        "fc60d5872fc46b3aa69f8b5b4351d5808f92bcc044606db097abab6dbcb1aee3216c48e8b3b66431b5b186d1d28f8ee15a5ca2df6668346291c2043d4eb3e90d",
+    ),
+    (
+        "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+        "508e9e6882b979fea900f62adceaca35",
+        "666f6f",
+        "8b70c1cc8310e1de20ac53ce28ae6e7207f33c3295e03bb5c0732a1d20dc64908922a8b052cf99b7c4fe107a5abb5b2c4085ae75890d02df26269d8945f84b0b",
+    ),
+    (
+        "ab9c2853ce297ddab85c993b3ae14bcad39b2c682beabc27d6d4eb20711d6560",
+        "f726936d19c800494e3fdaff20b276a8",
+        "666f6f",
+        "21655b5f1aa965996b3f97b3c849eafba922a0a62992f73b3d1b73106a84ad85e9b86a7b6005ea868337ff2d20a7f5fbd4cd10b0be49a68da2b2e0dc0ad8960f",
+    ),
+];
+
+#[test]
+fn test_rfc8032_ctx_vectors() {
+    for (secret, message, context, signature) in RFC8032_CTX {
+        let (public, private) = keypair_from_seed(unhex(secret).try_into().unwrap());
+        let (message, context) = (unhex(message), unhex(context));
+
+        let sig = sign_ctx(&message, &context, public, private).unwrap();
+        assert_eq!(sig.to_bytes().to_vec(), unhex(signature));
+
+        assert!(verify_ctx(sig, &message, &context, public));
+        assert!(!verify_ctx(sig, &message, b"baz", public));
+        assert!(!verify(sig, &message, public));
+    }
+}
+
+#[test]
+fn test_rfc8032_ph_vector() {
+    // RFC 8032, section 7.3 (message "abc", empty context).
+    let (public, private) = keypair_from_seed(
+        unhex("833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42")
+            .try_into()
+            .unwrap(),
+    );
+    let expected = unhex(
+        "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae41\
+         31f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406",
+    );
+
+    assert_eq!(
+        public.to_bytes().to_vec(),
+        unhex("ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf")
+    );
+
+    // The message is streamed in pieces.
+    let mut prehash = Sha512::new();
+    prehash.update(b"a");
+    prehash.update(b"bc");
+
+    let sig = sign_prehashed(prehash.clone(), b"", public, private).unwrap();
+    assert_eq!(sig.to_bytes().to_vec(), expected);
+    assert!(verify_prehashed(sig, prehash, b"", public));
+
+    let mut other = Sha512::new();
+    other.update(b"abd");
+    assert!(!verify_prehashed(sig, other, b"", public));
+
+    let mut abc = Sha512::new();
+    abc.update(b"abc");
+    assert!(!verify_prehashed(sig, abc.clone(), b"ctx", public));
+    assert!(!verify(sig, b"abc", public));
+    assert!(!verify_ctx(sig, b"abc", b"", public));
+}
+
+#[test]
+fn test_context_length_limit() {
+    let (public, private) = generate_keypair();
+    let long = [0u8; MAX_CONTEXT_LEN + 1];
+    let max = [0u8; MAX_CONTEXT_LEN];
+
+    assert_eq!(
+        sign_ctx(b"m", &long, public, private).err(),
+        Some(Ed25519Error::InvalidContext)
+    );
+    assert_eq!(
+        sign_prehashed(Sha512::new(), &long, public, private).err(),
+        Some(Ed25519Error::InvalidContext)
+    );
+
+    let sig = sign_ctx(b"m", &max, public, private).unwrap();
+    assert!(verify_ctx(sig, b"m", &max, public));
+    assert!(!verify_ctx(sig, b"m", &long, public));
+}
+
+fn signed_batch(n: usize) -> Vec<(Signature, Vec<u8>, PublicKey)> {
+    (0..n)
+        .map(|i| {
+            let (public, private) = keypair_from_seed([i as u8; 32]);
+            let message = format!("gossip message {i}").into_bytes();
+
+            (sign(&message, public, private), message, public)
+        })
+        .collect()
+}
+
+fn as_batch(items: &[(Signature, Vec<u8>, PublicKey)]) -> Vec<(Signature, &[u8], PublicKey)> {
+    items
+        .iter()
+        .map(|(signature, message, public)| (*signature, message.as_slice(), *public))
+        .collect()
+}
+
+#[test]
+fn test_verify_batch_accepts_valid_signatures() {
+    assert_eq!(verify_batch(&[]), Ok(()));
+
+    for n in [1, 2, 7, 64, 300] {
+        let items = signed_batch(n);
+        assert_eq!(verify_batch(&as_batch(&items)), Ok(()), "n = {n}");
+    }
+}
+
+#[test]
+fn test_verify_batch_identifies_invalid_signatures() {
+    let mut items = signed_batch(40);
+
+    // Wrong message, wrong key, tampered S.
+    items[3].1.push(b'!');
+    items[17].2 = items[18].2;
+    let mut bytes = items[39].0.to_bytes();
+    bytes[40] ^= 1;
+    items[39].0 = Signature::from_bytes(bytes);
+
+    assert_eq!(verify_batch(&as_batch(&items)), Err(vec![3, 17, 39]));
+
+    let single = signed_batch(1);
+    let mut single = as_batch(&single);
+    single[0].1 = b"other";
+    assert_eq!(verify_batch(&single), Err(vec![0]));
+}
+
+#[test]
+fn test_verify_batch_is_cofactored() {
+    // R has an order-2 component: `verify` (cofactorless) rejects the
+    // signature, while the cofactored batch equation holds.
+    let public = PublicKey::from_bytes(
+        unhex("03a107bff3ce10be1d70dd18e74bc09967e4d6309ba50d5f1ddc8664125531b8")
+            .try_into()
+            .unwrap(),
+    )
+    .unwrap();
+    let signature = Signature::from_slice(&unhex(
+        "ba2d3166455ccf449cfc27277dc2dca19848a28a67ba317d9bf3cafcdefcfc7a\
+         1ef6a6b6bcc2a869bac98f2d556ae68441f852612cebd108904e6b9280e4780b",
+    ))
+    .unwrap();
+    let message: &[u8] = b"small order R";
+
+    assert_eq!(
+        keypair_from_seed(core::array::from_fn(|i| i as u8)).0,
+        public
+    );
+    assert!(!verify(signature, message, public));
+
+    for n in [1, 8, 300] {
+        let items = signed_batch(n);
+        let mut batch = as_batch(&items);
+        batch.push((signature, message, public));
+
+        assert_eq!(verify_batch(&batch), Ok(()), "n = {n}");
+    }
+}
+
+/// Edge cases: (public key, signature, message, accepted by
+/// [Cofactorless, Strict, Zip215]).
+const POLICY_CASES: [(&str, &str, &str, [bool; 3]); 6] = [
+    // Honest signature (RFC 8032, test 1).
+    (
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        "",
+        [true, true, true],
+    ),
+    // Same signature with S + ℓ, below 2^253.
+    (
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901554c8c7872aa064e049dbb3013fbf29380d25bf5f0595bbe24655141438e7a101b",
+        "",
+        [false, false, false],
+    ),
+    // Identity public key: R = r · B, S = r is valid for any message.
+    (
+        "0100000000000000000000000000000000000000000000000000000000000000",
+        "e489da97c115cfea0b8f969a0c23c7c06c5930f767735a2546de4492ddea50a96079e1a8f68544e0c3745f068239364450a8d27120d95b7d08110851b2ec6109",
+        "706f6c696379",
+        [true, false, true],
+    ),
+    // Identity public key encoded with y = p + 1.
+    (
+        "eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "e489da97c115cfea0b8f969a0c23c7c06c5930f767735a2546de4492ddea50a96079e1a8f68544e0c3745f068239364450a8d27120d95b7d08110851b2ec6109",
+        "706f6c696379",
+        [true, false, true],
+    ),
+    // R = identity encoded with y = p + 1, S = 0.
+    (
+        "0100000000000000000000000000000000000000000000000000000000000000",
+        "eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f0000000000000000000000000000000000000000000000000000000000000000",
+        "706f6c696379",
+        [false, false, true],
+    ),
+    // R with an order-2 component.
+    (
+        "03a107bff3ce10be1d70dd18e74bc09967e4d6309ba50d5f1ddc8664125531b8",
+        "ba2d3166455ccf449cfc27277dc2dca19848a28a67ba317d9bf3cafcdefcfc7a1ef6a6b6bcc2a869bac98f2d556ae68441f852612cebd108904e6b9280e4780b",
+        "736d616c6c206f726465722052",
+        [false, false, true],
+    ),
+];
+
+#[test]
+fn test_verification_policies() {
+    let policies = [
+        VerificationPolicy::Cofactorless,
+        VerificationPolicy::Strict,
+        VerificationPolicy::Zip215,
+    ];
+
+    for (i, (public, signature, message, expected)) in POLICY_CASES.iter().enumerate() {
+        let public = PublicKey::from_bytes(unhex(public).try_into().unwrap()).unwrap();
+        let signature = Signature::from_slice(&unhex(signature)).unwrap();
+        let message = unhex(message);
+
+        for (policy, expected) in policies.iter().zip(expected) {
+            assert_eq!(
+                verify_with_policy(signature, &message, public, *policy),
+                *expected,
+                "case {i}, {policy:?}"
+            );
+        }
+
+        assert_eq!(verify(signature, &message, public), expected[0]);
+    }
+}
+
+#[test]
+fn test_verify_batch_matches_zip215() {
+    let items = signed_batch(8);
+
+    for (public, signature, message, expected) in POLICY_CASES {
+        let public = PublicKey::from_bytes(unhex(public).try_into().unwrap()).unwrap();
+        let signature = Signature::from_slice(&unhex(signature)).unwrap();
+        let message = unhex(message);
+
+        let mut batch = as_batch(&items);
+        batch.push((signature, &message, public));
+
+        let result = if expected[2] { Ok(()) } else { Err(vec![8]) };
+        assert_eq!(verify_batch(&batch), result);
+    }
+}
+
+#[test]
+fn test_verifying_key_matches_verify() {
+    let (public, private) = generate_keypair();
+    let key = VerifyingKey::new(public);
+
+    assert_eq!(key.public_key(), public);
+
+    for n in 0..8u8 {
+        let message = vec![n; n as usize * 50];
+        let signature = sign(&message, public, private);
+
+        assert!(key.verify(signature, &message));
+        assert!(!key.verify(signature, b"other message"));
+    }
+
+    let policies = [
+        VerificationPolicy::Cofactorless,
+        VerificationPolicy::Strict,
+        VerificationPolicy::Zip215,
+    ];
+
+    for (public, signature, message, expected) in POLICY_CASES {
+        let public = PublicKey::from_bytes(unhex(public).try_into().unwrap()).unwrap();
+        let signature = Signature::from_slice(&unhex(signature)).unwrap();
+        let message = unhex(message);
+        let key = VerifyingKey::from(public);
+
+        assert_eq!(key.verify(signature, &message), expected[0]);
+
+        for (policy, expected) in policies.iter().zip(expected) {
+            assert_eq!(
+                key.verify_with_policy(signature, &message, *policy),
+                expected
+            );
+        }
+    }
+}
+
+#[test]
+fn test_sign_and_verify_large_message() {
+    // 1 MiB message spanning many SHA-512 blocks, checked against an
+    // independent reference implementation.
+    let (public, private) = keypair_from_seed(core::array::from_fn(|i| i as u8));
+    let message: Vec<u8> = (0..1 << 20).map(|i| (i % 251) as u8).collect();
+
+    let signature = sign(&message, public, private);
+    assert_eq!(
+        signature.to_bytes().to_vec(),
+        unhex(
+            "ded3afc2fe174606d846141eec6dbeeabf70f21ad7b17b1e206d22749423f492\
+             01156f3b9059ef96c707abf4a5e28f4fc79407c12545cf62a6c113f57a54330a"
+        )
+    );
+    assert!(verify(signature, &message, public));
+
+    for policy in [VerificationPolicy::Strict, VerificationPolicy::Zip215] {
+        assert!(verify_with_policy(signature, &message, public, policy));
+    }
+}
+
+#[test]
+fn test_sign_hedged() {
+    let (public, private) = generate_keypair();
+    let message = b"hedged";
+
+    let first = sign_hedged(message, public, private);
+    let second = sign_hedged(message, public, private);
+
+    assert_ne!(first, second);
+    assert_ne!(first, sign(message, public, private));
+
+    for signature in [first, second] {
+        assert!(verify(signature, message, public));
+        assert!(verify_with_policy(
+            signature,
+            message,
+            public,
+            VerificationPolicy::Strict
+        ));
+        assert!(!verify(signature, b"other", public));
+    }
+}
@@ -0,0 +1,373 @@
+use cryptal::encoding::base64::{self, Base64Error, Variant};
+use cryptal::encoding::bech32::{self, Bech32Error};
+use cryptal::encoding::der::{self, DerError, Reader};
+use cryptal::encoding::json::{JsonError, Value};
+use cryptal::encoding::pem::{self, PemError};
+
+// -------------------------------------------------------
+// Base64 (RFC 4648, section 10)
+// -------------------------------------------------------
+
+const RFC4648: [(&str, &str); 7] = [
+    ("", ""),
+    ("f", "Zg=="),
+    ("fo", "Zm8="),
+    ("foo", "Zm9v"),
+    ("foob", "Zm9vYg=="),
+    ("fooba", "Zm9vYmE="),
+    ("foobar", "Zm9vYmFy"),
+];
+
+#[test]
+fn base64_rfc4648_vectors() {
+    for (input, expected) in RFC4648 {
+        assert_eq!(
+            base64::encode(input.as_bytes(), Variant::Standard),
+            expected
+        );
+        assert_eq!(
+            base64::decode(expected, Variant::Standard).unwrap(),
+            input.as_bytes()
+        );
+
+        let unpadded = expected.trim_end_matches('=');
+        assert_eq!(
+            base64::encode(input.as_bytes(), Variant::StandardNoPad),
+            unpadded
+        );
+        assert_eq!(
+            base64::decode(unpadded, Variant::StandardNoPad).unwrap(),
+            input.as_bytes()
+        );
+    }
+}
+
+#[test]
+fn base64_all_bytes_roundtrip() {
+    let data: Vec<u8> = (0..=255).collect();
+
+    for variant in [
+        Variant::Standard,
+        Variant::StandardNoPad,
+        Variant::UrlSafe,
+        Variant::UrlSafeNoPad,
+    ] {
+        let encoded = base64::encode(&data, variant);
+        assert_eq!(base64::decode(&encoded, variant).unwrap(), data);
+    }
+}
+
+#[test]
+fn base64url_alphabet() {
+    let data = [0xfb, 0xff, 0xbf];
+
+    assert_eq!(base64::encode(&data, Variant::Standard), "+/+/");
+    assert_eq!(base64::encode(&data, Variant::UrlSafe), "-_-_");
+    assert_eq!(base64::encode(&data[..2], Variant::UrlSafe), "-_8=");
+    assert_eq!(base64::encode(&data[..2], Variant::UrlSafeNoPad), "-_8");
+
+    assert_eq!(
+        base64::decode("-_8", Variant::UrlSafeNoPad).unwrap(),
+        data[..2]
+    );
+    assert_eq!(
+        base64::decode("+/8", Variant::UrlSafeNoPad),
+        Err(Base64Error::InvalidCharacter)
+    );
+    assert_eq!(
+        base64::decode("-_8=", Variant::UrlSafeNoPad),
+        Err(Base64Error::InvalidPadding)
+    );
+    assert_eq!(
+        base64::decode("-_-_", Variant::Standard),
+        Err(Base64Error::InvalidCharacter)
+    );
+}
+
+#[test]
+fn base64_rejects_invalid_input() {
+    assert_eq!(
+        base64::decode("Zm9", Variant::Standard),
+        Err(Base64Error::InvalidLength)
+    );
+    assert_eq!(
+        base64::decode("Zg", Variant::Standard),
+        Err(Base64Error::InvalidLength)
+    );
+    assert_eq!(
+        base64::decode("Z", Variant::StandardNoPad),
+        Err(Base64Error::InvalidLength)
+    );
+    assert_eq!(
+        base64::decode("Zm9\n", Variant::StandardNoPad),
+        Err(Base64Error::InvalidCharacter)
+    );
+    assert_eq!(
+        base64::decode("Zg==", Variant::StandardNoPad),
+        Err(Base64Error::InvalidPadding)
+    );
+    assert_eq!(
+        base64::decode("Z===", Variant::Standard),
+        Err(Base64Error::InvalidPadding)
+    );
+    assert_eq!(
+        base64::decode("=Zg=", Variant::Standard),
+        Err(Base64Error::InvalidPadding)
+    );
+
+    // Non-zero trailing bits.
+    assert_eq!(
+        base64::decode("Zh==", Variant::Standard),
+        Err(Base64Error::NonCanonical)
+    );
+    assert_eq!(
+        base64::decode("Zm9=", Variant::Standard),
+        Err(Base64Error::NonCanonical)
+    );
+    assert_eq!(
+        base64::decode("Zh", Variant::StandardNoPad),
+        Err(Base64Error::NonCanonical)
+    );
+}
+
+// -------------------------------------------------------
+// Bech32 (BIP-173)
+// -------------------------------------------------------
+
+#[test]
+fn bech32_bip173_vectors() {
+    assert_eq!(
+        bech32::decode("A12UEL5L").unwrap(),
+        ("a".to_string(), Vec::new())
+    );
+    assert_eq!(
+        bech32::decode("a12uel5l").unwrap(),
+        ("a".to_string(), Vec::new())
+    );
+    assert_eq!(bech32::encode("a", &[]), "a12uel5l");
+
+    let (hrp, data) = bech32::decode("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw").unwrap();
+    assert_eq!(hrp, "abcdef");
+    assert_eq!(
+        data,
+        [
+            0x00, 0x44, 0x32, 0x14, 0xc7, 0x42, 0x54, 0xb6, 0x35, 0xcf, 0x84, 0x65, 0x3a, 0x56,
+            0xd7, 0xc6, 0x75, 0xbe, 0x77, 0xdf,
+        ]
+    );
+    assert_eq!(
+        bech32::encode("abcdef", &data),
+        "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw"
+    );
+}
+
+#[test]
+fn bech32_roundtrip() {
+    let data: Vec<u8> = (0..32).collect();
+    let encoded = bech32::encode("age", &data);
+
+    assert!(encoded.starts_with("age1"));
+    assert_eq!(
+        bech32::decode(&encoded).unwrap(),
+        ("age".to_string(), data.clone())
+    );
+    assert_eq!(
+        bech32::decode(&encoded.to_ascii_uppercase()).unwrap(),
+        ("age".to_string(), data)
+    );
+}
+
+#[test]
+fn bech32_rejects_invalid_input() {
+    // Mixed case.
+    assert_eq!(bech32::decode("A12uEL5L"), Err(Bech32Error::MixedCase));
+    // Invalid checksum.
+    assert_eq!(
+        bech32::decode("a12uel5m"),
+        Err(Bech32Error::InvalidChecksum)
+    );
+    // Missing separator and empty HRP.
+    assert_eq!(
+        bech32::decode("pzry9x0s0muk"),
+        Err(Bech32Error::InvalidFormat)
+    );
+    assert_eq!(
+        bech32::decode("1pzry9x0s0muk"),
+        Err(Bech32Error::InvalidFormat)
+    );
+    // Character outside the data alphabet.
+    assert_eq!(
+        bech32::decode("x1b4n0q5v"),
+        Err(Bech32Error::InvalidCharacter)
+    );
+    // Checksum too short.
+    assert_eq!(bech32::decode("li1dgmt3"), Err(Bech32Error::InvalidFormat));
+}
+
+// -------------------------------------------------------
+// DER
+// -------------------------------------------------------
+
+#[test]
+fn der_roundtrip_lengths() {
+    for len in [0, 1, 127, 128, 255, 256, 70_000] {
+        let contents = vec![0xab; len];
+
+        let mut out = Vec::new();
+        der::write(der::OCTET_STRING, &contents, &mut out);
+        der::write(der::INTEGER, &[5], &mut out);
+
+        let mut reader = Reader::new(&out);
+        assert_eq!(reader.read(der::OCTET_STRING).unwrap(), &contents[..]);
+        assert_eq!(reader.peek_tag(), Some(der::INTEGER));
+        assert_eq!(reader.read_optional(der::SEQUENCE), Ok(None));
+        assert_eq!(reader.read(der::INTEGER).unwrap(), &[5]);
+        assert!(reader.finish().is_ok());
+    }
+}
+
+#[test]
+fn der_rejects_invalid_input() {
+    let cases: [(&[u8], DerError); 7] = [
+        (&[], DerError::Truncated),
+        (&[0x04], DerError::Truncated),
+        (&[0x04, 0x02, 0x00], DerError::Truncated),
+        (&[0x02, 0x01, 0x00], DerError::UnexpectedTag),
+        // Indefinite length.
+        (&[0x04, 0x80, 0x00, 0x00], DerError::InvalidLength),
+        // Long form for a length that fits in the short form.
+        (&[0x04, 0x81, 0x01, 0x00], DerError::InvalidLength),
+        // Leading zero length byte.
+        (&[0x04, 0x82, 0x00, 0x80], DerError::InvalidLength),
+    ];
+
+    for (input, err) in cases {
+        assert_eq!(Reader::new(input).read(der::OCTET_STRING), Err(err));
+    }
+
+    let mut reader = Reader::new(&[0x04, 0x00, 0x05]);
+    reader.read(der::OCTET_STRING).unwrap();
+    assert_eq!(reader.finish(), Err(DerError::TrailingData));
+}
+
+// -------------------------------------------------------
+// PEM (RFC 7468)
+// -------------------------------------------------------
+
+#[test]
+fn pem_roundtrip() {
+    let data: Vec<u8> = (0..=255).collect();
+    let encoded = pem::encode("TEST DATA", &data);
+
+    assert!(encoded.starts_with("-----BEGIN TEST DATA-----\n"));
+    assert!(encoded.ends_with("-----END TEST DATA-----\n"));
+    assert!(encoded.lines().all(|line| line.len() <= 64));
+
+    assert_eq!(
+        pem::decode(&encoded).unwrap(),
+        ("TEST DATA".to_string(), data.clone())
+    );
+
+    let crlf = format!("\r\n  {}  \r\n", encoded.replace('\n', "\r\n"));
+    assert_eq!(pem::decode(&crlf).unwrap().1, data);
+}
+
+#[test]
+fn pem_rejects_invalid_input() {
+    let cases = [
+        ("", PemError::MissingHeader),
+        (
+            "-----BEGIN A----\nAA==\n-----END A-----",
+            PemError::MissingHeader,
+        ),
+        ("-----BEGIN A-----\nAA==\n", PemError::MissingFooter),
+        (
+            "-----BEGIN A-----\nAA==\n-----END B-----",
+            PemError::MissingFooter,
+        ),
+        (
+            "-----BEGIN A-----\nAA==\n-----END A-----\ntrailing",
+            PemError::MissingFooter,
+        ),
+        (
+            "-----BEGIN A-----\nProc-Type: 4,ENCRYPTED\nAA==\n-----END A-----",
+            PemError::InvalidBase64(Base64Error::InvalidLength),
+        ),
+        (
+            "-----BEGIN A-----\nAB==\n-----END A-----",
+            PemError::InvalidBase64(Base64Error::NonCanonical),
+        ),
+    ];
+
+    for (input, err) in cases {
+        assert_eq!(pem::decode(input), Err(err), "{input:?}");
+    }
+}
+
+// -------------------------------------------------------
+// JSON (RFC 8259)
+// -------------------------------------------------------
+
+#[test]
+fn json_roundtrip() {
+    let input = r#" { "a" : [1, -2.5, 1e3, true, false, null],
+        "b" : { "c" : "\u00e9\ud83d\ude00\n\"\\/" }, "" : {} } "#;
+
+    let value = Value::parse(input).unwrap();
+    assert_eq!(value.get("a").unwrap().as_array().unwrap().len(), 6);
+    assert_eq!(
+        value.get("a").unwrap().as_array().unwrap()[2].as_i64(),
+        Some(1000)
+    );
+    assert_eq!(
+        value.get("a").unwrap().as_array().unwrap()[1].as_i64(),
+        None
+    );
+    assert_eq!(
+        value.get("b").unwrap().get("c").unwrap().as_str(),
+        Some("\u{e9}\u{1f600}\n\"\\/")
+    );
+    assert_eq!(value.get("missing"), None);
+
+    let encoded = value.to_json();
+    assert_eq!(
+        encoded,
+        "{\"a\":[1,-2.5,1000,true,false,null],\"b\":{\"c\":\"\u{e9}\u{1f600}\\n\\\"\\\\/\"},\"\":{}}"
+    );
+    assert_eq!(Value::parse(&encoded).unwrap(), value);
+
+    assert_eq!(Value::from(-7i64).to_json(), "-7");
+    assert_eq!(Value::from("\u{1}").to_json(), r#""\u0001""#);
+}
+
+#[test]
+fn json_rejects_invalid_input() {
+    let cases = [
+        ("", JsonError::UnexpectedEnd),
+        ("[1,", JsonError::UnexpectedEnd),
+        ("[1,]", JsonError::UnexpectedCharacter),
+        ("{'a':1}", JsonError::UnexpectedCharacter),
+        ("nul", JsonError::UnexpectedEnd),
+        ("01", JsonError::TrailingData),
+        ("1.", JsonError::InvalidNumber),
+        ("-", JsonError::InvalidNumber),
+        (r#""\x""#, JsonError::InvalidString),
+        (r#""\ud800""#, JsonError::InvalidString),
+        ("\"\u{1}\"", JsonError::InvalidString),
+        (r#"{"a":1,"a":2}"#, JsonError::DuplicateKey),
+        (
+            r#"{"a":1,"b":{"a":2},"c":3,"b":4}"#,
+            JsonError::DuplicateKey,
+        ),
+        ("{} {}", JsonError::TrailingData),
+    ];
+
+    for (input, err) in cases {
+        assert_eq!(Value::parse(input), Err(err), "{input:?}");
+    }
+
+    let deep = "[".repeat(65) + &"]".repeat(65);
+    assert_eq!(Value::parse(&deep), Err(JsonError::TooDeep));
+    let nested = "[".repeat(64) + &"]".repeat(64);
+    assert!(Value::parse(&nested).is_ok());
+}
@@ -0,0 +1,142 @@
+use cryptal::derivation::{HkdfError, hkdf, hkdf_expand, hkdf_extract, hmac_sha256};
+
+#[test]
+fn hmac_sha256_rfc4231_case_1() {
+    let expected = [
+        0xb0, 0x34, 0x4c, 0x61, 0xd8, 0xdb, 0x38, 0x53, 0x5c, 0xa8, 0xaf, 0xce, 0xaf, 0x0b, 0xf1,
+        0x2b, 0x88, 0x1d, 0xc2, 0x00, 0xc9, 0x83, 0x3d, 0xa7, 0x26, 0xe9, 0x37, 0x6c, 0x2e, 0x32,
+        0xcf, 0xf7,
+    ];
+
+    assert_eq!(hmac_sha256(&[0x0b; 20], b"Hi There"), expected);
+}
+
+#[test]
+fn hmac_sha256_rfc4231_case_2() {
+    let expected = [
+        0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95, 0x75,
+        0xc7, 0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9, 0x64, 0xec,
+        0x38, 0x43,
+    ];
+
+    assert_eq!(
+        hmac_sha256(b"Jefe", b"what do ya want for nothing?"),
+        expected
+    );
+}
+
+#[test]
+fn hmac_sha256_rfc4231_case_6() {
+    let expected = [
+        0x60, 0xe4, 0x31, 0x59, 0x1e, 0xe0, 0xb6, 0x7f, 0x0d, 0x8a, 0x26, 0xaa, 0xcb, 0xf5, 0xb7,
+        0x7f, 0x8e, 0x0b, 0xc6, 0x21, 0x37, 0x28, 0xc5, 0x14, 0x05, 0x46, 0x04, 0x0f, 0x0e, 0xe3,
+        0x7f, 0x54,
+    ];
+
+    assert_eq!(
+        hmac_sha256(
+            &[0xaa; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First"
+        ),
+        expected
+    );
+}
+
+/// RFC 5869 A.1: basic test case.
+#[test]
+fn hkdf_rfc5869_case_1() {
+    let expected_prk = [
+        0x07, 0x77, 0x09, 0x36, 0x2c, 0x2e, 0x32, 0xdf, 0x0d, 0xdc, 0x3f, 0x0d, 0xc4, 0x7b, 0xba,
+        0x63, 0x90, 0xb6, 0xc7, 0x3b, 0xb5, 0x0f, 0x9c, 0x31, 0x22, 0xec, 0x84, 0x4a, 0xd7, 0xc2,
+        0xb3, 0xe5,
+    ];
+    let expected_okm = [
+        0x3c, 0xb2, 0x5f, 0x25, 0xfa, 0xac, 0xd5, 0x7a, 0x90, 0x43, 0x4f, 0x64, 0xd0, 0x36, 0x2f,
+        0x2a, 0x2d, 0x2d, 0x0a, 0x90, 0xcf, 0x1a, 0x5a, 0x4c, 0x5d, 0xb0, 0x2d, 0x56, 0xec, 0xc4,
+        0xc5, 0xbf, 0x34, 0x00, 0x72, 0x08, 0xd5, 0xb8, 0x87, 0x18, 0x58, 0x65,
+    ];
+
+    let prk = hkdf_extract(&(0x00..=0x0c).collect::<Vec<u8>>(), &[0x0b; 22]);
+    assert_eq!(prk, expected_prk);
+
+    let mut okm = [0u8; 42];
+    hkdf_expand(&prk, &(0xf0..=0xf9).collect::<Vec<u8>>(), &mut okm).unwrap();
+    assert_eq!(okm, expected_okm);
+}
+
+/// RFC 5869 A.2: longer inputs and outputs.
+#[test]
+fn hkdf_rfc5869_case_2() {
+    let expected_prk = [
+        0x06, 0xa6, 0xb8, 0x8c, 0x58, 0x53, 0x36, 0x1a, 0x06, 0x10, 0x4c, 0x9c, 0xeb, 0x35, 0xb4,
+        0x5c, 0xef, 0x76, 0x00, 0x14, 0x90, 0x46, 0x71, 0x01, 0x4a, 0x19, 0x3f, 0x40, 0xc1, 0x5f,
+        0xc2, 0x44,
+    ];
+    let expected_okm = [
+        0xb1, 0x1e, 0x39, 0x8d, 0xc8, 0x03, 0x27, 0xa1, 0xc8, 0xe7, 0xf7, 0x8c, 0x59, 0x6a, 0x49,
+        0x34, 0x4f, 0x01, 0x2e, 0xda, 0x2d, 0x4e, 0xfa, 0xd8, 0xa0, 0x50, 0xcc, 0x4c, 0x19, 0xaf,
+        0xa9, 0x7c, 0x59, 0x04, 0x5a, 0x99, 0xca, 0xc7, 0x82, 0x72, 0x71, 0xcb, 0x41, 0xc6, 0x5e,
+        0x59, 0x0e, 0x09, 0xda, 0x32, 0x75, 0x60, 0x0c, 0x2f, 0x09, 0xb8, 0x36, 0x77, 0x93, 0xa9,
+        0xac, 0xa3, 0xdb, 0x71, 0xcc, 0x30, 0xc5, 0x81, 0x79, 0xec, 0x3e, 0x87, 0xc1, 0x4c, 0x01,
+        0xd5, 0xc1, 0xf3, 0x43, 0x4f, 0x1d, 0x87,
+    ];
+
+    let prk = hkdf_extract(
+        &(0x60..=0xaf).collect::<Vec<u8>>(),
+        &(0x00..=0x4f).collect::<Vec<u8>>(),
+    );
+    assert_eq!(prk, expected_prk);
+
+    let mut okm = [0u8; 82];
+    hkdf_expand(&prk, &(0xb0..=0xff).collect::<Vec<u8>>(), &mut okm).unwrap();
+    assert_eq!(okm, expected_okm);
+}
+
+/// RFC 5869 A.3: empty salt and info.
+#[test]
+fn hkdf_rfc5869_case_3() {
+    let expected_prk = [
+        0x19, 0xef, 0x24, 0xa3, 0x2c, 0x71, 0x7b, 0x16, 0x7f, 0x33, 0xa9, 0x1d, 0x6f, 0x64, 0x8b,
+        0xdf, 0x96, 0x59, 0x67, 0x76, 0xaf, 0xdb, 0x63, 0x77, 0xac, 0x43, 0x4c, 0x1c, 0x29, 0x3c,
+        0xcb, 0x04,
+    ];
+    let expected_okm = [
+        0x8d, 0xa4, 0xe7, 0x75, 0xa5, 0x63, 0xc1, 0x8f, 0x71, 0x5f, 0x80, 0x2a, 0x06, 0x3c, 0x5a,
+        0x31, 0xb8, 0xa1, 0x1f, 0x5c, 0x5e, 0xe1, 0x87, 0x9e, 0xc3, 0x45, 0x4e, 0x5f, 0x3c, 0x73,
+        0x8d, 0x2d, 0x9d, 0x20, 0x13, 0x95, 0xfa, 0xa4, 0xb6, 0x1a, 0x96, 0xc8,
+    ];
+
+    let prk = hkdf_extract(&[], &[0x0b; 22]);
+    assert_eq!(prk, expected_prk);
+
+    let mut okm = [0u8; 42];
+    hkdf_expand(&prk, &[], &mut okm).unwrap();
+    assert_eq!(okm, expected_okm);
+}
+
+#[test]
+fn hkdf_one_shot_matches_two_step() {
+    let prk = hkdf_extract(b"salt", b"input keying material");
+
+    let mut two_step = [0u8; 100];
+    hkdf_expand(&prk, b"context", &mut two_step).unwrap();
+
+    let mut one_shot = [0u8; 100];
+    hkdf(b"salt", b"input keying material", b"context", &mut one_shot).unwrap();
+
+    assert_eq!(one_shot, two_step);
+}
+
+#[test]
+fn hkdf_rejects_oversized_output() {
+    let prk = hkdf_extract(&[], b"ikm");
+
+    let mut okm = vec![0u8; 255 * 32];
+    assert!(hkdf_expand(&prk, &[], &mut okm).is_ok());
+
+    let mut okm = vec![0u8; 255 * 32 + 1];
+    assert_eq!(
+        hkdf_expand(&prk, &[], &mut okm),
+        Err(HkdfError::OutputTooLong)
+    );
+}
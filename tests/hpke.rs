@@ -0,0 +1,422 @@
+use cryptal::encryption::hpke::{
+    self, HpkeError, Mode, RecipientContext, SenderContext, derive_keypair,
+    setup_sender_with_ephemeral,
+};
+use cryptal::keys::x25519::generate_keypair;
+
+// RFC 9180 Appendix A.2 ciphersuite: DHKEM(X25519, HKDF-SHA256),
+// HKDF-SHA256, ChaCha20-Poly1305.
+//
+// The base, PSK, auth and auth-PSK mode values are those of RFC 9180
+// A.2.1 to A.2.4.
+
+const INFO: &[u8] = b"Ode on a Grecian Urn";
+const PLAINTEXT: &[u8] = b"Beauty is truth, truth beauty";
+const PSK_ID: &[u8] = b"Ennyn Durin aran Moria";
+
+const PSK: [u8; 32] = [
+    0x02, 0x47, 0xfd, 0x33, 0xb9, 0x13, 0x76, 0x0f, 0xa1, 0xfa, 0x51, 0xe1, 0x89, 0x2d, 0x9f, 0x30,
+    0x7f, 0xbe, 0x65, 0xeb, 0x17, 0x1e, 0x81, 0x32, 0xc2, 0xaf, 0x18, 0x55, 0x5a, 0x73, 0x8b, 0x82,
+];
+
+const BASE_IKM_E: [u8; 32] = [
+    0x90, 0x9a, 0x9b, 0x35, 0xd3, 0xdc, 0x47, 0x13, 0xa5, 0xe7, 0x2a, 0x4d, 0xa2, 0x74, 0xb5, 0x5d,
+    0x3d, 0x38, 0x21, 0xa3, 0x7e, 0x5d, 0x09, 0x9e, 0x74, 0xa6, 0x47, 0xdb, 0x58, 0x3a, 0x90, 0x4b,
+];
+
+const BASE_IKM_R: [u8; 32] = [
+    0x1a, 0xc0, 0x1f, 0x18, 0x1f, 0xdf, 0x9f, 0x35, 0x27, 0x97, 0x65, 0x51, 0x61, 0xc5, 0x8b, 0x75,
+    0xc6, 0x56, 0xa6, 0xcc, 0x27, 0x16, 0xdc, 0xb6, 0x63, 0x72, 0xda, 0x83, 0x55, 0x42, 0xe1, 0xdf,
+];
+
+const BASE_ENC: [u8; 32] = [
+    0x1a, 0xfa, 0x08, 0xd3, 0xde, 0xc0, 0x47, 0xa6, 0x43, 0x88, 0x51, 0x63, 0xf1, 0x18, 0x04, 0x76,
+    0xfa, 0x7d, 0xdb, 0x54, 0xc6, 0xa8, 0x02, 0x9e, 0xa3, 0x3f, 0x95, 0x79, 0x6b, 0xf2, 0xac, 0x4a,
+];
+
+const BASE_CT_0: [u8; 45] = [
+    0x1c, 0x52, 0x50, 0xd8, 0x03, 0x4e, 0xc2, 0xb7, 0x84, 0xba, 0x2c, 0xfd, 0x69, 0xdb, 0xdb, 0x8a,
+    0xf4, 0x06, 0xcf, 0xe3, 0xff, 0x93, 0x8e, 0x13, 0x1f, 0x0d, 0xef, 0x8c, 0x8b, 0x60, 0xb4, 0xdb,
+    0x21, 0x99, 0x3c, 0x62, 0xce, 0x81, 0x88, 0x3d, 0x2d, 0xd1, 0xb5, 0x1a, 0x28,
+];
+
+const BASE_CT_1: [u8; 45] = [
+    0x6b, 0x53, 0xc0, 0x51, 0xe4, 0x19, 0x9c, 0x51, 0x8d, 0xe7, 0x95, 0x94, 0xe1, 0xc4, 0xab, 0x18,
+    0xb9, 0x6f, 0x08, 0x15, 0x49, 0xd4, 0x5c, 0xe0, 0x15, 0xbe, 0x00, 0x20, 0x90, 0xbb, 0x11, 0x9e,
+    0x85, 0x28, 0x53, 0x37, 0xcc, 0x95, 0xba, 0x5f, 0x59, 0x99, 0x2d, 0xc9, 0x8c,
+];
+
+const BASE_EXPORT: [u8; 32] = [
+    0x5a, 0xcb, 0x09, 0x21, 0x11, 0x39, 0xc4, 0x3b, 0x30, 0x90, 0x48, 0x9a, 0x9d, 0xa4, 0x33, 0xe8,
+    0xa3, 0x0e, 0xe7, 0x18, 0x8b, 0xa8, 0xb0, 0xa9, 0xa1, 0xcc, 0xf0, 0xc2, 0x29, 0x28, 0x3e, 0x53,
+];
+
+const BASE_CT_256: [u8; 45] = [
+    0x7a, 0x4a, 0x13, 0xe9, 0xef, 0x23, 0x97, 0x8e, 0x2c, 0x52, 0x0f, 0xd4, 0xd2, 0xe7, 0x57, 0x51,
+    0x4a, 0xe1, 0x60, 0xcd, 0x0c, 0xd0, 0x5e, 0x55, 0x6e, 0xf6, 0x92, 0x37, 0x0c, 0xa5, 0x30, 0x76,
+    0x21, 0x4c, 0x0c, 0x40, 0xd4, 0xc7, 0x28, 0xd6, 0xed, 0x9e, 0x72, 0x7a, 0x5b,
+];
+
+const PSK_IKM_E: [u8; 32] = [
+    0x35, 0x70, 0x6a, 0x0b, 0x09, 0xfb, 0x26, 0xfb, 0x45, 0xc3, 0x9c, 0x2f, 0x50, 0x79, 0xc7, 0x09,
+    0xc7, 0xcf, 0x98, 0xe4, 0x3a, 0xfa, 0x97, 0x3f, 0x14, 0xd8, 0x8e, 0xce, 0x7e, 0x29, 0xc2, 0xe3,
+];
+
+const PSK_IKM_R: [u8; 32] = [
+    0x26, 0xb9, 0x23, 0xea, 0xde, 0x72, 0x94, 0x1c, 0x8a, 0x85, 0xb0, 0x99, 0x86, 0xcd, 0xfa, 0x3f,
+    0x12, 0x96, 0x85, 0x22, 0x61, 0xad, 0xed, 0xc5, 0x2d, 0x58, 0xd2, 0x93, 0x02, 0x69, 0x81, 0x2b,
+];
+
+const PSK_ENC: [u8; 32] = [
+    0x22, 0x61, 0x29, 0x9c, 0x3f, 0x40, 0xa9, 0xaf, 0xc1, 0x33, 0xb9, 0x69, 0xa9, 0x7f, 0x05, 0xe9,
+    0x5b, 0xe2, 0xc5, 0x14, 0xe5, 0x4f, 0x3d, 0xe2, 0x6c, 0xbe, 0x56, 0x44, 0xac, 0x73, 0x5b, 0x04,
+];
+
+const PSK_CT_0: [u8; 45] = [
+    0x4a, 0x17, 0x7f, 0x9c, 0x0d, 0x6f, 0x15, 0xcf, 0xdf, 0x53, 0x3f, 0xb6, 0x5b, 0xf8, 0x4a, 0xec,
+    0xdc, 0x6a, 0xb1, 0x6b, 0x8b, 0x85, 0xb4, 0xcf, 0x65, 0xa3, 0x70, 0xe0, 0x7f, 0xc1, 0xd7, 0x8d,
+    0x28, 0xfb, 0x07, 0x32, 0x14, 0x52, 0x52, 0x76, 0xf4, 0xa8, 0x96, 0x08, 0xff,
+];
+
+const PSK_CT_1: [u8; 45] = [
+    0x5c, 0x3c, 0xab, 0xae, 0x2f, 0x0b, 0x3e, 0x12, 0x4d, 0x8d, 0x86, 0x4c, 0x11, 0x6f, 0xd8, 0xf2,
+    0x0f, 0x3f, 0x56, 0xfd, 0xa9, 0x88, 0xc3, 0x57, 0x3b, 0x40, 0xb0, 0x99, 0x97, 0xfd, 0x6c, 0x76,
+    0x9e, 0x77, 0xc8, 0xed, 0xa6, 0xcd, 0xa4, 0xf9, 0x47, 0xf5, 0xb7, 0x04, 0xa8,
+];
+
+const PSK_EXPORT: [u8; 32] = [
+    0xad, 0x40, 0xe3, 0xae, 0x14, 0xf2, 0x1c, 0x99, 0xbf, 0xde, 0xbc, 0x20, 0xae, 0x14, 0xab, 0x86,
+    0xf4, 0xca, 0x2d, 0xc9, 0xa4, 0x79, 0x9d, 0x20, 0x0f, 0x43, 0xa2, 0x5f, 0x99, 0xfa, 0x78, 0xae,
+];
+
+const AUTH_IKM_E: [u8; 32] = [
+    0x93, 0x8d, 0x3d, 0xaa, 0x5a, 0x89, 0x04, 0x54, 0x0b, 0xc2, 0x4f, 0x48, 0xae, 0x90, 0xee, 0xd3,
+    0xf4, 0xf7, 0xf1, 0x18, 0x39, 0x56, 0x05, 0x97, 0xb5, 0x5e, 0x7c, 0x95, 0x98, 0xc9, 0x96, 0xc0,
+];
+
+const AUTH_IKM_R: [u8; 32] = [
+    0x64, 0x83, 0x5d, 0x5e, 0xe6, 0x4a, 0xa7, 0xaa, 0xd5, 0x7c, 0x6f, 0x2e, 0x4f, 0x75, 0x8f, 0x76,
+    0x96, 0x61, 0x7f, 0x88, 0x29, 0xe7, 0x0b, 0xc9, 0xac, 0x7a, 0x5e, 0xf9, 0x5d, 0x1c, 0x75, 0x6c,
+];
+
+const AUTH_IKM_S: [u8; 32] = [
+    0x9d, 0x8f, 0x94, 0x53, 0x7d, 0x5a, 0x3d, 0xde, 0xf7, 0x12, 0x34, 0xc0, 0xba, 0xed, 0xfa, 0xd4,
+    0xca, 0x68, 0x61, 0x63, 0x4d, 0x0b, 0x94, 0xc3, 0x00, 0x7f, 0xed, 0x55, 0x7a, 0xd1, 0x7d, 0xf6,
+];
+
+const AUTH_ENC: [u8; 32] = [
+    0xf7, 0x67, 0x4c, 0xc8, 0xcd, 0x7b, 0xaa, 0x58, 0x72, 0xd1, 0xf3, 0x3d, 0xba, 0xff, 0xe3, 0x31,
+    0x42, 0x39, 0xf6, 0x19, 0x7d, 0xdf, 0x5d, 0xed, 0x17, 0x46, 0x76, 0x0b, 0xfc, 0x84, 0x7e, 0x0e,
+];
+
+const AUTH_CT_0: [u8; 45] = [
+    0xab, 0x1a, 0x13, 0xc9, 0xd4, 0xf0, 0x1a, 0x87, 0xec, 0x34, 0x40, 0xdb, 0xd7, 0x56, 0xe2, 0x67,
+    0x7b, 0xd2, 0xec, 0xf9, 0xdf, 0x0c, 0xe7, 0xed, 0x73, 0x86, 0x9b, 0x98, 0xe0, 0x0c, 0x09, 0xbe,
+    0x11, 0x1c, 0xb9, 0xfd, 0xf0, 0x77, 0x34, 0x7a, 0xeb, 0x88, 0xe6, 0x1b, 0xdf,
+];
+
+const AUTH_CT_1: [u8; 45] = [
+    0x32, 0x65, 0xc7, 0x80, 0x7f, 0xff, 0xf7, 0xfd, 0xac, 0xe2, 0x16, 0x59, 0xa2, 0xc6, 0xcc, 0xff,
+    0xee, 0x52, 0xa2, 0x6d, 0x27, 0x0c, 0x76, 0x46, 0x8e, 0xd7, 0x42, 0x02, 0xa6, 0x54, 0x78, 0xbf,
+    0xae, 0xdf, 0xff, 0x9c, 0x2b, 0x76, 0x34, 0xe2, 0x4f, 0x10, 0xb7, 0x10, 0x16,
+];
+
+const AUTH_EXPORT: [u8; 32] = [
+    0x1d, 0xf3, 0x9d, 0xc5, 0xdd, 0x60, 0xed, 0xcb, 0xf5, 0xf9, 0xae, 0x80, 0x4e, 0x15, 0xad, 0xa6,
+    0x6e, 0x88, 0x5b, 0x28, 0xed, 0x79, 0x29, 0x11, 0x6f, 0x76, 0x83, 0x69, 0xa3, 0xf9, 0x50, 0xee,
+];
+
+const AUTH_PSK_IKM_E: [u8; 32] = [
+    0x49, 0xd6, 0xea, 0xc8, 0xc6, 0xc5, 0x58, 0xc9, 0x53, 0xa0, 0xa2, 0x52, 0x92, 0x9a, 0x81, 0x87,
+    0x45, 0xbb, 0x08, 0xcd, 0x3d, 0x29, 0xe1, 0x5f, 0x9f, 0x5d, 0xb5, 0xeb, 0x2e, 0x7d, 0x4b, 0x84,
+];
+
+const AUTH_PSK_IKM_R: [u8; 32] = [
+    0xf3, 0x30, 0x4d, 0xdc, 0xf1, 0x58, 0x48, 0x48, 0x82, 0x71, 0xf1, 0x2b, 0x75, 0xec, 0xaf, 0x72,
+    0x30, 0x1f, 0xaa, 0xbf, 0x6a, 0xd2, 0x83, 0x65, 0x4a, 0x14, 0xc3, 0x98, 0x83, 0x2e, 0xb1, 0x84,
+];
+
+const AUTH_PSK_IKM_S: [u8; 32] = [
+    0x20, 0xad, 0xe1, 0xd5, 0x20, 0x3d, 0xe1, 0xaa, 0xdf, 0xb2, 0x61, 0xc4, 0x70, 0x0b, 0x64, 0x32,
+    0xe2, 0x60, 0xd0, 0xd3, 0x17, 0xbe, 0x6e, 0xbb, 0xb8, 0xd7, 0xff, 0xfb, 0x1f, 0x86, 0xad, 0x9d,
+];
+
+const AUTH_PSK_ENC: [u8; 32] = [
+    0x65, 0x6a, 0x2e, 0x00, 0xdc, 0x99, 0x90, 0xfd, 0x18, 0x9e, 0x6e, 0x47, 0x34, 0x59, 0x39, 0x2d,
+    0xf5, 0x56, 0xe9, 0xa2, 0x75, 0x87, 0x54, 0xa0, 0x9d, 0xb3, 0xf5, 0x11, 0x79, 0xa3, 0xfc, 0x02,
+];
+
+const AUTH_PSK_CT_0: [u8; 45] = [
+    0x9a, 0xa5, 0x2e, 0x29, 0x27, 0x4f, 0xc6, 0x17, 0x2e, 0x38, 0xa4, 0x46, 0x13, 0x61, 0xd2, 0x34,
+    0x25, 0x85, 0xd3, 0xae, 0xec, 0x67, 0xfb, 0x3b, 0x72, 0x1e, 0xcd, 0x63, 0xf0, 0x59, 0x57, 0x7c,
+    0x7f, 0xe8, 0x86, 0xbe, 0x0e, 0xde, 0x01, 0x45, 0x6e, 0xbc, 0x67, 0xd5, 0x97,
+];
+
+const AUTH_PSK_CT_1: [u8; 45] = [
+    0x59, 0x46, 0x0b, 0xac, 0xdb, 0xe7, 0xa9, 0x20, 0xef, 0x28, 0x06, 0xa7, 0x49, 0x37, 0xd5, 0xa6,
+    0x91, 0xd6, 0xd5, 0x06, 0x2d, 0x7d, 0xaa, 0xfc, 0xad, 0x7d, 0xb7, 0xe4, 0xd8, 0xc6, 0x49, 0xad,
+    0xff, 0xe5, 0x75, 0xc1, 0x88, 0x9c, 0x5c, 0x2e, 0x3a, 0x49, 0xaf, 0x8e, 0x3e,
+];
+
+const AUTH_PSK_EXPORT: [u8; 32] = [
+    0xd3, 0xba, 0xe0, 0x66, 0xaa, 0x8d, 0xa2, 0x7d, 0x52, 0x7d, 0x85, 0xc0, 0x40, 0xf7, 0xdd, 0x6c,
+    0xcb, 0x60, 0x22, 0x1c, 0x90, 0x2e, 0xe3, 0x6a, 0x82, 0xf7, 0x0b, 0xcd, 0x62, 0xa6, 0x0e, 0xe4,
+];
+
+/// RFC 9180 A.2.1 `skEm`, derived from `BASE_IKM_E`.
+const BASE_SK_E: [u8; 32] = [
+    0xf4, 0xec, 0x9b, 0x33, 0xb7, 0x92, 0xc3, 0x72, 0xc1, 0xd2, 0xc2, 0x06, 0x35, 0x07, 0xb6, 0x84,
+    0xef, 0x92, 0x5b, 0x8c, 0x75, 0xa4, 0x2d, 0xbc, 0xbf, 0x57, 0xd6, 0x3c, 0xcd, 0x38, 0x16, 0x00,
+];
+
+/// Builds the sender context of a vector from its input keying material.
+fn sender(
+    mode: Mode,
+    ikm_e: &[u8; 32],
+    ikm_r: &[u8; 32],
+    ikm_s: Option<&[u8; 32]>,
+) -> ([u8; 32], SenderContext) {
+    let (pk_r, _) = derive_keypair(ikm_r);
+    let (_, sk_e) = derive_keypair(ikm_e);
+    let sk_s = ikm_s.map(|ikm| derive_keypair(ikm).1);
+
+    let (psk, psk_id): (&[u8], &[u8]) = match mode {
+        Mode::Psk | Mode::AuthPsk => (&PSK, PSK_ID),
+        Mode::Base | Mode::Auth => (&[], &[]),
+    };
+
+    setup_sender_with_ephemeral(mode, &pk_r, INFO, psk, psk_id, sk_s.as_ref(), &sk_e).unwrap()
+}
+
+/// Builds the recipient context of a vector through the mode-specific API.
+fn recipient(
+    mode: Mode,
+    enc: &[u8; 32],
+    ikm_r: &[u8; 32],
+    ikm_s: Option<&[u8; 32]>,
+) -> RecipientContext {
+    let (_, sk_r) = derive_keypair(ikm_r);
+    let pk_s = ikm_s.map(|ikm| derive_keypair(ikm).0);
+
+    match mode {
+        Mode::Base => hpke::setup_base_recipient(enc, &sk_r, INFO),
+        Mode::Psk => hpke::setup_psk_recipient(enc, &sk_r, INFO, &PSK, PSK_ID),
+        Mode::Auth => hpke::setup_auth_recipient(enc, &sk_r, INFO, &pk_s.unwrap()),
+        Mode::AuthPsk => {
+            hpke::setup_auth_psk_recipient(enc, &sk_r, INFO, &PSK, PSK_ID, &pk_s.unwrap())
+        }
+    }
+    .unwrap()
+}
+
+/// Checks the encapsulated key, the first two ciphertexts and an export.
+fn check_vector(
+    mode: Mode,
+    ikm_e: &[u8; 32],
+    ikm_r: &[u8; 32],
+    ikm_s: Option<&[u8; 32]>,
+    expected_enc: &[u8; 32],
+    expected_ct: [&[u8; 45]; 2],
+    expected_export: &[u8; 32],
+) {
+    let (enc, mut tx) = sender(mode, ikm_e, ikm_r, ikm_s);
+    assert_eq!(&enc, expected_enc);
+
+    let mut rx = recipient(mode, &enc, ikm_r, ikm_s);
+
+    for (seq, expected) in expected_ct.iter().enumerate() {
+        let aad = format!("Count-{seq}");
+
+        let ct = tx.seal(aad.as_bytes(), PLAINTEXT).unwrap();
+        assert_eq!(&ct[..], &expected[..]);
+
+        let pt = rx.open(aad.as_bytes(), &ct).unwrap();
+        assert_eq!(pt, PLAINTEXT);
+    }
+
+    let mut tx_export = [0u8; 32];
+    let mut rx_export = [0u8; 32];
+    tx.export(b"TestContext", &mut tx_export).unwrap();
+    rx.export(b"TestContext", &mut rx_export).unwrap();
+
+    assert_eq!(&tx_export, expected_export);
+    assert_eq!(&rx_export, expected_export);
+}
+
+#[test]
+fn hpke_derive_keypair_rfc9180() {
+    let (_, sk_e) = derive_keypair(&BASE_IKM_E);
+    assert_eq!(sk_e, BASE_SK_E);
+}
+
+#[test]
+fn hpke_base_mode_vector() {
+    check_vector(
+        Mode::Base,
+        &BASE_IKM_E,
+        &BASE_IKM_R,
+        None,
+        &BASE_ENC,
+        [&BASE_CT_0, &BASE_CT_1],
+        &BASE_EXPORT,
+    );
+}
+
+#[test]
+fn hpke_base_mode_sequence_256() {
+    let (enc, mut tx) = sender(Mode::Base, &BASE_IKM_E, &BASE_IKM_R, None);
+    let mut rx = recipient(Mode::Base, &enc, &BASE_IKM_R, None);
+
+    for seq in 0..256 {
+        let aad = format!("Count-{seq}");
+        let ct = tx.seal(aad.as_bytes(), PLAINTEXT).unwrap();
+        rx.open(aad.as_bytes(), &ct).unwrap();
+    }
+
+    let ct = tx.seal(b"Count-256", PLAINTEXT).unwrap();
+    assert_eq!(ct, BASE_CT_256);
+    assert_eq!(rx.open(b"Count-256", &ct).unwrap(), PLAINTEXT);
+}
+
+#[test]
+fn hpke_psk_mode_vector() {
+    check_vector(
+        Mode::Psk,
+        &PSK_IKM_E,
+        &PSK_IKM_R,
+        None,
+        &PSK_ENC,
+        [&PSK_CT_0, &PSK_CT_1],
+        &PSK_EXPORT,
+    );
+}
+
+#[test]
+fn hpke_auth_mode_vector() {
+    check_vector(
+        Mode::Auth,
+        &AUTH_IKM_E,
+        &AUTH_IKM_R,
+        Some(&AUTH_IKM_S),
+        &AUTH_ENC,
+        [&AUTH_CT_0, &AUTH_CT_1],
+        &AUTH_EXPORT,
+    );
+}
+
+#[test]
+fn hpke_auth_psk_mode_vector() {
+    check_vector(
+        Mode::AuthPsk,
+        &AUTH_PSK_IKM_E,
+        &AUTH_PSK_IKM_R,
+        Some(&AUTH_PSK_IKM_S),
+        &AUTH_PSK_ENC,
+        [&AUTH_PSK_CT_0, &AUTH_PSK_CT_1],
+        &AUTH_PSK_EXPORT,
+    );
+}
+
+#[test]
+fn hpke_single_shot_roundtrip() {
+    let (pk_r, sk_r) = generate_keypair();
+
+    let (enc, ct) = hpke::seal(&pk_r, b"app info", b"header", b"hello node").unwrap();
+    assert_eq!(ct.len(), 10 + hpke::TAG_LEN);
+
+    let pt = hpke::open(&enc, &sk_r, b"app info", b"header", &ct).unwrap();
+    assert_eq!(pt, b"hello node");
+
+    assert_eq!(
+        hpke::open(&enc, &sk_r, b"other info", b"header", &ct),
+        Err(HpkeError::AuthenticationFailed)
+    );
+    assert_eq!(
+        hpke::open(&enc, &sk_r, b"app info", b"header", &ct[..15]),
+        Err(HpkeError::InvalidLength)
+    );
+}
+
+#[test]
+fn hpke_open_failure_keeps_sequence() {
+    let (pk_r, sk_r) = generate_keypair();
+
+    let (enc, mut tx) = hpke::setup_base_sender(&pk_r, b"").unwrap();
+    let mut rx = hpke::setup_base_recipient(&enc, &sk_r, b"").unwrap();
+
+    let first = tx.seal(b"", b"first").unwrap();
+    let second = tx.seal(b"", b"second").unwrap();
+
+    let mut forged = first.clone();
+    forged[0] ^= 1;
+
+    assert_eq!(rx.open(b"", &forged), Err(HpkeError::AuthenticationFailed));
+    assert_eq!(rx.open(b"", &second), Err(HpkeError::AuthenticationFailed));
+    assert_eq!(rx.open(b"", &first).unwrap(), b"first");
+    assert_eq!(rx.open(b"", &second).unwrap(), b"second");
+}
+
+#[test]
+fn hpke_auth_rejects_wrong_sender() {
+    let (pk_r, sk_r) = generate_keypair();
+    let (_, sk_s) = generate_keypair();
+    let (pk_other, _) = generate_keypair();
+
+    let (enc, mut tx) = hpke::setup_auth_sender(&pk_r, b"", &sk_s).unwrap();
+    let ct = tx.seal(b"", b"signed by key").unwrap();
+
+    let mut rx = hpke::setup_auth_recipient(&enc, &sk_r, b"", &pk_other).unwrap();
+    assert_eq!(rx.open(b"", &ct), Err(HpkeError::AuthenticationFailed));
+}
+
+#[test]
+fn hpke_rejects_inconsistent_psk_inputs() {
+    let (pk_r, sk_r) = generate_keypair();
+
+    assert!(matches!(
+        hpke::setup_psk_sender(&pk_r, b"", &PSK, &[]),
+        Err(HpkeError::InvalidMode)
+    ));
+    assert!(matches!(
+        hpke::setup_psk_sender(&pk_r, b"", &[], PSK_ID),
+        Err(HpkeError::InvalidMode)
+    ));
+    assert!(matches!(
+        hpke::setup_psk_recipient(&[9u8; 32], &sk_r, b"", &[], &[]),
+        Err(HpkeError::InvalidMode)
+    ));
+    assert!(matches!(
+        setup_sender_with_ephemeral(Mode::Base, &pk_r, b"", &PSK, PSK_ID, None, &sk_r),
+        Err(HpkeError::InvalidMode)
+    ));
+    assert!(matches!(
+        setup_sender_with_ephemeral(Mode::Auth, &pk_r, b"", &[], &[], None, &sk_r),
+        Err(HpkeError::InvalidMode)
+    ));
+}
+
+#[test]
+fn hpke_rejects_small_order_key() {
+    assert!(matches!(
+        hpke::setup_base_sender(&[0u8; 32], b""),
+        Err(HpkeError::WeakPublicKey)
+    ));
+
+    let (_, sk_r) = generate_keypair();
+    assert!(matches!(
+        hpke::setup_base_recipient(&[0u8; 32], &sk_r, b""),
+        Err(HpkeError::WeakPublicKey)
+    ));
+}
+
+#[test]
+fn hpke_export_length_limit() {
+    let (pk_r, _) = generate_keypair();
+    let (_, tx) = hpke::setup_base_sender(&pk_r, b"").unwrap();
+
+    let mut out = vec![0u8; hpke::MAX_EXPORT_LEN];
+    assert!(tx.export(b"", &mut out).is_ok());
+
+    let mut out = vec![0u8; hpke::MAX_EXPORT_LEN + 1];
+    assert_eq!(tx.export(b"", &mut out), Err(HpkeError::InvalidLength));
+}
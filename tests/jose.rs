@@ -0,0 +1,443 @@
+use cryptal::encoding::base64::{Variant, decode, encode};
+use cryptal::encoding::json::Value;
+use cryptal::keys::ed25519::generate_keypair;
+use cryptal::keys::x25519;
+use cryptal::protocol::jose::{
+    Claims, ContentEncryption, JoseError, JweHeader, Jwk, JwkKey, JwsHeader, Validation,
+    decode_jws_header, decrypt_jwe, encrypt_jwe, sign_jws, sign_jwt, verify_jws, verify_jwt,
+};
+
+// RFC 8037, Appendix A.1 to A.4.
+const RFC8037_JWK: &str = r#"{"kty":"OKP","crv":"Ed25519",
+    "d":"nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A",
+    "x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#;
+const RFC8037_THUMBPRINT: &str = "kPrK_qmxVWaYVA9wwBF6Iuo3vVzz7TxHCTwXBygrS4k";
+const RFC8037_JWS: &str = "eyJhbGciOiJFZERTQSJ9.RXhhbXBsZSBvZiBFZDI1NTE5IHNpZ25pbmc.\
+    hgyY0il_MGCjP0JzlnLWG1PPOt7-09PGcvMg3AIbQR6dWbhijcNR4ki4iylGjg5BhVsPt9g7sVvpAr_MuM0KAg";
+
+// Generated with Python `cryptography` (C20P) and libsodium (XC20P):
+// recipient secret 00..1f, ephemeral secret 20..3f, apu "Alice", apv "Bob".
+const JWE_C20P: &str = "eyJhbGciOiJFQ0RILUVTIiwiZW5jIjoiQzIwUCIsImtpZCI6ImJvYiIsImVwayI6eyJrdHkiOiJPS1AiLCJjcnYiOiJYMjU1MTkiLCJ4IjoiTllCeTFqWllnTkd1NmpLYTM1RWhPRGhSN1NHaWpqdDE2V1hRMHMwV1lsUSJ9LCJhcHUiOiJRV3hwWTJVIiwiYXB2IjoiUW05aSJ9..ZGVmZ2hpamtsbW5v.LdSJtx-WHPnRztp_HkoiggU.w1_ktIABBrz_M_1mxoZxRw";
+const JWE_XC20P: &str = "eyJhbGciOiJFQ0RILUVTIiwiZW5jIjoiWEMyMFAiLCJraWQiOiJib2IiLCJlcGsiOnsia3R5IjoiT0tQIiwiY3J2IjoiWDI1NTE5IiwieCI6Ik5ZQnkxalpZZ05HdTZqS2EzNUVoT0RoUjdTR2lqanQxNldYUTBzMFdZbFEifSwiYXB1IjoiUVd4cFkyVSIsImFwdiI6IlFtOWkifQ..ZGVmZ2hpamtsbW5vcHFyc3R1dnd4eXp7.qLJMwNSzXJjjIqAhug7J1gw.2029G-67XTKsL_UF-d-yCg";
+
+const NOW: i64 = 1_700_000_000;
+
+fn rfc8037_key() -> Jwk {
+    Jwk::from_json(RFC8037_JWK).unwrap()
+}
+
+fn ed25519_keys(
+    jwk: &Jwk,
+) -> (
+    cryptal::keys::ed25519::PublicKey,
+    cryptal::keys::ed25519::PrivateKey,
+) {
+    match jwk.key {
+        JwkKey::Ed25519 {
+            public,
+            private: Some(private),
+        } => (public, private),
+        _ => panic!("expected a private Ed25519 key"),
+    }
+}
+
+fn forge(header: &str, payload: &str) -> String {
+    format!(
+        "{}.{}.{}",
+        encode(header.as_bytes(), Variant::UrlSafeNoPad),
+        encode(payload.as_bytes(), Variant::UrlSafeNoPad),
+        encode(&[0u8; 64], Variant::UrlSafeNoPad)
+    )
+}
+
+#[test]
+fn test_rfc8037_jwk_and_thumbprint() {
+    let jwk = rfc8037_key();
+    assert!(jwk.is_private());
+    assert_eq!(jwk.thumbprint(), RFC8037_THUMBPRINT);
+    assert_eq!(jwk.to_public().thumbprint(), RFC8037_THUMBPRINT);
+    assert!(!jwk.to_public().is_private());
+
+    let public = jwk.to_public().to_json().unwrap();
+    assert_eq!(
+        public,
+        r#"{"kty":"OKP","crv":"Ed25519","x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#
+    );
+
+    let decoded = Jwk::from_json(&jwk.to_json().unwrap()).unwrap();
+    assert_eq!(decoded.thumbprint(), RFC8037_THUMBPRINT);
+    assert!(decoded.is_private());
+}
+
+#[test]
+fn test_rfc8037_jws() {
+    let (public, private) = ed25519_keys(&rfc8037_key());
+
+    let token = sign_jws(
+        b"Example of Ed25519 signing",
+        &JwsHeader::default(),
+        &private,
+    );
+    assert_eq!(token, RFC8037_JWS);
+
+    let (header, payload) = verify_jws(RFC8037_JWS, &public).unwrap();
+    assert_eq!(header, JwsHeader::default());
+    assert_eq!(payload, b"Example of Ed25519 signing");
+}
+
+#[test]
+fn test_x25519_jwk() {
+    let (public, secret) = x25519::generate_keypair();
+
+    let mut jwk = Jwk::from_x25519(secret);
+    jwk.kid = Some("enc-1".to_string());
+
+    let decoded = Jwk::from_json(&jwk.to_json().unwrap()).unwrap();
+    assert_eq!(decoded.kid.as_deref(), Some("enc-1"));
+    match decoded.key {
+        JwkKey::X25519 {
+            public: p,
+            private: Some(s),
+        } => assert_eq!((p, s), (public, secret)),
+        _ => panic!("expected a private X25519 key"),
+    }
+}
+
+#[test]
+fn test_rejects_invalid_jwks() {
+    let (other, _) = x25519::generate_keypair();
+    let mismatched = format!(
+        r#"{{"kty":"OKP","crv":"Ed25519","x":"{}","d":"nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A"}}"#,
+        encode(&other, Variant::UrlSafeNoPad)
+    );
+
+    assert_eq!(
+        Jwk::from_json(&mismatched).err(),
+        Some(JoseError::InvalidKey)
+    );
+    assert_eq!(
+        Jwk::from_json(r#"{"kty":"EC","crv":"P-256","x":"AA","y":"AA"}"#).err(),
+        Some(JoseError::UnsupportedAlgorithm)
+    );
+    assert_eq!(
+        Jwk::from_json(r#"{"kty":"OKP","crv":"Ed448","x":"AA"}"#).err(),
+        Some(JoseError::UnsupportedAlgorithm)
+    );
+    assert_eq!(
+        Jwk::from_json(r#"{"kty":"OKP","crv":"X25519","x":"AAAA"}"#).err(),
+        Some(JoseError::InvalidKey)
+    );
+    assert_eq!(
+        Jwk::from_json(r#"{"kty":"OKP","crv":"X25519"}"#).err(),
+        Some(JoseError::InvalidKey)
+    );
+    assert!(matches!(
+        Jwk::from_json("{\"kty\":"),
+        Err(JoseError::InvalidJson(_))
+    ));
+}
+
+#[test]
+fn test_jws_rejects_tampering_and_other_algorithms() {
+    let (public, private) = generate_keypair();
+    let (other, _) = generate_keypair();
+
+    let header = JwsHeader {
+        typ: None,
+        kid: Some("sig-1".to_string()),
+    };
+    let token = sign_jws(b"payload", &header, &private);
+
+    assert_eq!(decode_jws_header(&token).unwrap(), header);
+    assert_eq!(
+        verify_jws(&token, &other).err(),
+        Some(JoseError::InvalidSignature)
+    );
+
+    let mut parts: Vec<&str> = token.split('.').collect();
+    let tampered_payload = encode(b"payloaD", Variant::UrlSafeNoPad);
+    parts[1] = &tampered_payload;
+    assert_eq!(
+        verify_jws(&parts.join("."), &public).err(),
+        Some(JoseError::InvalidSignature)
+    );
+
+    assert_eq!(
+        verify_jws(&forge(r#"{"alg":"none"}"#, "{}"), &public).err(),
+        Some(JoseError::UnsupportedAlgorithm)
+    );
+    assert_eq!(
+        verify_jws(&forge(r#"{"alg":"HS256"}"#, "{}"), &public).err(),
+        Some(JoseError::UnsupportedAlgorithm)
+    );
+    assert_eq!(
+        verify_jws(&forge(r#"{"alg":"EdDSA","crit":["b64"]}"#, "{}"), &public).err(),
+        Some(JoseError::UnsupportedExtension)
+    );
+    assert_eq!(
+        verify_jws(&forge(r#"{"alg":"EdDSA","alg":"none"}"#, "{}"), &public).err(),
+        Some(JoseError::InvalidJson(
+            cryptal::encoding::json::JsonError::DuplicateKey
+        ))
+    );
+    assert_eq!(
+        verify_jws("a.b", &public).err(),
+        Some(JoseError::InvalidFormat)
+    );
+    assert_eq!(
+        verify_jws(&format!("{token}.x"), &public).err(),
+        Some(JoseError::InvalidFormat)
+    );
+}
+
+#[test]
+fn test_jwt_roundtrip() {
+    let (public, private) = generate_keypair();
+
+    let claims = Claims {
+        iss: Some("https://id.example".to_string()),
+        sub: Some("node-7".to_string()),
+        aud: vec!["api".to_string()],
+        exp: Some(NOW + 300),
+        nbf: Some(NOW - 10),
+        iat: Some(NOW - 10),
+        jti: Some("42".to_string()),
+        custom: vec![
+            ("scope".to_string(), Value::from("read write")),
+            ("admin".to_string(), Value::from(false)),
+        ],
+    };
+
+    let token = sign_jwt(&claims, Some("sig-1"), &private);
+
+    let header = decode_jws_header(&token).unwrap();
+    assert_eq!(header.typ.as_deref(), Some("JWT"));
+    assert_eq!(header.kid.as_deref(), Some("sig-1"));
+
+    let mut validation = Validation::new(NOW);
+    validation.audience = Some("api".to_string());
+    validation.issuer = Some("https://id.example".to_string());
+
+    let decoded = verify_jwt(&token, &public, &validation).unwrap();
+    assert_eq!(decoded, claims);
+    assert_eq!(
+        decoded.get("scope").and_then(Value::as_str),
+        Some("read write")
+    );
+
+    let payload = decode(token.split('.').nth(1).unwrap(), Variant::UrlSafeNoPad).unwrap();
+    assert_eq!(
+        String::from_utf8(payload).unwrap(),
+        r#"{"iss":"https://id.example","sub":"node-7","aud":"api","exp":1700000300,"nbf":1699999990,"iat":1699999990,"jti":"42","scope":"read write","admin":false}"#
+    );
+}
+
+#[test]
+fn test_jwt_claims_from_json() {
+    let claims = Claims::from_json(r#"{"exp":1700000300.9,"nbf":-1.5}"#).unwrap();
+    assert_eq!(claims.exp, Some(1700000300));
+    assert_eq!(claims.nbf, Some(-2));
+
+    assert_eq!(
+        Claims::from_json(r#"{"exp":1e300}"#).err(),
+        Some(JoseError::InvalidClaims)
+    );
+    assert_eq!(
+        Claims::from_json("[]").err(),
+        Some(JoseError::InvalidClaims)
+    );
+    assert!(matches!(
+        Claims::from_json("{"),
+        Err(JoseError::InvalidJson(_))
+    ));
+}
+
+#[test]
+fn test_jwt_claim_validation() {
+    let (public, private) = generate_keypair();
+
+    let sign = |claims: Claims| sign_jwt(&claims, None, &private);
+    let check = |token: &str, validation: &Validation| verify_jwt(token, &public, validation).err();
+
+    let validation = Validation::new(NOW);
+
+    let expired = sign(Claims {
+        exp: Some(NOW),
+        ..Claims::default()
+    });
+    assert_eq!(check(&expired, &validation), Some(JoseError::Expired));
+
+    let not_yet = sign(Claims {
+        exp: Some(NOW + 100),
+        nbf: Some(NOW + 30),
+        ..Claims::default()
+    });
+    assert_eq!(check(&not_yet, &validation), Some(JoseError::NotYetValid));
+
+    let mut lenient = Validation::new(NOW);
+    lenient.leeway = 60;
+    assert_eq!(check(&expired, &lenient), None);
+    assert_eq!(check(&not_yet, &lenient), None);
+
+    let no_exp = sign(Claims::default());
+    assert_eq!(check(&no_exp, &validation), Some(JoseError::InvalidClaims));
+    let mut optional_exp = Validation::new(NOW);
+    optional_exp.require_exp = false;
+    assert_eq!(check(&no_exp, &optional_exp), None);
+
+    let audiences = sign(Claims {
+        exp: Some(NOW + 100),
+        aud: vec!["api".to_string(), "web".to_string()],
+        iss: Some("issuer".to_string()),
+        ..Claims::default()
+    });
+    assert_eq!(
+        check(&audiences, &validation),
+        Some(JoseError::InvalidAudience)
+    );
+
+    let mut web = Validation::new(NOW);
+    web.audience = Some("web".to_string());
+    assert_eq!(check(&audiences, &web), None);
+
+    web.audience = Some("admin".to_string());
+    assert_eq!(check(&audiences, &web), Some(JoseError::InvalidAudience));
+
+    web.audience = Some("web".to_string());
+    web.issuer = Some("other".to_string());
+    assert_eq!(check(&audiences, &web), Some(JoseError::InvalidIssuer));
+
+    let typed = sign_jws(
+        br#"{"exp":1800000000}"#,
+        &JwsHeader {
+            typ: Some("at+jwt".to_string()),
+            kid: None,
+        },
+        &private,
+    );
+    assert_eq!(check(&typed, &validation), Some(JoseError::InvalidHeader));
+
+    let bad_claim = sign_jws(br#"{"exp":"tomorrow"}"#, &JwsHeader::default(), &private);
+    assert_eq!(
+        check(&bad_claim, &validation),
+        Some(JoseError::InvalidClaims)
+    );
+}
+
+#[test]
+fn test_jwe_roundtrip() {
+    let (public, secret) = x25519::generate_keypair();
+
+    for enc in [ContentEncryption::C20P, ContentEncryption::XC20P] {
+        let mut header = JweHeader::new(enc);
+        header.kid = Some("enc-1".to_string());
+        header.cty = Some("JWT".to_string());
+        header.apv = b"recipient".to_vec();
+
+        for plaintext in [&b""[..], b"secret token", &[7u8; 1000]] {
+            let token = encrypt_jwe(plaintext, &header, &public).unwrap();
+            assert_eq!(token.split('.').nth(1), Some(""));
+
+            let (decoded, opened) = decrypt_jwe(&token, &secret).unwrap();
+            assert_eq!(decoded, header);
+            assert_eq!(opened, plaintext);
+        }
+    }
+}
+
+#[test]
+fn test_jwe_interoperability() {
+    let mut secret = [0u8; 32];
+    for (i, byte) in secret.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+
+    for (token, enc) in [
+        (JWE_C20P, ContentEncryption::C20P),
+        (JWE_XC20P, ContentEncryption::XC20P),
+    ] {
+        let (header, plaintext) = decrypt_jwe(token, &secret).unwrap();
+        assert_eq!(header.enc, enc);
+        assert_eq!(header.kid.as_deref(), Some("bob"));
+        assert_eq!(header.apu, b"Alice");
+        assert_eq!(header.apv, b"Bob");
+        assert_eq!(plaintext, b"hello from python");
+    }
+}
+
+#[test]
+fn test_jwe_rejects_invalid_tokens() {
+    let (public, secret) = x25519::generate_keypair();
+    let (_, other) = x25519::generate_keypair();
+
+    let token = encrypt_jwe(b"data", &JweHeader::new(ContentEncryption::XC20P), &public).unwrap();
+
+    assert_eq!(
+        decrypt_jwe(&token, &other).err(),
+        Some(JoseError::DecryptionFailed)
+    );
+
+    let mut parts: Vec<String> = token.split('.').map(str::to_string).collect();
+    let header = String::from_utf8(decode(&parts[0], Variant::UrlSafeNoPad).unwrap()).unwrap();
+
+    // The protected header is authenticated.
+    let mut tampered = parts.clone();
+    tampered[0] = encode(
+        header
+            .replace("\"XC20P\"", "\"XC20P\",\"kid\":\"x\"")
+            .as_bytes(),
+        Variant::UrlSafeNoPad,
+    );
+    assert_eq!(
+        decrypt_jwe(&tampered.join("."), &secret).err(),
+        Some(JoseError::DecryptionFailed)
+    );
+
+    let mut tampered = parts.clone();
+    tampered[0] = encode(
+        header.replace("ECDH-ES", "ECDH-ES+A128KW").as_bytes(),
+        Variant::UrlSafeNoPad,
+    );
+    assert_eq!(
+        decrypt_jwe(&tampered.join("."), &secret).err(),
+        Some(JoseError::UnsupportedAlgorithm)
+    );
+
+    let mut tampered = parts.clone();
+    tampered[0] = encode(
+        header
+            .replace("\"XC20P\"", "\"XC20P\",\"zip\":\"DEF\"")
+            .as_bytes(),
+        Variant::UrlSafeNoPad,
+    );
+    assert_eq!(
+        decrypt_jwe(&tampered.join("."), &secret).err(),
+        Some(JoseError::UnsupportedExtension)
+    );
+
+    let mut tampered = parts.clone();
+    tampered[0] = encode(
+        header.replace("XC20P", "C20P").as_bytes(),
+        Variant::UrlSafeNoPad,
+    );
+    assert_eq!(
+        decrypt_jwe(&tampered.join("."), &secret).err(),
+        Some(JoseError::InvalidFormat)
+    );
+
+    parts[1] = "AAAA".to_string();
+    assert_eq!(
+        decrypt_jwe(&parts.join("."), &secret).err(),
+        Some(JoseError::InvalidFormat)
+    );
+
+    assert_eq!(
+        encrypt_jwe(
+            b"data",
+            &JweHeader::new(ContentEncryption::C20P),
+            &[0u8; 32]
+        )
+        .err(),
+        Some(JoseError::WeakPublicKey)
+    );
+}
@@ -0,0 +1,262 @@
+use cryptal::encoding::base64::{self, Variant};
+use cryptal::keys::minisign::{MinisignError, MinisignPublicKey, MinisignSecretKey, ScryptLimits};
+
+// Key and signing vectors built with libsodium following the minisign
+// formats, since signing needs a known secret key: seed 0x40..0x60,
+// key id 3d1c7a5e9b20f486, secret key encrypted with "correct horse"
+// (opslimit 2^16, memlimit 2^24).
+const PUBLIC_KEY: &str = "\
+untrusted comment: minisign public key 86F4209B5E7A1C3D
+RWQ9HHpemyD0hiVDuS/xCVURR2rcg2nbbdyTNmWhGXjdoUBO4QZsqVWd
+";
+const SECRET_KEY: &str = "\
+untrusted comment: minisign encrypted secret key
+RWRTY0IyAAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8AAAEAAAAAAAAAAAEAAAAADshgtvPiJV//iHlBcpTGCdt4pwyyXus8GFZZTH4aFJzwl65cKNzHyXGUDyxhakGtgyzzq2YQ1Jaj9V8c9s4CEYJ96ZOHa681K3E6CxnzJANXglfNPfH+WAmHwdmWJ5ht54ArkdMP/gw=
+";
+const MESSAGE: &[u8] = b"minisign test message\n";
+const SIGNATURE: &str = "\
+untrusted comment: signature from minisign secret key
+RUQ9HHpemyD0hsNKdtGYQMkZ3SvQYoOR3FUL324Fp8bRPbcWx5AlmbK4ogzPUm6my1tkXT5mXcYBrmiqShde4s1XqRFJXGxsGgM=
+trusted comment: timestamp:1700000000\tfile:message.txt\thashed
+/UHI/XH3F5b+kO/L4uMoXQzbQD47r4tPHH5ToikoboBaAFEHOLhnyKz2NRl5h0ZT5uXv7MmS9iL7dnPWwc8rAQ==
+";
+const LEGACY_SIGNATURE: &str = "\
+untrusted comment: signature from minisign secret key
+RWQ9HHpemyD0hkfdYhYyGD4tAUVepesidYYsKZBzngvwbOHxFxAm6fnbaITacDWwxg1WQNgtD5DUssXMmCc5TnOEH/Z7UfXrngg=
+trusted comment: timestamp:1700000000\tfile:message.txt
+QHot1OhyNvw4Vh3TDBRKQlp+tLre7beSl9FTSXts4nRtNkdsTesGNV7s4+Bqs+KDsVn6uaaP+33l40B1cN2XDA==
+";
+
+const TEST_LIMITS: ScryptLimits = ScryptLimits {
+    opslimit: 1 << 16,
+    memlimit: 1 << 24,
+};
+
+#[test]
+fn test_minisign_vectors() {
+    let (public, comment) = MinisignPublicKey::decode(PUBLIC_KEY).unwrap();
+    assert_eq!(comment, "minisign public key 86F4209B5E7A1C3D");
+    assert_eq!(public.encode(), PUBLIC_KEY);
+
+    let (secret, _) = MinisignSecretKey::decode(SECRET_KEY, Some(b"correct horse")).unwrap();
+    assert_eq!(secret.public_key(), public);
+
+    assert_eq!(
+        public.verify(SIGNATURE, MESSAGE).unwrap(),
+        "timestamp:1700000000\tfile:message.txt\thashed"
+    );
+    assert_eq!(
+        public.verify(LEGACY_SIGNATURE, MESSAGE).unwrap(),
+        "timestamp:1700000000\tfile:message.txt"
+    );
+
+    let trusted = "timestamp:1700000000\tfile:message.txt\thashed";
+    assert_eq!(secret.sign(MESSAGE, "", trusted).unwrap(), SIGNATURE);
+
+    let trusted = "timestamp:1700000000\tfile:message.txt";
+    assert_eq!(
+        secret.sign_legacy(MESSAGE, "", trusted).unwrap(),
+        LEGACY_SIGNATURE
+    );
+}
+
+// Signatures produced by `minisign -S` over the message "test", as
+// published in the minisign-verify test suite.
+const TOOL_PUBLIC_KEY: &str = "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+const TOOL_SIGNATURE: &str = "\
+untrusted comment: signature from minisign secret key
+RUQf6LRCGA9i559r3g7V1qNyJDApGip8MfqcadIgT9CuhV3EMhHoN1mGTkUidF/z7SrlQgXdy8ofjb7bNJJylDOocrCo8KLzZwo=
+trusted comment: timestamp:1556193335\tfile:test
+y/rUw2y8/hOUYjZU71eHp/Wo1KZ40fGy2VJEDl34XMJM+TX48Ss/17u3IvIfbVR1FkZZSNCisQbuQY+bHwhEBg==
+";
+const TOOL_PREHASHED_SIGNATURE: &str = "\
+untrusted comment: signature from minisign secret key
+RUQf6LRCGA9i559r3g7V1qNyJDApGip8MfqcadIgT9CuhV3EMhHoN1mGTkUidF/z7SrlQgXdy8ofjb7bNJJylDOocrCo8KLzZwo=
+trusted comment: timestamp:1633700835\tfile:test\tprehashed
+wLMDjy9FLAuxZ3q4NlEvkgtyhrr0gtTu6KC4KBJdITbbOeAi1zBIYo0v4iTgt8jJpIidRJnp94ABQkJAgAooBQ==
+";
+// `minisign -S -l` (legacy, non-prehashed) output of the same key.
+const TOOL_LEGACY_SIGNATURE: &str = "\
+untrusted comment: signature from minisign secret key
+RWQf6LRCGA9i59SLOFxz6NxvASXDJeRtuZykwQepbDEGt87ig1BNpWaVWuNrm73YiIiJbq71Wi+dP9eKL8OC351vwIasSSbXxwA=
+trusted comment: timestamp:1555779966\tfile:test
+QtKMXWyYcwdpZAlPF7tE2ENJkRd1ujvKjlj1m9RtHTBnZPa5WKU5uWRs5GoP5M/VqE81QFuMKI5k/SfNQUaOAA==
+";
+
+#[test]
+fn test_minisign_tool_signatures() {
+    let public = MinisignPublicKey::from_base64(TOOL_PUBLIC_KEY).unwrap();
+
+    for (signature, trusted) in [
+        (TOOL_SIGNATURE, "timestamp:1556193335\tfile:test"),
+        (
+            TOOL_PREHASHED_SIGNATURE,
+            "timestamp:1633700835\tfile:test\tprehashed",
+        ),
+        (TOOL_LEGACY_SIGNATURE, "timestamp:1555779966\tfile:test"),
+    ] {
+        assert_eq!(public.verify(signature, b"test").unwrap(), trusted);
+        assert_eq!(
+            public.verify(signature, b"Test"),
+            Err(MinisignError::InvalidSignature)
+        );
+    }
+
+    let (other, _) = MinisignPublicKey::decode(PUBLIC_KEY).unwrap();
+    assert_eq!(
+        other.verify(TOOL_SIGNATURE, b"test"),
+        Err(MinisignError::KeyMismatch)
+    );
+}
+
+#[test]
+fn test_minisign_published_key() {
+    // The key minisign releases are signed with.
+    let public =
+        MinisignPublicKey::from_base64("RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3")
+            .unwrap();
+    assert!(
+        public
+            .encode()
+            .starts_with("untrusted comment: minisign public key E7620F1842B4E81F\n")
+    );
+    assert_eq!(
+        public.to_base64(),
+        "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3"
+    );
+}
+
+#[test]
+fn test_minisign_secret_key_roundtrip() {
+    let secret = MinisignSecretKey::generate();
+
+    let encoded = secret
+        .encode_with_limits(Some(b"pw"), "minisign encrypted secret key", TEST_LIMITS)
+        .unwrap();
+    assert_eq!(
+        MinisignSecretKey::decode(&encoded, None).err(),
+        Some(MinisignError::PasswordRequired)
+    );
+    assert_eq!(
+        MinisignSecretKey::decode(&encoded, Some(b"pW")).err(),
+        Some(MinisignError::WrongPassword)
+    );
+
+    let (decoded, comment) = MinisignSecretKey::decode(&encoded, Some(b"pw")).unwrap();
+    assert_eq!(comment, "minisign encrypted secret key");
+    assert_eq!(decoded.public_key(), secret.public_key());
+    assert_eq!(decoded.private.seed(), secret.private.seed());
+
+    let plain = secret.encode(None, "minisign secret key").unwrap();
+    let (decoded, _) = MinisignSecretKey::decode(&plain, Some(b"ignored")).unwrap();
+    assert_eq!(decoded.public_key(), secret.public_key());
+}
+
+/// Rewrites the scrypt limits stored in an encrypted secret key file.
+fn with_limits(secret_key: &str, opslimit: u64, memlimit: u64) -> String {
+    let mut lines = secret_key.lines();
+    let comment = lines.next().unwrap();
+
+    let mut data = base64::decode(lines.next().unwrap(), Variant::Standard).unwrap();
+    data[38..46].copy_from_slice(&opslimit.to_le_bytes());
+    data[46..54].copy_from_slice(&memlimit.to_le_bytes());
+
+    format!("{comment}\n{}\n", base64::encode(&data, Variant::Standard))
+}
+
+#[test]
+fn test_minisign_rejects_excessive_limits() {
+    let password = Some(&b"correct horse"[..]);
+
+    // Would derive p = 2^27 and allocate 128 GiB of scrypt blocks.
+    for (opslimit, memlimit) in [(1 << 62, 1 << 20), (1 << 16, 1 << 31), (1 << 26, 1 << 24)] {
+        let crafted = with_limits(SECRET_KEY, opslimit, memlimit);
+        assert_eq!(
+            MinisignSecretKey::decode(&crafted, password).err(),
+            Some(MinisignError::InvalidKdfParams)
+        );
+    }
+
+    // Limits within the defaults are read, but yield another mask.
+    let crafted = with_limits(SECRET_KEY, 1 << 16, 1 << 20);
+    assert_eq!(
+        MinisignSecretKey::decode(&crafted, password).err(),
+        Some(MinisignError::WrongPassword)
+    );
+
+    let secret = MinisignSecretKey::generate();
+    let limits = ScryptLimits {
+        memlimit: (1 << 30) + 1,
+        ..ScryptLimits::default()
+    };
+    assert_eq!(
+        secret.encode_with_limits(Some(b"pw"), "", limits).err(),
+        Some(MinisignError::InvalidKdfParams)
+    );
+}
+
+#[test]
+fn test_minisign_sign_verify() {
+    let secret = MinisignSecretKey::generate();
+    let public = secret.public_key();
+    let message = vec![0x5au8; 100_000];
+
+    let signature = secret.sign(&message, "custom", "trusted").unwrap();
+    assert!(signature.starts_with("untrusted comment: custom\n"));
+    assert_eq!(public.verify(&signature, &message).unwrap(), "trusted");
+    assert_eq!(
+        public.verify(&signature, &message[1..]),
+        Err(MinisignError::InvalidSignature)
+    );
+
+    // The trusted comment is authenticated.
+    let forged = signature.replace("trusted comment: trusted", "trusted comment: forged");
+    assert_eq!(
+        public.verify(&forged, &message),
+        Err(MinisignError::InvalidSignature)
+    );
+
+    let (other, _) = MinisignPublicKey::decode(PUBLIC_KEY).unwrap();
+    assert_eq!(
+        other.verify(&signature, &message),
+        Err(MinisignError::KeyMismatch)
+    );
+}
+
+#[test]
+fn test_minisign_rejects_invalid_input() {
+    let (public, _) = MinisignPublicKey::decode(PUBLIC_KEY).unwrap();
+    let (secret, _) = MinisignSecretKey::decode(SECRET_KEY, Some(b"correct horse")).unwrap();
+
+    assert_eq!(
+        secret.sign(MESSAGE, "", "two\nlines").err(),
+        Some(MinisignError::InvalidComment)
+    );
+
+    // Missing global signature.
+    let truncated: String = SIGNATURE
+        .lines()
+        .take(3)
+        .map(|l| format!("{l}\n"))
+        .collect();
+    assert_eq!(
+        public.verify(&truncated, MESSAGE),
+        Err(MinisignError::InvalidEncoding)
+    );
+
+    // Unknown signature algorithm ("EX").
+    let unknown = SIGNATURE.replacen("RUQ9", "RVg9", 1);
+    assert_eq!(
+        public.verify(&unknown, MESSAGE),
+        Err(MinisignError::UnsupportedAlgorithm)
+    );
+
+    assert_eq!(
+        MinisignPublicKey::from_base64("RWQf6LRC").err(),
+        Some(MinisignError::InvalidEncoding)
+    );
+    assert_eq!(
+        MinisignPublicKey::decode(SIGNATURE).err(),
+        Some(MinisignError::InvalidEncoding)
+    );
+}
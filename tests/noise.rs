@@ -0,0 +1,583 @@
+use cryptal::protocol::noise::{
+    HandshakeConfig, HandshakePattern, HandshakeState, HashChoice, MAX_MESSAGE_LEN, NoiseError,
+    NoiseParams, TransportState,
+};
+
+// Cacophony test vectors (https://github.com/haskell-cryptography/cacophony)
+// for the supported patterns. All vectors share the keys, prologue, PSK
+// and payloads below.
+
+const PROLOGUE: &[u8] = b"John Galt";
+const PSK: [u8; 32] = *b"This is my Austrian perspective!";
+
+const INIT_STATIC: &str = "e61ef9919cde45dd5f82166404bd08e38bceb5dfdfded0a34c8df7ed542214d1";
+const INIT_EPHEMERAL: &str = "893e28b9dc6ca8d611ab664754b8ceb7bac5117349a4439a6b0569da977c464a";
+const RESP_STATIC: &str = "4a3acbfdb163dec651dfa3194dece676d437029c62a408b4c5ea9114246e4893";
+const RESP_STATIC_PUBLIC: &str = "31e0303fd6418d2f8c0e78b91f22e8caed0fbe48656dcf4767e4834f701b8f62";
+const RESP_EPHEMERAL: &str = "bbdb4cdbd309f1a1f2e1456967fe288cadd6f712d65dc7b7793d5e63da6b375b";
+
+const PAYLOADS: [&[u8]; 6] = [
+    b"Ludwig von Mises",
+    b"Murray Rothbard",
+    b"F. A. Hayek",
+    b"Carl Menger",
+    b"Jean-Baptiste Say",
+    b"Eugen B\xf6hm von Bawerk",
+];
+
+struct Vector {
+    name: &'static str,
+    handshake_hash: &'static str,
+    ciphertexts: [&'static str; 6],
+}
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn key(s: &str) -> [u8; 32] {
+    unhex(s).try_into().unwrap()
+}
+
+fn handshake(params: &NoiseParams, initiator: bool, fixed: bool) -> HandshakeState {
+    let psks = [PSK];
+    let psk_count = params.name().matches("psk").count();
+
+    let config = HandshakeConfig {
+        prologue: PROLOGUE,
+        local_static: Some(key(if initiator { INIT_STATIC } else { RESP_STATIC })),
+        remote_static: Some(key(RESP_STATIC_PUBLIC)),
+        psks: &psks[..psk_count],
+        local_ephemeral: fixed.then(|| {
+            key(if initiator {
+                INIT_EPHEMERAL
+            } else {
+                RESP_EPHEMERAL
+            })
+        }),
+    };
+
+    if initiator {
+        HandshakeState::initiator(params, config).unwrap()
+    } else {
+        HandshakeState::responder(params, config).unwrap()
+    }
+}
+
+#[test]
+fn noise_cacophony_vectors() {
+    for vector in VECTORS {
+        let params = NoiseParams::parse(vector.name).unwrap();
+        assert_eq!(params.name(), vector.name);
+
+        let mut init = handshake(&params, true, true);
+        let mut resp = handshake(&params, false, true);
+        let mut transport: Option<(TransportState, TransportState)> = None;
+
+        for (i, (payload, expected)) in PAYLOADS.iter().zip(vector.ciphertexts).enumerate() {
+            let expected = unhex(expected);
+
+            match &mut transport {
+                None => {
+                    let (sender, receiver) = if i % 2 == 0 {
+                        (&mut init, &mut resp)
+                    } else {
+                        (&mut resp, &mut init)
+                    };
+
+                    let message = sender.write_message(payload).unwrap();
+                    assert_eq!(message, expected, "{} message {i}", vector.name);
+                    assert_eq!(receiver.read_message(&message).unwrap(), *payload);
+
+                    if init.is_finished() {
+                        assert!(resp.is_finished());
+                        assert_eq!(init.handshake_hash(), unhex(vector.handshake_hash));
+                        assert_eq!(resp.handshake_hash(), unhex(vector.handshake_hash));
+
+                        let done_init =
+                            std::mem::replace(&mut init, handshake(&params, true, true));
+                        let done_resp =
+                            std::mem::replace(&mut resp, handshake(&params, false, true));
+
+                        transport = Some((
+                            done_init.into_transport().unwrap(),
+                            done_resp.into_transport().unwrap(),
+                        ));
+                    }
+                }
+                Some((init_t, resp_t)) => {
+                    // One-way patterns only send from the initiator.
+                    let (sender, receiver) =
+                        if i % 2 == 0 || params.pattern() == HandshakePattern::N {
+                            (init_t, resp_t)
+                        } else {
+                            (resp_t, init_t)
+                        };
+
+                    let message = sender.write_message(payload).unwrap();
+                    assert_eq!(message, expected, "{} message {i}", vector.name);
+                    assert_eq!(receiver.read_message(&message).unwrap(), *payload);
+                }
+            }
+        }
+
+        assert!(transport.is_some(), "{}", vector.name);
+    }
+}
+
+#[test]
+fn noise_params_parsing() {
+    let params = NoiseParams::parse("Noise_XXpsk3_25519_ChaChaPoly_BLAKE2b").unwrap();
+    assert_eq!(params.pattern(), HandshakePattern::XX);
+    assert_eq!(params.hash(), HashChoice::Blake2b);
+
+    let params = NoiseParams::new(HandshakePattern::NK, HashChoice::Sha256)
+        .with_psk(0)
+        .unwrap()
+        .with_psk(2)
+        .unwrap();
+    assert_eq!(params.name(), "Noise_NKpsk0+psk2_25519_ChaChaPoly_SHA256");
+    assert_eq!(NoiseParams::parse(&params.name()), Ok(params));
+
+    for name in [
+        "Noise_NN_25519_ChaChaPoly_SHA256",
+        "Noise_XX_448_ChaChaPoly_SHA256",
+        "Noise_XX_25519_AESGCM_SHA256",
+        "Noise_XX_25519_ChaChaPoly_SHA512",
+        "Noise_XXpsk4_25519_ChaChaPoly_SHA256",
+        "Noise_XXpsk2+psk1_25519_ChaChaPoly_SHA256",
+        "Noise_XXfallback_25519_ChaChaPoly_SHA256",
+        "Noise_XX_25519_ChaChaPoly",
+    ] {
+        assert_eq!(
+            NoiseParams::parse(name),
+            Err(NoiseError::UnsupportedProtocol),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn noise_missing_keys() {
+    let ik = NoiseParams::parse("Noise_IK_25519_ChaChaPoly_SHA256").unwrap();
+
+    assert_eq!(
+        HandshakeState::initiator(
+            &ik,
+            HandshakeConfig {
+                local_static: Some([1; 32]),
+                ..Default::default()
+            }
+        )
+        .err(),
+        Some(NoiseError::MissingKey)
+    );
+    assert_eq!(
+        HandshakeState::responder(&ik, HandshakeConfig::default()).err(),
+        Some(NoiseError::MissingKey)
+    );
+
+    let psk = NoiseParams::parse("Noise_XXpsk3_25519_ChaChaPoly_SHA256").unwrap();
+    assert_eq!(
+        HandshakeState::responder(
+            &psk,
+            HandshakeConfig {
+                local_static: Some([1; 32]),
+                ..Default::default()
+            }
+        )
+        .err(),
+        Some(NoiseError::MissingKey)
+    );
+}
+
+/// Runs a full XX handshake with random ephemeral keys.
+fn xx_session() -> (TransportState, TransportState) {
+    let params = NoiseParams::parse("Noise_XX_25519_ChaChaPoly_BLAKE2b").unwrap();
+    let mut init = handshake(&params, true, false);
+    let mut resp = handshake(&params, false, false);
+
+    let m = init.write_message(b"").unwrap();
+    resp.read_message(&m).unwrap();
+    let m = resp.write_message(b"").unwrap();
+    init.read_message(&m).unwrap();
+    let m = init.write_message(b"").unwrap();
+    resp.read_message(&m).unwrap();
+
+    assert_eq!(init.handshake_hash(), resp.handshake_hash());
+    assert_eq!(init.remote_static(), Some(key(RESP_STATIC_PUBLIC)));
+
+    (
+        init.into_transport().unwrap(),
+        resp.into_transport().unwrap(),
+    )
+}
+
+#[test]
+fn noise_handshake_state_machine() {
+    let params = NoiseParams::parse("Noise_XX_25519_ChaChaPoly_SHA256").unwrap();
+    let mut init = handshake(&params, true, false);
+    let mut resp = handshake(&params, false, false);
+
+    // Wrong turns.
+    assert_eq!(resp.write_message(b""), Err(NoiseError::InvalidState));
+    assert_eq!(init.read_message(&[0; 32]), Err(NoiseError::InvalidState));
+
+    let m = init.write_message(b"").unwrap();
+    resp.read_message(&m).unwrap();
+
+    // Too long; the state is left unchanged.
+    assert_eq!(
+        resp.write_message(&[0; MAX_MESSAGE_LEN]),
+        Err(NoiseError::MessageTooLong)
+    );
+    assert!(resp.is_my_turn());
+
+    // Not finished.
+    assert!(resp.into_transport().is_err());
+}
+
+#[test]
+fn noise_handshake_tampering() {
+    let params = NoiseParams::parse("Noise_IK_25519_ChaChaPoly_SHA256").unwrap();
+    let mut init = handshake(&params, true, false);
+    let mut resp = handshake(&params, false, false);
+
+    let mut m = init.write_message(b"hello").unwrap();
+    *m.last_mut().unwrap() ^= 1;
+
+    assert_eq!(resp.read_message(&m), Err(NoiseError::DecryptionFailed));
+
+    // The handshake is failed for good.
+    let last = m.len() - 1;
+    m[last] ^= 1;
+    assert_eq!(resp.read_message(&m), Err(NoiseError::InvalidState));
+
+    // Truncated message.
+    let mut resp = handshake(&params, false, false);
+    assert_eq!(resp.read_message(&m[..40]), Err(NoiseError::InvalidMessage));
+}
+
+#[test]
+fn noise_wrong_psk() {
+    let params = NoiseParams::parse("Noise_NKpsk0_25519_ChaChaPoly_SHA256").unwrap();
+    let mut init = handshake(&params, true, false);
+
+    let resp_psks = [[0u8; 32]];
+    let mut resp = HandshakeState::responder(
+        &params,
+        HandshakeConfig {
+            local_static: Some(key(RESP_STATIC)),
+            psks: &resp_psks,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let m = init.write_message(b"").unwrap();
+    assert_eq!(resp.read_message(&m), Err(NoiseError::DecryptionFailed));
+}
+
+#[test]
+fn noise_transport() {
+    let (mut init, mut resp) = xx_session();
+
+    let a = init.write_message(b"first").unwrap();
+    let b = init.write_message(b"second").unwrap();
+
+    // Reordering and replay are rejected.
+    assert_eq!(resp.read_message(&b), Err(NoiseError::DecryptionFailed));
+    assert_eq!(resp.read_message(&a).unwrap(), b"first");
+    assert_eq!(resp.read_message(&a), Err(NoiseError::DecryptionFailed));
+    assert_eq!(resp.read_message(&b).unwrap(), b"second");
+
+    let c = resp.write_message(b"reply").unwrap();
+    assert_eq!(init.read_message(&c).unwrap(), b"reply");
+
+    assert_eq!(
+        init.write_message(&[0; MAX_MESSAGE_LEN]),
+        Err(NoiseError::MessageTooLong)
+    );
+    assert_eq!(resp.read_message(&[0; 15]), Err(NoiseError::InvalidMessage));
+}
+
+#[test]
+fn noise_transport_rekey() {
+    let (mut init, mut resp) = xx_session();
+
+    init.rekey_outgoing();
+    let m = init.write_message(b"after rekey").unwrap();
+    assert_eq!(resp.read_message(&m), Err(NoiseError::DecryptionFailed));
+
+    resp.rekey_incoming();
+    assert_eq!(resp.read_message(&m).unwrap(), b"after rekey");
+
+    // The other direction is unaffected.
+    let m = resp.write_message(b"reply").unwrap();
+    assert_eq!(init.read_message(&m).unwrap(), b"reply");
+}
+
+#[test]
+fn noise_one_way_transport() {
+    let params = NoiseParams::parse("Noise_N_25519_ChaChaPoly_SHA256").unwrap();
+    let mut init = handshake(&params, true, false);
+    let mut resp = handshake(&params, false, false);
+
+    let m = init.write_message(b"").unwrap();
+    resp.read_message(&m).unwrap();
+
+    let mut init = init.into_transport().unwrap();
+    let mut resp = resp.into_transport().unwrap();
+
+    assert_eq!(resp.write_message(b"no"), Err(NoiseError::InvalidState));
+    assert_eq!(init.read_message(&[0; 16]), Err(NoiseError::InvalidState));
+
+    let m = init.write_message(b"yes").unwrap();
+    assert_eq!(resp.read_message(&m).unwrap(), b"yes");
+}
+
+const VECTORS: &[Vector] = &[
+    Vector {
+        name: "Noise_NK_25519_ChaChaPoly_BLAKE2b",
+        handshake_hash: "f87aa4eb6416e5b0d2b6e6f0b7bc41f3c5986a5d32d55c08d67cbd412f3ec2fa04d8e358ab95b3bbfab054a140a98eccf4284bb6309b600981d451ecac484932",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c7944f3041e39b0c8ba56008f2d1183fea6ac83564ead0267b0842ec4c521ed1e1407",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f1448088432281dcc1835131f305dca14525e15e27d1f32294aa835e40fc18be480c1db9",
+            "357e24e9f28ba22080666f7efacc01b2a0a4e358e742aeeff2aaf5",
+            "8b23b34ff3169de06a39551e969ca7876cc5122a4acff74bf2ec29",
+            "5c104779b6f36e59fca73ed94b0ae092eae1d76dd109caf5060aaaedba385d7076",
+            "34ae0518d0cd3aa641ed372ea94935ceecd87f8c4b422ce21a33d3f6f5493891e3e915d83f",
+        ],
+    },
+    Vector {
+        name: "Noise_NK_25519_ChaChaPoly_SHA256",
+        handshake_hash: "2efa38a9c7c93ac98f3a097af25c2f58b9e7673787717bc27e98827118c2c1a5",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c79448134d00711fdb390a0d178fa008f6d47d2891e5ea18ae136c3b4c23ac384efb0",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f1448088438ea16e3701bc0d77744f117bee22451c9afa7f4cdbbcff00c04a8ee0913c88",
+            "a62de29ce27cb80245d440d986ed816c156e9d757d7008df2198b0",
+            "174a35f11c689f4530d7208618e0564ae12f2f50ba8eb4df5382ff",
+            "337e475ebb8eae60f91974c4e455a5af38d1d8628d1803b160d60442874b0a1777",
+            "047e80e060b7bb08b53c5a23dfe9920cae135b9d1dc6302fc475003062723700366346ac9d",
+        ],
+    },
+    Vector {
+        name: "Noise_IK_25519_ChaChaPoly_BLAKE2b",
+        handshake_hash: "1c8fa891cb414fedba6daa7c6f4ae0a6d98e5f9768cc9cecd27e805614943ee9c8a1b27fbfb76dc197255c8aa69f6b4285c423840b8bedf45e652ca64f797d81",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c7944ba83a447b38c83e327ad936929812f624884847b7831e95e197b2f797088efdd2f88f1db7e1fb0e99c64419097af91cee64e470f4b6fcd9298ce0b56fe20f86e13bf70439c538e3602a7127af71a29cc",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f1448088439f069b267a06b3de3ecb1043bcb098e9af91d9c64748d998c7b47890871571",
+            "cd54383060e7a28434cca27fb1cc524cfbabeb18181589df219d07",
+            "a856d3bf0246bfc476c655009cd1ed677b8dcc5b349ae8ef2a05f2",
+            "49063084b2c51f098337cb8a13739ac848f907e67cfb2cc8a8b60586467aa02fc7",
+            "8b9709d23b47e4639df7678d7a21741eba4ef1e9c60383001c7435549c20f9d56f30e935d3",
+        ],
+    },
+    Vector {
+        name: "Noise_IK_25519_ChaChaPoly_SHA256",
+        handshake_hash: "0b0f68fb0c27e03ce9b97565995ed4838cc0581b762ef72b062f6a546419fad7",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c7944718da798efbcd91528520204f904b9bd6c7413dccdc214d951e15253e39987f18146e8cd0873654207148333479d4d16c289f0294b29960a72f48e0b7bba2e89083169825e59642148d492020664ccf7",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f1448088435361e70b2ed446e6c9ec387d1d6b3b840f194e373979d241b203c4acafccf5",
+            "050e9f3c8fac16b68dbce8f8c4bfbf6617c897f9ada4aa29aa19c8",
+            "344233a6cabb7141d80f3da2fedc311d9646bbb0f505afe403a667",
+            "62cdeeb172ad7ade7aa7d9e069da5790f12331bfa00177787a1d0810c67dc3b2b4",
+            "029bead1b40992327044d409d9a1f3ad8f36c3c452775d557e18bbeb2e8dfcead32d514024",
+        ],
+    },
+    Vector {
+        name: "Noise_XX_25519_ChaChaPoly_BLAKE2b",
+        handshake_hash: "8cf47d7b3cb5804c0109d48e8bcdbee2cbb65687d8ea2c92994ca361fb86151ad93627b98936cbb32de56e8abb21def3925011ac3e35db9cbeea73ab9a4392c2",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c79444c756477696720766f6e204d69736573",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f1448088430505b6745ce64a5f33f0e8e3b83f11ce8802bca507f4f2d8b564dbe277e1966116e132faa2dfd70b8b077b9f94b913df5056ae1319469b824a98d54bbaa82c325595587064f978c4b6d104f7596e6f",
+            "99579e1c1ee15e422a57ddd6b16d37087b17558e8369c18991b4b2ca3a824abf904cdcf5458b5431a75af034ca9e9b982de039eaaf156775e2d580cd4e5ebae89c3f8cb2594b556d8a8169",
+            "fc56eea290b3f3a21aac0c70cd5787b5ee99be37d2f4d751329b55",
+            "bb31c9da10d5639a4cdb88a12f5c61de41bbc7df09bf75d94f8184fe4157f5c68f",
+            "f6199cadb152fb27f82be0a0891ec76a33598ae92a46cab2fb5a8ed5bf48b7f267f8370af7",
+        ],
+    },
+    Vector {
+        name: "Noise_XX_25519_ChaChaPoly_SHA256",
+        handshake_hash: "c8e5f64e846193be2a834104c2a009868d6c9f3bd3c186299888b488b2f1f58e",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c79444c756477696720766f6e204d69736573",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f14480884381cbad1f276e038c48378ffce2b65285e08d6b68aaa3629a5a8639392490e5b9bd5269c2f1e4f488ed8831161f19b7815528f8982ffe09be9b5c412f8a0db50f8814c7194e83f23dbd8d162c9326ad",
+            "c7195ffacac1307ff99046f219750fc47693e23c3cb08b89c2af808b444850a80ae475b9df0f169ae80a89be0865b57f58c9fea0d4ec82a286427402f113e4b6ae769a1d95941d49b25030",
+            "96763ed773f8e47bb3712f0e29b3060ffc956ffc146cee53d5e1df",
+            "3e40f15f6f3a46ae446b253bf8b1d9ffb6ed9b174d272328ff91a7e2e5c79c07f5",
+            "eb3f3515110702e047a6c9da4478b6ead94873c11c0f2d710ddb3f09fce024b3a58502ae3f",
+        ],
+    },
+    Vector {
+        name: "Noise_N_25519_ChaChaPoly_BLAKE2b",
+        handshake_hash: "3d5785437634d9059ed8db74288f0fa2729a7366d75f74ef498c66e85a1d2d9735a10664d61896d1885367b5a4dedb4c9c7228d647d887b6a671f32760db8c4d",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c794493e145393e742220f7d46ee2d19db28f99f56c13e4b434adf2403530d61f2d4d",
+            "6e2d9ea31cf9daa4a98c0bb691c51c94ada9e61422ddf6c852d6292c3b78ed",
+            "e9bd8264cea01a9967e56bbb7c305bbedb6440427ee113716aa010",
+            "70f12312f1ba4e3b8fba71fd6db55698d7014fb15be35d76a45922",
+            "2553c4c4196e0a1a86115e74073a6a0b4e70e1df7a84de6056b0f5992760e38026",
+            "08cfdd4c4eb84b32ccca6281a56f43ae4ba8e420eda369d9ad14cfad966e4cf6d4f5f0fd20",
+        ],
+    },
+    Vector {
+        name: "Noise_N_25519_ChaChaPoly_SHA256",
+        handshake_hash: "6497ab83a10e5d03b42e6f770738f62f91584b0b589380fddff642b141af56b6",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c794430db5925e72ccdb0333fb13bd1f920cc34627b8fe30f81383a15d67a9ba306ca",
+            "b9546f9f6bc43ff1ab776874425ddd59a45f6294633df65c8e55ee14cbc175",
+            "4732bd7c598a84a15a477ce67562f54bc4fac4ef04ea178c5796c9",
+            "2fbd9d4fd39df3bbfc22b63525ba454cdd65d1cf9b3ae658612f5f",
+            "81619224c9c0d7ec75eb670b7d3154b8f97bfbd07cf0fe3df2f538b7d19dc5f21e",
+            "8c21c98a5236dad958a67c39829d1bfcfcb0d529af864b17902185f56f3cb7bd86998ddc29",
+        ],
+    },
+    Vector {
+        name: "Noise_NKpsk0_25519_ChaChaPoly_BLAKE2b",
+        handshake_hash: "a32c665fae82ea979751627b098c45e755574719a147af4bd6bb92a790dc6dc64b33f09424aa7d21ae70c46207b5d103c7150cdc6df02dc2aa470c76647da0e0",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c794432c362f61a0162a93280a9e1c53edb8ce7487fd12a4d1731495a24051a5afca8",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f1448088430e8d0924ed6525d4045188c3b225604c254ac2d8d33a8a3b0c7f5eb1975ba4",
+            "4781e2a7bef65e0ae3da3508cf44a8b7a92880077b8e73afaef494",
+            "f14b1feb6ff970fc3993f1c2383f33fa48138bfc5aa2ee89e40107",
+            "4ab72fba875b34dbc38da46b09fe1ad4d72faeb3514180d39a57b6cf22d646fcfa",
+            "1ca4c48531c304d8d1472124d5e082e29332d9527a743ac875b3bea0fb372f1d08168561b1",
+        ],
+    },
+    Vector {
+        name: "Noise_NKpsk0_25519_ChaChaPoly_SHA256",
+        handshake_hash: "1609ef057bdd62c752b5960546a255a78aebff08c5f07ef2adaa1db8350e7077",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c7944190fec41487219f2069c3ba7b7f9521437045935231f0ed399dfd4baf6bd825b",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f14480884385010e0c56e886e6da0c69aee7388bcf4000cc357af5ebd11a46a169a3712c",
+            "4beed26535f1a387c950fab9a162dc613cc5bf84e8a62653130b83",
+            "ceaffe71ce7f1bf7b080736d62e0579ce5dc1530a36e7df795a4cc",
+            "da927a272831394122d0f2fef3e16ddf0814c4878401135b44b1e23873b45b2929",
+            "4fed2b394fb4dbdc9cf863bc99ebb3397651d27bdd32e40d8f7fed109e46445c0fd66fc3f3",
+        ],
+    },
+    Vector {
+        name: "Noise_NKpsk2_25519_ChaChaPoly_BLAKE2b",
+        handshake_hash: "710d588598033b8ac37ef52452a93f256dcfb627cbafb2bab13e3a7dcefe85e5d3cdfa52b34985c8a1e7ec9262839a1a2c6a7b772188f5c8713c0b266e2c43a5",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c7944d6032cbd631c828c049c37ae18b070d3e01e8bc992a074dba70a91541e0d270c",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f144808843bb124fae15b4702fb4bd87e6cc1c8aa5ac59b72630fcea34546604dbe4f10f",
+            "ab51473cd41a30eac21de3467f4f8fccc06300dce2c11e9de1ef79",
+            "883b2e7f22185acd331df8c6675dc44c66754566c91654a416f522",
+            "9557dedffd8857d999e1dc1a407257f2080a8e25c3bd8eb08469340766973f8675",
+            "aaf2e77996ec737c9e85ce0e89fdc159ddd12d942b076fca9f894c0be7aa8be8f4769dd49a",
+        ],
+    },
+    Vector {
+        name: "Noise_NKpsk2_25519_ChaChaPoly_SHA256",
+        handshake_hash: "5a0c1a79a0b863fe5d000e829b7e4ffc76200e5c08082d4494968e3f47d0ff61",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c7944acda3057fb216b0fb4c6d571e776b426612636e99cf4ac1de41442fb2128ca29",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f14480884330fcb4ab2b68a6f612414145258aa079533be57174ec7ae7c76845312a3f07",
+            "129789ee959ffef891a580c6fc073cf91d706e26602cc096c35d84",
+            "36b35298bc84e0a24644e309563b2d6c3f9a31dc142b122e0266db",
+            "4b080dfd53e42e3f45d96f75f15fdbcce95a75fb83c51ee366281528204c1bf0b4",
+            "0bce31a0c1c37626c3e4a0000afa7e4e06636e1bbc44fc1a24e18e373f07c8ad6e3a03b877",
+        ],
+    },
+    Vector {
+        name: "Noise_XXpsk3_25519_ChaChaPoly_BLAKE2b",
+        handshake_hash: "46b5997dfa51492ae9be5b611748455e83aebdbc2ea3c47d264ec6dd408cc076e962ff056890c60398fe3e7a430bbe057f34383a84df9ff99391266dcfd638b0",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c79440aa831d1e3bbd16bd7ffda87e9f7b6eeca2510006499a573ac27af3392ed1b69",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f14480884381a78489d56c9e2ac962826f25684980b25244dbfd505b7d903a94d4f4fd84004a1c7fbe94d686bc4c1f67259364f6f2a41cb40bf5198b9b3975be2cdd49d3e065a554e12c477d82029fabc6555bc1",
+            "291dfeff6eff386e29adb0bdbd5a1ae9e09de4449c8695d109adcb63e80e489555792a3e9150eff75d6d45571f824ed231173cb66e31e7da914241f86d0cbb678335ffe768a7445d2f5bc1",
+            "6579dd60eb5e6a8b29dd60004289f5d3c2f69fcdbf683a869f59a8",
+            "24715fd39c3c7606e81b26e69e47b54d043d9d87c512f9417bae51ab48ebb17722",
+            "e49194a013d873712318643acbf0e3851086326bb168c500a766439ff543a7a1e2deb3215c",
+        ],
+    },
+    Vector {
+        name: "Noise_XXpsk3_25519_ChaChaPoly_SHA256",
+        handshake_hash: "a477edf6a131bbdb54707f6ea30eab6cd935d9b560f0e5fd1f053a95a99669fb",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c7944c9f5ff0e8079630cb7e270c20bbf480821b77a384a645c71a2fd9b3db1c16a5f",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f144808843b123def17f71e6ae8e57e0e1dec5949c5f7415c6f33517398747d821a06dc23ad430aa1fd7381d46195c378a819fd574425462cbb2d4ca339e738a0b7001dc91423fbf55a99af0c6f1df21012ceb2f",
+            "52187316111b118d4c060364f7b975dc0809b2590779aff2d63113c564f11744493384db7bf32d5ae6686df6ab06d508d2e07caaf1d6afc010b978735fc78900e71ae1d314130d042e729a",
+            "eaedc672d4c21e0e2955758756fb98f194c4e90d5deb5b6cf30b27",
+            "522d543c5fe799d09a3d9da7ff54d0dc03c8af1dc7751d2ff708339d2290943e98",
+            "7d4e2c3873eef6a213b04e72f9df60a91666072d3544c5d96c34a09e2329b5030bee796741",
+        ],
+    },
+    Vector {
+        name: "Noise_IKpsk1_25519_ChaChaPoly_BLAKE2b",
+        handshake_hash: "186371bec588c76e8deb325921814d59591519641d70e37cde7ab4bc8b17600e3d9062228a6132e257baf34bc156927e5d85a7d3ea93f7524caf13710b226b3b",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c7944902b84cee06d6795dc5f2501c05ba76688c6ce7d2ab36d91ce6d3211721f3280f6264f7c2ea2e6b13d78afb983ae1ffb05f1f4e9fa55356622301ebaff6e9f21c1f43208cbd63fb9b4082159b8fc5bb3",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f1448088430b230ee9def0e1e7c053a14848bbfb1458026c9aa2bbbd4b237cb92491392b",
+            "3ef532df887f77d7b044cb218ff0674cba4810998d84112690527a",
+            "cb6c35ca87c7deef55e8e9bdbd7ccdfb8de119accfadb834cbb56b",
+            "ed8a704db23c02f24b78473428e0ab7ba1bb5996f9a68fa45a87509537f498e41c",
+            "13e78d501bcb6d55e9c0821febbe88d021663a91fbce42c12bf219cf5d7efef5f6a2ee9ca6",
+        ],
+    },
+    Vector {
+        name: "Noise_IKpsk1_25519_ChaChaPoly_SHA256",
+        handshake_hash: "3ad252ed6f724c52da3450383b7d8b806c183e1ef157bbe0465ad24997ec4717",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c7944ac58a6c31948ce200911c5b27b67f1c4d1bed490532dd94ed17164fcc5784d3730fc302b70cc0f19beedaeb56bd974c0e57d747d11534c746eb2a32ac3fde3e4cdf6c3a4705762a6c6ca664b3bc89490",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f144808843846a944a0652fb390d213d0700d5ae8fef7aad0ecc79a9216d15de5d7f3ee4",
+            "5d872673f64813a47a00369b15c8da92691605ad71ba019de8e718",
+            "2045266a750b6af2547f7eb1391058196b742d0aac4b3a1bcc1913",
+            "9ec47ed0e7628c7d7a4eed631b963740ac2fd754eadaa9232e99054af4f7b29174",
+            "310e359407350594cfb96eb4596e35677d4a71ceb42aa8cbba097bb9e7150b0d1bd749c4aa",
+        ],
+    },
+    Vector {
+        name: "Noise_IKpsk2_25519_ChaChaPoly_BLAKE2b",
+        handshake_hash: "ed837c9084f2f0333a7ec60b9bcacd9921394858de8ced118b5966d1147ba390084dab42326c565a309c29317a6079be30aa1d790d25a517b43287426cb1d36a",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c7944b6fe0240b839afac809de1630eaf99d8f9b941394f0512c0554633bbb021d15176135c0a20e7fdb9a9ed066f22f054b47625b1d5061cb27d6a17f053273db7c25216a5addc506ee321bc0618feb5849d",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f1448088438f627e69fc120006f746b9d420bf4056cf8481ab32d9e904ffaeeaa56a5290",
+            "07fc0034398a5f1010322be193d62f94bebf2948a20b3e15d681fe",
+            "2ea5400f5b8a16971c8b67bde75aeb7987d454b1c0beeb2566932d",
+            "96eef918ef51639fc0e156da1823c4f71b33fac5af983f5da9384f7da20d86ed91",
+            "f02766a56496e1fbf32e05274baebf6d693c8f3e4c6e0dc7ebdc5ac170f15dae433b92f469",
+        ],
+    },
+    Vector {
+        name: "Noise_IKpsk2_25519_ChaChaPoly_SHA256",
+        handshake_hash: "8310f86394dc0dabb40beb8210031556db4403ab1202db7034c526232147a700",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c79442ec9b09893d0f510791784c10cbc959f25b1766e0def6e301d14fbca1c7790ac829b8b3674f5f649a5f0e98479662cbfbf2b2c47cd4b09fcd266cd29d7cb675f1808849707847840f6d178ec4d3733aa",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f1448088439a1b3cebf680b2c74217fcb5eba4ff58a9468cd90c4aca6194f57479b379a7",
+            "a8fde7a0accec190cd306c5950d4fd8e04a205ec288aa747d8b347",
+            "59caddd9984a3bbe24c4fb31a2bd455b7eba3fa0980674b1a3a5f9",
+            "3b9bfebd210c22ba0cff9de79b4007d7a552fffbf92616881faa8a883e25b80258",
+            "f37512df1043d564d7c46ac85c53d3b6a9a05724bc297e7142808f217561651217fe85b782",
+        ],
+    },
+    Vector {
+        name: "Noise_Npsk0_25519_ChaChaPoly_BLAKE2b",
+        handshake_hash: "3c861551e56f993c33510aac7473d90f6d65edca9291f352aa8f0238005aa2366d8a090bf34f40b3778f8e8c63a08aec68536d01e6e2befc75c28deada3b87d9",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c7944fb9a3a09da1ffa70cafee2e7e4067553eb04ea777e357fffe555a7641052b73b",
+            "45e99a12248b003401526a620cf452cb4173c9825da0375e682d50f5974650",
+            "6bed8c3130569ee81b807a42375e58c0b6d31980466688c75e1075",
+            "dd4b0396371cf8b3a468cb06559d77c642f1de61539a66108e519a",
+            "c05e307be5f2e2d03ca94c7007b11aaa7a037d063d97842e47d4245194f6645125",
+            "d7eeb816e7462ca6a97900eefb4ecadc23a0f93f4aba1ec6f85771824cd01b073efb0f2edd",
+        ],
+    },
+    Vector {
+        name: "Noise_Npsk0_25519_ChaChaPoly_SHA256",
+        handshake_hash: "ee775949deda7ae61c3bd3b400b71eb303cf74c532321d5931c565c58de24f09",
+        ciphertexts: [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c7944ecdeee2b0f760b7dabd274df50ce1eec70bf1c286eb266cd7b2851ee15836c25",
+            "6bfddfc16fbc4d500c71ef3370c9a7eb91ae85266e6f7610483aac6b1d5cc6",
+            "c38ca516544a96ac13da6526648a39434fb81f4ae3494c963a5a76",
+            "d13bb93b4f84de0f598f083d2ffe0438becbbf71a45507e1e1d7f1",
+            "9f52a1fe9e403fb1658deaa400ea1901f9025b940f59b498706e91e277fb0b3401",
+            "2f6ff9f3d7b7664fe41736fae81eb191ed66d7f8fe7cde3bf1e5d189581218a8d56ff47f67",
+        ],
+    },
+];
@@ -0,0 +1,123 @@
+use cryptal::encryption::chacha20poly1305::{Chacha20Poly1305Error, decrypt_with_aad};
+use cryptal::encryption::nonce::{
+    CounterNonce, NONCE_LEN, NonceError, NonceSequence, OVERHEAD, OpeningKey, RandomNonce,
+    SealingKey,
+};
+
+const KEY: [u8; 32] = [0x42; 32];
+
+#[test]
+fn counter_nonce_layout() {
+    let mut seq = CounterNonce::new([1, 2, 3, 4]);
+
+    assert_eq!(seq.advance().unwrap(), [1, 2, 3, 4, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(seq.advance().unwrap(), [1, 2, 3, 4, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(seq.position(), Some(2));
+
+    let mut seq = CounterNonce::resume([1, 2, 3, 4], 0x0102_0304_0506_0708);
+    assert_eq!(seq.advance().unwrap(), [1, 2, 3, 4, 1, 2, 3, 4, 5, 6, 7, 8]);
+}
+
+#[test]
+fn counter_nonce_exhaustion() {
+    let mut seq = CounterNonce::resume([0; 4], u64::MAX - 1);
+
+    assert!(seq.advance().is_ok());
+    assert_eq!(seq.advance().unwrap()[4..], [0xff; 8]);
+    assert_eq!(seq.position(), None);
+
+    // Exhaustion is permanent.
+    assert_eq!(seq.advance(), Err(NonceError::Exhausted));
+    assert_eq!(seq.advance(), Err(NonceError::Exhausted));
+}
+
+#[test]
+fn random_nonce_limit() {
+    let mut seq = RandomNonce::with_limit(3);
+
+    let a = seq.advance().unwrap();
+    let b = seq.advance().unwrap();
+    let c = seq.advance().unwrap();
+
+    assert!(a != b && b != c && a != c);
+    assert_eq!(seq.remaining(), 0);
+    assert_eq!(seq.advance(), Err(NonceError::Exhausted));
+
+    assert_eq!(RandomNonce::with_limit(u64::MAX).remaining(), 1 << 32);
+}
+
+#[test]
+fn sealing_key_roundtrip() {
+    let mut sealing = SealingKey::new(KEY, CounterNonce::with_random_prefix());
+    let opening = OpeningKey::new(KEY);
+
+    let first = sealing.seal(b"header", b"hello").unwrap();
+    let second = sealing.seal(b"header", b"hello").unwrap();
+
+    assert_eq!(first.len(), 5 + OVERHEAD);
+    assert_ne!(first[..NONCE_LEN], second[..NONCE_LEN]);
+    assert_ne!(first, second);
+
+    // Messages carry their nonce and can be opened in any order.
+    assert_eq!(opening.open(b"header", &second).unwrap(), b"hello");
+    assert_eq!(opening.open(b"header", &first).unwrap(), b"hello");
+    assert_eq!(sealing.nonces().position(), Some(2));
+}
+
+#[test]
+fn sealing_key_matches_raw_aead() {
+    let mut sealing = SealingKey::new(KEY, CounterNonce::new([9; 4]));
+    let sealed = sealing.seal(b"aad", b"plaintext").unwrap();
+
+    let (nonce, rest) = sealed.split_at(NONCE_LEN);
+    let (body, tag) = rest.split_at(rest.len() - 16);
+    assert_eq!(nonce, [9, 9, 9, 9, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+    let mut plaintext = vec![0u8; body.len()];
+    decrypt_with_aad(
+        &KEY,
+        nonce.try_into().unwrap(),
+        b"aad",
+        body,
+        tag.try_into().unwrap(),
+        &mut plaintext,
+    )
+    .unwrap();
+
+    assert_eq!(plaintext, b"plaintext");
+}
+
+#[test]
+fn sealing_key_exhaustion() {
+    let mut sealing = SealingKey::new(KEY, RandomNonce::with_limit(1));
+
+    assert!(sealing.seal(&[], b"one").is_ok());
+    assert_eq!(sealing.seal(&[], b"two"), Err(NonceError::Exhausted));
+}
+
+#[test]
+fn opening_key_rejects_invalid_input() {
+    let mut sealing = SealingKey::new(KEY, RandomNonce::new());
+    let opening = OpeningKey::new(KEY);
+
+    let mut sealed = sealing.seal(b"aad", b"hello").unwrap();
+
+    assert_eq!(
+        opening.open(b"other", &sealed),
+        Err(Chacha20Poly1305Error::AuthenticationFailed)
+    );
+    assert_eq!(
+        opening.open(b"aad", &sealed[..OVERHEAD - 1]),
+        Err(Chacha20Poly1305Error::InvalidLength)
+    );
+
+    sealed[0] ^= 1;
+    assert_eq!(
+        opening.open(b"aad", &sealed),
+        Err(Chacha20Poly1305Error::AuthenticationFailed)
+    );
+    assert_eq!(
+        OpeningKey::new([0; 32]).open(b"aad", &sealed),
+        Err(Chacha20Poly1305Error::AuthenticationFailed)
+    );
+}
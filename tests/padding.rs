@@ -0,0 +1,128 @@
+use cryptal::encryption::nonce::{CounterNonce, OVERHEAD, OpeningKey, SealingKey};
+use cryptal::encryption::padding::{Padding, PaddingError, pad, padded_len, unpad};
+
+/// Reference Padmé from the PURBs paper, on the padded input length.
+fn padme_reference(len: u64) -> u64 {
+    if len < 2 {
+        return len;
+    }
+
+    let e = 63 - len.leading_zeros() as u64;
+    let s = 64 - e.leading_zeros() as u64;
+    let last_bits = e - s;
+    let mask = (1u64 << last_bits) - 1;
+
+    (len + mask) & !mask
+}
+
+#[test]
+fn padme_lengths() {
+    // Message lengths; the marker byte adds one.
+    assert_eq!(padded_len(Padding::Padme, 0), Ok(1));
+    assert_eq!(padded_len(Padding::Padme, 7), Ok(8));
+    assert_eq!(padded_len(Padding::Padme, 8), Ok(10));
+    assert_eq!(padded_len(Padding::Padme, 999), Ok(1024));
+    assert_eq!(padded_len(Padding::Padme, 1000), Ok(1024));
+    assert_eq!(padded_len(Padding::Padme, 1024), Ok(1088));
+
+    for len in 0..5000u64 {
+        let got = padded_len(Padding::Padme, len as usize).unwrap() as u64;
+
+        assert_eq!(got, padme_reference(len + 1), "len = {len}");
+        // At most ~12% overhead.
+        assert!(got * 100 <= (len + 1) * 112 + 100, "len = {len}");
+    }
+}
+
+#[test]
+fn bucket_lengths() {
+    assert_eq!(padded_len(Padding::Bucket(256), 0), Ok(256));
+    assert_eq!(padded_len(Padding::Bucket(256), 255), Ok(256));
+    assert_eq!(padded_len(Padding::Bucket(256), 256), Ok(512));
+    assert_eq!(padded_len(Padding::Bucket(1), 10), Ok(11));
+    assert_eq!(
+        padded_len(Padding::Bucket(0), 10),
+        Err(PaddingError::InvalidBucket)
+    );
+    assert_eq!(padded_len(Padding::None, 10), Ok(10));
+}
+
+#[test]
+fn pad_unpad_roundtrip() {
+    for padding in [Padding::Padme, Padding::Bucket(16), Padding::Bucket(1)] {
+        for len in 0..300 {
+            let message: Vec<u8> = (0..len).map(|i| (i % 256) as u8).collect();
+            let padded = pad(padding, &message).unwrap();
+
+            assert_eq!(padded.len(), padded_len(padding, len).unwrap());
+            assert_eq!(padded[len], 0x80);
+            assert_eq!(unpad(&padded), Ok(len));
+        }
+    }
+
+    assert_eq!(pad(Padding::None, b"abc").unwrap(), b"abc");
+}
+
+#[test]
+fn iso7816_layout() {
+    assert_eq!(
+        pad(Padding::Bucket(8), b"abc").unwrap(),
+        [b'a', b'b', b'c', 0x80, 0, 0, 0, 0]
+    );
+
+    // Messages ending in marker-like bytes stay unambiguous.
+    let padded = pad(Padding::Bucket(8), &[0x80, 0x00]).unwrap();
+    assert_eq!(unpad(&padded), Ok(2));
+}
+
+#[test]
+fn unpad_rejects_invalid_padding() {
+    assert_eq!(unpad(&[]), Err(PaddingError::InvalidPadding));
+    assert_eq!(unpad(&[0, 0, 0]), Err(PaddingError::InvalidPadding));
+    assert_eq!(unpad(&[1, 2, 3]), Err(PaddingError::InvalidPadding));
+    assert_eq!(unpad(&[0x80, 0, 1]), Err(PaddingError::InvalidPadding));
+    assert_eq!(unpad(&[0x80, 0x81]), Err(PaddingError::InvalidPadding));
+    assert_eq!(unpad(&[0x80]), Ok(0));
+    assert_eq!(unpad(&[0x80, 0x80, 0]), Ok(1));
+}
+
+#[test]
+fn sealing_key_padding() {
+    let key = [7u8; 32];
+    let mut sealing = SealingKey::new(key, CounterNonce::new([0; 4]))
+        .with_padding(Padding::Bucket(64))
+        .unwrap();
+    let opening = OpeningKey::new(key).with_padding();
+
+    let short = sealing.seal(b"aad", b"hi").unwrap();
+    let long = sealing.seal(b"aad", &[1u8; 50]).unwrap();
+
+    // Both messages fall into the same bucket.
+    assert_eq!(short.len(), 64 + OVERHEAD);
+    assert_eq!(long.len(), short.len());
+
+    assert_eq!(opening.open(b"aad", &short).unwrap(), b"hi");
+    assert_eq!(opening.open(b"aad", &long).unwrap(), [1u8; 50]);
+
+    // Without the option, the padding is returned as part of the message.
+    let raw = OpeningKey::new(key).open(b"aad", &short).unwrap();
+    assert_eq!(raw.len(), 64);
+    assert_eq!(raw[..3], [b'h', b'i', 0x80]);
+
+    assert!(
+        SealingKey::new(key, CounterNonce::new([0; 4]))
+            .with_padding(Padding::Bucket(0))
+            .is_err()
+    );
+}
+
+#[test]
+fn opening_key_rejects_unpadded_message() {
+    let key = [7u8; 32];
+    let mut sealing = SealingKey::new(key, CounterNonce::new([0; 4]));
+    let opening = OpeningKey::new(key).with_padding();
+
+    // A message whose last byte is not a valid padding byte.
+    let sealed = sealing.seal(&[], b"no padding").unwrap();
+    assert!(opening.open(&[], &sealed).is_err());
+}
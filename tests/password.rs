@@ -0,0 +1,232 @@
+use cryptal::derivation::Argon2Params;
+use cryptal::encryption::password::{
+    DEFAULT_LIMITS, HEADER_LEN, MAGIC, OVERHEAD, PasswordError, PasswordParams, needs_upgrade,
+    open_with_password, open_with_password_limited, params_of, reseal_with_password,
+    seal_with_password,
+};
+use cryptal::hash::blake2b;
+
+fn params(mem_kib: u32, time: u32) -> Argon2Params {
+    Argon2Params {
+        mem_kib,
+        time,
+        lanes: 1,
+        tag_len: 32,
+        secret: None,
+        associated_data: None,
+    }
+}
+
+/// Rewrites the header checksum after a deliberate header modification.
+fn fix_checksum(sealed: &mut [u8]) {
+    let checksum = blake2b(16, &sealed[..61]);
+    sealed[61..HEADER_LEN].copy_from_slice(&checksum[..16]);
+}
+
+#[test]
+fn password_roundtrip() {
+    let sealed = seal_with_password(b"correct horse", b"secret blob", &params(32, 1)).unwrap();
+
+    assert_eq!(sealed.len(), b"secret blob".len() + OVERHEAD);
+    assert_eq!(sealed[..4], MAGIC);
+
+    let opened = open_with_password(b"correct horse", &sealed).unwrap();
+    assert_eq!(opened, b"secret blob");
+}
+
+#[test]
+fn password_empty_plaintext() {
+    let sealed = seal_with_password(b"pw", b"", &params(32, 1)).unwrap();
+    assert_eq!(sealed.len(), OVERHEAD);
+
+    assert_eq!(open_with_password(b"pw", &sealed).unwrap(), b"");
+}
+
+#[test]
+fn password_is_randomized() {
+    let a = seal_with_password(b"pw", b"same", &params(32, 1)).unwrap();
+    let b = seal_with_password(b"pw", b"same", &params(32, 1)).unwrap();
+
+    assert_ne!(a, b);
+}
+
+#[test]
+fn password_wrong_passphrase() {
+    let sealed = seal_with_password(b"correct horse", b"secret blob", &params(32, 1)).unwrap();
+
+    assert_eq!(
+        open_with_password(b"battery staple", &sealed),
+        Err(PasswordError::WrongPassphrase)
+    );
+}
+
+#[test]
+fn password_corrupt_ciphertext() {
+    let mut sealed = seal_with_password(b"pw", b"secret blob", &params(32, 1)).unwrap();
+
+    sealed[HEADER_LEN + 3] ^= 0x01;
+    assert_eq!(
+        open_with_password(b"pw", &sealed),
+        Err(PasswordError::Corrupted)
+    );
+
+    let mut sealed = seal_with_password(b"pw", b"secret blob", &params(32, 1)).unwrap();
+
+    let last = sealed.len() - 1;
+    sealed[last] ^= 0x80;
+    assert_eq!(
+        open_with_password(b"pw", &sealed),
+        Err(PasswordError::Corrupted)
+    );
+}
+
+#[test]
+fn password_corrupt_header() {
+    let sealed = seal_with_password(b"pw", b"secret blob", &params(32, 1)).unwrap();
+
+    // Cost parameters, salt, nonce and check value are all covered.
+    for offset in [5, 9, 20, 40, 50, 70] {
+        let mut damaged = sealed.clone();
+        damaged[offset] ^= 0x04;
+
+        assert_eq!(
+            open_with_password(b"pw", &damaged),
+            Err(PasswordError::Corrupted)
+        );
+    }
+}
+
+#[test]
+fn password_invalid_format() {
+    let sealed = seal_with_password(b"pw", b"secret blob", &params(32, 1)).unwrap();
+
+    assert_eq!(
+        open_with_password(b"pw", &sealed[..OVERHEAD - 1]),
+        Err(PasswordError::InvalidFormat)
+    );
+
+    let mut bad_magic = sealed.clone();
+    bad_magic[0] = b'X';
+    assert_eq!(
+        open_with_password(b"pw", &bad_magic),
+        Err(PasswordError::InvalidFormat)
+    );
+
+    let mut bad_version = sealed.clone();
+    bad_version[4] = 2;
+    fix_checksum(&mut bad_version);
+    assert_eq!(
+        open_with_password(b"pw", &bad_version),
+        Err(PasswordError::UnsupportedVersion)
+    );
+}
+
+#[test]
+fn password_rejects_expensive_header() {
+    let mut sealed = seal_with_password(b"pw", b"secret blob", &params(32, 1)).unwrap();
+
+    sealed[5..9].copy_from_slice(&u32::MAX.to_le_bytes());
+    fix_checksum(&mut sealed);
+
+    assert_eq!(
+        open_with_password(b"pw", &sealed),
+        Err(PasswordError::InvalidParams)
+    );
+}
+
+#[test]
+fn password_open_with_caller_limits() {
+    let sealed = seal_with_password(b"pw", b"secret blob", &params(64, 2)).unwrap();
+
+    let strict = PasswordParams {
+        mem_kib: 32,
+        ..DEFAULT_LIMITS
+    };
+    assert_eq!(
+        open_with_password_limited(b"pw", &sealed, &strict),
+        Err(PasswordError::InvalidParams)
+    );
+
+    let strict = PasswordParams {
+        time: 1,
+        ..DEFAULT_LIMITS
+    };
+    assert_eq!(
+        open_with_password_limited(b"pw", &sealed, &strict),
+        Err(PasswordError::InvalidParams)
+    );
+
+    let exact = PasswordParams {
+        mem_kib: 64,
+        time: 2,
+        lanes: 1,
+    };
+    assert_eq!(
+        open_with_password_limited(b"pw", &sealed, &exact).unwrap(),
+        b"secret blob"
+    );
+}
+
+#[test]
+fn password_seal_rejects_costs_above_default_limits() {
+    assert_eq!(
+        seal_with_password(b"pw", b"data", &params(32, DEFAULT_LIMITS.time + 1)),
+        Err(PasswordError::InvalidParams)
+    );
+}
+
+#[test]
+fn password_rejects_invalid_params() {
+    let mut bad = params(32, 1);
+    bad.lanes = 0;
+
+    assert_eq!(
+        seal_with_password(b"pw", b"data", &bad),
+        Err(PasswordError::InvalidParams)
+    );
+}
+
+#[test]
+fn password_upgrade_on_reseal() {
+    let sealed = seal_with_password(b"pw", b"secret blob", &params(32, 1)).unwrap();
+
+    assert_eq!(
+        params_of(&sealed).unwrap(),
+        PasswordParams {
+            mem_kib: 32,
+            time: 1,
+            lanes: 1
+        }
+    );
+    assert!(needs_upgrade(&sealed, &params(64, 1)).unwrap());
+    assert!(!needs_upgrade(&sealed, &params(32, 1)).unwrap());
+
+    let upgraded = reseal_with_password(b"pw", &sealed, &params(64, 1)).unwrap();
+    assert_eq!(params_of(&upgraded).unwrap().mem_kib, 64);
+    assert!(!needs_upgrade(&upgraded, &params(64, 1)).unwrap());
+    assert_eq!(
+        open_with_password(b"pw", &upgraded).unwrap(),
+        b"secret blob"
+    );
+
+    // Re-sealing never lowers the stored costs.
+    let resealed = reseal_with_password(b"pw", &upgraded, &params(32, 2)).unwrap();
+    assert_eq!(
+        params_of(&resealed).unwrap(),
+        PasswordParams {
+            mem_kib: 64,
+            time: 2,
+            lanes: 1
+        }
+    );
+}
+
+#[test]
+fn password_reseal_requires_passphrase() {
+    let sealed = seal_with_password(b"pw", b"secret blob", &params(32, 1)).unwrap();
+
+    assert_eq!(
+        reseal_with_password(b"wrong", &sealed, &params(64, 1)),
+        Err(PasswordError::WrongPassphrase)
+    );
+}
@@ -0,0 +1,101 @@
+use cryptal::derivation::{ScryptError, ScryptParams, scrypt};
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn scrypt_rfc7914_vector_1() {
+    let mut out = [0u8; 64];
+    let params = ScryptParams {
+        log_n: 4,
+        r: 1,
+        p: 1,
+    };
+    scrypt(b"", b"", &params, &mut out).unwrap();
+
+    assert_eq!(
+        out.to_vec(),
+        unhex(
+            "77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442\
+             fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906"
+        )
+    );
+}
+
+#[test]
+fn scrypt_rfc7914_vector_2() {
+    let mut out = [0u8; 64];
+    let params = ScryptParams {
+        log_n: 10,
+        r: 8,
+        p: 16,
+    };
+    scrypt(b"password", b"NaCl", &params, &mut out).unwrap();
+
+    assert_eq!(
+        out.to_vec(),
+        unhex(
+            "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b373162\
+             2eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640"
+        )
+    );
+}
+
+#[test]
+fn scrypt_output_prefix_is_stable() {
+    // PBKDF2 output blocks are independent, so a shorter output is a
+    // prefix of a longer one.
+    let params = ScryptParams {
+        log_n: 4,
+        r: 2,
+        p: 2,
+    };
+    let mut short = [0u8; 20];
+    let mut long = [0u8; 100];
+    scrypt(b"pw", b"salt", &params, &mut short).unwrap();
+    scrypt(b"pw", b"salt", &params, &mut long).unwrap();
+
+    assert_eq!(short[..], long[..20]);
+}
+
+#[test]
+fn scrypt_rejects_invalid_params() {
+    let mut out = [0u8; 32];
+
+    for params in [
+        ScryptParams {
+            log_n: 0,
+            r: 8,
+            p: 1,
+        },
+        ScryptParams {
+            log_n: 4,
+            r: 0,
+            p: 1,
+        },
+        ScryptParams {
+            log_n: 4,
+            r: 8,
+            p: 0,
+        },
+        ScryptParams {
+            log_n: 4,
+            r: 1 << 15,
+            p: 1 << 15,
+        },
+        ScryptParams {
+            log_n: 64,
+            r: 8,
+            p: 1,
+        },
+    ] {
+        assert_eq!(
+            scrypt(b"pw", b"salt", &params, &mut out),
+            Err(ScryptError::InvalidParams)
+        );
+    }
+}
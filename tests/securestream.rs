@@ -0,0 +1,303 @@
+use std::io::{self, Cursor, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+use cryptal::encryption::padding::{Padding, PaddingError};
+use cryptal::protocol::securestream::{
+    HEADER_LEN, MAX_MESSAGE_LEN, Role, SecureStream, StreamError,
+};
+
+const KEY: [u8; 32] = [0x42; 32];
+const TAG_LEN: usize = 16;
+
+/// One end of an in-memory connection: reads from `input`, writes to
+/// `output`.
+#[derive(Default)]
+struct Pipe {
+    input: Cursor<Vec<u8>>,
+    output: Vec<u8>,
+}
+
+impl Read for Pipe {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.input.read(buf)
+    }
+}
+
+impl Write for Pipe {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.output.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn sender() -> SecureStream<Pipe> {
+    SecureStream::new(Pipe::default(), &KEY, Role::Initiator)
+}
+
+fn receiver(wire: Vec<u8>) -> SecureStream<Pipe> {
+    let pipe = Pipe {
+        input: Cursor::new(wire),
+        output: Vec::new(),
+    };
+
+    SecureStream::new(pipe, &KEY, Role::Responder)
+}
+
+/// Splits the sender's output into frames.
+fn frames(wire: &[u8]) -> Vec<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut rest = wire;
+
+    while !rest.is_empty() {
+        let len = u32::from_be_bytes(rest[1..HEADER_LEN].try_into().unwrap()) as usize;
+        let (frame, tail) = rest.split_at(HEADER_LEN + len + TAG_LEN);
+        out.push(frame.to_vec());
+        rest = tail;
+    }
+
+    out
+}
+
+#[test]
+fn securestream_roundtrip() {
+    let mut alice = sender();
+    let messages: [&[u8]; 4] = [b"hello", b"", &[0xAB; 5000], b"bye"];
+
+    for m in messages {
+        alice.send(m).unwrap();
+    }
+    alice.close().unwrap();
+    assert_eq!(alice.send(b"late"), Err(StreamError::Closed));
+
+    let wire = alice.into_inner().output;
+    assert_eq!(frames(&wire).len(), 5);
+    assert_eq!(frames(&wire)[0].len(), HEADER_LEN + 5 + TAG_LEN);
+
+    let mut bob = receiver(wire);
+    for m in messages {
+        assert_eq!(bob.recv().unwrap().unwrap(), m);
+    }
+    assert_eq!(bob.recv(), Ok(None));
+    assert_eq!(bob.recv(), Ok(None));
+}
+
+#[test]
+fn securestream_padding() {
+    let mut alice = sender().with_padding(Padding::Bucket(256)).unwrap();
+    let messages: [&[u8]; 3] = [b"", b"hello", &[0xAB; 300]];
+
+    for m in messages {
+        alice.send(m).unwrap();
+    }
+    alice.close().unwrap();
+
+    let wire = alice.into_inner().output;
+    let lens: Vec<usize> = frames(&wire).iter().map(Vec::len).collect();
+    assert_eq!(
+        lens,
+        [256, 256, 512, 0].map(|len| HEADER_LEN + len + TAG_LEN)
+    );
+
+    let mut bob = receiver(wire.clone()).with_padding(Padding::Padme).unwrap();
+    for m in messages {
+        assert_eq!(bob.recv().unwrap().unwrap(), m);
+    }
+    assert_eq!(bob.recv(), Ok(None));
+
+    // Without padding, the receiver sees the padded messages.
+    let mut bob = receiver(wire);
+    assert_eq!(bob.recv().unwrap().unwrap().len(), 256);
+
+    // Unpadded messages are rejected when padding is expected.
+    let mut alice = sender();
+    alice.send(b"hello").unwrap();
+
+    let mut bob = receiver(alice.into_inner().output)
+        .with_padding(Padding::Padme)
+        .unwrap();
+    assert_eq!(bob.recv(), Err(StreamError::InvalidFrame));
+
+    assert!(matches!(
+        sender().with_padding(Padding::Bucket(0)),
+        Err(PaddingError::InvalidBucket)
+    ));
+}
+
+#[test]
+fn securestream_padded_message_too_long() {
+    let mut alice = sender().with_padding(Padding::Padme).unwrap();
+
+    assert_eq!(
+        alice.send(&vec![0u8; MAX_MESSAGE_LEN]),
+        Err(StreamError::MessageTooLong)
+    );
+    assert_eq!(alice.send(b"still usable"), Ok(()));
+}
+
+#[test]
+fn securestream_directions_use_distinct_keys() {
+    let mut alice = sender();
+    alice.send(b"ping").unwrap();
+    let wire = alice.into_inner().output;
+
+    // A second initiator cannot read frames sent by the initiator.
+    let mut eve = SecureStream::new(
+        Pipe {
+            input: Cursor::new(wire.clone()),
+            output: Vec::new(),
+        },
+        &KEY,
+        Role::Initiator,
+    );
+    assert_eq!(eve.recv(), Err(StreamError::DecryptionFailed));
+
+    let mut wrong_key = SecureStream::new(
+        Pipe {
+            input: Cursor::new(wire),
+            output: Vec::new(),
+        },
+        &[0x43; 32],
+        Role::Responder,
+    );
+    assert_eq!(wrong_key.recv(), Err(StreamError::DecryptionFailed));
+}
+
+#[test]
+fn securestream_detects_tampering() {
+    let mut alice = sender();
+    alice.send(b"attack at dawn").unwrap();
+    let wire = alice.into_inner().output;
+
+    for i in 0..wire.len() {
+        let mut tampered = wire.clone();
+        tampered[i] ^= 1;
+
+        let mut bob = receiver(tampered);
+        assert!(bob.recv().is_err(), "byte {i}");
+        assert_eq!(bob.recv(), Err(StreamError::InvalidState));
+    }
+}
+
+#[test]
+fn securestream_detects_replay_and_reordering() {
+    let mut alice = sender();
+    alice.send(b"first").unwrap();
+    alice.send(b"second").unwrap();
+    let f = frames(&alice.into_inner().output);
+
+    let mut bob = receiver([f[1].clone(), f[0].clone()].concat());
+    assert_eq!(bob.recv(), Err(StreamError::DecryptionFailed));
+
+    let mut bob = receiver([f[0].clone(), f[0].clone()].concat());
+    assert_eq!(bob.recv().unwrap().unwrap(), b"first");
+    assert_eq!(bob.recv(), Err(StreamError::DecryptionFailed));
+
+    // A dropped frame.
+    let mut bob = receiver(f[1].clone());
+    assert_eq!(bob.recv(), Err(StreamError::DecryptionFailed));
+}
+
+#[test]
+fn securestream_detects_truncation() {
+    let mut alice = sender();
+    alice.send(b"one").unwrap();
+    alice.send(b"two").unwrap();
+    let wire = alice.into_inner().output;
+
+    // Cut at a frame boundary: no close frame.
+    let mut bob = receiver(wire.clone());
+    assert_eq!(bob.recv().unwrap().unwrap(), b"one");
+    assert_eq!(bob.recv().unwrap().unwrap(), b"two");
+    assert_eq!(bob.recv(), Err(StreamError::Truncated));
+
+    // Cut inside a frame.
+    let mut bob = receiver(wire[..wire.len() - 1].to_vec());
+    assert_eq!(bob.recv().unwrap().unwrap(), b"one");
+    assert_eq!(bob.recv(), Err(StreamError::Truncated));
+}
+
+#[test]
+fn securestream_rejects_oversized_frames() {
+    let mut alice = sender();
+    assert_eq!(
+        alice.send(&vec![0; MAX_MESSAGE_LEN + 1]),
+        Err(StreamError::MessageTooLong)
+    );
+
+    let mut header = vec![0u8];
+    header.extend_from_slice(&(MAX_MESSAGE_LEN as u32 + 1).to_be_bytes());
+
+    let mut bob = receiver(header);
+    assert_eq!(bob.recv(), Err(StreamError::InvalidFrame));
+}
+
+#[test]
+fn securestream_rekeys_periodically() {
+    let mut alice = sender().with_rekey_interval(3);
+    let mut plain = sender();
+
+    for i in 0..10u8 {
+        alice.send(&[i; 8]).unwrap();
+        plain.send(&[i; 8]).unwrap();
+    }
+    alice.close().unwrap();
+
+    let rekeyed = frames(&alice.get_ref().output);
+    let f = frames(&plain.get_ref().output);
+
+    // Frames differ from the third one on, once the key has changed.
+    assert_eq!(rekeyed[..3], f[..3]);
+    for i in 3..10 {
+        assert_ne!(rekeyed[i], f[i]);
+    }
+
+    let mut bob = receiver(alice.into_inner().output).with_rekey_interval(3);
+    for i in 0..10u8 {
+        assert_eq!(bob.recv().unwrap().unwrap(), [i; 8]);
+    }
+    assert_eq!(bob.recv(), Ok(None));
+
+    // A peer with a different interval fails once the keys diverge.
+    let wire = plain.into_inner().output;
+    let mut bob = receiver(wire).with_rekey_interval(3);
+    for i in 0..3u8 {
+        assert_eq!(bob.recv().unwrap().unwrap(), [i; 8]);
+    }
+    assert_eq!(bob.recv(), Err(StreamError::DecryptionFailed));
+}
+
+#[test]
+fn securestream_over_tcp() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = thread::spawn(move || {
+        let (socket, _) = listener.accept().unwrap();
+        let mut stream = SecureStream::new(socket, &KEY, Role::Responder);
+
+        while let Some(message) = stream.recv().unwrap() {
+            stream.send(&message.to_ascii_uppercase()).unwrap();
+        }
+        stream.close().unwrap();
+    });
+
+    let socket = TcpStream::connect(addr).unwrap();
+    let mut client = SecureStream::new(socket, &KEY, Role::Initiator);
+
+    for word in ["alpha", "beta", "gamma"] {
+        client.send(word.as_bytes()).unwrap();
+        assert_eq!(
+            client.recv().unwrap().unwrap(),
+            word.to_ascii_uppercase().as_bytes()
+        );
+    }
+
+    client.close().unwrap();
+    assert_eq!(client.recv(), Ok(None));
+
+    server.join().unwrap();
+}
@@ -1,4 +1,4 @@
-use cryptal::hash::sha512;
+use cryptal::hash::{Sha512, sha512};
 
 fn sha512_test(input: &[u8]) -> [u8; 64] {
     let got = sha512(input);
@@ -141,3 +141,21 @@ fn sha512_block_boundary_256() {
     let buf = vec![0x22u8; 256];
     let _ = sha512_test(&buf);
 }
+
+#[test]
+fn sha512_streaming_matches_one_shot() {
+    let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+
+    for len in [0, 1, 111, 112, 127, 128, 129, 256, 1000] {
+        let input = &data[..len];
+
+        for chunk in [1, 3, 64, 127, 128, 200] {
+            let mut hasher = Sha512::new();
+            for piece in input.chunks(chunk) {
+                hasher.update(piece);
+            }
+
+            assert_eq!(hasher.finalize(), sha512(input), "len {len} chunk {chunk}");
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use cryptal::encoding::base64::{self, Variant};
+use cryptal::keys::ed25519::{self, Scalar, add_scalar};
+use cryptal::keys::signify::{MAX_KDF_ROUNDS, SignifyError, SignifyPublicKey, SignifySecretKey};
+
+// Built with libsodium following the `signify` formats: seed 0x80..0xa0,
+// key id a1b2c3d4e5f60718, unencrypted secret key (`signify -G -n`).
+const PUBLIC_KEY: &str = "\
+untrusted comment: signify public key
+RWShssPU5fYHGM0Us3+VbpUxlP9/tzs9gdzFYdYadTgJS3w+GmQ+5fOq
+";
+const SECRET_KEY: &str = "\
+untrusted comment: signify secret key
+RWRCSwAAAAAAAAAAAAAAAAAAAAAAAAAArbqIkHQSizShssPU5fYHGICBgoOEhYaHiImKi4yNjo+QkZKTlJWWl5iZmpucnZ6fzRSzf5VulTGU/3+3Oz2B3MVh1hp1OAlLfD4aZD7l86o=
+";
+const MESSAGE: &[u8] = b"signify test message\n";
+const SIGNATURE: &str = "\
+untrusted comment: verify with key.pub
+RWShssPU5fYHGNGzcJk3aGkjQsyt2KXccMUMbpvCKy2sLSytiya7LISPq8oHc6XA4tXFJy06U2HMay4TSjLuvBzYe+XPKilQwAQ=
+";
+
+#[test]
+fn test_signify_vectors() {
+    let (public, comment) = SignifyPublicKey::decode(PUBLIC_KEY).unwrap();
+    assert_eq!(comment, "signify public key");
+    assert_eq!(
+        public.key_id,
+        [0xa1, 0xb2, 0xc3, 0xd4, 0xe5, 0xf6, 0x07, 0x18]
+    );
+
+    let (secret, comment) = SignifySecretKey::decode(SECRET_KEY, None).unwrap();
+    assert_eq!(comment, "signify secret key");
+    assert_eq!(secret.public_key(), public);
+    assert_eq!(
+        secret.private.seed(),
+        Some(core::array::from_fn(|i| 0x80 + i as u8))
+    );
+
+    assert_eq!(public.verify(SIGNATURE, MESSAGE), Ok(()));
+    assert_eq!(
+        public.verify(SIGNATURE, b"signify test message"),
+        Err(SignifyError::InvalidSignature)
+    );
+
+    // Ed25519 signatures are deterministic.
+    assert_eq!(
+        secret.sign(MESSAGE, "verify with key.pub").unwrap(),
+        SIGNATURE
+    );
+    assert_eq!(public.encode("signify public key").unwrap(), PUBLIC_KEY);
+}
+
+#[test]
+fn test_signify_encrypted_roundtrip() {
+    let secret = SignifySecretKey::generate();
+    let encoded = secret
+        .encode(Some(b"correct horse"), "signify secret key")
+        .unwrap();
+
+    assert_eq!(
+        SignifySecretKey::decode(&encoded, None).err(),
+        Some(SignifyError::PasswordRequired)
+    );
+    assert_eq!(
+        SignifySecretKey::decode(&encoded, Some(b"wrong horse")).err(),
+        Some(SignifyError::WrongPassword)
+    );
+
+    let (decoded, _) = SignifySecretKey::decode(&encoded, Some(b"correct horse")).unwrap();
+    assert_eq!(decoded.public_key(), secret.public_key());
+    assert_eq!(decoded.private.seed(), secret.private.seed());
+
+    let signature = decoded.sign(b"data", "verify with key.pub").unwrap();
+    assert_eq!(secret.public_key().verify(&signature, b"data"), Ok(()));
+}
+
+/// Rewrites the bcrypt_pbkdf rounds stored in a secret key file.
+fn with_rounds(secret_key: &str, rounds: u32) -> String {
+    let mut lines = secret_key.lines();
+    let comment = lines.next().unwrap();
+
+    let mut data = base64::decode(lines.next().unwrap(), Variant::Standard).unwrap();
+    data[4..8].copy_from_slice(&rounds.to_be_bytes());
+
+    format!("{comment}\n{}\n", base64::encode(&data, Variant::Standard))
+}
+
+#[test]
+fn test_signify_rejects_excessive_rounds() {
+    let password = Some(&b"correct horse"[..]);
+    let encoded = SignifySecretKey::generate()
+        .encode(password, "signify secret key")
+        .unwrap();
+
+    for rounds in [MAX_KDF_ROUNDS + 1, u32::MAX] {
+        assert_eq!(
+            SignifySecretKey::decode(&with_rounds(&encoded, rounds), password).err(),
+            Some(SignifyError::InvalidKdfParams)
+        );
+    }
+}
+
+#[test]
+fn test_signify_rejects_invalid_input() {
+    let (public, _) = SignifyPublicKey::decode(PUBLIC_KEY).unwrap();
+
+    let other = SignifySecretKey::generate();
+    let signature = other.sign(MESSAGE, "").unwrap();
+    assert_eq!(
+        public.verify(&signature, MESSAGE),
+        Err(SignifyError::KeyMismatch)
+    );
+
+    assert_eq!(
+        SignifyPublicKey::decode(SIGNATURE).err(),
+        Some(SignifyError::InvalidEncoding)
+    );
+    assert_eq!(
+        SignifyPublicKey::decode(&PUBLIC_KEY.replace("untrusted", "trusted")).err(),
+        Some(SignifyError::InvalidEncoding)
+    );
+    assert_eq!(
+        public.verify(&format!("{SIGNATURE}extra\n"), MESSAGE),
+        Err(SignifyError::InvalidEncoding)
+    );
+    assert_eq!(
+        other.encode(None, "two\nlines").err(),
+        Some(SignifyError::InvalidComment)
+    );
+    assert_eq!(
+        other.encode(Some(b""), "").err(),
+        Some(SignifyError::EmptyPassword)
+    );
+
+    let (_, mut private) = ed25519::keypair_from_seed([3u8; 32]);
+    add_scalar(None, Some(&mut private), Scalar::from_bytes(&[1u8; 32]));
+    let derived = SignifySecretKey {
+        key_id: [0u8; 8],
+        private,
+    };
+    assert_eq!(
+        derived.encode(None, "").err(),
+        Some(SignifyError::MissingSeed)
+    );
+}
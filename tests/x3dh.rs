@@ -0,0 +1,253 @@
+use cryptal::keys::ed25519::{Scalar, add_scalar, generate_keypair};
+use cryptal::protocol::doubleratchet::Session;
+use cryptal::protocol::x3dh::{
+    IdentityKeyPair, InitialMessage, OneTimePreKey, PreKeyBundle, SignedPreKey, X3dhError,
+    initiate, respond,
+};
+
+struct Responder {
+    identity: IdentityKeyPair,
+    signed: SignedPreKey,
+    one_time: OneTimePreKey,
+}
+
+impl Responder {
+    fn new() -> Self {
+        Self::with_identity(IdentityKeyPair::generate())
+    }
+
+    fn with_identity(identity: IdentityKeyPair) -> Self {
+        Self {
+            signed: SignedPreKey::generate(&identity, 7),
+            one_time: OneTimePreKey::generate(42),
+            identity,
+        }
+    }
+
+    fn bundle(&self, one_time: bool) -> PreKeyBundle {
+        PreKeyBundle::new(
+            &self.identity,
+            &self.signed,
+            one_time.then_some(&self.one_time),
+        )
+    }
+}
+
+#[test]
+fn x3dh_agreement() {
+    let bob = Responder::new();
+    let alice = IdentityKeyPair::generate();
+
+    for one_time in [false, true] {
+        let bundle = bob.bundle(one_time);
+        let (message, a) = initiate(&alice, &bundle).unwrap();
+
+        assert_eq!(message.signed_prekey_id, 7);
+        assert_eq!(message.one_time_prekey_id, one_time.then_some(42));
+
+        let b = respond(
+            &bob.identity,
+            &bob.signed,
+            one_time.then_some(&bob.one_time),
+            &message,
+        )
+        .unwrap();
+
+        assert_eq!(a.shared_secret(), b.shared_secret());
+        assert_eq!(a.associated_data(), b.associated_data());
+        assert_eq!(a.associated_data()[..32], alice.public_key());
+        assert_eq!(a.associated_data()[32..], bob.identity.public_key());
+    }
+}
+
+/// Returns an identity whose scalar was reduced modulo ℓ by `add_scalar`.
+fn blinded_identity(tweak: u8) -> IdentityKeyPair {
+    let (mut public, mut private) = generate_keypair();
+    add_scalar(
+        Some(&mut public),
+        Some(&mut private),
+        Scalar::from_bytes(&[tweak; 32]),
+    );
+    IdentityKeyPair::from_keypair(public, private).unwrap()
+}
+
+#[test]
+fn x3dh_agreement_with_blinded_identities() {
+    let bob = Responder::with_identity(blinded_identity(42));
+
+    for alice in [IdentityKeyPair::generate(), blinded_identity(7)] {
+        let (message, a) = initiate(&alice, &bob.bundle(true)).unwrap();
+        let b = respond(&bob.identity, &bob.signed, Some(&bob.one_time), &message).unwrap();
+
+        assert_eq!(a.shared_secret(), b.shared_secret());
+    }
+}
+
+#[test]
+fn x3dh_rejects_mismatched_identity() {
+    let (public, _) = generate_keypair();
+    let (_, private) = generate_keypair();
+
+    assert!(matches!(
+        IdentityKeyPair::from_keypair(public, private),
+        Err(X3dhError::KeyMismatch)
+    ));
+}
+
+#[test]
+fn x3dh_fresh_secret_per_initiation() {
+    let bob = Responder::new();
+    let alice = IdentityKeyPair::generate();
+    let bundle = bob.bundle(true);
+
+    let (m1, a1) = initiate(&alice, &bundle).unwrap();
+    let (m2, a2) = initiate(&alice, &bundle).unwrap();
+
+    assert_ne!(m1.ephemeral_key, m2.ephemeral_key);
+    assert_ne!(a1.shared_secret(), a2.shared_secret());
+}
+
+#[test]
+fn x3dh_rejects_bad_signature() {
+    let bob = Responder::new();
+    let alice = IdentityKeyPair::generate();
+
+    let mut bundle = bob.bundle(true);
+    bundle.signed_prekey[0] ^= 1;
+    assert_eq!(
+        initiate(&alice, &bundle).err(),
+        Some(X3dhError::InvalidSignature)
+    );
+
+    let mut bundle = bob.bundle(true);
+    bundle.signed_prekey_id += 1;
+    assert_eq!(bundle.verify(), Err(X3dhError::InvalidSignature));
+
+    // A prekey signed by another identity.
+    let mut bundle = bob.bundle(false);
+    bundle.identity_key = alice.public_key();
+    assert_eq!(bundle.verify(), Err(X3dhError::InvalidSignature));
+}
+
+#[test]
+fn x3dh_signed_prekey_reload() {
+    let bob = Responder::new();
+    let reloaded = SignedPreKey::from_private(&bob.identity, 7, bob.signed.private_key());
+
+    assert_eq!(reloaded.public_key(), bob.signed.public_key());
+    assert_eq!(reloaded.signature(), bob.signed.signature());
+
+    let one_time = OneTimePreKey::from_private(42, bob.one_time.private_key());
+    assert_eq!(one_time.public_key(), bob.one_time.public_key());
+}
+
+#[test]
+fn x3dh_rejects_unknown_prekeys() {
+    let bob = Responder::new();
+    let alice = IdentityKeyPair::generate();
+
+    let (message, _) = initiate(&alice, &bob.bundle(true)).unwrap();
+    assert_eq!(
+        respond(&bob.identity, &bob.signed, None, &message).err(),
+        Some(X3dhError::UnknownPreKey)
+    );
+
+    let other = OneTimePreKey::generate(43);
+    assert_eq!(
+        respond(&bob.identity, &bob.signed, Some(&other), &message).err(),
+        Some(X3dhError::UnknownPreKey)
+    );
+
+    let other = SignedPreKey::generate(&bob.identity, 8);
+    assert_eq!(
+        respond(&bob.identity, &other, Some(&bob.one_time), &message).err(),
+        Some(X3dhError::UnknownPreKey)
+    );
+}
+
+#[test]
+fn x3dh_rejects_weak_keys() {
+    let bob = Responder::new();
+    let alice = IdentityKeyPair::generate();
+
+    let (mut message, _) = initiate(&alice, &bob.bundle(false)).unwrap();
+    message.ephemeral_key = [0; 32];
+
+    assert_eq!(
+        respond(&bob.identity, &bob.signed, None, &message).err(),
+        Some(X3dhError::WeakPublicKey)
+    );
+}
+
+#[test]
+fn x3dh_encoding() {
+    let bob = Responder::new();
+    let alice = IdentityKeyPair::generate();
+
+    for one_time in [false, true] {
+        let bundle = bob.bundle(one_time);
+        let bytes = bundle.to_bytes();
+
+        assert_eq!(bytes.len(), if one_time { 170 } else { 134 });
+        assert_eq!(PreKeyBundle::from_bytes(&bytes), Ok(bundle));
+        assert_eq!(
+            PreKeyBundle::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(X3dhError::InvalidBundle)
+        );
+
+        let (message, _) = initiate(&alice, &bundle).unwrap();
+        let bytes = message.to_bytes();
+
+        assert_eq!(bytes.len(), if one_time { 74 } else { 70 });
+        assert_eq!(InitialMessage::from_bytes(&bytes), Ok(message));
+        assert_eq!(
+            InitialMessage::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(X3dhError::InvalidMessage)
+        );
+    }
+
+    let mut bytes = bob.bundle(false).to_bytes();
+    bytes[0] = 2;
+    assert_eq!(
+        PreKeyBundle::from_bytes(&bytes),
+        Err(X3dhError::InvalidBundle)
+    );
+
+    let mut bytes = bob.bundle(false).to_bytes();
+    bytes[1] = 0x80;
+    assert_eq!(
+        PreKeyBundle::from_bytes(&bytes),
+        Err(X3dhError::InvalidBundle)
+    );
+}
+
+#[test]
+fn x3dh_bootstraps_double_ratchet() {
+    let bob = Responder::new();
+    let alice = IdentityKeyPair::generate();
+
+    let bundle = PreKeyBundle::from_bytes(&bob.bundle(true).to_bytes()).unwrap();
+    let (message, a) = initiate(&alice, &bundle).unwrap();
+
+    let mut alice_session = Session::initiator(a.shared_secret(), &bundle.signed_prekey).unwrap();
+    let first = alice_session
+        .encrypt(b"hello bob", a.associated_data())
+        .unwrap();
+
+    let message = InitialMessage::from_bytes(&message.to_bytes()).unwrap();
+    let b = respond(&bob.identity, &bob.signed, Some(&bob.one_time), &message).unwrap();
+
+    let mut bob_session = Session::responder(b.shared_secret(), &bob.signed.private_key());
+    assert_eq!(
+        bob_session.decrypt(&first, b.associated_data()).unwrap(),
+        b"hello bob"
+    );
+
+    let reply = bob_session
+        .encrypt(b"hi alice", b.associated_data())
+        .unwrap();
+    assert_eq!(
+        alice_session.decrypt(&reply, a.associated_data()).unwrap(),
+        b"hi alice"
+    );
+}